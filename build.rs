@@ -14,13 +14,16 @@
 
 const WINDOWS_ICON_PATH: &str = "./resources/packaging/windows/graphics/sniffnet.ico";
 const SERVICES_LIST_PATH: &str = "./services.txt";
+const OUI_LIST_PATH: &str = "./oui.txt";
 
 fn main() {
     println!("cargo:rerun-if-changed={WINDOWS_ICON_PATH}");
     println!("cargo:rerun-if-changed={SERVICES_LIST_PATH}");
+    println!("cargo:rerun-if-changed={OUI_LIST_PATH}");
 
     set_icon();
     build_services_phf();
+    build_oui_phf();
 }
 
 fn set_icon() {
@@ -63,6 +66,43 @@ fn build_services_phf() {
     .unwrap();
 }
 
+fn build_oui_phf() {
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("oui.rs");
+    let mut output = BufWriter::new(File::create(out_path).unwrap());
+
+    let mut oui_map = phf_codegen::Map::new();
+
+    let input = BufReader::new(File::open(OUI_LIST_PATH).unwrap());
+    let mut num_entries = 0;
+    for line_res in input.lines() {
+        // we want to panic if one of the lines is err...
+        let line = line_res.unwrap();
+        if line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split('\t');
+        // we want to panic if the OUI is not made of 6 hexadecimal digits
+        let oui_str = parts.next().unwrap();
+        assert_eq!(oui_str.len(), 6);
+        let key = u32::from_str_radix(oui_str, 16).unwrap();
+        // we want to panic if the vendor is missing
+        let vendor = parts.next().unwrap().trim();
+        assert!(!vendor.is_empty());
+        assert!(parts.next().is_none());
+        oui_map.entry(key, &format!("{vendor:?}"));
+        num_entries += 1;
+    }
+    assert_eq!(num_entries, 234);
+
+    writeln!(
+        &mut output,
+        "#[allow(clippy::unreadable_literal)]\n\
+        static OUI_VENDORS: phf::Map<u32, &'static str> = {};",
+        oui_map.build()
+    )
+    .unwrap();
+}
+
 fn get_valid_service_fmt_const(s: &str) -> String {
     match s.trim() {
         invalid
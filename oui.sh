@@ -0,0 +1,34 @@
+#!/bin/bash
+
+# Script used to generate the file ./oui.txt
+# The source for OUI-to-vendor mappings is the MA-L registry of the IEEE Registration Authority
+# Available at: https://standards-oui.ieee.org/oui/oui.csv
+# After running it, update the number of entries asserted in ./build.rs
+
+OUT=./oui.txt
+
+{
+  echo "# Organizationally Unique Identifiers (OUI) of the MA-L registry of the IEEE Registration Authority,"
+  echo "# used to show the vendor of the devices of the local network."
+  echo "#"
+  echo "# Format: one 'OUI<TAB>vendor' entry per line (OUI as 6 uppercase hexadecimal digits)."
+  echo "# Source: https://standards-oui.ieee.org/oui/oui.csv (retrieved on $(date -u +%Y-%m-%d)), converted by ./oui.sh"
+  curl https://standards-oui.ieee.org/oui/oui.csv \
+    | python3 -c '
+import csv
+import sys
+
+vendors = {}
+for row in csv.reader(sys.stdin):
+    if len(row) < 3 or row[0] != "MA-L":
+        continue
+    oui = row[1].strip().upper()
+    vendor = " ".join(row[2].split())
+    if len(oui) == 6 and vendor:
+        vendors.setdefault(oui, vendor)
+for oui in sorted(vendors):
+    print(f"{oui}\t{vendors[oui]}")
+'
+} > $OUT
+
+grep -c -v '^#' $OUT
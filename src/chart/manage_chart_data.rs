@@ -1,57 +1,225 @@
 use splines::{Interpolation, Key, Spline};
 
+use crate::networking::types::data_info::DataInfo;
 use crate::{RunTimeData, TrafficChart};
 
+/// Number of seconds displayed in the chart
+const CHART_SECONDS: usize = 30;
+
 /// This function is invoked every second by the application subscription
 ///
-/// It updates data (packets and bytes per second) to be displayed in the chart of gui run page
-pub fn update_charts_data(runtime_data: &mut RunTimeData, traffic_chart: &mut TrafficChart) {
-    #[allow(clippy::cast_precision_loss)]
-    let tot_seconds = traffic_chart.ticks as f32;
-    traffic_chart.ticks += 1;
+/// It updates data (packets and bytes per second) to be displayed in the chart of gui run page;
+/// traffic is displayed at the second its packets were captured, according to their timestamps.
+///
+/// When sniffing live traffic, `current_second` is the current time,
+/// used to keep the chart moving even if no packets are captured.
+pub fn update_charts_data(
+    runtime_data: &mut RunTimeData,
+    traffic_chart: &mut TrafficChart,
+    current_second: Option<i64>,
+) {
+    let traffic_per_second = std::mem::take(&mut runtime_data.traffic_per_second);
+    let adapters_per_second = std::mem::take(&mut runtime_data.adapters_per_second);
+    if let Some(first_second) = traffic_per_second.keys().next() {
+        set_first_second(traffic_chart, *first_second);
+    }
+    let Some(first_second) = traffic_chart.first_second else {
+        return;
+    };
 
-    #[allow(clippy::cast_precision_loss)]
-    let out_bytes_entry =
-        -1.0 * (runtime_data.tot_out_bytes - runtime_data.tot_out_bytes_prev) as f32;
-    #[allow(clippy::cast_precision_loss)]
-    let in_bytes_entry = (runtime_data.tot_in_bytes - runtime_data.tot_in_bytes_prev) as f32;
-    #[allow(clippy::cast_precision_loss)]
-    let out_packets_entry =
-        -1.0 * (runtime_data.tot_out_packets - runtime_data.tot_out_packets_prev) as f32;
-    #[allow(clippy::cast_precision_loss)]
-    let in_packets_entry = (runtime_data.tot_in_packets - runtime_data.tot_in_packets_prev) as f32;
+    // update sent and received bytes and packets
+    for (second, data_info) in traffic_per_second {
+        let x = chart_x(traffic_chart, first_second, second);
+        for (spline, entry) in [
+            (&mut traffic_chart.out_bytes, out_bytes_entry(&data_info)),
+            (&mut traffic_chart.in_bytes, in_bytes_entry(&data_info)),
+            (
+                &mut traffic_chart.out_packets,
+                out_packets_entry(&data_info),
+            ),
+            (&mut traffic_chart.in_packets, in_packets_entry(&data_info)),
+        ] {
+            add_to_spline(spline, x, entry);
+        }
+    }
 
-    let out_bytes_key = Key::new(tot_seconds, out_bytes_entry, Interpolation::Cosine);
-    let in_bytes_key = Key::new(tot_seconds, in_bytes_entry, Interpolation::Cosine);
-    let out_packets_key = Key::new(tot_seconds, out_packets_entry, Interpolation::Cosine);
-    let in_packets_key = Key::new(tot_seconds, in_packets_entry, Interpolation::Cosine);
+    // update traffic data of each adapter (bounded by the totals, so axes ranges are unchanged)
+    for (second, adapters) in adapters_per_second {
+        let x = chart_x(traffic_chart, first_second, second);
+        for (adapter, data_info) in adapters {
+            let adapter_traffic = traffic_chart.adapters.entry(adapter).or_default();
+            for (spline, entry) in [
+                (&mut adapter_traffic.out_bytes, out_bytes_entry(&data_info)),
+                (&mut adapter_traffic.in_bytes, in_bytes_entry(&data_info)),
+                (
+                    &mut adapter_traffic.out_packets,
+                    out_packets_entry(&data_info),
+                ),
+                (
+                    &mut adapter_traffic.in_packets,
+                    in_packets_entry(&data_info),
+                ),
+            ] {
+                add_to_spline(spline, x, entry);
+            }
+        }
+    }
 
-    // update sent bytes traffic data
-    update_spline(&mut traffic_chart.out_bytes, out_bytes_key);
-    traffic_chart.min_bytes = get_min(&traffic_chart.out_bytes);
-    runtime_data.tot_out_bytes_prev = runtime_data.tot_out_bytes;
+    // the last complete second of a live capture
+    if let Some(current_second) = current_second {
+        chart_x(traffic_chart, first_second, current_second - 1);
+    }
 
-    // update received bytes traffic data
-    update_spline(&mut traffic_chart.in_bytes, in_bytes_key);
-    traffic_chart.max_bytes = get_max(&traffic_chart.in_bytes);
-    runtime_data.tot_in_bytes_prev = runtime_data.tot_in_bytes;
+    // seconds without traffic
+    #[allow(clippy::cast_precision_loss)]
+    let last_x = traffic_chart.ticks.saturating_sub(1) as f32;
+    for spline in [
+        &mut traffic_chart.out_bytes,
+        &mut traffic_chart.in_bytes,
+        &mut traffic_chart.out_packets,
+        &mut traffic_chart.in_packets,
+    ] {
+        fill_spline(spline, last_x);
+    }
+    for adapter_traffic in traffic_chart.adapters.values_mut() {
+        for spline in [
+            &mut adapter_traffic.out_bytes,
+            &mut adapter_traffic.in_bytes,
+            &mut adapter_traffic.out_packets,
+            &mut adapter_traffic.in_packets,
+        ] {
+            fill_spline(spline, last_x);
+        }
+    }
 
-    // update sent packets traffic data
-    update_spline(&mut traffic_chart.out_packets, out_packets_key);
+    traffic_chart.min_bytes = get_min(&traffic_chart.out_bytes);
+    traffic_chart.max_bytes = get_max(&traffic_chart.in_bytes);
     traffic_chart.min_packets = get_min(&traffic_chart.out_packets);
-    runtime_data.tot_out_packets_prev = runtime_data.tot_out_packets;
-
-    // update received packets traffic data
-    update_spline(&mut traffic_chart.in_packets, in_packets_key);
     traffic_chart.max_packets = get_max(&traffic_chart.in_packets);
+
+    // totals at the end of the current time interval, used by notifications
+    runtime_data.tot_out_bytes_prev = runtime_data.tot_out_bytes;
+    runtime_data.tot_in_bytes_prev = runtime_data.tot_in_bytes;
+    runtime_data.tot_out_packets_prev = runtime_data.tot_out_packets;
     runtime_data.tot_in_packets_prev = runtime_data.tot_in_packets;
 }
 
+/// Sets the first second of the chart, moving it back if packets earlier than the ones
+/// already displayed are received (capture files and multi-adapter captures may be out of order)
+fn set_first_second(traffic_chart: &mut TrafficChart, second: i64) {
+    let Some(first_second) = traffic_chart.first_second else {
+        traffic_chart.first_second = Some(second);
+        return;
+    };
+    if second >= first_second {
+        return;
+    }
+    let shift = u32::try_from(first_second - second).unwrap_or(u32::MAX);
+    traffic_chart.first_second = Some(second);
+    traffic_chart.ticks = traffic_chart.ticks.saturating_add(shift);
+    let adapters_splines = traffic_chart
+        .adapters
+        .values_mut()
+        .flat_map(|adapter_traffic| {
+            [
+                &mut adapter_traffic.out_bytes,
+                &mut adapter_traffic.in_bytes,
+                &mut adapter_traffic.out_packets,
+                &mut adapter_traffic.in_packets,
+            ]
+        });
+    for spline in [
+        &mut traffic_chart.out_bytes,
+        &mut traffic_chart.in_bytes,
+        &mut traffic_chart.out_packets,
+        &mut traffic_chart.in_packets,
+    ]
+    .into_iter()
+    .chain(adapters_splines)
+    {
+        if spline.is_empty() {
+            continue;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let shifted_keys = spline
+            .keys()
+            .iter()
+            .map(|key| Key::new(key.t + shift as f32, key.value, key.interpolation))
+            .collect();
+        *spline = Spline::from_vec(shifted_keys);
+        // seconds without traffic before the ones already displayed (older ones are discarded anyway)
+        let oldest_x = shift.saturating_sub(u32::try_from(CHART_SECONDS).unwrap_or(u32::MAX));
+        for x in (oldest_x..shift).rev() {
+            #[allow(clippy::cast_precision_loss)]
+            update_spline(spline, Key::new(x as f32, 0.0, Interpolation::Cosine));
+        }
+    }
+}
+
+/// Returns the x coordinate of the given second, extending the chart up to it if needed
+fn chart_x(traffic_chart: &mut TrafficChart, first_second: i64, second: i64) -> f32 {
+    // the first second is the earliest one received so far
+    let seconds = u32::try_from(second - first_second).unwrap_or_default();
+    traffic_chart.ticks = traffic_chart.ticks.max(seconds + 1);
+    #[allow(clippy::cast_precision_loss)]
+    let x = seconds as f32;
+    x
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn out_bytes_entry(data_info: &DataInfo) -> f32 {
+    -(data_info.outgoing_bytes() as f32)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn in_bytes_entry(data_info: &DataInfo) -> f32 {
+    data_info.incoming_bytes() as f32
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn out_packets_entry(data_info: &DataInfo) -> f32 {
+    -(data_info.outgoing_packets() as f32)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn in_packets_entry(data_info: &DataInfo) -> f32 {
+    data_info.incoming_packets() as f32
+}
+
+/// Adds the traffic of a second to the chart, summing it to the one already displayed for
+/// the same second (packets of a second may be received across different time intervals)
+fn add_to_spline(spline: &mut Spline<f32, f32>, x: f32, mut value: f32) {
+    fill_spline(spline, x - 1.0);
+    if let Some(index) = spline.keys().iter().position(|key| (key.t - x).abs() < 0.5) {
+        if let Some(key) = spline.remove(index) {
+            value += key.value;
+        }
+    } else if spline.len() >= CHART_SECONDS && spline.get(0).is_some_and(|key| x < key.t) {
+        // too old to be displayed
+        return;
+    }
+    update_spline(spline, Key::new(x, value, Interpolation::Cosine));
+}
+
+/// Adds the seconds without traffic up to the given one
+fn fill_spline(spline: &mut Spline<f32, f32>, last_x: f32) {
+    let Some(last_key) = spline.keys().last() else {
+        return;
+    };
+    // older seconds wouldn't be displayed anyway
+    #[allow(clippy::cast_precision_loss)]
+    let mut x = (last_key.t + 1.0).max(last_x - CHART_SECONDS as f32 + 1.0);
+    while x <= last_x {
+        update_spline(spline, Key::new(x, 0.0, Interpolation::Cosine));
+        x += 1.0;
+    }
+}
+
 fn update_spline(spline: &mut Spline<f32, f32>, new_key: Key<f32, f32>) {
-    if spline.len() >= 30 {
+    spline.add(new_key);
+    while spline.len() > CHART_SECONDS {
         spline.remove(0);
     }
-    spline.add(new_key);
 }
 
 /// Finds the minimum y value to be displayed in chart.
@@ -78,10 +246,15 @@ fn get_max(spline: &Spline<f32, f32>) -> f32 {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use chrono::{DateTime, Local};
     use splines::{Interpolation, Key, Spline};
 
     use crate::chart::manage_chart_data::{get_max, get_min, update_charts_data};
-    use crate::{ChartType, Language, RunTimeData, StyleType, TrafficChart};
+    use crate::networking::types::data_info::DataInfo;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::{Language, RunTimeData, StyleType, TrafficChart};
 
     fn spline_from_vec(vec: Vec<(i32, i32)>) -> Spline<f32, f32> {
         Spline::from_vec(
@@ -91,174 +264,191 @@ fn spline_from_vec(vec: Vec<(i32, i32)>) -> Spline<f32, f32> {
         )
     }
 
+    /// Traffic of a second, with a single incoming and outgoing packet of the given sizes
+    fn traffic(second: i64, in_bytes: u128, out_bytes: u128) -> DataInfo {
+        let timestamp = DateTime::from_timestamp(second, 500_000_000)
+            .unwrap()
+            .with_timezone(&Local);
+        let mut data_info = DataInfo::default();
+        for (bytes, direction) in [
+            (in_bytes, TrafficDirection::Incoming),
+            (out_bytes, TrafficDirection::Outgoing),
+        ] {
+            if bytes > 0 {
+                data_info.add_packet(bytes, direction, timestamp);
+            }
+        }
+        data_info
+    }
+
     #[test]
-    fn test_chart_data_updates() {
-        let sent_vec = vec![
-            (0, -500),
-            (1, -1000),
-            (2, -1000),
-            (3, -1000),
-            (4, -1000),
-            (5, -1000),
-            (6, -1000),
-            (7, -1000),
-            (8, -1000),
-            (9, -1000),
-            (10, -1000),
-            (11, -1000),
-            (12, -1000),
-            (13, -1000),
-            (14, -1000),
-            (15, -1000),
-            (16, -1000),
-            (17, -1000),
-            (18, -1000),
-            (19, -1000),
-            (20, -1000),
-            (21, -1000),
-            (22, -1000),
-            (23, -1000),
-            (24, -1000),
-            (25, -1000),
-            (26, -1000),
-            (27, -1000),
-            (28, -1000),
-        ];
-        let sent = spline_from_vec(sent_vec);
-        let received_vec = vec![
-            (0, 1000),
-            (1, 21000),
-            (2, 21000),
-            (3, 21000),
-            (4, 21000),
-            (5, 21000),
-            (6, 21000),
-            (7, 21000),
-            (8, 21000),
-            (9, 21000),
-            (10, 21000),
-            (11, 21000),
-            (12, 21000),
-            (13, 21000),
-            (14, 21000),
-            (15, 21000),
-            (16, 21000),
-            (17, 21000),
-            (18, 21000),
-            (19, 21000),
-            (20, 21000),
-            (21, 21000),
-            (22, 21000),
-            (23, 21000),
-            (24, 21000),
-            (25, 21000),
-            (26, 21000),
-            (27, 21000),
-            (28, 21000),
-        ];
-        let received = spline_from_vec(received_vec);
-        let tot_sent = 1000 * 28 + 500;
-        let tot_received = 21000 * 28 + 1000;
-        let mut traffic_chart = TrafficChart {
-            ticks: 29,
-            out_bytes: sent.clone(),
-            in_bytes: received.clone(),
-            out_packets: sent.clone(),
-            in_packets: received.clone(),
-            min_bytes: -1000.0,
-            max_bytes: 21000.0,
-            min_packets: -1000.0,
-            max_packets: 21000.0,
-            language: Language::default(),
-            chart_type: ChartType::Packets,
-            style: StyleType::default(),
-            thumbnail: false,
-        };
-        let mut runtime_data = RunTimeData {
-            all_bytes: 0,
-            all_packets: 0,
-            tot_out_bytes: tot_sent + 1111,
-            tot_in_bytes: tot_received + 2222,
-            tot_out_packets: tot_sent + 3333,
-            tot_in_packets: tot_received + 4444,
-            dropped_packets: 0,
-            tot_out_bytes_prev: tot_sent,
-            tot_in_bytes_prev: tot_received,
-            tot_out_packets_prev: tot_sent,
-            tot_in_packets_prev: tot_received,
-            logged_notifications: Default::default(),
-            tot_emitted_notifications: 0,
-        };
-
-        assert_eq!(get_min(&sent), -1000.0);
-        assert_eq!(get_max(&received), 21000.0);
-
-        update_charts_data(&mut runtime_data, &mut traffic_chart);
-
-        assert_eq!(get_min(&traffic_chart.out_packets), -3333.0);
-        assert_eq!(get_max(&traffic_chart.in_bytes), 21000.0);
-
-        // runtime_data correctly updated?
-        assert_eq!(runtime_data.tot_out_bytes_prev, tot_sent + 1111);
-        assert_eq!(runtime_data.tot_in_bytes_prev, tot_received + 2222);
-        assert_eq!(runtime_data.tot_out_packets_prev, tot_sent + 3333);
-        assert_eq!(runtime_data.tot_in_packets_prev, tot_received + 4444);
-
-        let mut sent_bytes = sent.clone();
-        sent_bytes.add(Key::new(29.0, -1111.0, Interpolation::Cosine));
-        let mut received_packets = received.clone();
-        received_packets.add(Key::new(29.0, 4444.0, Interpolation::Cosine));
-        let mut sent_packets = sent;
-        sent_packets.add(Key::new(29.0, -3333.0, Interpolation::Cosine));
-        let mut received_bytes = received;
-        received_bytes.add(Key::new(29.0, 2222.0, Interpolation::Cosine));
-
-        // traffic_chart correctly updated?
-        assert_eq!(traffic_chart.ticks, 30);
-        assert_eq!(traffic_chart.min_bytes, -1111.0);
-        assert_eq!(traffic_chart.min_packets, -3333.0);
-        assert_eq!(traffic_chart.max_bytes, 21000.0);
-        assert_eq!(traffic_chart.max_packets, 21000.0);
-        assert_eq!(traffic_chart.out_bytes.keys(), sent_bytes.keys());
-        assert_eq!(traffic_chart.in_packets.keys(), received_packets.keys());
-        assert_eq!(traffic_chart.out_packets.keys(), sent_packets.keys());
-        assert_eq!(traffic_chart.in_bytes.keys(), received_bytes.keys());
-
-        runtime_data.tot_out_bytes += 99;
-        runtime_data.tot_in_packets += 990;
-        runtime_data.tot_in_bytes += 2;
-        update_charts_data(&mut runtime_data, &mut traffic_chart);
-        runtime_data.tot_out_bytes += 77;
-        runtime_data.tot_in_packets += 1;
-        runtime_data.tot_out_packets += 220;
-        update_charts_data(&mut runtime_data, &mut traffic_chart);
-
-        sent_bytes.remove(0);
-        sent_bytes.remove(0);
-        sent_bytes.add(Key::new(30.0, -99.0, Interpolation::Cosine));
-        sent_bytes.add(Key::new(31.0, -77.0, Interpolation::Cosine));
-        received_packets.remove(0);
-        received_packets.remove(0);
-        received_packets.add(Key::new(30.0, 990.0, Interpolation::Cosine));
-        received_packets.add(Key::new(31.0, 1.0, Interpolation::Cosine));
-        sent_packets.remove(0);
-        sent_packets.remove(0);
-        sent_packets.add(Key::new(30.0, 0.0, Interpolation::Cosine));
-        sent_packets.add(Key::new(31.0, -220.0, Interpolation::Cosine));
-        received_bytes.remove(0);
-        received_bytes.remove(0);
-        received_bytes.add(Key::new(30.0, 2.0, Interpolation::Cosine));
-        received_bytes.add(Key::new(31.0, 0.0, Interpolation::Cosine));
-
-        // traffic_chart correctly updated?
-        assert_eq!(traffic_chart.ticks, 32);
-        assert_eq!(traffic_chart.min_bytes, -1111.0);
-        assert_eq!(traffic_chart.min_packets, -3333.0);
-        assert_eq!(traffic_chart.max_bytes, 21000.0);
-        assert_eq!(traffic_chart.max_packets, 21000.0);
-        assert_eq!(traffic_chart.out_bytes.keys(), sent_bytes.keys());
-        assert_eq!(traffic_chart.in_packets.keys(), received_packets.keys());
-        assert_eq!(traffic_chart.out_packets.keys(), sent_packets.keys());
-        assert_eq!(traffic_chart.in_bytes.keys(), received_bytes.keys());
+    fn test_get_min_max() {
+        let spline = spline_from_vec(vec![(0, -500), (1, 1000), (2, -1000), (3, 21000)]);
+        assert_eq!(get_min(&spline), -1000.0);
+        assert_eq!(get_max(&spline), 21000.0);
+        assert_eq!(get_min(&Spline::default()), 0.0);
+        assert_eq!(get_max(&Spline::default()), 0.0);
+    }
+
+    #[test]
+    fn test_chart_data_by_packet_timestamp() {
+        let mut traffic_chart = TrafficChart::new(StyleType::default(), Language::default());
+        let mut runtime_data = RunTimeData::new();
+
+        // no packets yet
+        update_charts_data(&mut runtime_data, &mut traffic_chart, Some(1_700_000_000));
+        assert_eq!(traffic_chart.ticks, 0);
+        assert!(traffic_chart.in_bytes.is_empty());
+
+        // the second without traffic is displayed anyway
+        runtime_data.traffic_per_second = BTreeMap::from([
+            (1_700_000_000, traffic(1_700_000_000, 100, 50)),
+            (1_700_000_002, traffic(1_700_000_002, 300, 0)),
+        ]);
+        runtime_data.tot_in_bytes = 400;
+        update_charts_data(&mut runtime_data, &mut traffic_chart, None);
+        assert_eq!(traffic_chart.first_second, Some(1_700_000_000));
+        assert_eq!(traffic_chart.ticks, 3);
+        assert_eq!(
+            traffic_chart.in_bytes.keys(),
+            spline_from_vec(vec![(0, 100), (1, 0), (2, 300)]).keys()
+        );
+        assert_eq!(
+            traffic_chart.out_packets.keys(),
+            spline_from_vec(vec![(0, -1), (1, 0), (2, 0)]).keys()
+        );
+        assert_eq!(runtime_data.tot_in_bytes_prev, 400);
+        assert!(runtime_data.traffic_per_second.is_empty());
+
+        // packets of a second already displayed are summed to the previous ones
+        runtime_data.traffic_per_second = BTreeMap::from([
+            (1_700_000_002, traffic(1_700_000_002, 20, 0)),
+            (1_700_000_003, traffic(1_700_000_003, 0, 10)),
+        ]);
+        update_charts_data(&mut runtime_data, &mut traffic_chart, None);
+        assert_eq!(traffic_chart.ticks, 4);
+        assert_eq!(
+            traffic_chart.in_bytes.keys(),
+            spline_from_vec(vec![(0, 100), (1, 0), (2, 320), (3, 0)]).keys()
+        );
+        assert_eq!(
+            traffic_chart.out_bytes.keys(),
+            spline_from_vec(vec![(0, -50), (1, 0), (2, 0), (3, -10)]).keys()
+        );
+        assert_eq!(traffic_chart.max_bytes, 320.0);
+        assert_eq!(traffic_chart.min_bytes, -50.0);
+        assert_eq!(traffic_chart.max_packets, 2.0);
+        assert_eq!(traffic_chart.min_packets, -1.0);
+
+        // live captures keep moving until the last complete second
+        update_charts_data(&mut runtime_data, &mut traffic_chart, Some(1_700_000_006));
+        assert_eq!(traffic_chart.ticks, 6);
+        assert_eq!(
+            traffic_chart.in_packets.keys(),
+            spline_from_vec(vec![(0, 1), (1, 0), (2, 2), (3, 0), (4, 0), (5, 0)]).keys()
+        );
+    }
+
+    #[test]
+    fn test_chart_data_keeps_last_seconds() {
+        let mut traffic_chart = TrafficChart::new(StyleType::default(), Language::default());
+        let mut runtime_data = RunTimeData::new();
+
+        // a capture file is read faster than its original timing
+        runtime_data.traffic_per_second = (0..40)
+            .map(|second| (1000 + second, traffic(1000 + second, 10, 0)))
+            .collect();
+        update_charts_data(&mut runtime_data, &mut traffic_chart, None);
+        assert_eq!(traffic_chart.ticks, 40);
+        assert_eq!(traffic_chart.in_bytes.len(), 30);
+        assert_eq!(traffic_chart.in_bytes.get(0).unwrap().t, 10.0);
+
+        // a long pause in the capture
+        runtime_data.traffic_per_second = BTreeMap::from([(2000, traffic(2000, 5, 0))]);
+        update_charts_data(&mut runtime_data, &mut traffic_chart, None);
+        assert_eq!(traffic_chart.ticks, 1001);
+        assert_eq!(traffic_chart.in_bytes.len(), 30);
+        assert_eq!(traffic_chart.in_bytes.get(0).unwrap().t, 971.0);
+        assert_eq!(traffic_chart.in_bytes.get(28).unwrap().value, 0.0);
+        assert_eq!(traffic_chart.in_bytes.get(29).unwrap().value, 5.0);
+
+        // seconds too old to be displayed are discarded
+        runtime_data.traffic_per_second = BTreeMap::from([(1500, traffic(1500, 5, 0))]);
+        update_charts_data(&mut runtime_data, &mut traffic_chart, None);
+        assert_eq!(traffic_chart.in_bytes.get(0).unwrap().t, 971.0);
+        assert_eq!(get_max(&traffic_chart.in_bytes), 5.0);
+    }
+
+    #[test]
+    fn test_chart_data_earlier_than_first_second() {
+        let mut traffic_chart = TrafficChart::new(StyleType::default(), Language::default());
+        let mut runtime_data = RunTimeData::new();
+        runtime_data.traffic_per_second = BTreeMap::from([
+            (1_700_000_010, traffic(1_700_000_010, 100, 0)),
+            (1_700_000_011, traffic(1_700_000_011, 200, 0)),
+        ]);
+        update_charts_data(&mut runtime_data, &mut traffic_chart, None);
+        assert_eq!(traffic_chart.ticks, 2);
+
+        // packets out of order move the chart start back, instead of piling up on the first second
+        runtime_data.traffic_per_second =
+            BTreeMap::from([(1_700_000_008, traffic(1_700_000_008, 50, 0))]);
+        update_charts_data(&mut runtime_data, &mut traffic_chart, None);
+        assert_eq!(traffic_chart.first_second, Some(1_700_000_008));
+        assert_eq!(traffic_chart.ticks, 4);
+        assert_eq!(
+            traffic_chart.in_bytes.keys(),
+            spline_from_vec(vec![(0, 50), (1, 0), (2, 100), (3, 200)]).keys()
+        );
+    }
+
+    #[test]
+    fn test_chart_data_updates_per_adapter() {
+        let mut traffic_chart = TrafficChart::new(StyleType::default(), Language::default());
+        let mut runtime_data = RunTimeData::new();
+
+        runtime_data.traffic_per_second = BTreeMap::from([
+            (1000, traffic(1000, 1000, 700)),
+            (1001, traffic(1001, 300, 0)),
+        ]);
+        runtime_data.adapters_per_second = BTreeMap::from([
+            (
+                1000,
+                HashMap::from([
+                    ("eth0".to_string(), traffic(1000, 1000, 200)),
+                    ("wg0".to_string(), traffic(1000, 0, 500)),
+                ]),
+            ),
+            (
+                1001,
+                HashMap::from([("eth0".to_string(), traffic(1001, 300, 0))]),
+            ),
+        ]);
+        update_charts_data(&mut runtime_data, &mut traffic_chart, None);
+
+        let eth0_traffic = traffic_chart.adapters.get("eth0").unwrap();
+        assert_eq!(
+            eth0_traffic.in_bytes.keys(),
+            spline_from_vec(vec![(0, 1000), (1, 300)]).keys()
+        );
+        assert_eq!(
+            eth0_traffic.out_bytes.keys(),
+            spline_from_vec(vec![(0, -200), (1, 0)]).keys()
+        );
+        assert_eq!(
+            eth0_traffic.in_packets.keys(),
+            spline_from_vec(vec![(0, 1), (1, 1)]).keys()
+        );
+        // adapters without traffic in a second are displayed anyway
+        let wg0_traffic = traffic_chart.adapters.get("wg0").unwrap();
+        assert_eq!(
+            wg0_traffic.out_bytes.keys(),
+            spline_from_vec(vec![(0, -500), (1, 0)]).keys()
+        );
+        assert_eq!(
+            wg0_traffic.in_packets.keys(),
+            spline_from_vec(vec![(0, 0), (1, 0)]).keys()
+        );
+        assert!(runtime_data.adapters_per_second.is_empty());
     }
 }
@@ -1,6 +1,7 @@
 //! This module defines the behavior of the `TrafficChart` struct, used to display chart in GUI run page
 
 use std::cmp::min;
+use std::collections::BTreeMap;
 use std::ops::Range;
 
 use iced::widget::Container;
@@ -19,8 +20,10 @@
 
 /// Struct defining the chart to be displayed in gui run page
 pub struct TrafficChart {
-    /// Current time interval number
+    /// Number of seconds displayed since the first one (i.e., the number of time intervals)
     pub ticks: u32,
+    /// Timestamp of the first second with traffic, according to the packets timestamps
+    pub first_second: Option<i64>,
     /// Sent bytes filtered and their time occurrence
     pub out_bytes: Spline<f32, f32>,
     /// Received bytes filtered and their time occurrence
@@ -45,12 +48,49 @@ pub struct TrafficChart {
     pub style: StyleType,
     /// Whether the chart is for the thumbnail page
     pub thumbnail: bool,
+    /// Whether a separate series is displayed for each network adapter
+    pub split_by_adapter: bool,
+    /// Traffic of each network adapter
+    pub adapters: BTreeMap<String, AdapterTraffic>,
+}
+
+/// Traffic of a single network adapter, displayed when the chart is split by adapter
+#[derive(Default)]
+pub struct AdapterTraffic {
+    /// Sent bytes filtered and their time occurrence
+    pub out_bytes: Spline<f32, f32>,
+    /// Received bytes filtered and their time occurrence
+    pub in_bytes: Spline<f32, f32>,
+    /// Sent packets filtered and their time occurrence
+    pub out_packets: Spline<f32, f32>,
+    /// Received packets filtered and their time occurrence
+    pub in_packets: Spline<f32, f32>,
+}
+
+impl AdapterTraffic {
+    fn spline_to_plot(
+        &self,
+        chart_type: ChartType,
+        direction: TrafficDirection,
+    ) -> &Spline<f32, f32> {
+        match chart_type {
+            ChartType::Packets => match direction {
+                TrafficDirection::Incoming => &self.in_packets,
+                TrafficDirection::Outgoing => &self.out_packets,
+            },
+            ChartType::Bytes => match direction {
+                TrafficDirection::Incoming => &self.in_bytes,
+                TrafficDirection::Outgoing => &self.out_bytes,
+            },
+        }
+    }
 }
 
 impl TrafficChart {
     pub fn new(style: StyleType, language: Language) -> Self {
         TrafficChart {
             ticks: 0,
+            first_second: None,
             out_bytes: Spline::default(),
             in_bytes: Spline::default(),
             out_packets: Spline::default(),
@@ -63,6 +103,8 @@ pub fn new(style: StyleType, language: Language) -> Self {
             chart_type: ChartType::Bytes,
             style,
             thumbnail: false,
+            split_by_adapter: false,
+            adapters: BTreeMap::new(),
         }
     }
 
@@ -221,16 +263,41 @@ fn build_chart<DB: DrawingBackend>(
             .draw()
             .unwrap();
 
-        // draw incoming and outgoing series
-        for direction in [TrafficDirection::Incoming, TrafficDirection::Outgoing] {
-            let area_series = self.area_series(direction);
-            let label = self.series_label(direction);
-            let legend_style = self.series_color(direction).filled();
-            chart
-                .draw_series(area_series)
-                .expect("Error drawing graph")
-                .label(label)
-                .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 25, y + 5)], legend_style));
+        if self.split_by_adapter {
+            // draw incoming and outgoing series of each adapter, with the same color
+            for (i, (adapter, adapter_traffic)) in self.adapters.iter().enumerate() {
+                let color = Palette99::pick(i).to_rgba();
+                let legend_style = color.filled();
+                for direction in [TrafficDirection::Incoming, TrafficDirection::Outgoing] {
+                    // lighter filling than usual, since series of different adapters overlap
+                    let area_series = AreaSeries::new(
+                        sample_spline(adapter_traffic.spline_to_plot(self.chart_type, direction)),
+                        0.0,
+                        color.mix(0.15),
+                    )
+                    .border_style(ShapeStyle::from(&color).stroke_width(CHARTS_LINE_BORDER));
+                    let series = chart.draw_series(area_series).expect("Error drawing graph");
+                    if direction == TrafficDirection::Incoming {
+                        series.label(adapter).legend(move |(x, y)| {
+                            Rectangle::new([(x, y - 5), (x + 25, y + 5)], legend_style)
+                        });
+                    }
+                }
+            }
+        } else {
+            // draw incoming and outgoing series
+            for direction in [TrafficDirection::Incoming, TrafficDirection::Outgoing] {
+                let area_series = self.area_series(direction);
+                let label = self.series_label(direction);
+                let legend_style = self.series_color(direction).filled();
+                chart
+                    .draw_series(area_series)
+                    .expect("Error drawing graph")
+                    .label(label)
+                    .legend(move |(x, y)| {
+                        Rectangle::new([(x, y - 5), (x + 25, y + 5)], legend_style)
+                    });
+            }
         }
 
         // chart legend
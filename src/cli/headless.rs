@@ -0,0 +1,356 @@
+//! Module implementing the headless mode: traffic is analyzed without starting the GUI,
+//! and a report is periodically printed on the standard output.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+use pcap::Device;
+use serde_json::{json, Value};
+
+use crate::chart::types::chart_type::ChartType;
+use crate::cli::CliArgs;
+use crate::mmdb::asn::ASN_MMDB;
+use crate::mmdb::country::COUNTRY_MMDB;
+use crate::mmdb::types::mmdb_reader::MmdbReader;
+use crate::networking::types::capture_context::CaptureContext;
+use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::data_info_host::DataInfoHost;
+use crate::networking::types::host::Host;
+use crate::networking::types::my_device::MyDevice;
+use crate::networking::user_services::load_user_services;
+use crate::report::export_entries::export_entries;
+use crate::report::get_report_entries::{get_host_entries, get_service_entries};
+use crate::report::types::host_sort_metric::HostSortMetric;
+use crate::report::types::search_parameters::SearchParameters;
+use crate::report::types::sort_type::SortType;
+use crate::secondary_threads::parse_packets::parse_packets;
+use crate::{
+    ByteMultiple, ConfigDevice, Configs, InfoTraffic, ReportSortType, Service, SNIFFNET_LOWERCASE,
+};
+
+/// Maximum number of hosts and services included in each report
+const TOP_ENTRIES: usize = 10;
+
+/// Analyzes the traffic of the selected source, printing a report every `interval` seconds.
+///
+/// The process exits when the whole capture file has been read, or when it's interrupted.
+pub fn run_headless(cli_args: CliArgs, configs: &Configs) -> ! {
+    let (device, capture_context) = if let Some(file_path) = &cli_args.import_pcap {
+        (
+            MyDevice::from_capture_file(file_path),
+            CaptureContext::new_offline(file_path, cli_args.real_time, &cli_args.filters.bpf),
+        )
+    } else {
+        let device_name = cli_args
+            .interface
+            .clone()
+            .unwrap_or_else(|| configs.device.device_name.clone());
+        if !Device::list()
+            .unwrap_or_default()
+            .iter()
+            .any(|dev| dev.name.eq(&device_name))
+        {
+            exit_with_error(&format!("network adapter '{device_name}' not found"));
+        }
+        let device = ConfigDevice { device_name }.to_my_device();
+        let capture_context = CaptureContext::new(
+            &device,
+            &cli_args.export_pcap,
+            cli_args.pcap_rotation,
+            &cli_args.filters,
+            configs.settings.snaplen,
+        );
+        (device, capture_context)
+    };
+    if let Some(error) = capture_context.error() {
+        exit_with_error(error);
+    }
+
+    let current_capture_id = Arc::new(Mutex::new(0));
+    let info_traffic = Arc::new(Mutex::new(InfoTraffic::new()));
+    let country_mmdb_reader = Arc::new(MmdbReader::from(
+        &configs.settings.mmdb_country,
+        COUNTRY_MMDB,
+    ));
+    let asn_mmdb_reader = Arc::new(MmdbReader::from(&configs.settings.mmdb_asn, ASN_MMDB));
+    for error in load_user_services(&configs.settings.services_file) {
+        eprintln!("{SNIFFNET_LOWERCASE}: services file: {error}");
+    }
+    let filters = cli_args.filters;
+    let reverse_dns = cli_args.reverse_dns && configs.settings.reverse_dns;
+    let info_traffic_mutex = info_traffic.clone();
+    let parse_packets_handle = thread::Builder::new()
+        .name("thread_parse_packets".to_string())
+        .spawn(move || {
+            parse_packets(
+                &current_capture_id,
+                &device,
+                &filters,
+                &info_traffic_mutex,
+                &country_mmdb_reader,
+                &asn_mmdb_reader,
+                capture_context,
+                reverse_dns,
+            );
+        })
+        .unwrap();
+
+    let interval = Duration::from_secs(cli_args.interval);
+    loop {
+        // wake up periodically, to promptly report the end of an imported file
+        let start = Instant::now();
+        while start.elapsed() < interval && !parse_packets_handle.is_finished() {
+            thread::sleep(Duration::from_millis(100).min(interval - start.elapsed()));
+        }
+        let report = get_report(&info_traffic, cli_args.json);
+        println!("{report}");
+        if let Some(path) = &cli_args.export {
+            if let Err(error) = export_entries(
+                &info_traffic.lock().unwrap(),
+                &SearchParameters::default(),
+                ReportSortType::default(),
+                path,
+            ) {
+                eprintln!(
+                    "{SNIFFNET_LOWERCASE}: failed to export connections to '{path}': {error}"
+                );
+            }
+        }
+        // the traffic of each second is only needed by the charts of the GUI
+        let mut info_traffic_lock = info_traffic.lock().unwrap();
+        info_traffic_lock.traffic_per_second.clear();
+        info_traffic_lock.adapters_per_second.clear();
+        drop(info_traffic_lock);
+        if parse_packets_handle.is_finished() {
+            if let Some(error) = &info_traffic.lock().unwrap().capture_error {
+                exit_with_error(error);
+            }
+            std::process::exit(0);
+        }
+    }
+}
+
+fn exit_with_error(error: &str) -> ! {
+    eprintln!("{SNIFFNET_LOWERCASE}: {error}");
+    std::process::exit(1);
+}
+
+/// Returns the report of the traffic observed so far, formatted as a table or as a JSON line
+fn get_report(info_traffic: &Arc<Mutex<InfoTraffic>>, json: bool) -> String {
+    let mut hosts = get_host_entries(
+        info_traffic,
+        ChartType::Bytes,
+        SortType::Descending,
+        HostSortMetric::Traffic,
+    );
+    hosts.truncate(TOP_ENTRIES);
+    let mut services = get_service_entries(info_traffic, ChartType::Bytes, SortType::Descending);
+    services.truncate(TOP_ENTRIES);
+    let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let info_traffic_lock = info_traffic.lock().unwrap();
+    if json {
+        json_report(&timestamp, &info_traffic_lock, &hosts, &services).to_string()
+    } else {
+        table_report(&timestamp, &info_traffic_lock, &hosts, &services)
+    }
+}
+
+fn table_report(
+    timestamp: &str,
+    info_traffic: &InfoTraffic,
+    hosts: &[(Host, DataInfoHost)],
+    services: &[(Service, DataInfo)],
+) -> String {
+    let filtered_packets = info_traffic.tot_in_packets + info_traffic.tot_out_packets;
+    let filtered_bytes = info_traffic.tot_in_bytes + info_traffic.tot_out_bytes;
+    let mut report = format!(
+        "[{timestamp}]\n\
+        Packets: {filtered_packets} filtered of {} observed (in: {}, out: {}, dropped: {})\n\
+        Bytes: {} filtered of {} observed (in: {}, out: {})\n",
+        info_traffic.all_packets,
+        info_traffic.tot_in_packets,
+        info_traffic.tot_out_packets,
+        info_traffic.dropped_packets,
+        ByteMultiple::formatted_string(filtered_bytes),
+        ByteMultiple::formatted_string(info_traffic.all_bytes),
+        ByteMultiple::formatted_string(info_traffic.tot_in_bytes),
+        ByteMultiple::formatted_string(info_traffic.tot_out_bytes),
+    );
+
+    report.push_str(&format!(
+        "\n{:<40} {:<7} {:<30} {:>10} {:>10}\n",
+        "HOST", "COUNTRY", "ASN", "PACKETS", "BYTES"
+    ));
+    for (host, data_info_host) in hosts {
+        report.push_str(&format!(
+            "{:<40} {:<7} {:<30} {:>10} {:>10}\n",
+            host.domain,
+            host.country.to_string(),
+            host.asn.name,
+            data_info_host.data_info.tot_packets(),
+            ByteMultiple::formatted_string(data_info_host.data_info.tot_bytes())
+        ));
+    }
+
+    report.push_str(&format!(
+        "\n{:<40} {:>10} {:>10}\n",
+        "SERVICE", "PACKETS", "BYTES"
+    ));
+    for (service, data_info) in services {
+        report.push_str(&format!(
+            "{:<40} {:>10} {:>10}\n",
+            service.to_string(),
+            data_info.tot_packets(),
+            ByteMultiple::formatted_string(data_info.tot_bytes())
+        ));
+    }
+
+    report
+}
+
+fn json_report(
+    timestamp: &str,
+    info_traffic: &InfoTraffic,
+    hosts: &[(Host, DataInfoHost)],
+    services: &[(Service, DataInfo)],
+) -> Value {
+    json!({
+        "timestamp": timestamp,
+        "packets": {
+            "observed": info_traffic.all_packets,
+            "incoming": info_traffic.tot_in_packets,
+            "outgoing": info_traffic.tot_out_packets,
+            "dropped": info_traffic.dropped_packets,
+        },
+        "bytes": {
+            "observed": info_traffic.all_bytes,
+            "incoming": info_traffic.tot_in_bytes,
+            "outgoing": info_traffic.tot_out_bytes,
+        },
+        "hosts": hosts.iter().map(|(host, data_info_host)| json!({
+            "domain": host.domain,
+            "country": host.country.to_string(),
+            "asn": {
+                "number": host.asn.number,
+                "name": host.asn.name,
+            },
+            "traffic": data_info_json(&data_info_host.data_info),
+        })).collect::<Vec<Value>>(),
+        "services": services.iter().map(|(service, data_info)| json!({
+            "service": service.to_string(),
+            "traffic": data_info_json(data_info),
+        })).collect::<Vec<Value>>(),
+    })
+}
+
+fn data_info_json(data_info: &DataInfo) -> Value {
+    json!({
+        "incoming_packets": data_info.incoming_packets(),
+        "outgoing_packets": data_info.outgoing_packets(),
+        "incoming_bytes": data_info.incoming_bytes(),
+        "outgoing_bytes": data_info.outgoing_bytes(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::countries::types::country::Country;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::tcp_quality::TcpQuality;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::networking::types::traffic_type::TrafficType;
+
+    use super::*;
+
+    fn info_traffic_for_tests() -> InfoTraffic {
+        let mut info_traffic = InfoTraffic::new();
+        info_traffic.all_packets = 10;
+        info_traffic.all_bytes = 2000;
+        info_traffic.tot_in_packets = 5;
+        info_traffic.tot_out_packets = 3;
+        info_traffic.tot_in_bytes = 1200;
+        info_traffic.tot_out_bytes = 600;
+        info_traffic
+    }
+
+    fn data_info_for_tests() -> DataInfo {
+        let now = Local::now();
+        let mut data_info = DataInfo::new_with_first_packet(1200, TrafficDirection::Incoming, now);
+        data_info.add_packet(600, TrafficDirection::Outgoing, now);
+        data_info
+    }
+
+    fn host_entries_for_tests() -> Vec<(Host, DataInfoHost)> {
+        let host = Host {
+            domain: "example.com".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE".to_string(),
+            },
+            country: Country::US,
+        };
+        let data_info_host = DataInfoHost {
+            data_info: data_info_for_tests(),
+            is_favorite: false,
+            is_loopback: false,
+            is_local: false,
+            traffic_type: TrafficType::Unicast,
+            tcp_quality: TcpQuality::default(),
+        };
+        vec![(host, data_info_host)]
+    }
+
+    fn service_entries_for_tests() -> Vec<(Service, DataInfo)> {
+        vec![(Service::Name("https"), data_info_for_tests())]
+    }
+
+    #[test]
+    fn test_json_report() {
+        let report = json_report(
+            "2024-04-20 10:00:00",
+            &info_traffic_for_tests(),
+            &host_entries_for_tests(),
+            &service_entries_for_tests(),
+        );
+        assert_eq!(
+            report.to_string(),
+            "{\"bytes\":{\"incoming\":1200,\"observed\":2000,\"outgoing\":600},\
+            \"hosts\":[{\"asn\":{\"name\":\"GOOGLE\",\"number\":15169},\"country\":\"US\",\"domain\":\"example.com\",\
+            \"traffic\":{\"incoming_bytes\":1200,\"incoming_packets\":1,\"outgoing_bytes\":600,\"outgoing_packets\":1}}],\
+            \"packets\":{\"dropped\":0,\"incoming\":5,\"observed\":10,\"outgoing\":3},\
+            \"services\":[{\"service\":\"https\",\
+            \"traffic\":{\"incoming_bytes\":1200,\"incoming_packets\":1,\"outgoing_bytes\":600,\"outgoing_packets\":1}}],\
+            \"timestamp\":\"2024-04-20 10:00:00\"}"
+        );
+    }
+
+    #[test]
+    fn test_table_report() {
+        let report = table_report(
+            "2024-04-20 10:00:00",
+            &info_traffic_for_tests(),
+            &host_entries_for_tests(),
+            &service_entries_for_tests(),
+        );
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "[2024-04-20 10:00:00]");
+        assert_eq!(
+            lines[1],
+            "Packets: 8 filtered of 10 observed (in: 5, out: 3, dropped: 0)"
+        );
+        assert_eq!(
+            lines[2],
+            "Bytes: 1.8 KB filtered of 2.0 KB observed (in: 1.2 KB, out: 600 B)"
+        );
+        assert!(lines[4].starts_with("HOST"));
+        assert!(lines[5].starts_with("example.com"));
+        assert!(lines[5].contains("US"));
+        assert!(lines[5].contains("GOOGLE"));
+        assert!(lines[5].ends_with("1.8 KB"));
+        assert!(lines[7].starts_with("SERVICE"));
+        assert!(lines[8].starts_with("https"));
+        assert!(lines[8].ends_with("1.8 KB"));
+    }
+}
@@ -1,32 +1,262 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::networking::types::filters::Filters;
+use crate::networking::types::ip_collection::AddressCollection;
+use crate::networking::types::pcap_rotation::PcapRotation;
+use crate::networking::types::port_collection::PortCollection;
 use crate::utils::formatted_strings::APP_VERSION;
-use crate::{Configs, SNIFFNET_LOWERCASE};
+use crate::{Configs, IpVersion, Protocol, SNIFFNET_LOWERCASE};
+
+pub mod headless;
+
+/// Options supplied via CLI which affect the app execution
+#[derive(Debug, PartialEq)]
+pub struct CliArgs {
+    /// PCAP file to be analyzed in place of a network adapter
+    pub import_pcap: Option<String>,
+    /// Whether the imported PCAP file should be replayed respecting its original timing
+    pub real_time: bool,
+    /// Whether traffic should be reported on the standard output without starting the GUI
+    pub headless: bool,
+    /// Name of the network adapter to be analyzed
+    pub interface: Option<String>,
+    /// Filters to apply on the observed traffic
+    pub filters: Filters,
+    /// Seconds between two consecutive reports (headless mode)
+    pub interval: u64,
+    /// Whether reports should be printed as JSON lines instead of tables (headless mode)
+    pub json: bool,
+    /// File where all the connections are written at every report (headless mode)
+    pub export: Option<String>,
+    /// Whether host names should be looked up via reverse DNS
+    pub reverse_dns: bool,
+    /// PCAP file where the filtered packets are recorded
+    pub export_pcap: Option<String>,
+    /// Criteria to split the recorded packets into several files
+    pub pcap_rotation: PcapRotation,
+}
+
+impl Default for CliArgs {
+    fn default() -> Self {
+        Self {
+            import_pcap: None,
+            real_time: false,
+            headless: false,
+            interface: None,
+            filters: Filters::default(),
+            interval: 5,
+            json: false,
+            export: None,
+            reverse_dns: true,
+            export_pcap: None,
+            pcap_rotation: PcapRotation::default(),
+        }
+    }
+}
 
 /// Parse CLI arguments, and exit if `--help`, `--version`, or an
 /// unknown argument was supplied
-pub fn parse_cli_args() {
-    let mut args = std::env::args().skip(1);
-    if let Some(arg) = args.next() {
+pub fn parse_cli_args() -> CliArgs {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(cli_args) => cli_args,
+        Err(exit_code) => std::process::exit(exit_code),
+    }
+}
+
+/// Returns the parsed options, or the exit code in case the app shouldn't start
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<CliArgs, i32> {
+    let mut cli_args = CliArgs::default();
+    while let Some(arg) = args.next() {
         match arg.as_str() {
-            "--help" | "-h" => print_help(),
-            "--version" | "-v" => print_version(),
-            "--restore-default" => restore_default(),
+            "--help" | "-h" => {
+                print_help();
+                return Err(0);
+            }
+            "--version" | "-v" => {
+                print_version();
+                return Err(0);
+            }
+            "--restore-default" => {
+                restore_default();
+                return Err(0);
+            }
+            "--import-pcap" => {
+                let Some(path) = args.next() else {
+                    missing_value(&arg);
+                    return Err(1);
+                };
+                cli_args.import_pcap = Some(path);
+            }
+            "--real-time" => cli_args.real_time = true,
+            "--headless" => cli_args.headless = true,
+            "--json" => cli_args.json = true,
+            "--no-rdns" => cli_args.reverse_dns = false,
+            "--interface" | "-i" => {
+                let Some(name) = args.next() else {
+                    missing_value(&arg);
+                    return Err(1);
+                };
+                cli_args.interface = Some(name);
+            }
+            "--interval" => {
+                let Some(value) = args.next() else {
+                    missing_value(&arg);
+                    return Err(1);
+                };
+                match value.parse::<u64>() {
+                    Ok(seconds) if seconds > 0 => cli_args.interval = seconds,
+                    _ => {
+                        invalid_value(&arg, &value);
+                        return Err(1);
+                    }
+                }
+            }
+            "--export" => {
+                let Some(path) = args.next() else {
+                    missing_value(&arg);
+                    return Err(1);
+                };
+                cli_args.export = Some(path);
+            }
+            "--export-pcap" => {
+                let Some(path) = args.next() else {
+                    missing_value(&arg);
+                    return Err(1);
+                };
+                cli_args.export_pcap = Some(path);
+            }
+            "--rotate-mb" | "--rotate-minutes" | "--max-files" => {
+                let Some(value) = args.next() else {
+                    missing_value(&arg);
+                    return Err(1);
+                };
+                if !set_rotation(&mut cli_args.pcap_rotation, &arg, &value) {
+                    invalid_value(&arg, &value);
+                    return Err(1);
+                }
+            }
+            "--bpf" => {
+                let Some(expression) = args.next() else {
+                    missing_value(&arg);
+                    return Err(1);
+                };
+                // the expression is compiled right away, to report its errors before starting
+                cli_args.filters.set_bpf(expression);
+                if let Some(error) = &cli_args.filters.bpf_error {
+                    invalid_value(&arg, &format!("{}: {error}", cli_args.filters.bpf));
+                    return Err(1);
+                }
+            }
+            "--ip-version" | "--protocol" | "--address" | "--port" => {
+                let Some(value) = args.next() else {
+                    missing_value(&arg);
+                    return Err(1);
+                };
+                if !set_filter(&mut cli_args.filters, &arg, &value) {
+                    invalid_value(&arg, &value);
+                    return Err(1);
+                }
+            }
             _ => {
                 unknown_argument(&arg);
-                std::process::exit(1);
+                return Err(1);
             }
         }
-        std::process::exit(0);
+    }
+    if cli_args.export_pcap.is_none() && cli_args.pcap_rotation != PcapRotation::default() {
+        missing_option("--export-pcap");
+        return Err(1);
+    }
+    Ok(cli_args)
+}
+
+/// Updates the rotation criteria with the value supplied for the given option;
+/// returns false if the value is not a positive number
+fn set_rotation(pcap_rotation: &mut PcapRotation, arg: &str, value: &str) -> bool {
+    let Ok(limit) = value.parse::<u64>() else {
+        return false;
+    };
+    if limit == 0 {
+        return false;
+    }
+    match arg {
+        "--rotate-mb" => pcap_rotation.max_size_mb = Some(limit),
+        "--rotate-minutes" => pcap_rotation.max_minutes = Some(limit),
+        "--max-files" => {
+            pcap_rotation.max_files = Some(usize::try_from(limit).unwrap_or(usize::MAX));
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Updates the filters with the value supplied for the given option;
+/// returns false if the value is not valid
+fn set_filter(filters: &mut Filters, arg: &str, value: &str) -> bool {
+    match arg {
+        "--ip-version" => parse_list(value, &IpVersion::ALL)
+            .map(|ip_versions| filters.ip_versions = ip_versions)
+            .is_some(),
+        "--protocol" => parse_list(value, &Protocol::ALL)
+            .map(|protocols| filters.protocols = protocols)
+            .is_some(),
+        "--address" => AddressCollection::new(value)
+            .map(|address_collection| {
+                filters.address_str = value.to_string();
+                filters.address_collection = address_collection;
+            })
+            .is_some(),
+        "--port" => PortCollection::new(value)
+            .map(|port_collection| {
+                filters.port_str = value.to_string();
+                filters.port_collection = port_collection;
+            })
+            .is_some(),
+        _ => false,
     }
 }
 
+/// Parses a comma separated list of values, comparing them case-insensitively with
+/// the string representation of the allowed ones
+fn parse_list<T: Copy + Eq + Hash + ToString>(value: &str, all: &[T]) -> Option<HashSet<T>> {
+    let mut set = HashSet::new();
+    for item in value.split(',').map(str::trim) {
+        let parsed = all
+            .iter()
+            .find(|candidate| candidate.to_string().eq_ignore_ascii_case(item))?;
+        set.insert(*parsed);
+    }
+    Some(set)
+}
+
 fn print_help() {
     println!(
         "Application to comfortably monitor your Internet traffic\n\
         Usage: {SNIFFNET_LOWERCASE} [OPTIONS]\n\
         Options:\n\
-        \t-h, --help            Print help\n\
-        \t--restore-default     Restore default settings\n\
-        \t-v, --version         Print version info\n\
+        \t-h, --help                 Print help\n\
+        \t--import-pcap <FILE>       Analyze the given PCAP file instead of a network adapter\n\
+        \t--real-time                Replay the imported PCAP file with its original timing\n\
+        \t-i, --interface <NAME>     Network adapter to be analyzed\n\
+        \t--ip-version <VERSIONS>    IP versions to be considered (e.g., ipv4,ipv6)\n\
+        \t--protocol <PROTOCOLS>     Protocols to be considered (e.g., tcp,udp,icmp,arp/l2)\n\
+        \t--address <ADDRESSES>      IP addresses to be considered (e.g., 10.0.0.0/8,!10.0.0.1)\n\
+        \t--port <PORTS>             Ports to be considered (e.g., 8000-8999,!53)\n\
+        \t--bpf <EXPRESSION>         BPF filter to attach to the capture (e.g., 'not arp')\n\
+        \t--no-rdns                  Identify hosts by address instead of looking up their names\n\
+        \t--headless                 Print traffic reports on the terminal without starting the GUI\n\
+        \t--interval <SECONDS>       Seconds between two reports in headless mode (default: 5)\n\
+        \t--json                     Print headless reports as JSON lines instead of tables\n\
+        \t--export <FILE>            Write all connections to FILE at every report in headless mode\n\
+        \t                           (CSV, or JSON if FILE ends with .json)\n\
+        \t--export-pcap <FILE>       Record the filtered packets to the given PCAP file\n\
+        \t                           (PCAPNG if FILE ends with .pcapng)\n\
+        \t--rotate-mb <MB>           Start a new PCAP file when the current one reaches MB megabytes\n\
+        \t--rotate-minutes <MIN>     Start a new PCAP file every MIN minutes\n\
+        \t--max-files <N>            Keep only the last N PCAP files, deleting the oldest ones\n\
+        \t--restore-default          Restore default settings\n\
+        \t-v, --version              Print version info\n\
         (Run without options to start the app)"
     );
 }
@@ -40,6 +270,27 @@ fn restore_default() {
     println!("Default settings have been restored");
 }
 
+fn missing_value(arg: &str) {
+    eprintln!(
+        "{SNIFFNET_LOWERCASE}: option '{arg}' requires a value\n\
+        For more information, try '{SNIFFNET_LOWERCASE} --help'"
+    );
+}
+
+fn invalid_value(arg: &str, value: &str) {
+    eprintln!(
+        "{SNIFFNET_LOWERCASE}: invalid value '{value}' for option '{arg}'\n\
+        For more information, try '{SNIFFNET_LOWERCASE} --help'"
+    );
+}
+
+fn missing_option(option: &str) {
+    eprintln!(
+        "{SNIFFNET_LOWERCASE}: option '{option}' is required by the supplied options\n\
+        For more information, try '{SNIFFNET_LOWERCASE} --help'"
+    );
+}
+
 fn unknown_argument(arg: &str) {
     eprintln!(
         "{SNIFFNET_LOWERCASE}: unknown option '{arg}'\n\
@@ -60,6 +311,147 @@ mod tests {
 
     use super::*;
 
+    fn args(list: &[&str]) -> impl Iterator<Item = String> {
+        list.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_parse_import_pcap_args() {
+        assert_eq!(parse_args(args(&[])), Ok(CliArgs::default()));
+        assert_eq!(
+            parse_args(args(&["--import-pcap", "trace.pcapng"])),
+            Ok(CliArgs {
+                import_pcap: Some("trace.pcapng".to_string()),
+                ..CliArgs::default()
+            })
+        );
+        assert_eq!(
+            parse_args(args(&["--real-time", "--import-pcap", "trace.pcap"])),
+            Ok(CliArgs {
+                import_pcap: Some("trace.pcap".to_string()),
+                real_time: true,
+                ..CliArgs::default()
+            })
+        );
+        assert_eq!(parse_args(args(&["--import-pcap"])), Err(1));
+        assert_eq!(parse_args(args(&["--unknown"])), Err(1));
+    }
+
+    #[test]
+    fn test_parse_headless_args() {
+        let cli_args = parse_args(args(&[
+            "--headless",
+            "-i",
+            "eth0",
+            "--interval",
+            "10",
+            "--json",
+        ]))
+        .unwrap();
+        assert!(cli_args.headless);
+        assert!(cli_args.json);
+        assert!(cli_args.reverse_dns);
+        assert_eq!(cli_args.interface, Some("eth0".to_string()));
+        assert_eq!(cli_args.interval, 10);
+        assert_eq!(cli_args.filters, Filters::default());
+
+        let cli_args = parse_args(args(&["--bpf", "vlan and tcp port 443"])).unwrap();
+        assert_eq!(cli_args.filters.bpf, "vlan and tcp port 443");
+        assert_eq!(cli_args.filters.bpf_error, None);
+        assert_eq!(parse_args(args(&["--bpf"])), Err(1));
+        assert_eq!(parse_args(args(&["--bpf", "tcp port"])), Err(1));
+
+        let cli_args = parse_args(args(&["--headless", "--export", "connections.json"])).unwrap();
+        assert_eq!(cli_args.export, Some("connections.json".to_string()));
+        assert_eq!(parse_args(args(&["--export"])), Err(1));
+
+        let cli_args = parse_args(args(&["--headless", "--no-rdns"])).unwrap();
+        assert!(!cli_args.reverse_dns);
+
+        assert_eq!(parse_args(args(&["--interval", "0"])), Err(1));
+        assert_eq!(parse_args(args(&["--interval", "five"])), Err(1));
+        assert_eq!(parse_args(args(&["--interface"])), Err(1));
+    }
+
+    #[test]
+    fn test_parse_export_pcap_args() {
+        let cli_args = parse_args(args(&["--export-pcap", "capture.pcap"])).unwrap();
+        assert_eq!(cli_args.export_pcap, Some("capture.pcap".to_string()));
+        assert_eq!(cli_args.pcap_rotation, PcapRotation::default());
+
+        let cli_args = parse_args(args(&[
+            "--rotate-mb",
+            "100",
+            "--rotate-minutes",
+            "60",
+            "--max-files",
+            "10",
+            "--export-pcap",
+            "capture.pcap",
+        ]))
+        .unwrap();
+        assert_eq!(
+            cli_args.pcap_rotation,
+            PcapRotation {
+                max_size_mb: Some(100),
+                max_minutes: Some(60),
+                max_files: Some(10),
+            }
+        );
+
+        // rotation requires an output file
+        assert_eq!(parse_args(args(&["--max-files", "10"])), Err(1));
+        assert_eq!(
+            parse_args(args(&["--export-pcap", "a.pcap", "--rotate-mb", "0"])),
+            Err(1)
+        );
+        assert_eq!(
+            parse_args(args(&["--export-pcap", "a.pcap", "--rotate-minutes", "x"])),
+            Err(1)
+        );
+        assert_eq!(parse_args(args(&["--export-pcap"])), Err(1));
+    }
+
+    #[test]
+    fn test_parse_filters_args() {
+        let cli_args = parse_args(args(&[
+            "--ip-version",
+            "ipv4",
+            "--protocol",
+            "TCP,udp",
+            "--address",
+            "192.168.1.1, 10.0.0.0-10.0.0.255",
+            "--port",
+            "443,8000-8999",
+        ]))
+        .unwrap();
+        let filters = cli_args.filters;
+        assert_eq!(filters.ip_versions, HashSet::from([IpVersion::IPv4]));
+        assert_eq!(
+            filters.protocols,
+            HashSet::from([Protocol::TCP, Protocol::UDP])
+        );
+        assert_eq!(filters.address_str, "192.168.1.1, 10.0.0.0-10.0.0.255");
+        assert_eq!(
+            filters.address_collection,
+            AddressCollection::new("192.168.1.1, 10.0.0.0-10.0.0.255").unwrap()
+        );
+        assert_eq!(filters.port_str, "443,8000-8999");
+        assert_eq!(
+            filters.port_collection,
+            PortCollection::new("443,8000-8999").unwrap()
+        );
+        assert!(filters.are_valid());
+
+        assert_eq!(parse_args(args(&["--ip-version", "ipv5"])), Err(1));
+        assert_eq!(parse_args(args(&["--protocol", "tcp,"])), Err(1));
+        assert_eq!(parse_args(args(&["--address", "1.1.1.1-"])), Err(1));
+        assert_eq!(parse_args(args(&["--port", "70000"])), Err(1));
+    }
+
     #[test]
     #[serial]
     fn test_restore_default_configs() {
@@ -72,10 +464,13 @@ fn test_restore_default_configs() {
                 scale_factor: 0.65,
                 mmdb_country: "countrymmdb".to_string(),
                 mmdb_asn: "asnmmdb".to_string(),
+                services_file: "servicesfile".to_string(),
                 style_path: format!(
                     "{}/resources/themes/catppuccin.toml",
                     env!("CARGO_MANIFEST_DIR")
                 ),
+                reverse_dns: false,
+                snaplen: 1024,
                 notifications: Notifications {
                     volume: 100,
                     packets_notification: Default::default(),
@@ -16,7 +16,16 @@ pub struct ConfigSettings {
     pub scale_factor: f64,
     pub mmdb_country: String,
     pub mmdb_asn: String,
+    /// Path of the file defining custom services
+    #[serde(default)]
+    pub services_file: String,
     pub style_path: String,
+    /// Whether host names should be looked up via reverse DNS
+    #[serde(default = "reverse_dns_default")]
+    pub reverse_dns: bool,
+    /// Maximum number of bytes captured for each packet
+    #[serde(default = "snaplen_default")]
+    pub snaplen: u16,
     pub notifications: Notifications,
     // StyleType should be last in order to deserialize as a table properly
     pub style: StyleType,
@@ -54,13 +63,27 @@ fn default() -> Self {
             scale_factor: 1.0,
             mmdb_country: String::new(),
             mmdb_asn: String::new(),
+            services_file: String::new(),
             style_path: String::new(),
+            reverse_dns: true,
+            snaplen: snaplen_default(),
             notifications: Notifications::default(),
             style: StyleType::default(),
         }
     }
 }
 
+// needed to load settings stored by previous versions
+fn reverse_dns_default() -> bool {
+    true
+}
+
+// limit stored packets slice dimension (to keep more in the buffer),
+// while still capturing TLS handshakes and HTTP headers to extract server names
+fn snaplen_default() -> u16 {
+    4096
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ConfigSettings;
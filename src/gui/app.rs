@@ -17,10 +17,13 @@
 use crate::gui::components::modal::{get_clear_all_overlay, get_exit_overlay, Modal};
 use crate::gui::components::types::my_modal::MyModal;
 use crate::gui::pages::connection_details_page::connection_details_page;
+use crate::gui::pages::devices_page::devices_page;
+use crate::gui::pages::hosts_page::hosts_page;
 use crate::gui::pages::initial_page::initial_page;
 use crate::gui::pages::inspect_page::inspect_page;
 use crate::gui::pages::notifications_page::notifications_page;
 use crate::gui::pages::overview_page::overview_page;
+use crate::gui::pages::services_page::services_page;
 use crate::gui::pages::settings_general_page::settings_general_page;
 use crate::gui::pages::settings_notifications_page::settings_notifications_page;
 use crate::gui::pages::settings_style_page::settings_style_page;
@@ -74,6 +77,9 @@ fn view(&self) -> Element<Message, StyleType> {
                 RunningPage::Init => initial_page(self),
                 RunningPage::Overview => overview_page(self),
                 RunningPage::Inspect => inspect_page(self),
+                RunningPage::Hosts => hosts_page(self),
+                RunningPage::Services => services_page(self),
+                RunningPage::Devices => devices_page(self),
                 RunningPage::Notifications => notifications_page(self),
             }
         };
@@ -12,11 +12,12 @@
 use crate::gui::pages::types::settings_page::SettingsPage;
 use crate::gui::styles::button::ButtonType;
 use crate::gui::styles::container::ContainerType;
+use crate::gui::styles::text::TextType;
 use crate::gui::styles::types::gradient_type::GradientType;
 use crate::gui::types::message::Message;
 use crate::gui::types::sniffer::Sniffer;
 use crate::translations::translations::{quit_analysis_translation, settings_translation};
-use crate::translations::translations_3::thumbnail_mode_translation;
+use crate::translations::translations_3::{save_session_translation, thumbnail_mode_translation};
 use crate::utils::types::icon::Icon;
 use crate::{Language, StyleType, SNIFFNET_TITLECASE};
 
@@ -44,6 +45,8 @@ pub fn header(sniffer: &Sniffer) -> Container<'static, Message, StyleType> {
 
     let last_opened_setting = sniffer.last_opened_setting;
     let is_running = sniffer.running_page.ne(&RunningPage::Init);
+    // saved sessions can't be modified, so there is nothing new to save
+    let can_save_session = is_running && sniffer.session.is_none();
 
     let logo = Icon::Sniffnet
         .to_text()
@@ -62,7 +65,15 @@ pub fn header(sniffer: &Sniffer) -> Container<'static, Message, StyleType> {
                 Container::new(Space::with_width(60))
             })
             .push(horizontal_space())
-            .push(Container::new(Space::with_width(40)))
+            .push(if can_save_session {
+                Container::new(get_button_save_session(
+                    font,
+                    language,
+                    sniffer.session_error.as_deref(),
+                ))
+            } else {
+                Container::new(Space::with_width(40))
+            })
             .push(Space::with_width(20))
             .push(logo)
             .push(Space::with_width(20))
@@ -102,6 +113,37 @@ fn get_button_reset(font: Font, language: Language) -> Tooltip<'static, Message,
     .style(ContainerType::Tooltip)
 }
 
+fn get_button_save_session(
+    font: Font,
+    language: Language,
+    session_error: Option<&str>,
+) -> Tooltip<'static, Message, StyleType> {
+    let content = button(
+        Icon::File
+            .to_text()
+            .size(24)
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .padding(0)
+    .height(40)
+    .width(40)
+    .style(ButtonType::Thumbnail)
+    .on_press(Message::SaveSession);
+
+    // the last error is reported until the session is successfully saved
+    let tooltip = if let Some(error) = session_error {
+        Text::new(format!("{}\n{error}", save_session_translation(language)))
+            .style(TextType::Danger)
+    } else {
+        Text::new(save_session_translation(language))
+    };
+
+    Tooltip::new(content, tooltip.font(font), Position::Left)
+        .gap(0)
+        .style(ContainerType::Tooltip)
+}
+
 pub fn get_button_settings(
     font: Font,
     language: Language,
@@ -3,30 +3,38 @@
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::scrollable::Direction;
 use iced::widget::tooltip::Position;
-use iced::widget::{button, horizontal_space, lazy, vertical_space, Rule, Scrollable};
+use iced::widget::{button, lazy, vertical_space, Rule, Scrollable};
 use iced::widget::{Column, Container, Row, Text, Tooltip};
 use iced::{Alignment, Font, Length};
 
 use crate::countries::country_utils::{get_computer_tooltip, get_flag_tooltip};
 use crate::gui::components::button::button_hide;
+use crate::gui::styles::button::ButtonType;
 use crate::gui::styles::container::ContainerType;
 use crate::gui::styles::scrollbar::ScrollbarType;
 use crate::gui::styles::style_constants::FONT_SIZE_TITLE;
 use crate::gui::styles::text::TextType;
 use crate::gui::styles::types::gradient_type::GradientType;
+use crate::gui::types::export_pcap::PcapSelection;
 use crate::gui::types::message::Message;
 use crate::gui::types::timing_events::TimingEvents;
 use crate::networking::manage_packets::{
     get_address_to_lookup, get_traffic_type, is_local_connection, is_my_address,
 };
+use crate::networking::packet_dissector::{
+    get_packet_layers, get_packet_payload, get_packet_summary, hex_dump,
+};
 use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::host::Host;
 use crate::networking::types::icmp_type::IcmpType;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::name_source::NameSource;
+use crate::networking::types::packet_buffer::CapturedPacket;
+use crate::networking::types::program::Program;
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::translations::translations::{
-    address_translation, incoming_translation, outgoing_translation, packets_translation,
-    protocol_translation,
+    address_translation, bytes_translation, incoming_translation, network_adapter_translation,
+    outgoing_translation, packets_translation, protocol_translation,
 };
 use crate::translations::translations_2::{
     administrative_entity_translation, connection_details_translation, destination_translation,
@@ -34,11 +42,17 @@
     transmitted_data_translation,
 };
 use crate::translations::translations_3::{
-    copy_translation, messages_translation, service_translation,
+    closed_by_translation, closed_translation, copy_translation, details_translation,
+    detected_from_payload_translation, duplicate_acks_translation, guessed_from_port_translation,
+    handshake_rtt_translation, messages_translation, no_packets_buffered_translation,
+    opened_translation, out_of_order_translation, packet_list_translation, payload_translation,
+    program_translation, retransmissions_translation, segments_translation,
+    select_packet_translation, server_name_translation, service_translation, tcp_state_translation,
+    zero_windows_translation,
 };
 use crate::utils::formatted_strings::get_socket_address;
 use crate::utils::types::icon::Icon;
-use crate::{ByteMultiple, ConfigSettings, Language, Protocol, Sniffer, StyleType};
+use crate::{ByteMultiple, ConfigSettings, Language, Protocol, Service, Sniffer, StyleType};
 
 pub fn connection_details_page(
     sniffer: &Sniffer,
@@ -49,6 +63,8 @@ pub fn connection_details_page(
             sniffer.runtime_data.tot_out_packets + sniffer.runtime_data.tot_in_packets,
             sniffer.timing_events.was_just_copy_ip(&key.address1),
             sniffer.timing_events.was_just_copy_ip(&key.address2),
+            sniffer.show_connection_packets,
+            sniffer.selected_packet,
         ),
         move |_| page_content(sniffer, &key),
     ))
@@ -81,6 +97,31 @@ fn page_content(
         .hosts
         .get(&host_option.clone().unwrap_or_default().1)
         .copied();
+    let name_source = info_traffic_lock
+        .name_sources
+        .get(&address_to_lookup)
+        .copied();
+    // packets of both the directions of the connection
+    let packets = if sniffer.show_connection_packets {
+        let mut packets: Vec<(CapturedPacket, bool)> =
+            [(key.clone(), true), (key.reversed(), false)]
+                .iter()
+                .filter_map(|(key, is_forward)| {
+                    let buffer = info_traffic_lock.packets.get(key)?;
+                    Some(
+                        buffer
+                            .packets()
+                            .iter()
+                            .map(|packet| (packet.clone(), *is_forward)),
+                    )
+                })
+                .flatten()
+                .collect();
+        packets.sort_by_key(|(packet, _)| packet.id);
+        packets
+    } else {
+        Vec::new()
+    };
     drop(info_traffic_lock);
 
     let header_and_content = Column::new().width(Length::Fill).push(page_header(
@@ -88,8 +129,18 @@ fn page_content(
         font_headers,
         color_gradient,
         language,
+        key,
+        sniffer.show_connection_packets,
     ));
 
+    if sniffer.show_connection_packets {
+        let content = packets_content(&packets, sniffer.selected_packet, font, language);
+        return Container::new(header_and_content.push(content))
+            .width(1000)
+            .height(500)
+            .style(ContainerType::Modal);
+    }
+
     let mut source_caption = Row::new().align_items(Alignment::Center).spacing(10).push(
         Text::new(source_translation(language))
             .font(font)
@@ -104,7 +155,7 @@ fn page_content(
     );
     let mut host_info_col = Column::new();
     if let Some((r_dns, host)) = host_option {
-        host_info_col = get_host_info_col(&r_dns, &host, font, language);
+        host_info_col = get_host_info_col(&r_dns, name_source, &host, font, language);
         let host_info = host_info_option.unwrap_or_default();
         let flag = get_flag_tooltip(host.country, &host_info, language, font, false);
         let computer = get_local_tooltip(sniffer, &address_to_lookup, key);
@@ -157,10 +208,46 @@ fn page_header(
     font_headers: Font,
     color_gradient: GradientType,
     language: Language,
+    key: &AddressPortPair,
+    show_packets: bool,
 ) -> Container<'static, Message, StyleType> {
+    let toggle_label = if show_packets {
+        details_translation(language)
+    } else {
+        packet_list_translation(language)
+    };
     Container::new(
         Row::new()
-            .push(horizontal_space())
+            .push(
+                Container::new(
+                    Row::new()
+                        .spacing(5)
+                        .push(
+                            button(
+                                Text::new(toggle_label)
+                                    .font(font)
+                                    .vertical_alignment(Vertical::Center),
+                            )
+                            .padding([2, 10])
+                            .style(ButtonType::Standard)
+                            .on_press(Message::ShowConnectionPackets(!show_packets)),
+                        )
+                        .push(
+                            button(
+                                Text::new("PCAP")
+                                    .font(font)
+                                    .vertical_alignment(Vertical::Center),
+                            )
+                            .padding([2, 10])
+                            .style(ButtonType::Standard)
+                            .on_press(Message::ExportPcapSelection(PcapSelection::Connection(
+                                key.clone(),
+                            ))),
+                        ),
+                )
+                .width(Length::Fill)
+                .align_x(Horizontal::Center),
+            )
             .push(
                 Text::new(connection_details_translation(language))
                     .font(font_headers)
@@ -198,8 +285,8 @@ fn col_info(
             Row::new().spacing(5).push(Icon::Clock.to_text()).push(
                 Text::new(format!(
                     "{} - {}",
-                    val.initial_timestamp.to_string().get(11..19).unwrap(),
-                    val.final_timestamp.to_string().get(11..19).unwrap()
+                    val.initial_timestamp.format("%H:%M:%S%.6f"),
+                    val.final_timestamp.format("%H:%M:%S%.6f")
                 ))
                 .font(font),
             ),
@@ -211,9 +298,112 @@ fn col_info(
         ));
 
     if !is_icmp {
+        let service = match val.service {
+            Service::Name(_) if val.service_from_payload => format!(
+                "{} ({})",
+                val.service,
+                detected_from_payload_translation(language)
+            ),
+            Service::Name(_) => format!(
+                "{} ({})",
+                val.service,
+                guessed_from_port_translation(language)
+            ),
+            Service::Unknown | Service::NotApplicable => val.service.to_string(),
+        };
         ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
             service_translation(language),
-            &val.service.to_string(),
+            &service,
+            font,
+        ));
+    }
+
+    if let Some(tcp_connection) = &val.tcp_connection {
+        let mut lifecycle = tcp_connection.state.to_string();
+        if let Some(opened_at) = tcp_connection.opened_at {
+            lifecycle.push_str(&format!(
+                "\n   {}: {}",
+                opened_translation(language),
+                opened_at.format("%H:%M:%S")
+            ));
+        }
+        if let Some(closed_at) = tcp_connection.closed_at {
+            lifecycle.push_str(&format!(
+                "\n   {}: {}",
+                closed_translation(language),
+                closed_at.format("%H:%M:%S")
+            ));
+        }
+        if let Some(closed_by) = &tcp_connection.closed_by {
+            lifecycle.push_str(&format!(
+                "\n   {}: {closed_by}",
+                closed_by_translation(language)
+            ));
+        }
+        if let Some(rtt) = tcp_connection.handshake_rtt() {
+            lifecycle.push_str(&format!(
+                "\n   {}: {:.1} ms",
+                handshake_rtt_translation(language),
+                rtt.as_secs_f64() * 1000.0
+            ));
+        }
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            &format!("{} (TCP)", tcp_state_translation(language)),
+            &lifecycle,
+            font,
+        ));
+    }
+
+    let tcp_quality = &val.tcp_quality;
+    if tcp_quality.segments > 0 {
+        let quality = format!(
+            "{}\n   {}: {} ({:.1}%)\n   {}: {}\n   {}: {}\n   {}: {}",
+            tcp_quality.segments,
+            retransmissions_translation(language),
+            tcp_quality.retransmissions,
+            tcp_quality.retransmission_rate(),
+            out_of_order_translation(language),
+            tcp_quality.out_of_order,
+            duplicate_acks_translation(language),
+            tcp_quality.duplicate_acks,
+            zero_windows_translation(language),
+            tcp_quality.zero_windows,
+        );
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            &format!("{} (TCP)", segments_translation(language)),
+            &quality,
+            font,
+        ));
+    }
+
+    if let Some(server_name) = &val.server_name {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            server_name_translation(language),
+            server_name,
+            font,
+        ));
+    }
+
+    let adapter = match (&key.interface, val.interface_index) {
+        (Some(interface), Some(interface_index)) => {
+            Some(format!("{interface} (#{interface_index})"))
+        }
+        (Some(interface), None) => Some(interface.clone()),
+        (None, Some(interface_index)) => Some(format!("#{interface_index}")),
+        (None, None) => None,
+    };
+    if let Some(adapter) = adapter {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            network_adapter_translation(language),
+            &adapter,
+            font,
+        ));
+    }
+
+    if val.program != Program::Unknown {
+        ret_val = ret_val.push(TextType::highlighted_subtitle_with_desc(
+            program_translation(language),
+            &val.program.to_string_with_pid(),
             font,
         ));
     }
@@ -265,6 +455,7 @@ fn col_info(
 
 fn get_host_info_col(
     r_dns: &str,
+    name_source: Option<NameSource>,
     host: &Host,
     font: Font,
     language: Language,
@@ -276,7 +467,7 @@ fn get_host_info_col(
     if r_dns.parse::<IpAddr>().is_err() {
         host_info_col = host_info_col.push(TextType::highlighted_subtitle_with_desc(
             fqdn_translation(language),
-            r_dns,
+            &name_source.map_or_else(|| r_dns.to_string(), |source| format!("{r_dns} ({source})")),
             font,
         ));
     }
@@ -304,7 +495,12 @@ fn get_local_tooltip(
     } else {
         &key.address1
     };
-    let my_interface_addresses = &*sniffer.device.addresses.lock().unwrap();
+    // saved sessions refer to the addresses of the adapter analyzed at the time
+    let my_interface_addresses = &if let Some(session) = &sniffer.session {
+        session.device_addresses()
+    } else {
+        sniffer.device.addresses.lock().unwrap().clone()
+    };
     get_computer_tooltip(
         is_my_address(local_address, my_interface_addresses),
         is_local_connection(local_address, my_interface_addresses),
@@ -365,6 +561,108 @@ fn get_src_or_dest_col(
         ))
 }
 
+fn packets_content(
+    packets: &[(CapturedPacket, bool)],
+    selected_packet: Option<u128>,
+    font: Font,
+    language: Language,
+) -> Row<'static, Message, StyleType> {
+    let mut packet_list = Column::new().spacing(2).padding([0, 10, 0, 0]);
+    if packets.is_empty() {
+        packet_list =
+            packet_list.push(Text::new(no_packets_buffered_translation(language)).font(font));
+    }
+    for (packet, is_forward) in packets {
+        let arrow = if *is_forward { "→" } else { "←" };
+        let row = Row::new()
+            .spacing(10)
+            .push(Text::new(packet.timestamp.format("%H:%M:%S%.6f").to_string()).font(font))
+            .push(Text::new(arrow).font(font))
+            .push(
+                Text::new(packet.original_len.to_string())
+                    .font(font)
+                    .width(45)
+                    .horizontal_alignment(Horizontal::Right),
+            )
+            .push(Text::new(get_packet_summary(packet)).font(font));
+        packet_list = packet_list.push(
+            button(row)
+                .padding([2, 5])
+                .width(Length::Fill)
+                .style(if selected_packet == Some(packet.id) {
+                    ButtonType::BorderedRoundSelected
+                } else {
+                    ButtonType::Neutral
+                })
+                .on_press(Message::SelectPacket(packet.id)),
+        );
+    }
+
+    let mut packet_info = Column::new().spacing(10).padding([0, 10, 10, 0]);
+    if let Some(packet) = packets
+        .iter()
+        .map(|(packet, _)| packet)
+        .find(|packet| Some(packet.id) == selected_packet)
+    {
+        for layer in get_packet_layers(packet) {
+            let fields = layer
+                .fields
+                .iter()
+                .map(|(name, value)| format!("{name}: {value}"))
+                .collect::<Vec<_>>()
+                .join("\n   ");
+            packet_info = packet_info.push(TextType::highlighted_subtitle_with_desc(
+                &layer.name,
+                &fields,
+                font,
+            ));
+        }
+        let payload = get_packet_payload(packet);
+        packet_info = packet_info.push(
+            Column::new()
+                .push(
+                    Text::new(format!(
+                        "{} ({} {}):",
+                        payload_translation(language),
+                        payload.len(),
+                        bytes_translation(language)
+                    ))
+                    .style(TextType::Subtitle)
+                    .font(font),
+                )
+                .push(Text::new(hex_dump(payload)).font(font).size(12)),
+        );
+    } else if !packets.is_empty() {
+        packet_info = packet_info.push(Text::new(select_packet_translation(language)).font(font));
+    }
+
+    Row::new()
+        .padding([10, 10])
+        .spacing(10)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .push(
+            Container::new(
+                Scrollable::new(packet_list)
+                    .direction(Direction::Vertical(ScrollbarType::properties())),
+            )
+            .padding(7)
+            .width(Length::FillPortion(2))
+            .height(Length::Fill)
+            .style(ContainerType::BorderedRound),
+        )
+        .push(
+            Container::new(Scrollable::new(packet_info).direction(Direction::Both {
+                vertical: ScrollbarType::properties(),
+                horizontal: ScrollbarType::properties(),
+            }))
+            .padding(7)
+            .width(Length::FillPortion(3))
+            .height(Length::Fill)
+            .style(ContainerType::BorderedRound),
+        )
+}
+
 fn assemble_widgets(
     col_info: Column<'static, Message, StyleType>,
     source_col: Column<'static, Message, StyleType>,
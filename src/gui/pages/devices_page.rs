@@ -0,0 +1,233 @@
+use iced::alignment::{Horizontal, Vertical};
+use iced::widget::text_input::Side;
+use iced::widget::{button, lazy, text_input, Rule, TextInput};
+use iced::widget::{Column, Container, Row, Text};
+use iced::{Alignment, Font, Length, Pixels};
+
+use crate::gui::components::tab::get_pages_tabs;
+use crate::gui::pages::hosts_page::{data_col, paginated_report, report_header_row};
+use crate::gui::pages::overview_page::{get_bars, get_bars_length};
+use crate::gui::styles::button::ButtonType;
+use crate::gui::styles::container::ContainerType;
+use crate::gui::styles::style_constants::{FONT_SIZE_FOOTER, ICONS};
+use crate::gui::styles::text::TextType;
+use crate::gui::styles::text_input::TextInputType;
+use crate::gui::types::message::Message;
+use crate::networking::lan_devices::get_vendor;
+use crate::report::get_report_entries::get_searched_devices;
+use crate::report::types::search_parameters::SearchParameters;
+use crate::report::types::sort_type::SortType;
+use crate::translations::translations::{incoming_translation, outgoing_translation};
+use crate::translations::translations_3::devices_translation;
+use crate::utils::types::icon::Icon;
+use crate::{ByteMultiple, ChartType, ConfigSettings, RunningPage, Sniffer, StyleType};
+
+/// Width of the column with the device information
+const DEVICE_COL_WIDTH: f32 = 780.0;
+
+/// Computes the body of gui devices page
+pub fn devices_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
+    let ConfigSettings {
+        style, language, ..
+    } = sniffer.configs.lock().unwrap().settings;
+    let font = style.get_extension().font;
+    let font_headers = style.get_extension().font_headers;
+
+    let mut body = Column::new()
+        .width(Length::Fill)
+        .padding(10)
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+    let mut tab_and_body = Column::new().height(Length::Fill);
+
+    let tabs = get_pages_tabs(
+        RunningPage::Devices,
+        font,
+        font_headers,
+        language,
+        sniffer.unread_notifications,
+    );
+
+    tab_and_body = tab_and_body.push(tabs);
+
+    let report = lazy(
+        (
+            sniffer.runtime_data.tot_out_packets + sniffer.runtime_data.tot_in_packets,
+            style,
+            language,
+            sniffer.traffic_chart.chart_type,
+            sniffer.devices_sort_type,
+            sniffer.devices_search.clone(),
+            sniffer.devices_page_number,
+        ),
+        move |_| lazy_devices_report(sniffer),
+    );
+
+    let col_report = Column::new()
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .push(report_header_row(
+            devices_translation(language),
+            language,
+            font,
+            sniffer.devices_sort_type,
+            Message::DevicesReportSortSelection,
+        ))
+        .push(Rule::horizontal(5))
+        .push(report);
+
+    body = body
+        .push(
+            Container::new(devices_search_input(&sniffer.devices_search, font))
+                .padding(10)
+                .style(ContainerType::BorderedRound),
+        )
+        .push(
+            Container::new(col_report)
+                .align_y(Vertical::Center)
+                .align_x(Horizontal::Center)
+                .padding([10, 7, 3, 7])
+                .width(1042)
+                .style(ContainerType::BorderedRound),
+        );
+
+    Container::new(Column::new().push(tab_and_body.push(body))).height(Length::Fill)
+}
+
+fn lazy_devices_report(sniffer: &Sniffer) -> Column<'static, Message, StyleType> {
+    let ConfigSettings {
+        style, language, ..
+    } = sniffer.configs.lock().unwrap().settings;
+    let font = style.get_extension().font;
+    let chart_type = sniffer.traffic_chart.chart_type;
+
+    let (search_results, results_number) = get_searched_devices(sniffer);
+    let first_entry_data_info = search_results
+        .iter()
+        .map(|(_, device)| device.data_info)
+        .max_by(|d1, d2| d1.compare(d2, SortType::Ascending, chart_type))
+        .unwrap_or_default();
+
+    let mut scroll_report = Column::new().align_items(Alignment::Start);
+    for (mac_address, device) in &search_results {
+        let data_info = &device.data_info;
+        let (incoming_bar_len, outgoing_bar_len) = get_bars_length(
+            DEVICE_COL_WIDTH * 0.9,
+            chart_type,
+            &first_entry_data_info,
+            data_info,
+        );
+
+        let (incoming, outgoing) = match chart_type {
+            ChartType::Packets => (
+                data_info.incoming_packets().to_string(),
+                data_info.outgoing_packets().to_string(),
+            ),
+            ChartType::Bytes => (
+                ByteMultiple::formatted_string(data_info.incoming_bytes()),
+                ByteMultiple::formatted_string(data_info.outgoing_bytes()),
+            ),
+        };
+        let addresses = device
+            .addresses
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let device_bar = Column::new()
+            .width(DEVICE_COL_WIDTH)
+            .spacing(1)
+            .push(
+                Row::new()
+                    .push(Text::new(mac_address.clone()).font(font))
+                    .push(
+                        Text::new(
+                            get_vendor(mac_address)
+                                .map(|vendor| format!(" - {vendor}"))
+                                .unwrap_or_default(),
+                        )
+                        .font(font),
+                    ),
+            )
+            .push(
+                Text::new(addresses)
+                    .font(font)
+                    .size(FONT_SIZE_FOOTER)
+                    .style(TextType::Subtitle),
+            )
+            .push(
+                Row::new()
+                    .spacing(5)
+                    .align_items(Alignment::Center)
+                    .push(Icon::Clock.to_text().size(FONT_SIZE_FOOTER))
+                    .push(
+                        Text::new(format!(
+                            "{} - {}   {}: {incoming}   {}: {outgoing}",
+                            device.first_seen.format("%H:%M:%S"),
+                            device.last_seen().format("%H:%M:%S"),
+                            incoming_translation(language),
+                            outgoing_translation(language),
+                        ))
+                        .font(font)
+                        .size(FONT_SIZE_FOOTER),
+                    ),
+            )
+            .push(get_bars(incoming_bar_len, outgoing_bar_len));
+
+        let content = Row::new()
+            .align_items(Alignment::Center)
+            .spacing(5)
+            .push(device_bar)
+            .push(data_col(data_info.tot_packets().to_string(), font))
+            .push(data_col(
+                ByteMultiple::formatted_string(data_info.tot_bytes()),
+                font,
+            ));
+
+        scroll_report = scroll_report.push(
+            button(content)
+                .padding([5, 15, 8, 10])
+                .on_press(Message::Search(SearchParameters::new_device_search(
+                    mac_address,
+                )))
+                .style(ButtonType::Neutral),
+        );
+    }
+
+    paginated_report(
+        scroll_report,
+        font,
+        language,
+        sniffer.devices_page_number,
+        search_results.len(),
+        results_number,
+    )
+}
+
+/// Search bar of the devices (matched against MAC addresses, vendors, and IP addresses)
+fn devices_search_input(search: &str, font: Font) -> TextInput<'static, Message, StyleType> {
+    let is_search_active = !search.is_empty();
+    let mut input = TextInput::new("", search)
+        .on_input(Message::DevicesSearch)
+        .padding([2, 5])
+        .size(FONT_SIZE_FOOTER)
+        .font(font)
+        .width(300)
+        .style(if is_search_active {
+            TextInputType::Badge
+        } else {
+            TextInputType::Standard
+        });
+    if !is_search_active {
+        input = input.icon(text_input::Icon {
+            font: ICONS,
+            code_point: Icon::Funnel.codepoint(),
+            size: Some(Pixels(12.0)),
+            spacing: 2.0,
+            side: Side::Left,
+        });
+    }
+    input
+}
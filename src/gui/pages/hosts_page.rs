@@ -0,0 +1,275 @@
+use iced::alignment::{Horizontal, Vertical};
+use iced::widget::scrollable::Direction;
+use iced::widget::{button, horizontal_space, lazy, vertical_space, Rule, Space};
+use iced::widget::{Column, Container, Row, Scrollable, Text};
+use iced::{Alignment, Font, Length};
+
+use crate::countries::country_utils::get_flag_tooltip;
+use crate::gui::components::tab::get_pages_tabs;
+use crate::gui::pages::inspect_page::{get_change_page_row, host_filters_col, sort_arrows};
+use crate::gui::pages::overview_page::{get_bars, get_bars_length, get_star_button};
+use crate::gui::styles::button::ButtonType;
+use crate::gui::styles::container::ContainerType;
+use crate::gui::styles::scrollbar::ScrollbarType;
+use crate::gui::styles::style_constants::FONT_SIZE_TITLE;
+use crate::gui::styles::text::TextType;
+use crate::gui::types::message::Message;
+use crate::report::get_report_entries::get_searched_hosts;
+use crate::report::types::report_col::ReportCol;
+use crate::report::types::search_parameters::SearchParameters;
+use crate::report::types::sort_type::SortType;
+use crate::translations::translations_2::no_search_results_translation;
+use crate::translations::translations_3::hosts_translation;
+use crate::utils::types::icon::Icon;
+use crate::{
+    ByteMultiple, ConfigSettings, Language, ReportSortType, RunningPage, Sniffer, StyleType,
+};
+
+/// Width of the column with the host names
+const HOST_COL_WIDTH: f32 = 740.0;
+/// Width of the columns with packets and bytes
+const DATA_COL_WIDTH: f32 = 120.0;
+
+/// Computes the body of gui hosts page
+pub fn hosts_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
+    let ConfigSettings {
+        style, language, ..
+    } = sniffer.configs.lock().unwrap().settings;
+    let font = style.get_extension().font;
+    let font_headers = style.get_extension().font_headers;
+
+    let mut body = Column::new()
+        .width(Length::Fill)
+        .padding(10)
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+    let mut tab_and_body = Column::new().height(Length::Fill);
+
+    let tabs = get_pages_tabs(
+        RunningPage::Hosts,
+        font,
+        font_headers,
+        language,
+        sniffer.unread_notifications,
+    );
+
+    tab_and_body = tab_and_body.push(tabs);
+
+    let num_favorites = sniffer.info_traffic.lock().unwrap().favorite_hosts.len();
+    let report = lazy(
+        (
+            sniffer.runtime_data.tot_out_packets + sniffer.runtime_data.tot_in_packets,
+            num_favorites,
+            style,
+            language,
+            sniffer.traffic_chart.chart_type,
+            sniffer.hosts_sort_type,
+            sniffer.hosts_search.clone(),
+            sniffer.hosts_page_number,
+        ),
+        move |_| lazy_hosts_report(sniffer),
+    );
+
+    let col_report = Column::new()
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .push(report_header_row(
+            hosts_translation(language),
+            language,
+            font,
+            sniffer.hosts_sort_type,
+            Message::HostsReportSortSelection,
+        ))
+        .push(Rule::horizontal(5))
+        .push(report);
+
+    body = body
+        .push(
+            Container::new(host_filters_col(
+                &sniffer.hosts_search,
+                font,
+                language,
+                Message::HostsSearch,
+            ))
+            .padding(10)
+            .style(ContainerType::BorderedRound),
+        )
+        .push(
+            Container::new(col_report)
+                .align_y(Vertical::Center)
+                .align_x(Horizontal::Center)
+                .padding([10, 7, 3, 7])
+                .width(1042)
+                .style(ContainerType::BorderedRound),
+        );
+
+    Container::new(Column::new().push(tab_and_body.push(body))).height(Length::Fill)
+}
+
+fn lazy_hosts_report(sniffer: &Sniffer) -> Column<'static, Message, StyleType> {
+    let ConfigSettings {
+        style, language, ..
+    } = sniffer.configs.lock().unwrap().settings;
+    let font = style.get_extension().font;
+    let chart_type = sniffer.traffic_chart.chart_type;
+
+    let (search_results, results_number) = get_searched_hosts(sniffer);
+    let first_entry_data_info = search_results
+        .iter()
+        .map(|(_, d)| d.data_info)
+        .max_by(|d1, d2| d1.compare(d2, SortType::Ascending, chart_type))
+        .unwrap_or_default();
+
+    let mut scroll_report = Column::new().align_items(Alignment::Start);
+    for (host, data_info_host) in &search_results {
+        let (incoming_bar_len, outgoing_bar_len) = get_bars_length(
+            HOST_COL_WIDTH * 0.9,
+            chart_type,
+            &first_entry_data_info,
+            &data_info_host.data_info,
+        );
+
+        let host_bar = Column::new()
+            .width(HOST_COL_WIDTH)
+            .spacing(1)
+            .push(
+                Row::new()
+                    .push(Text::new(host.domain.clone()).font(font))
+                    .push(
+                        Text::new(if host.asn.name.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" - {}", host.asn.name)
+                        })
+                        .font(font),
+                    ),
+            )
+            .push(get_bars(incoming_bar_len, outgoing_bar_len));
+
+        let content = Row::new()
+            .align_items(Alignment::Center)
+            .spacing(5)
+            .push(get_star_button(data_info_host.is_favorite, host.clone()))
+            .push(get_flag_tooltip(
+                host.country,
+                data_info_host,
+                language,
+                font,
+                false,
+            ))
+            .push(host_bar)
+            .push(data_col(
+                data_info_host.data_info.tot_packets().to_string(),
+                font,
+            ))
+            .push(data_col(
+                ByteMultiple::formatted_string(data_info_host.data_info.tot_bytes()),
+                font,
+            ));
+
+        scroll_report = scroll_report.push(
+            button(content)
+                .padding([5, 15, 5, 10])
+                .on_press(Message::Search(SearchParameters::new_host_search(host)))
+                .style(ButtonType::Neutral),
+        );
+    }
+
+    paginated_report(
+        scroll_report,
+        font,
+        language,
+        sniffer.hosts_page_number,
+        search_results.len(),
+        results_number,
+    )
+}
+
+/// Header of the hosts and services reports, with the sort buttons for packets and bytes
+pub fn report_header_row(
+    title: &str,
+    language: Language,
+    font: Font,
+    sort_type: ReportSortType,
+    message: fn(ReportSortType) -> Message,
+) -> Row<'static, Message, StyleType> {
+    let mut ret_val = Row::new()
+        .height(56)
+        .padding([0, 15, 0, 10])
+        .align_items(Alignment::Center)
+        .push(
+            Text::new(title.to_string())
+                .font(font)
+                .style(TextType::Title)
+                .size(FONT_SIZE_TITLE),
+        )
+        .push(horizontal_space());
+    for report_col in [ReportCol::Packets, ReportCol::Bytes] {
+        ret_val = ret_val.push(
+            Column::new()
+                .align_items(Alignment::Center)
+                .width(DATA_COL_WIDTH)
+                .height(Length::Fill)
+                .push(Text::new(report_col.get_title(language)).font(font))
+                .push(sort_arrows(sort_type, &report_col, message)),
+        );
+    }
+    ret_val
+}
+
+/// Right-aligned cell with the amount of packets or bytes of an entry
+pub fn data_col(value: String, font: Font) -> Container<'static, Message, StyleType> {
+    Container::new(Text::new(value).font(font))
+        .width(DATA_COL_WIDTH)
+        .align_x(Horizontal::Center)
+}
+
+/// Shows the given page of results, or a placeholder if no result satisfies the search
+pub fn paginated_report(
+    scroll_report: Column<'static, Message, StyleType>,
+    font: Font,
+    language: Language,
+    page_number: usize,
+    page_results_number: usize,
+    results_number: usize,
+) -> Column<'static, Message, StyleType> {
+    let ret_val = Column::new()
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_items(Alignment::Start);
+
+    if results_number > 0 {
+        let start_entry_num = (page_number - 1) * 20 + 1;
+        let end_entry_num = start_entry_num + page_results_number - 1;
+        ret_val
+            .push(
+                Scrollable::new(scroll_report)
+                    .height(Length::Fill)
+                    .width(Length::Fill)
+                    .direction(Direction::Vertical(ScrollbarType::properties())),
+            )
+            .push(Rule::horizontal(5))
+            .push(get_change_page_row(
+                font,
+                language,
+                page_number,
+                start_entry_num,
+                end_entry_num,
+                results_number,
+            ))
+    } else {
+        ret_val.push(
+            Column::new()
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding(20)
+                .align_items(Alignment::Center)
+                .push(vertical_space())
+                .push(Icon::Funnel.to_text().size(60))
+                .push(Space::with_height(15))
+                .push(Text::new(no_search_results_translation(language)).font(font))
+                .push(Space::with_height(Length::FillPortion(2))),
+        )
+    }
+}
@@ -24,6 +24,7 @@
 use crate::gui::styles::text_input::TextInputType;
 use crate::gui::styles::types::gradient_type::GradientType;
 use crate::gui::types::export_pcap::ExportPcap;
+use crate::gui::types::import_pcap::ImportPcap;
 use crate::gui::types::message::Message;
 use crate::gui::types::sniffer::Sniffer;
 use crate::networking::types::filters::Filters;
@@ -34,7 +35,11 @@
     ip_version_translation, protocol_translation, select_filters_translation, start_translation,
 };
 use crate::translations::translations_3::{
-    directory_translation, export_capture_translation, file_name_translation, port_translation,
+    bpf_filter_translation, capture_file_translation, capture_from_file_translation,
+    directory_translation, export_capture_translation, file_name_translation,
+    import_capture_translation, max_files_translation, new_file_every_translation,
+    open_session_translation, pcapng_format_translation, port_translation,
+    replay_real_time_translation,
 };
 use crate::utils::formatted_strings::{get_invalid_filters_string, get_path_termination_string};
 use crate::utils::types::file_info::FileInfo;
@@ -65,6 +70,10 @@ pub fn initial_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
     let port_active = &sniffer.filters.port_str;
     let col_port_filter = col_port_input(port_active, font, language);
 
+    let bpf_active = &sniffer.filters.bpf;
+    let bpf_error = sniffer.filters.bpf_error.clone();
+    let col_bpf_filter = col_bpf_input(bpf_active, bpf_error, font, language);
+
     let filters_pane = Column::new()
         .width(FillPortion(6))
         .padding(10)
@@ -87,6 +96,7 @@ pub fn initial_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
                 .push(col_address_filter)
                 .push(col_port_filter),
         )
+        .push(col_bpf_filter)
         .push(Rule::horizontal(40))
         .push(
             Container::new(get_export_pcap_group(&sniffer.export_pcap, language, font))
@@ -99,6 +109,7 @@ pub fn initial_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
                 language,
                 color_gradient,
                 &sniffer.filters,
+                &sniffer.import_pcap,
             ))
             .width(Length::Fill)
             .height(Length::Fill)
@@ -264,11 +275,53 @@ fn col_port_input(
         .push(input_row)
 }
 
+fn col_bpf_input(
+    value: &str,
+    error: Option<String>,
+    font: Font,
+    language: Language,
+) -> Column<'static, Message, StyleType> {
+    let input_row = Row::new().padding([0, 0, 0, 5]).push(
+        TextInput::new(Filters::BPF_PLACEHOLDER_STR, value)
+            .padding([3, 5])
+            .on_input(Message::BpfFilter)
+            .font(font)
+            .width(510)
+            .style(if error.is_some() {
+                TextInputType::Error
+            } else {
+                TextInputType::Standard
+            }),
+    );
+
+    let mut ret_val = Column::new()
+        .width(Length::Fill)
+        .spacing(7)
+        .push(
+            Text::new(bpf_filter_translation(language))
+                .font(font)
+                .style(TextType::Subtitle)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(input_row);
+
+    if let Some(error) = error {
+        ret_val = ret_val.push(
+            Row::new()
+                .padding([0, 0, 0, 5])
+                .push(Text::new(error).font(font).style(TextType::Danger)),
+        );
+    }
+
+    ret_val
+}
+
 fn button_start(
     font: Font,
     language: Language,
     color_gradient: GradientType,
     filters: &Filters,
+    import_pcap: &ImportPcap,
 ) -> Tooltip<'static, Message, StyleType> {
     let mut content = button(
         Icon::Rocket
@@ -286,11 +339,14 @@ fn button_start(
     //tooltip.push_str(" [⏎]");
     let mut position = Position::Top;
 
-    if filters.are_valid() {
-        content = content.on_press(Message::Start);
-    } else {
+    if !filters.are_valid() {
         tooltip = get_invalid_filters_string(filters, language);
         position = Position::FollowCursor;
+    } else if import_pcap.enabled() && import_pcap.file_path().is_none() {
+        tooltip = capture_from_file_translation(language).to_string();
+        position = Position::FollowCursor;
+    } else {
+        content = content.on_press(Message::Start);
     }
 
     Tooltip::new(content, Text::new(tooltip).font(font), position)
@@ -350,21 +406,145 @@ fn get_col_adapter(sniffer: &Sniffer, font: Font) -> Column<Message, StyleType>
                 |scroll_adapters, adapter| {
                     let name = adapter.0.clone();
                     let description = adapter.1.clone();
+                    let is_selected = name == sniffer.device.name
+                        || sniffer.additional_devices.iter().any(|d| d.name == name);
+                    let name2 = name.clone();
+                    // the checkbox permits to capture from several adapters at once
+                    let checkbox = Checkbox::new("", is_selected)
+                        .on_toggle(move |checked| {
+                            Message::AdapterMultiSelection(name2.clone(), checked)
+                        })
+                        .size(18)
+                        .font(font);
                     scroll_adapters.push(
-                        Button::new(Text::new(description).font(font))
-                            .padding([20, 30])
-                            .width(Length::Fill)
-                            .style(if name == sniffer.device.name {
-                                ButtonType::BorderedRoundSelected
-                            } else {
-                                ButtonType::BorderedRound
-                            })
-                            .on_press(Message::AdapterSelection(name)),
+                        Row::new()
+                            .spacing(10)
+                            .align_items(Alignment::Center)
+                            .push(
+                                Button::new(Text::new(description).font(font))
+                                    .padding([20, 30])
+                                    .width(Length::Fill)
+                                    .style(if is_selected {
+                                        ButtonType::BorderedRoundSelected
+                                    } else {
+                                        ButtonType::BorderedRound
+                                    })
+                                    .on_press(Message::AdapterSelection(name)),
+                            )
+                            .push(checkbox),
                     )
                 },
             ))
+            .height(Length::Fill)
             .direction(Direction::Vertical(ScrollbarType::properties())),
         )
+        .push(get_import_pcap_group(&sniffer.import_pcap, language, font))
+        .push(get_open_session_group(
+            sniffer.session_error.as_deref(),
+            language,
+            font,
+        ))
+}
+
+fn get_import_pcap_group(
+    import_pcap: &ImportPcap,
+    language: Language,
+    font: Font,
+) -> Container<'static, Message, StyleType> {
+    let enabled = import_pcap.enabled();
+    let path = import_pcap.path();
+
+    let caption = import_capture_translation(language);
+    let checkbox = Checkbox::new(caption, enabled)
+        .on_toggle(move |_| Message::ToggleImportPcap)
+        .size(18)
+        .font(font);
+
+    let mut ret_val = Column::new().spacing(10).push(checkbox);
+
+    if enabled {
+        let inner_col = Column::new()
+            .spacing(10)
+            .padding([0, 0, 0, 45])
+            .push(
+                Row::new()
+                    .align_items(Alignment::Center)
+                    .spacing(5)
+                    .push(Text::new(format!("{}:", capture_file_translation(language))).font(font))
+                    .push(Text::new(get_path_termination_string(path, 20)).font(font))
+                    .push(button_open_file(
+                        path.to_owned(),
+                        FileInfo::Capture,
+                        language,
+                        font,
+                        true,
+                        Message::InputPcapFile,
+                    )),
+            )
+            .push(
+                Checkbox::new(
+                    replay_real_time_translation(language),
+                    import_pcap.real_time(),
+                )
+                .on_toggle(move |_| Message::ToggleReplayRealTime)
+                .size(18)
+                .font(font),
+            );
+        ret_val = ret_val.push(inner_col);
+    }
+
+    Container::new(ret_val)
+        .padding(10)
+        .width(Length::Fill)
+        .style(ContainerType::BorderedRound)
+}
+
+fn get_open_session_group(
+    session_error: Option<&str>,
+    language: Language,
+    font: Font,
+) -> Container<'static, Message, StyleType> {
+    let mut ret_val = Column::new().spacing(10).push(
+        Row::new()
+            .align_items(Alignment::Center)
+            .spacing(5)
+            .push(Text::new(format!("{}:", open_session_translation(language))).font(font))
+            .push(button_open_file(
+                String::new(),
+                FileInfo::Session,
+                language,
+                font,
+                true,
+                Message::LoadSession,
+            )),
+    );
+
+    if let Some(error) = session_error {
+        ret_val = ret_val.push(
+            Text::new(error.to_string())
+                .font(font)
+                .style(TextType::Danger),
+        );
+    }
+
+    Container::new(ret_val)
+        .padding(10)
+        .width(Length::Fill)
+        .style(ContainerType::BorderedRound)
+}
+
+/// Input for a rotation limit of the exported capture (empty if there's no limit)
+fn rotation_input<T: ToString>(
+    limit: Option<T>,
+    message: fn(String) -> Message,
+    font: Font,
+) -> TextInput<'static, Message, StyleType> {
+    let value = limit.map(|limit| limit.to_string()).unwrap_or_default();
+    TextInput::new("-", &value)
+        .on_input(message)
+        .padding([2, 5])
+        .font(font)
+        .width(60)
 }
 
 fn get_export_pcap_group(
@@ -375,6 +555,7 @@ fn get_export_pcap_group(
     let enabled = export_pcap.enabled();
     let file_name = export_pcap.file_name();
     let directory = export_pcap.directory();
+    let rotation = export_pcap.rotation();
 
     let caption = export_capture_translation(language);
     let checkbox = Checkbox::new(caption, enabled)
@@ -415,6 +596,43 @@ fn get_export_pcap_group(
                         true,
                         Message::OutputPcapDir,
                     )),
+            )
+            .push(
+                Checkbox::new(pcapng_format_translation(language), export_pcap.is_pcapng())
+                    .on_toggle(move |_| Message::TogglePcapng)
+                    .size(18)
+                    .font(font),
+            )
+            .push(
+                Row::new()
+                    .align_items(Alignment::Center)
+                    .spacing(5)
+                    .push(
+                        Text::new(format!("{}:", new_file_every_translation(language))).font(font),
+                    )
+                    .push(rotation_input(
+                        rotation.max_size_mb,
+                        Message::PcapRotationSize,
+                        font,
+                    ))
+                    .push(Text::new("MB").font(font))
+                    .push(rotation_input(
+                        rotation.max_minutes,
+                        Message::PcapRotationMinutes,
+                        font,
+                    ))
+                    .push(Text::new("min").font(font)),
+            )
+            .push(
+                Row::new()
+                    .align_items(Alignment::Center)
+                    .spacing(5)
+                    .push(Text::new(format!("{}:", max_files_translation(language))).font(font))
+                    .push(rotation_input(
+                        rotation.max_files,
+                        Message::PcapMaxFiles,
+                        font,
+                    )),
             );
         ret_val = ret_val.push(inner_col);
         Container::new(ret_val)
@@ -6,7 +6,7 @@
 use iced::widget::text_input::Side;
 use iced::widget::tooltip::Position;
 use iced::widget::{
-    button, horizontal_space, text_input, vertical_space, Rule, Space, Toggler, Tooltip,
+    button, horizontal_space, text_input, vertical_space, Checkbox, Rule, Space, Toggler, Tooltip,
 };
 use iced::widget::{lazy, Button, Column, Container, Row, Scrollable, Text, TextInput};
 use iced::{alignment, Alignment, Font, Length, Pixels};
@@ -19,20 +19,35 @@
 use crate::gui::styles::style_constants::{FONT_SIZE_FOOTER, FONT_SIZE_SUBTITLE, ICONS};
 use crate::gui::styles::text::TextType;
 use crate::gui::styles::text_input::TextInputType;
+use crate::gui::types::export_pcap::PcapSelection;
 use crate::gui::types::message::Message;
 use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::tcp_connection::TcpStats;
 use crate::networking::types::traffic_direction::TrafficDirection;
-use crate::report::get_report_entries::get_searched_entries;
+use crate::report::get_report_entries::{get_searched_entries, get_searched_non_ip_entries};
 use crate::report::types::report_col::ReportCol;
 use crate::report::types::search_parameters::{FilterInputType, SearchParameters};
+use crate::translations::translations::{
+    bytes_translation, network_adapter_translation, packets_translation, protocol_translation,
+};
 use crate::translations::translations_2::{
-    administrative_entity_translation, country_translation, domain_name_translation,
-    no_search_results_translation, only_show_favorites_translation, showing_results_translation,
+    administrative_entity_translation, country_translation, destination_translation,
+    domain_name_translation, mac_address_translation, no_search_results_translation,
+    only_show_favorites_translation, showing_results_translation, source_translation,
+};
+use crate::translations::translations_3::{
+    export_packets_translation, export_results_translation, failed_handshakes_translation,
+    filter_by_host_translation, half_open_translation, non_ip_traffic_translation,
+    resets_translation, split_by_host_translation, tcp_connections_translation,
 };
-use crate::translations::translations_3::filter_by_host_translation;
 use crate::utils::types::icon::Icon;
-use crate::{ConfigSettings, Language, ReportSortType, RunningPage, Sniffer, StyleType};
+use crate::{
+    ByteMultiple, ConfigSettings, Language, ReportSortType, RunningPage, Sniffer, StyleType,
+};
+
+/// Widths of the columns of the non-IP traffic table
+const NON_IP_COLS_WIDTH: [f32; 5] = [150.0, 250.0, 250.0, 150.0, 150.0];
 
 /// Computes the body of gui inspect page
 pub fn inspect_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
@@ -60,47 +75,89 @@ pub fn inspect_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
 
     tab_and_body = tab_and_body.push(tabs);
 
-    let report = lazy(
-        (
-            sniffer.runtime_data.tot_out_packets + sniffer.runtime_data.tot_in_packets,
-            style,
-            language,
-            sniffer.report_sort_type,
-            sniffer.search.clone(),
-            sniffer.page_number,
-        ),
-        move |_| lazy_report(sniffer),
-    );
+    let tot_packets = sniffer.runtime_data.tot_out_packets + sniffer.runtime_data.tot_in_packets;
 
-    let col_report = Column::new()
-        .height(Length::Fill)
-        .width(Length::Fill)
-        .align_items(Alignment::Start)
-        .push(report_header_row(
-            language,
-            &sniffer.search,
-            font,
-            sniffer.report_sort_type,
-        ))
-        .push(Space::with_height(4))
-        .push(Rule::horizontal(5))
-        .push(report);
+    let mut filters_row = Row::new().spacing(10).align_items(Alignment::Center);
+    let col_report = if sniffer.show_non_ip {
+        filters_row = filters_row.push_maybe(non_ip_container(sniffer, font, language));
 
-    body = body
-        .push(
-            Container::new(host_filters_col(&sniffer.search, font, language))
+        let non_ip_report = lazy(
+            (tot_packets, style, language, sniffer.non_ip_search.clone()),
+            move |_| lazy_non_ip_report(sniffer),
+        );
+
+        Column::new()
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .align_items(Alignment::Start)
+            .push(non_ip_header_row(language, font))
+            .push(Space::with_height(4))
+            .push(Rule::horizontal(5))
+            .push(non_ip_report)
+    } else {
+        filters_row = filters_row
+            .push(
+                Container::new(host_filters_col(
+                    &sniffer.search,
+                    font,
+                    language,
+                    Message::Search,
+                ))
                 .padding(10)
                 .style(ContainerType::BorderedRound),
-        )
-        .push(
-            Container::new(col_report)
-                .align_y(Vertical::Center)
-                .align_x(Horizontal::Center)
-                .padding([10, 7, 3, 7])
-                .width(1042)
-                .style(ContainerType::BorderedRound),
+            )
+            .push_maybe(adapter_filter_container(sniffer, font, language))
+            .push_maybe(mac_filter_container(sniffer, font, language))
+            .push_maybe(tcp_stats_container(sniffer, font, language))
+            .push_maybe(non_ip_container(sniffer, font, language))
+            .push(button_export_report(
+                font,
+                language,
+                sniffer.export_report_error.as_deref(),
+            ))
+            .push(export_pcap_col(
+                font,
+                language,
+                sniffer.export_pcap.split_by_host(),
+                sniffer.export_pcap_selection_error.as_deref(),
+            ));
+
+        let report = lazy(
+            (
+                tot_packets,
+                style,
+                language,
+                sniffer.report_sort_type,
+                sniffer.search.clone(),
+                sniffer.page_number,
+            ),
+            move |_| lazy_report(sniffer),
         );
 
+        Column::new()
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .align_items(Alignment::Start)
+            .push(report_header_row(
+                language,
+                &sniffer.search,
+                font,
+                sniffer.report_sort_type,
+            ))
+            .push(Space::with_height(4))
+            .push(Rule::horizontal(5))
+            .push(report)
+    };
+
+    body = body.push(filters_row).push(
+        Container::new(col_report)
+            .align_y(Vertical::Center)
+            .align_x(Horizontal::Center)
+            .padding([10, 7, 3, 7])
+            .width(1042)
+            .style(ContainerType::BorderedRound),
+    );
+
     Container::new(Column::new().push(tab_and_body.push(body))).height(Length::Fill)
 }
 
@@ -165,6 +222,161 @@ fn lazy_report(sniffer: &Sniffer) -> Column<'static, Message, StyleType> {
     ret_val
 }
 
+fn lazy_non_ip_report(sniffer: &Sniffer) -> Column<'static, Message, StyleType> {
+    let ConfigSettings {
+        style, language, ..
+    } = sniffer.configs.lock().unwrap().settings;
+    let font = style.get_extension().font;
+
+    let entries = get_searched_non_ip_entries(
+        &sniffer.info_traffic.lock().unwrap(),
+        &sniffer.non_ip_search,
+    );
+
+    if entries.is_empty() {
+        return Column::new()
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(20)
+            .align_items(Alignment::Center)
+            .push(vertical_space())
+            .push(Icon::Funnel.to_text().size(60))
+            .push(Space::with_height(15))
+            .push(Text::new(no_search_results_translation(language)).font(font))
+            .push(Space::with_height(Length::FillPortion(2)));
+    }
+
+    let mut scroll_report = Column::new().align_items(Alignment::Start);
+    for (key, data_info) in entries {
+        let text_type = if data_info.outgoing_packets() > data_info.incoming_packets() {
+            TextType::Outgoing
+        } else {
+            TextType::Incoming
+        };
+        let values = [
+            key.protocol.to_string(),
+            key.mac_address1,
+            key.mac_address2,
+            data_info.tot_packets().to_string(),
+            ByteMultiple::formatted_string(data_info.tot_bytes()),
+        ];
+        let mut row = Row::new().padding(2).align_items(Alignment::Center);
+        for (value, width) in values.into_iter().zip(NON_IP_COLS_WIDTH) {
+            row = row.push(
+                Container::new(Text::new(value).font(font).style(text_type))
+                    .align_x(Horizontal::Center)
+                    .width(width),
+            );
+        }
+        scroll_report = scroll_report.push(row);
+    }
+
+    Column::new()
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_items(Alignment::Start)
+        .push(
+            Scrollable::new(scroll_report)
+                .height(Length::Fill)
+                .width(Length::Fill)
+                .direction(Direction::Vertical(ScrollbarType::properties())),
+        )
+}
+
+fn non_ip_header_row(language: Language, font: Font) -> Row<'static, Message, StyleType> {
+    let capitalize = |str: &str| {
+        let mut chars = str.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
+    };
+    let titles = [
+        protocol_translation(language).to_string(),
+        format!(
+            "{} ({})",
+            mac_address_translation(language),
+            source_translation(language).to_lowercase()
+        ),
+        format!(
+            "{} ({})",
+            mac_address_translation(language),
+            destination_translation(language).to_lowercase()
+        ),
+        capitalize(packets_translation(language)),
+        capitalize(bytes_translation(language)),
+    ];
+
+    let mut ret_val = Row::new().padding([0, 2]).align_items(Alignment::Center);
+    for (title, width) in titles.into_iter().zip(NON_IP_COLS_WIDTH) {
+        ret_val = ret_val.push(
+            Container::new(Text::new(title).font(font))
+                .align_x(Horizontal::Center)
+                .width(width)
+                .height(30),
+        );
+    }
+    ret_val
+}
+
+/// Container with the toggler to show the non-IP traffic (and its search bar when it's shown);
+/// only displayed if some non-IP traffic was observed
+fn non_ip_container(
+    sniffer: &Sniffer,
+    font: Font,
+    language: Language,
+) -> Option<Container<'static, Message, StyleType>> {
+    if !sniffer.show_non_ip && sniffer.info_traffic.lock().unwrap().non_ip.is_empty() {
+        return None;
+    }
+
+    let mut content = Column::new()
+        .align_items(Alignment::Start)
+        .spacing(10)
+        .push(
+            Toggler::new(
+                non_ip_traffic_translation(language).to_owned(),
+                sniffer.show_non_ip,
+                Message::ShowNonIpTraffic,
+            )
+            .width(Length::Shrink)
+            .spacing(5)
+            .size(23)
+            .font(font),
+        );
+
+    if sniffer.show_non_ip {
+        let is_search_active = !sniffer.non_ip_search.is_empty();
+        let mut input = TextInput::new("", &sniffer.non_ip_search)
+            .on_input(Message::NonIpSearch)
+            .padding([2, 5])
+            .size(FONT_SIZE_FOOTER)
+            .font(font)
+            .width(250)
+            .style(if is_search_active {
+                TextInputType::Badge
+            } else {
+                TextInputType::Standard
+            });
+        if !is_search_active {
+            input = input.icon(text_input::Icon {
+                font: ICONS,
+                code_point: Icon::Funnel.codepoint(),
+                size: Some(Pixels(12.0)),
+                spacing: 2.0,
+                side: Side::Left,
+            });
+        }
+        content = content.push(input);
+    }
+
+    Some(
+        Container::new(content)
+            .padding(10)
+            .style(ContainerType::BorderedRound),
+    )
+}
+
 fn report_header_row(
     language: Language,
     search_params: &SearchParameters,
@@ -206,12 +418,17 @@ fn report_header_row(
                     report_col.get_filter_input_type(),
                     search_params.clone(),
                     font,
+                    Message::Search,
                 ))
                 .height(Length::Fill)
                 .align_y(Vertical::Center),
             );
         } else {
-            col_header = col_header.push(sort_arrows(sort_type, &report_col));
+            col_header = col_header.push(sort_arrows(
+                sort_type,
+                &report_col,
+                Message::ReportSortSelection,
+            ));
         }
         ret_val = ret_val.push(col_header);
     }
@@ -253,9 +470,10 @@ fn title_report_col_display(
     }
 }
 
-fn sort_arrows(
+pub fn sort_arrows(
     active_sort_type: ReportSortType,
     report_col: &ReportCol,
+    message: fn(ReportSortType) -> Message,
 ) -> Container<'static, Message, StyleType> {
     Container::new(
         button(
@@ -265,9 +483,7 @@ fn sort_arrows(
                 .vertical_alignment(Vertical::Center),
         )
         .style(active_sort_type.button_type(report_col))
-        .on_press(Message::ReportSortSelection(
-            active_sort_type.next_sort(report_col),
-        )),
+        .on_press(message(active_sort_type.next_sort(report_col))),
     )
     .align_y(Vertical::Center)
     .height(Length::Fill)
@@ -306,10 +522,12 @@ fn row_report_entry(
     ret_val
 }
 
-fn host_filters_col(
+/// Column with the host-related filters; the message is emitted when the search is updated
+pub fn host_filters_col(
     search_params: &SearchParameters,
     font: Font,
     language: Language,
+    message: fn(SearchParameters) -> Message,
 ) -> Column<'static, Message, StyleType> {
     let search_params2 = search_params.clone();
 
@@ -323,15 +541,31 @@ fn host_filters_col(
         title_row = title_row.push(button_clear_filter(
             search_params.reset_host_filters(),
             font,
+            message,
         ));
     }
 
-    let input_country =
-        filter_input(FilterInputType::Country, search_params.clone(), font).width(95);
-    let input_domain =
-        filter_input(FilterInputType::Domain, search_params.clone(), font).width(190);
-    let input_as_name =
-        filter_input(FilterInputType::AsName, search_params.clone(), font).width(190);
+    let input_country = filter_input(
+        FilterInputType::Country,
+        search_params.clone(),
+        font,
+        message,
+    )
+    .width(95);
+    let input_domain = filter_input(
+        FilterInputType::Domain,
+        search_params.clone(),
+        font,
+        message,
+    )
+    .width(190);
+    let input_as_name = filter_input(
+        FilterInputType::AsName,
+        search_params.clone(),
+        font,
+        message,
+    )
+    .width(190);
 
     let container_country = Row::new()
         .spacing(5)
@@ -360,7 +594,7 @@ fn host_filters_col(
                     only_show_favorites_translation(language).to_owned(),
                     search_params.only_favorites,
                     move |toggled| {
-                        Message::Search(SearchParameters {
+                        message(SearchParameters {
                             only_favorites: toggled,
                             ..search_params2.clone()
                         })
@@ -394,20 +628,149 @@ fn host_filters_col(
         )
 }
 
+/// Container with the network adapter filter, only displayed when capturing from several adapters
+fn adapter_filter_container(
+    sniffer: &Sniffer,
+    font: Font,
+    language: Language,
+) -> Option<Container<'static, Message, StyleType>> {
+    if sniffer.additional_devices.is_empty() {
+        return None;
+    }
+
+    let input_interface = filter_input(
+        FilterInputType::Interface,
+        sniffer.search.clone(),
+        font,
+        Message::Search,
+    )
+    .width(150);
+
+    Some(
+        Container::new(
+            Column::new()
+                .align_items(Alignment::Start)
+                .spacing(10)
+                .push(
+                    Text::new(network_adapter_translation(language))
+                        .font(font)
+                        .style(TextType::Subtitle)
+                        .size(FONT_SIZE_SUBTITLE),
+                )
+                .push(input_interface),
+        )
+        .padding(10)
+        .style(ContainerType::BorderedRound),
+    )
+}
+
+/// Container with the MAC address filter, only displayed when it's active
+/// (i.e., after selecting a device in the devices page)
+fn mac_filter_container(
+    sniffer: &Sniffer,
+    font: Font,
+    language: Language,
+) -> Option<Container<'static, Message, StyleType>> {
+    if sniffer.search.mac.is_empty() {
+        return None;
+    }
+
+    let input_mac = filter_input(
+        FilterInputType::Mac,
+        sniffer.search.clone(),
+        font,
+        Message::Search,
+    )
+    .width(200);
+
+    Some(
+        Container::new(
+            Column::new()
+                .align_items(Alignment::Start)
+                .spacing(10)
+                .push(
+                    Text::new(mac_address_translation(language))
+                        .font(font)
+                        .style(TextType::Subtitle)
+                        .size(FONT_SIZE_SUBTITLE),
+                )
+                .push(input_mac),
+        )
+        .padding(10)
+        .style(ContainerType::BorderedRound),
+    )
+}
+
+/// Container with the outcome of the TCP connections, only displayed when some were observed;
+/// each count filters the corresponding connections
+fn tcp_stats_container(
+    sniffer: &Sniffer,
+    font: Font,
+    language: Language,
+) -> Option<Container<'static, Message, StyleType>> {
+    let tcp_stats = sniffer.info_traffic.lock().unwrap().tcp_stats;
+    if tcp_stats == TcpStats::default() {
+        return None;
+    }
+
+    let mut content = Column::new().align_items(Alignment::Start).push(
+        Text::new(tcp_connections_translation(language))
+            .font(font)
+            .style(TextType::Subtitle)
+            .size(FONT_SIZE_SUBTITLE),
+    );
+    for (caption, count, tcp_state) in [
+        (
+            failed_handshakes_translation(language),
+            tcp_stats.failed_handshakes,
+            "=refused",
+        ),
+        (resets_translation(language), tcp_stats.resets, "=rst"),
+        (
+            half_open_translation(language),
+            tcp_stats.half_open,
+            "=half-open",
+        ),
+    ] {
+        content = content.push(
+            button(
+                Text::new(format!("{caption}: {count}"))
+                    .font(font)
+                    .size(FONT_SIZE_FOOTER),
+            )
+            .padding([2, 5])
+            .style(ButtonType::Neutral)
+            .on_press(Message::Search(SearchParameters {
+                tcp_state: tcp_state.to_string(),
+                ..sniffer.search.clone()
+            })),
+        );
+    }
+
+    Some(
+        Container::new(content)
+            .padding(10)
+            .style(ContainerType::BorderedRound),
+    )
+}
+
 fn filter_input(
     filter_input_type: FilterInputType,
     search_params: SearchParameters,
     font: Font,
+    message: fn(SearchParameters) -> Message,
 ) -> Container<'static, Message, StyleType> {
     let filter_value = filter_input_type.current_value(&search_params);
     let is_filter_active = !filter_value.is_empty();
 
-    let button_clear = button_clear_filter(filter_input_type.clear_search(&search_params), font);
+    let button_clear = button_clear_filter(
+        filter_input_type.clear_search(&search_params),
+        font,
+        message,
+    );
 
     let mut input = TextInput::new("", filter_value)
-        .on_input(move |new_value| {
-            Message::Search(filter_input_type.new_search(&search_params, new_value))
-        })
+        .on_input(move |new_value| message(filter_input_type.new_search(&search_params, new_value)))
         .padding([2, 5])
         .size(FONT_SIZE_FOOTER)
         .font(font)
@@ -468,7 +831,7 @@ fn get_button_change_page(increment: bool) -> Button<'static, Message, StyleType
     .on_press(Message::UpdatePageNumber(increment))
 }
 
-fn get_change_page_row(
+pub fn get_change_page_row(
     font: Font,
     language: Language,
     page_number: usize,
@@ -503,9 +866,82 @@ fn get_change_page_row(
         .push(horizontal_space())
 }
 
+fn button_export_report(
+    font: Font,
+    language: Language,
+    export_error: Option<&str>,
+) -> Tooltip<'static, Message, StyleType> {
+    let content = button(
+        Icon::File
+            .to_text()
+            .size(24)
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .padding(0)
+    .height(40)
+    .width(40)
+    .on_press(Message::ExportReport);
+
+    // the last error is reported until the results are successfully exported
+    let tooltip = if let Some(error) = export_error {
+        Text::new(format!("{}\n{error}", export_results_translation(language)))
+            .style(TextType::Danger)
+    } else {
+        Text::new(export_results_translation(language))
+    };
+
+    Tooltip::new(content, tooltip.font(font), Position::Right)
+        .gap(5)
+        .style(ContainerType::Tooltip)
+}
+
+/// Button to export the packets of the connections satisfying the search to a PCAP file,
+/// with the option to write a file for each host
+fn export_pcap_col(
+    font: Font,
+    language: Language,
+    split_by_host: bool,
+    export_error: Option<&str>,
+) -> Column<'static, Message, StyleType> {
+    let content = button(
+        Text::new("PCAP")
+            .font(font)
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .padding([0, 8])
+    .height(40)
+    .on_press(Message::ExportPcapSelection(PcapSelection::SearchResults));
+
+    // the last error is reported until a new export is started
+    let tooltip = if let Some(error) = export_error {
+        Text::new(format!("{}\n{error}", export_packets_translation(language)))
+            .style(TextType::Danger)
+    } else {
+        Text::new(export_packets_translation(language))
+    };
+
+    let checkbox = Checkbox::new(split_by_host_translation(language), split_by_host)
+        .on_toggle(move |_| Message::ToggleSplitByHost)
+        .size(15)
+        .font(font);
+
+    Column::new()
+        .spacing(5)
+        .align_items(Alignment::Center)
+        .push(
+            Tooltip::new(content, tooltip.font(font), Position::Right)
+                .gap(5)
+                .style(ContainerType::Tooltip),
+        )
+        .push(checkbox)
+}
+
 fn button_clear_filter(
     new_search_parameters: SearchParameters,
     font: Font,
+    message: fn(SearchParameters) -> Message,
 ) -> Button<'static, Message, StyleType> {
     button(
         Text::new("×")
@@ -518,7 +954,7 @@ fn button_clear_filter(
     .padding(2)
     .height(20)
     .width(20)
-    .on_press(Message::Search(new_search_parameters))
+    .on_press(message(new_search_parameters))
 }
 
 #[cfg(test)]
@@ -1,8 +1,11 @@
 pub mod connection_details_page;
+pub mod devices_page;
+pub mod hosts_page;
 pub mod initial_page;
 pub mod inspect_page;
 pub mod notifications_page;
 pub mod overview_page;
+pub mod services_page;
 pub mod settings_general_page;
 pub mod settings_notifications_page;
 pub mod settings_style_page;
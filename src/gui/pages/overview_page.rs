@@ -9,7 +9,7 @@
 use iced::widget::tooltip::Position;
 use iced::widget::{
     button, horizontal_space, lazy, vertical_space, Button, Column, Container, Row, Rule,
-    Scrollable, Space, Text, Tooltip,
+    Scrollable, Space, Text, Toggler, Tooltip,
 };
 use iced::Length::{Fill, FillPortion};
 use iced::{Alignment, Font, Length};
@@ -24,13 +24,18 @@
 use crate::gui::styles::style_constants::FONT_SIZE_TITLE;
 use crate::gui::styles::text::TextType;
 use crate::gui::styles::types::palette_extension::PaletteExtension;
+use crate::gui::types::export_pcap::PcapSelection;
 use crate::gui::types::message::Message;
 use crate::gui::types::sniffer::Sniffer;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::filters::Filters;
 use crate::networking::types::host::Host;
 use crate::networking::types::my_device::MyDevice;
-use crate::report::get_report_entries::{get_host_entries, get_service_entries};
+use crate::networking::types::session::SessionInfo;
+use crate::report::get_report_entries::{
+    get_host_entries, get_non_ip_entries, get_program_entries, get_service_entries,
+};
+use crate::report::types::host_sort_metric::HostSortMetric;
 use crate::report::types::search_parameters::SearchParameters;
 use crate::report::types::sort_type::SortType;
 use crate::translations::translations::{
@@ -43,8 +48,14 @@
     data_representation_translation, dropped_packets_translation, host_translation,
     only_top_30_items_translation,
 };
-use crate::translations::translations_3::{service_translation, unsupported_link_type_translation};
-use crate::utils::formatted_strings::{get_active_filters_string, get_percentage_string};
+use crate::translations::translations_3::{
+    capture_file_translation, export_packets_translation, non_ip_traffic_translation,
+    program_translation, saved_session_translation, service_translation,
+    split_by_adapter_translation, unsupported_link_type_translation,
+};
+use crate::utils::formatted_strings::{
+    get_active_filters_string, get_path_termination_string, get_percentage_string,
+};
 use crate::utils::types::icon::Icon;
 use crate::{ByteMultiple, ChartType, ConfigSettings, Language, RunningPage, StyleType};
 
@@ -69,7 +80,11 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
         match (observed, filtered) {
             (0, 0) => {
                 //no packets observed at all
-                body = body_no_packets(&sniffer.device, font, language, &sniffer.waiting);
+                body = if let Some(file_path) = sniffer.import_pcap.file_path() {
+                    body_no_packets_offline(file_path, font, language, &sniffer.waiting)
+                } else {
+                    body_no_packets(&sniffer.device, font, language, &sniffer.waiting)
+                };
             }
             (observed, 0) => {
                 //no packets have been filtered but some have been observed
@@ -110,6 +125,8 @@ pub fn overview_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
                         sniffer.traffic_chart.chart_type,
                         sniffer.host_sort_type,
                         sniffer.service_sort_type,
+                        sniffer.program_sort_type,
+                        sniffer.non_ip_sort_type,
                     ),
                     move |_| lazy_row_report(sniffer),
                 );
@@ -187,6 +204,32 @@ fn body_no_packets(
         .push(Space::with_height(FillPortion(2)))
 }
 
+fn body_no_packets_offline(
+    file_path: &str,
+    font: Font,
+    language: Language,
+    waiting: &str,
+) -> Column<'static, Message, StyleType> {
+    Column::new()
+        .width(Length::Fill)
+        .padding(10)
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(vertical_space())
+        .push(Icon::get_hourglass(waiting.len()).size(60))
+        .push(Space::with_height(15))
+        .push(
+            Text::new(format!(
+                "{}: {file_path}",
+                capture_file_translation(language)
+            ))
+            .horizontal_alignment(Horizontal::Center)
+            .font(font),
+        )
+        .push(Text::new(waiting.to_owned()).font(font).size(50))
+        .push(Space::with_height(FillPortion(2)))
+}
+
 fn body_no_observed(
     filters: &Filters,
     observed: u128,
@@ -244,15 +287,44 @@ fn body_pcap_error(
 }
 
 fn lazy_row_report(sniffer: &Sniffer) -> Container<'static, Message, StyleType> {
-    let col_host = col_host(840.0, sniffer);
+    // programs owning the connections are only identified when sniffing live traffic on Linux
+    let show_programs = if sniffer.session.is_some() {
+        !sniffer.info_traffic.lock().unwrap().programs.is_empty()
+    } else {
+        cfg!(target_os = "linux") && sniffer.import_pcap.file_path().is_none()
+    };
+
+    // frames not carrying IP packets are only displayed if observed
+    let show_non_ip = !sniffer.info_traffic.lock().unwrap().non_ip.is_empty();
+
+    let mut host_width = 840.0;
+    if show_programs {
+        host_width -= 290.0;
+    }
+    if show_non_ip {
+        host_width -= 240.0;
+    }
+    let col_host = col_host(host_width, sniffer);
     let col_service = col_service(250.0, sniffer);
 
-    let row_report = Row::new()
+    let mut row_report = Row::new()
         .padding([0, 10, 5, 10])
         .push(col_host)
         .push(Rule::vertical(40))
         .push(col_service);
 
+    if show_programs {
+        row_report = row_report
+            .push(Rule::vertical(40))
+            .push(col_program(250.0, sniffer));
+    }
+
+    if show_non_ip {
+        row_report = row_report
+            .push(Rule::vertical(40))
+            .push(col_non_ip(200.0, sniffer));
+    }
+
     Container::new(row_report)
         .height(FillPortion(4))
         .style(ContainerType::BorderedRound)
@@ -266,7 +338,12 @@ fn col_host(width: f32, sniffer: &Sniffer) -> Column<'static, Message, StyleType
     let chart_type = sniffer.traffic_chart.chart_type;
 
     let mut scroll_host = Column::new().width(width).align_items(Alignment::Center);
-    let entries = get_host_entries(&sniffer.info_traffic, chart_type, sniffer.host_sort_type);
+    let entries = get_host_entries(
+        &sniffer.info_traffic,
+        chart_type,
+        sniffer.host_sort_type,
+        sniffer.host_sort_metric,
+    );
     let first_entry_data_info = entries
         .iter()
         .map(|(_, d)| d.data_info)
@@ -282,6 +359,7 @@ fn col_host(width: f32, sniffer: &Sniffer) -> Column<'static, Message, StyleType
         );
 
         let star_button = get_star_button(data_info_host.is_favorite, host.clone());
+        let export_button = get_export_pcap_button(host.clone(), language, font);
 
         let host_bar = Column::new()
             .width(width)
@@ -298,6 +376,12 @@ fn col_host(width: f32, sniffer: &Sniffer) -> Column<'static, Message, StyleType
                         .font(font),
                     )
                     .push(horizontal_space())
+                    .push_maybe(
+                        sniffer
+                            .host_sort_metric
+                            .get_value(data_info_host)
+                            .map(|value| Text::new(format!("{value}  |  ")).font(font)),
+                    )
                     .push(
                         Text::new(if chart_type.eq(&ChartType::Packets) {
                             data_info_host.data_info.tot_packets().to_string()
@@ -313,6 +397,7 @@ fn col_host(width: f32, sniffer: &Sniffer) -> Column<'static, Message, StyleType
             .align_items(Alignment::Center)
             .spacing(5)
             .push(star_button)
+            .push(export_button)
             .push(get_flag_tooltip(
                 host.country,
                 data_info_host,
@@ -351,6 +436,7 @@ fn col_host(width: f32, sniffer: &Sniffer) -> Column<'static, Message, StyleType
                         .size(FONT_SIZE_TITLE),
                 )
                 .push(horizontal_space())
+                .push(sort_metric_button(sniffer.host_sort_metric, language, font))
                 .push(sort_arrows(
                     sniffer.host_sort_type,
                     Message::HostSortSelection,
@@ -443,6 +529,156 @@ fn col_service(width: f32, sniffer: &Sniffer) -> Column<'static, Message, StyleT
         )
 }
 
+fn col_program(width: f32, sniffer: &Sniffer) -> Column<'static, Message, StyleType> {
+    let ConfigSettings {
+        style, language, ..
+    } = sniffer.configs.lock().unwrap().settings;
+    let font = style.get_extension().font;
+    let chart_type = sniffer.traffic_chart.chart_type;
+
+    let mut scroll_program = Column::new().width(width).align_items(Alignment::Center);
+    let entries = get_program_entries(&sniffer.info_traffic, chart_type, sniffer.program_sort_type);
+    let first_entry_data_info = entries
+        .iter()
+        .map(|(_, d)| *d)
+        .max_by(|d1, d2| d1.compare(d2, SortType::Ascending, chart_type))
+        .unwrap_or_default();
+
+    for (program, data_info) in &entries {
+        let (incoming_bar_len, outgoing_bar_len) =
+            get_bars_length(width * 0.88, chart_type, &first_entry_data_info, data_info);
+
+        let content = Column::new()
+            .spacing(1)
+            .width(width)
+            .push(
+                Row::new()
+                    .push(Text::new(program.clone()).font(font))
+                    .push(horizontal_space())
+                    .push(
+                        Text::new(if chart_type.eq(&ChartType::Packets) {
+                            data_info.tot_packets().to_string()
+                        } else {
+                            ByteMultiple::formatted_string(data_info.tot_bytes())
+                        })
+                        .font(font),
+                    ),
+            )
+            .push(get_bars(incoming_bar_len, outgoing_bar_len));
+
+        scroll_program = scroll_program.push(
+            button(content)
+                .padding([5, 15, 8, 10])
+                .on_press(Message::Search(SearchParameters::new_program_search(
+                    program,
+                )))
+                .style(ButtonType::Neutral),
+        );
+    }
+
+    if entries.len() >= 30 {
+        scroll_program = scroll_program.push(Space::with_height(25)).push(
+            Text::new(only_top_30_items_translation(language))
+                .font(font)
+                .horizontal_alignment(Horizontal::Center),
+        );
+    }
+
+    Column::new()
+        .width(width + 11.0)
+        .push(
+            Row::new()
+                .height(45)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new(program_translation(language))
+                        .font(font)
+                        .style(TextType::Title)
+                        .size(FONT_SIZE_TITLE),
+                )
+                .push(horizontal_space())
+                .push(sort_arrows(
+                    sniffer.program_sort_type,
+                    Message::ProgramSortSelection,
+                )),
+        )
+        .push(
+            Scrollable::new(scroll_program)
+                .width(Length::Fill)
+                .direction(Direction::Vertical(ScrollbarType::properties())),
+        )
+}
+
+fn col_non_ip(width: f32, sniffer: &Sniffer) -> Column<'static, Message, StyleType> {
+    let ConfigSettings {
+        style, language, ..
+    } = sniffer.configs.lock().unwrap().settings;
+    let font = style.get_extension().font;
+    let chart_type = sniffer.traffic_chart.chart_type;
+
+    let mut scroll_non_ip = Column::new().width(width).align_items(Alignment::Center);
+    let entries = get_non_ip_entries(&sniffer.info_traffic, chart_type, sniffer.non_ip_sort_type);
+    let first_entry_data_info = entries
+        .iter()
+        .map(|(_, d)| *d)
+        .max_by(|d1, d2| d1.compare(d2, SortType::Ascending, chart_type))
+        .unwrap_or_default();
+
+    for (protocol, data_info) in &entries {
+        let (incoming_bar_len, outgoing_bar_len) =
+            get_bars_length(width * 0.88, chart_type, &first_entry_data_info, data_info);
+
+        let content = Column::new()
+            .spacing(1)
+            .width(width)
+            .push(
+                Row::new()
+                    .push(Text::new(protocol.to_string()).font(font))
+                    .push(horizontal_space())
+                    .push(
+                        Text::new(if chart_type.eq(&ChartType::Packets) {
+                            data_info.tot_packets().to_string()
+                        } else {
+                            ByteMultiple::formatted_string(data_info.tot_bytes())
+                        })
+                        .font(font),
+                    ),
+            )
+            .push(get_bars(incoming_bar_len, outgoing_bar_len));
+
+        scroll_non_ip = scroll_non_ip.push(
+            button(content)
+                .padding([5, 15, 8, 10])
+                .on_press(Message::NonIpSearch(format!("={protocol}")))
+                .style(ButtonType::Neutral),
+        );
+    }
+
+    Column::new()
+        .width(width + 11.0)
+        .push(
+            Row::new()
+                .height(45)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new(non_ip_traffic_translation(language))
+                        .font(font)
+                        .style(TextType::Title)
+                        .size(FONT_SIZE_TITLE),
+                )
+                .push(horizontal_space())
+                .push(sort_arrows(
+                    sniffer.non_ip_sort_type,
+                    Message::NonIpSortSelection,
+                )),
+        )
+        .push(
+            Scrollable::new(scroll_non_ip)
+                .width(Length::Fill)
+                .direction(Direction::Vertical(ScrollbarType::properties())),
+        )
+}
+
 fn lazy_col_info(
     total: u128,
     filtered: u128,
@@ -456,7 +692,17 @@ fn lazy_col_info(
         font, font_headers, ..
     } = style.get_extension();
 
-    let col_device = col_device(language, font, &sniffer.device);
+    let col_device = if let Some(session) = &sniffer.session {
+        col_session(language, font, session)
+    } else {
+        col_device(
+            language,
+            font,
+            &sniffer.device,
+            &sniffer.additional_devices,
+            sniffer.import_pcap.file_path(),
+        )
+    };
 
     let col_data_representation =
         col_data_representation(language, font, sniffer.traffic_chart.chart_type);
@@ -512,26 +758,40 @@ fn container_chart(sniffer: &Sniffer, font: Font) -> Container<Message, StyleTyp
     });
     chart_info_string.push(')');
 
+    let mut title_row = Row::new()
+        .padding([10, 0])
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .push(
+            traffic_rate_translation(language)
+                .font(font)
+                .style(TextType::Title)
+                .size(FONT_SIZE_TITLE),
+        )
+        .push(
+            Text::new(chart_info_string)
+                .style(TextType::Subtitle)
+                .font(font),
+        );
+
+    if !sniffer.additional_devices.is_empty() {
+        title_row = title_row.push(Space::with_width(20)).push(
+            Toggler::new(
+                split_by_adapter_translation(language).to_owned(),
+                traffic_chart.split_by_adapter,
+                Message::ChartSplitByAdapter,
+            )
+            .width(Length::Shrink)
+            .spacing(5)
+            .size(23)
+            .font(font),
+        );
+    }
+
     Container::new(
         Column::new()
             .align_items(Alignment::Center)
-            .push(
-                Row::new()
-                    .padding([10, 0])
-                    .spacing(10)
-                    .align_items(Alignment::Center)
-                    .push(
-                        traffic_rate_translation(language)
-                            .font(font)
-                            .style(TextType::Title)
-                            .size(FONT_SIZE_TITLE),
-                    )
-                    .push(
-                        Text::new(chart_info_string)
-                            .style(TextType::Subtitle)
-                            .font(font),
-                    ),
-            )
+            .push(title_row)
             .push(traffic_chart.view()),
     )
     .width(Fill)
@@ -544,22 +804,69 @@ fn col_device(
     language: Language,
     font: Font,
     device: &MyDevice,
+    additional_devices: &[MyDevice],
+    capture_file: Option<&str>,
 ) -> Column<'static, Message, StyleType> {
     let link_type = device.link_type;
+    let adapter_info = std::iter::once(device)
+        .chain(additional_devices)
+        .map(|device| {
+            #[cfg(not(target_os = "windows"))]
+            let adapter_info = &device.name;
+            #[cfg(target_os = "windows")]
+            let adapter_info = device.desc.as_ref().unwrap_or(&device.name);
+            adapter_info.as_str()
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let source_info = if let Some(file_path) = capture_file {
+        TextType::highlighted_subtitle_with_desc(
+            capture_file_translation(language),
+            &get_path_termination_string(file_path, 30),
+            font,
+        )
+    } else {
+        TextType::highlighted_subtitle_with_desc(
+            network_adapter_translation(language),
+            &adapter_info,
+            font,
+        )
+    };
+
+    Column::new()
+        .height(Length::Fill)
+        .spacing(10)
+        .push(source_info)
+        .push(link_type.link_type_col(language, font))
+}
+
+fn col_session(
+    language: Language,
+    font: Font,
+    session: &SessionInfo,
+) -> Column<'static, Message, StyleType> {
     #[cfg(not(target_os = "windows"))]
-    let adapter_info = &device.name;
+    let adapter_info = &session.device_name;
     #[cfg(target_os = "windows")]
-    let adapter_info = device.desc.as_ref().unwrap_or(&device.name);
+    let adapter_info = session.device_desc.as_ref().unwrap_or(&session.device_name);
+
+    let time_format = "%Y-%m-%d %H:%M:%S";
+    let time_interval = format!(
+        "{} - {}",
+        session.start_timestamp.format(time_format),
+        session.stop_timestamp.format(time_format)
+    );
 
     Column::new()
         .height(Length::Fill)
         .spacing(10)
         .push(TextType::highlighted_subtitle_with_desc(
-            network_adapter_translation(language),
-            adapter_info,
+            saved_session_translation(language),
+            &get_path_termination_string(adapter_info, 30),
             font,
         ))
-        .push(link_type.link_type_col(language, font))
+        .push(Text::new(time_interval).font(font))
 }
 
 fn col_data_representation(
@@ -660,7 +967,7 @@ fn col_bytes_packets(
 
 const MIN_BARS_LENGTH: f32 = 10.0;
 
-fn get_bars_length(
+pub fn get_bars_length(
     tot_width: f32,
     chart_type: ChartType,
     first_entry: &DataInfo,
@@ -729,7 +1036,7 @@ fn get_bars_length(
     (in_len, out_len)
 }
 
-fn get_bars(in_len: f32, out_len: f32) -> Row<'static, Message, StyleType> {
+pub fn get_bars(in_len: f32, out_len: f32) -> Row<'static, Message, StyleType> {
     Row::new()
         .push(if in_len > 0.0 {
             Row::new()
@@ -747,7 +1054,7 @@ fn get_bars(in_len: f32, out_len: f32) -> Row<'static, Message, StyleType> {
         })
 }
 
-fn get_star_button(is_favorite: bool, host: Host) -> Button<'static, Message, StyleType> {
+pub fn get_star_button(is_favorite: bool, host: Host) -> Button<'static, Message, StyleType> {
     button(
         Icon::Star
             .to_text()
@@ -766,6 +1073,33 @@ fn get_star_button(is_favorite: bool, host: Host) -> Button<'static, Message, St
     .on_press(Message::AddOrRemoveFavorite(host, !is_favorite))
 }
 
+/// Button to export the packets of all the connections of the host to a PCAP file
+fn get_export_pcap_button(
+    host: Host,
+    language: Language,
+    font: Font,
+) -> Tooltip<'static, Message, StyleType> {
+    let content = button(
+        Icon::File
+            .to_text()
+            .size(16)
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+    )
+    .padding(0)
+    .height(FLAGS_WIDTH_BIG * 0.75)
+    .width(FLAGS_WIDTH_BIG * 0.75)
+    .style(ButtonType::NotStarred)
+    .on_press(Message::ExportPcapSelection(PcapSelection::Host(host)));
+
+    Tooltip::new(
+        content,
+        Text::new(export_packets_translation(language)).font(font),
+        Position::FollowCursor,
+    )
+    .style(ContainerType::Tooltip)
+}
+
 fn get_active_filters_col(
     filters: &Filters,
     language: Language,
@@ -809,6 +1143,29 @@ fn get_active_filters_col(
     ret_val
 }
 
+fn sort_metric_button(
+    active_sort_metric: HostSortMetric,
+    language: Language,
+    font: Font,
+) -> Container<'static, Message, StyleType> {
+    Container::new(
+        button(
+            Text::new(active_sort_metric.get_label(language))
+                .font(font)
+                .vertical_alignment(Vertical::Center),
+        )
+        .padding([2, 8])
+        .style(if active_sort_metric == HostSortMetric::Traffic {
+            ButtonType::SortArrows
+        } else {
+            ButtonType::SortArrowActive
+        })
+        .on_press(Message::HostSortMetricSelection(active_sort_metric.next())),
+    )
+    .align_y(Vertical::Center)
+    .height(Length::Fill)
+}
+
 fn sort_arrows(
     active_sort_type: SortType,
     message: fn(SortType) -> Message,
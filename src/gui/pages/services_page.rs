@@ -0,0 +1,156 @@
+use iced::alignment::{Horizontal, Vertical};
+use iced::widget::{button, lazy, Rule};
+use iced::widget::{Column, Container, Row, Text};
+use iced::{Alignment, Length};
+
+use crate::gui::components::tab::get_pages_tabs;
+use crate::gui::pages::hosts_page::{data_col, paginated_report, report_header_row};
+use crate::gui::pages::inspect_page::host_filters_col;
+use crate::gui::pages::overview_page::{get_bars, get_bars_length};
+use crate::gui::styles::button::ButtonType;
+use crate::gui::styles::container::ContainerType;
+use crate::gui::types::message::Message;
+use crate::report::get_report_entries::get_searched_services;
+use crate::report::types::search_parameters::SearchParameters;
+use crate::report::types::sort_type::SortType;
+use crate::translations::translations_3::services_translation;
+use crate::{ByteMultiple, ConfigSettings, RunningPage, Sniffer, StyleType};
+
+/// Width of the column with the service names
+const SERVICE_COL_WIDTH: f32 = 780.0;
+
+/// Computes the body of gui services page
+pub fn services_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
+    let ConfigSettings {
+        style, language, ..
+    } = sniffer.configs.lock().unwrap().settings;
+    let font = style.get_extension().font;
+    let font_headers = style.get_extension().font_headers;
+
+    let mut body = Column::new()
+        .width(Length::Fill)
+        .padding(10)
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+    let mut tab_and_body = Column::new().height(Length::Fill);
+
+    let tabs = get_pages_tabs(
+        RunningPage::Services,
+        font,
+        font_headers,
+        language,
+        sniffer.unread_notifications,
+    );
+
+    tab_and_body = tab_and_body.push(tabs);
+
+    let num_favorites = sniffer.info_traffic.lock().unwrap().favorite_hosts.len();
+    let report = lazy(
+        (
+            sniffer.runtime_data.tot_out_packets + sniffer.runtime_data.tot_in_packets,
+            num_favorites,
+            style,
+            language,
+            sniffer.traffic_chart.chart_type,
+            sniffer.services_sort_type,
+            sniffer.hosts_search.clone(),
+            sniffer.services_page_number,
+        ),
+        move |_| lazy_services_report(sniffer),
+    );
+
+    let col_report = Column::new()
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .push(report_header_row(
+            services_translation(language),
+            language,
+            font,
+            sniffer.services_sort_type,
+            Message::ServicesReportSortSelection,
+        ))
+        .push(Rule::horizontal(5))
+        .push(report);
+
+    body = body
+        .push(
+            Container::new(host_filters_col(
+                &sniffer.hosts_search,
+                font,
+                language,
+                Message::HostsSearch,
+            ))
+            .padding(10)
+            .style(ContainerType::BorderedRound),
+        )
+        .push(
+            Container::new(col_report)
+                .align_y(Vertical::Center)
+                .align_x(Horizontal::Center)
+                .padding([10, 7, 3, 7])
+                .width(1042)
+                .style(ContainerType::BorderedRound),
+        );
+
+    Container::new(Column::new().push(tab_and_body.push(body))).height(Length::Fill)
+}
+
+fn lazy_services_report(sniffer: &Sniffer) -> Column<'static, Message, StyleType> {
+    let ConfigSettings {
+        style, language, ..
+    } = sniffer.configs.lock().unwrap().settings;
+    let font = style.get_extension().font;
+    let chart_type = sniffer.traffic_chart.chart_type;
+
+    let (search_results, results_number) = get_searched_services(sniffer);
+    let first_entry_data_info = search_results
+        .iter()
+        .map(|&(_, d)| d)
+        .max_by(|d1, d2| d1.compare(d2, SortType::Ascending, chart_type))
+        .unwrap_or_default();
+
+    let mut scroll_report = Column::new().align_items(Alignment::Start);
+    for (service, data_info) in &search_results {
+        let (incoming_bar_len, outgoing_bar_len) = get_bars_length(
+            SERVICE_COL_WIDTH * 0.9,
+            chart_type,
+            &first_entry_data_info,
+            data_info,
+        );
+
+        let service_bar = Column::new()
+            .width(SERVICE_COL_WIDTH)
+            .spacing(1)
+            .push(Text::new(service.to_string()).font(font))
+            .push(get_bars(incoming_bar_len, outgoing_bar_len));
+
+        let content = Row::new()
+            .align_items(Alignment::Center)
+            .spacing(5)
+            .push(service_bar)
+            .push(data_col(data_info.tot_packets().to_string(), font))
+            .push(data_col(
+                ByteMultiple::formatted_string(data_info.tot_bytes()),
+                font,
+            ));
+
+        scroll_report = scroll_report.push(
+            button(content)
+                .padding([5, 15, 8, 10])
+                .on_press(Message::Search(SearchParameters::new_service_search(
+                    service,
+                )))
+                .style(ButtonType::Neutral),
+        );
+    }
+
+    paginated_report(
+        scroll_report,
+        font,
+        language,
+        sniffer.services_page_number,
+        search_results.len(),
+        results_number,
+    )
+}
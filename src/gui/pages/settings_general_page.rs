@@ -4,7 +4,8 @@
 use iced::widget::text::LineHeight;
 use iced::widget::tooltip::Position;
 use iced::widget::{
-    button, vertical_space, Column, Container, PickList, Row, Rule, Slider, Space, Text, Tooltip,
+    button, vertical_space, Column, Container, PickList, Row, Rule, Slider, Space, Text, Toggler,
+    Tooltip,
 };
 use iced::{Alignment, Font, Length};
 
@@ -21,8 +22,9 @@
 use crate::translations::translations::language_translation;
 use crate::translations::translations_2::country_translation;
 use crate::translations::translations_3::{
-    learn_more_translation, mmdb_files_translation, params_not_editable_translation,
-    zoom_translation,
+    custom_services_translation, learn_more_translation, look_up_host_names_translation,
+    mmdb_files_translation, params_not_editable_translation, reverse_dns_translation,
+    services_translation, snapshot_length_translation, zoom_translation,
 };
 use crate::utils::formatted_strings::get_path_termination_string;
 use crate::utils::types::file_info::FileInfo;
@@ -30,6 +32,12 @@
 use crate::utils::types::web_page::WebPage;
 use crate::{ConfigSettings, Language, RunningPage, Sniffer, StyleType};
 
+/// Maximum number of errors of the services file shown in the page
+const MAX_DISPLAYED_ERRORS: usize = 5;
+
+/// Selectable maximum numbers of bytes captured for each packet
+const SNAPLEN_VALUES: [u16; 5] = [256, 1024, 4096, 16384, u16::MAX];
+
 pub fn settings_general_page(sniffer: &Sniffer) -> Container<Message, StyleType> {
     let ConfigSettings {
         style,
@@ -68,6 +76,9 @@ fn column_all_general_setting(
         scale_factor,
         mmdb_country,
         mmdb_asn,
+        services_file,
+        reverse_dns,
+        snaplen,
         ..
     } = sniffer.configs.lock().unwrap().settings.clone();
 
@@ -89,15 +100,40 @@ fn column_all_general_setting(
             .push(Space::with_height(10));
     }
 
-    column = column.push(mmdb_settings(
-        is_editable,
-        language,
-        font,
-        &mmdb_country,
-        &mmdb_asn,
-        &sniffer.country_mmdb_reader,
-        &sniffer.asn_mmdb_reader,
-    ));
+    column = column.push(
+        Row::new()
+            .align_items(Alignment::Start)
+            .push(mmdb_settings(
+                is_editable,
+                language,
+                font,
+                &mmdb_country,
+                &mmdb_asn,
+                &sniffer.country_mmdb_reader,
+                &sniffer.asn_mmdb_reader,
+            ))
+            .push(Rule::vertical(25))
+            .push(reverse_dns_settings(
+                is_editable,
+                language,
+                font,
+                reverse_dns,
+            )),
+    );
+
+    column = column.push(Rule::horizontal(25)).push(
+        Row::new()
+            .align_items(Alignment::Start)
+            .push(services_settings(
+                is_editable,
+                language,
+                font,
+                &services_file,
+                &sniffer.services_file_errors,
+            ))
+            .push(Rule::vertical(25))
+            .push(snaplen_settings(is_editable, language, font, snaplen)),
+    );
 
     column
 }
@@ -256,6 +292,7 @@ fn mmdb_settings(
     asn_reader: &Arc<MmdbReader>,
 ) -> Column<'static, Message, StyleType> {
     Column::new()
+        .width(Length::FillPortion(2))
         .spacing(5)
         .align_items(Alignment::Center)
         .push(
@@ -264,44 +301,148 @@ fn mmdb_settings(
                 .style(TextType::Subtitle)
                 .size(FONT_SIZE_SUBTITLE),
         )
-        .push(mmdb_selection_row(
+        .push(file_selection_row(
             is_editable,
             font,
             Message::CustomCountryDb,
             country_path,
-            country_reader,
+            is_mmdb_error(country_path, country_reader),
+            FileInfo::Database,
             country_translation(language),
             language,
         ))
-        .push(mmdb_selection_row(
+        .push(file_selection_row(
             is_editable,
             font,
             Message::CustomAsnDb,
             asn_path,
-            asn_reader,
+            is_mmdb_error(asn_path, asn_reader),
+            FileInfo::Database,
             "ASN",
             language,
         ))
 }
 
-fn mmdb_selection_row(
+fn services_settings(
     is_editable: bool,
+    language: Language,
     font: Font,
-    message: fn(String) -> Message,
-    custom_path: &str,
-    mmdb_reader: &Arc<MmdbReader>,
-    caption: &str,
+    services_path: &str,
+    errors: &[String],
+) -> Column<'static, Message, StyleType> {
+    let mut column = Column::new()
+        .spacing(5)
+        .align_items(Alignment::Center)
+        .push(
+            Text::new(custom_services_translation(language))
+                .font(font)
+                .style(TextType::Subtitle)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(file_selection_row(
+            is_editable,
+            font,
+            Message::CustomServicesFile,
+            services_path,
+            !errors.is_empty(),
+            FileInfo::Services,
+            services_translation(language),
+            language,
+        ));
+
+    for error in errors.iter().take(MAX_DISPLAYED_ERRORS) {
+        column = column.push(Text::new(error.clone()).font(font).style(TextType::Danger));
+    }
+    if errors.len() > MAX_DISPLAYED_ERRORS {
+        column = column.push(
+            Text::new(format!("... (+{})", errors.len() - MAX_DISPLAYED_ERRORS))
+                .font(font)
+                .style(TextType::Danger),
+        );
+    }
+
+    column
+}
+
+fn reverse_dns_settings(
+    is_editable: bool,
     language: Language,
-) -> Row<'static, Message, StyleType> {
-    let is_error = if custom_path.is_empty() {
+    font: Font,
+    reverse_dns: bool,
+) -> Column<'static, Message, StyleType> {
+    Column::new()
+        .width(Length::Fill)
+        .spacing(5)
+        .align_items(Alignment::Center)
+        .push(
+            Text::new(reverse_dns_translation(language))
+                .font(font)
+                .style(TextType::Subtitle)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(
+            Toggler::new(
+                look_up_host_names_translation(language).to_owned(),
+                reverse_dns,
+                move |toggled| {
+                    Message::ToggleReverseDns(if is_editable { toggled } else { reverse_dns })
+                },
+            )
+            .width(Length::Shrink)
+            .spacing(5)
+            .size(23)
+            .font(font),
+        )
+}
+
+fn snaplen_settings(
+    is_editable: bool,
+    language: Language,
+    font: Font,
+    snaplen: u16,
+) -> Column<'static, Message, StyleType> {
+    Column::new()
+        .width(Length::Fill)
+        .spacing(5)
+        .align_items(Alignment::Center)
+        .push(
+            Text::new(snapshot_length_translation(language))
+                .font(font)
+                .style(TextType::Subtitle)
+                .size(FONT_SIZE_SUBTITLE),
+        )
+        .push(
+            PickList::new(&SNAPLEN_VALUES[..], Some(snaplen), move |value| {
+                Message::SnaplenSelection(if is_editable { value } else { snaplen })
+            })
+            .padding([2, 7])
+            .font(font),
+        )
+}
+
+/// Checks if a custom database was selected but couldn't be loaded
+fn is_mmdb_error(custom_path: &str, mmdb_reader: &Arc<MmdbReader>) -> bool {
+    if custom_path.is_empty() {
         false
     } else {
         match **mmdb_reader {
             MmdbReader::Default(_) => true,
             MmdbReader::Custom(_) => false,
         }
-    };
+    }
+}
 
+#[allow(clippy::too_many_arguments)]
+fn file_selection_row(
+    is_editable: bool,
+    font: Font,
+    message: fn(String) -> Message,
+    custom_path: &str,
+    is_error: bool,
+    file_info: FileInfo,
+    caption: &str,
+    language: Language,
+) -> Row<'static, Message, StyleType> {
     Row::new()
         .align_items(Alignment::Center)
         .push(Text::new(format!("{caption}: ")).font(font))
@@ -317,18 +458,18 @@ fn mmdb_selection_row(
         .push(if custom_path.is_empty() {
             button_open_file(
                 custom_path.to_owned(),
-                FileInfo::Database,
+                file_info,
                 language,
                 font,
                 is_editable,
                 message,
             )
         } else {
-            button_clear_mmdb(message, font, is_editable)
+            button_clear_file(message, font, is_editable)
         })
 }
 
-fn button_clear_mmdb(
+fn button_clear_file(
     message: fn(String) -> Message,
     font: Font,
     is_editable: bool,
@@ -16,6 +16,7 @@
 use crate::networking::types::host::Host;
 use crate::networking::types::info_traffic::InfoTraffic;
 use crate::report::get_report_entries::{get_host_entries, get_service_entries};
+use crate::report::types::host_sort_metric::HostSortMetric;
 use crate::report::types::sort_type::SortType;
 use crate::translations::types::language::Language;
 
@@ -70,7 +71,12 @@ fn host_col(
         .padding([0, 5])
         .spacing(3)
         .width(Length::FillPortion(2));
-    let hosts = get_host_entries(info_traffic, chart_type, SortType::Neutral);
+    let hosts = get_host_entries(
+        info_traffic,
+        chart_type,
+        SortType::Neutral,
+        HostSortMetric::Traffic,
+    );
     let n_entry = min(hosts.len(), MAX_ENTRIES);
     for (host, data_info_host) in hosts.get(..n_entry).unwrap_or_default() {
         let flag = get_flag_tooltip(
@@ -1,6 +1,9 @@
 use crate::gui::types::message::Message;
 use crate::translations::translations::{notifications_translation, overview_translation};
 use crate::translations::translations_2::inspect_translation;
+use crate::translations::translations_3::{
+    devices_translation, hosts_translation, services_translation,
+};
 use crate::utils::types::icon::Icon;
 use crate::{Language, StyleType};
 
@@ -13,14 +16,23 @@ pub enum RunningPage {
     Overview,
     /// Inspect page.
     Inspect,
+    /// Hosts page.
+    Hosts,
+    /// Services page.
+    Services,
+    /// Devices page.
+    Devices,
     /// Notifications page.
     Notifications,
 }
 
 impl RunningPage {
-    pub const ALL: [RunningPage; 3] = [
+    pub const ALL: [RunningPage; 6] = [
         RunningPage::Overview,
         RunningPage::Inspect,
+        RunningPage::Hosts,
+        RunningPage::Services,
+        RunningPage::Devices,
         RunningPage::Notifications,
     ];
 
@@ -28,6 +40,9 @@ pub fn get_tab_label(&self, language: Language) -> &str {
         match self {
             RunningPage::Overview => overview_translation(language),
             RunningPage::Inspect => inspect_translation(language),
+            RunningPage::Hosts => hosts_translation(language),
+            RunningPage::Services => services_translation(language),
+            RunningPage::Devices => devices_translation(language),
             RunningPage::Notifications => notifications_translation(language),
             RunningPage::Init => "",
         }
@@ -36,7 +51,10 @@ pub fn get_tab_label(&self, language: Language) -> &str {
     pub fn next(self) -> Self {
         match self {
             RunningPage::Overview => RunningPage::Inspect,
-            RunningPage::Inspect => RunningPage::Notifications,
+            RunningPage::Inspect => RunningPage::Hosts,
+            RunningPage::Hosts => RunningPage::Services,
+            RunningPage::Services => RunningPage::Devices,
+            RunningPage::Devices => RunningPage::Notifications,
             RunningPage::Notifications => RunningPage::Overview,
             RunningPage::Init => RunningPage::Init,
         }
@@ -46,7 +64,10 @@ pub fn previous(self) -> Self {
         match self {
             RunningPage::Overview => RunningPage::Notifications,
             RunningPage::Inspect => RunningPage::Overview,
-            RunningPage::Notifications => RunningPage::Inspect,
+            RunningPage::Hosts => RunningPage::Inspect,
+            RunningPage::Services => RunningPage::Hosts,
+            RunningPage::Devices => RunningPage::Services,
+            RunningPage::Notifications => RunningPage::Devices,
             RunningPage::Init => RunningPage::Init,
         }
     }
@@ -55,6 +76,9 @@ pub fn icon(self) -> iced::widget::Text<'static, StyleType> {
         match self {
             RunningPage::Overview => Icon::Overview,
             RunningPage::Inspect => Icon::Inspect,
+            RunningPage::Hosts => Icon::Globe,
+            RunningPage::Services => Icon::Waves,
+            RunningPage::Devices => Icon::Lightning,
             RunningPage::Notifications => Icon::Notification,
             RunningPage::Init => Icon::Sniffnet,
         }
@@ -73,14 +97,20 @@ mod tests {
     #[test]
     fn test_previous_running_page() {
         assert_eq!(RunningPage::Overview.previous(), RunningPage::Notifications);
-        assert_eq!(RunningPage::Notifications.previous(), RunningPage::Inspect);
+        assert_eq!(RunningPage::Notifications.previous(), RunningPage::Devices);
+        assert_eq!(RunningPage::Devices.previous(), RunningPage::Services);
+        assert_eq!(RunningPage::Services.previous(), RunningPage::Hosts);
+        assert_eq!(RunningPage::Hosts.previous(), RunningPage::Inspect);
         assert_eq!(RunningPage::Inspect.previous(), RunningPage::Overview);
     }
 
     #[test]
     fn test_next_running_page() {
         assert_eq!(RunningPage::Overview.next(), RunningPage::Inspect);
-        assert_eq!(RunningPage::Inspect.next(), RunningPage::Notifications);
+        assert_eq!(RunningPage::Inspect.next(), RunningPage::Hosts);
+        assert_eq!(RunningPage::Hosts.next(), RunningPage::Services);
+        assert_eq!(RunningPage::Services.next(), RunningPage::Devices);
+        assert_eq!(RunningPage::Devices.next(), RunningPage::Notifications);
         assert_eq!(RunningPage::Notifications.next(), RunningPage::Overview);
     }
 }
@@ -1,9 +1,18 @@
 use std::path::PathBuf;
 
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::host::Host;
+use crate::networking::types::pcap_rotation::{parse_limit, PcapRotation};
+
 pub struct ExportPcap {
     enabled: bool,
     file_name: String,
     directory: String,
+    /// Whether the packets of each remote host are exported to their own file
+    /// when exporting the selected connections
+    split_by_host: bool,
+    /// Criteria to split the exported capture into several files
+    rotation: PcapRotation,
 }
 
 impl ExportPcap {
@@ -21,6 +30,64 @@ pub fn set_directory(&mut self, directory: String) {
         self.directory = directory;
     }
 
+    /// Switches between the PCAP and PCAPNG formats, changing the extension of the file name
+    pub fn toggle_pcapng(&mut self) {
+        let file_name = if self.file_name.is_empty() {
+            Self::DEFAULT_FILE_NAME
+        } else {
+            &self.file_name
+        };
+        let extension = if self.is_pcapng() { "pcap" } else { "pcapng" };
+        self.file_name = PathBuf::from(file_name)
+            .with_extension(extension)
+            .to_string_lossy()
+            .to_string();
+    }
+
+    /// Whether the packets are recorded in the PCAPNG format
+    pub fn is_pcapng(&self) -> bool {
+        is_pcapng(&self.file_name)
+    }
+
+    pub fn toggle_split_by_host(&mut self) {
+        self.split_by_host = !self.split_by_host;
+    }
+
+    pub fn split_by_host(&self) -> bool {
+        self.split_by_host
+    }
+
+    /// Sets the megabytes after which a new file is started (empty for no limit);
+    /// invalid values are ignored
+    pub fn set_rotation_size(&mut self, value: &str) {
+        if let Some(max_size_mb) = parse_limit(value) {
+            self.rotation.max_size_mb = max_size_mb;
+        }
+    }
+
+    /// Sets the minutes after which a new file is started (empty for no limit);
+    /// invalid values are ignored
+    pub fn set_rotation_minutes(&mut self, value: &str) {
+        if let Some(max_minutes) = parse_limit(value) {
+            self.rotation.max_minutes = max_minutes;
+        }
+    }
+
+    /// Sets the maximum number of files kept (empty for no limit); invalid values are ignored
+    pub fn set_max_files(&mut self, value: &str) {
+        if let Some(max_files) = parse_limit(value) {
+            self.rotation.max_files = max_files;
+        }
+    }
+
+    pub fn set_rotation(&mut self, rotation: PcapRotation) {
+        self.rotation = rotation;
+    }
+
+    pub fn rotation(&self) -> PcapRotation {
+        self.rotation
+    }
+
     pub fn enabled(&self) -> bool {
         self.enabled
     }
@@ -47,6 +114,39 @@ pub fn full_path(&self) -> Option<String> {
             None
         }
     }
+
+    /// Returns the path of the file for the given adapter, used when capturing from several
+    /// adapters at once (e.g., `sniffnet_eth0.pcap`)
+    pub fn full_path_for_adapter(&self, adapter: &str) -> Option<String> {
+        Some(path_with_suffix(&self.full_path()?, adapter))
+    }
+}
+
+/// Whether the packets have to be recorded in the PCAPNG format, according to the file extension
+pub fn is_pcapng(path: &str) -> bool {
+    PathBuf::from(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("pcapng"))
+}
+
+/// Appends the suffix to the stem of the file name (e.g., `sniffnet.pcap` becomes `sniffnet_suffix.pcap`)
+pub fn path_with_suffix(path: &str, suffix: &str) -> String {
+    let full_path = PathBuf::from(path);
+    let stem = full_path.file_stem().unwrap_or_default().to_string_lossy();
+    // adapter and host names may contain characters not allowed in file names (e.g., on Windows)
+    let suffix: String = suffix
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let mut file_name = format!("{stem}_{suffix}");
+    if let Some(extension) = full_path.extension() {
+        file_name.push('.');
+        file_name.push_str(&extension.to_string_lossy());
+    }
+    full_path
+        .with_file_name(file_name)
+        .to_string_lossy()
+        .to_string()
 }
 
 impl Default for ExportPcap {
@@ -55,6 +155,33 @@ fn default() -> Self {
             enabled: false,
             file_name: String::from(Self::DEFAULT_FILE_NAME),
             directory: std::env::var("HOME").unwrap_or_default(),
+            split_by_host: false,
+            rotation: PcapRotation::default(),
+        }
+    }
+}
+
+/// Traffic selected to be exported to its own PCAP file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PcapSelection {
+    /// A single connection, with both its directions
+    Connection(AddressPortPair),
+    /// Every connection of a host
+    Host(Host),
+    /// The connections currently shown in the inspect page
+    SearchResults,
+}
+
+impl PcapSelection {
+    /// Name proposed for the exported file
+    pub fn file_name(&self) -> String {
+        match self {
+            Self::Connection(key) => path_with_suffix(
+                ExportPcap::DEFAULT_FILE_NAME,
+                &format!("{}_{}", key.address2, key.port2.unwrap_or_default()),
+            ),
+            Self::Host(host) => path_with_suffix(ExportPcap::DEFAULT_FILE_NAME, &host.domain),
+            Self::SearchResults => ExportPcap::DEFAULT_FILE_NAME.to_string(),
         }
     }
 }
@@ -159,4 +286,111 @@ fn test_full_path() {
         export_pcap.set_directory("".to_string());
         assert_eq!(export_pcap.full_path(), Some("sniffnet.pcap".to_string()));
     }
+
+    #[test]
+    fn test_toggle_split_by_host() {
+        let mut export_pcap = ExportPcap::default();
+        assert!(!export_pcap.split_by_host());
+
+        export_pcap.toggle_split_by_host();
+        assert!(export_pcap.split_by_host());
+    }
+
+    #[test]
+    fn test_set_rotation() {
+        let mut export_pcap = ExportPcap::default();
+        assert_eq!(export_pcap.rotation(), PcapRotation::default());
+
+        export_pcap.set_rotation_size("100");
+        export_pcap.set_rotation_minutes("60");
+        export_pcap.set_max_files("10");
+        assert_eq!(
+            export_pcap.rotation(),
+            PcapRotation {
+                max_size_mb: Some(100),
+                max_minutes: Some(60),
+                max_files: Some(10),
+            }
+        );
+
+        // invalid values are ignored
+        export_pcap.set_rotation_size("10a");
+        export_pcap.set_max_files("0");
+        assert_eq!(export_pcap.rotation().max_size_mb, Some(100));
+        assert_eq!(export_pcap.rotation().max_files, Some(10));
+
+        // empty values remove the limits
+        export_pcap.set_rotation_size("");
+        export_pcap.set_rotation_minutes("");
+        assert!(!export_pcap.rotation().is_enabled());
+    }
+
+    #[test]
+    fn test_toggle_pcapng() {
+        let mut export_pcap = ExportPcap::default();
+        assert!(!export_pcap.is_pcapng());
+
+        export_pcap.toggle_pcapng();
+        assert!(export_pcap.is_pcapng());
+        assert_eq!(export_pcap.file_name(), "sniffnet.pcapng");
+        export_pcap.toggle_pcapng();
+        assert!(!export_pcap.is_pcapng());
+        assert_eq!(export_pcap.file_name(), "sniffnet.pcap");
+
+        export_pcap.set_file_name("capture.PCAPNG".to_string());
+        assert!(export_pcap.is_pcapng());
+        export_pcap.toggle_pcapng();
+        assert_eq!(export_pcap.file_name(), "capture.pcap");
+
+        export_pcap.set_file_name(String::new());
+        export_pcap.toggle_pcapng();
+        assert_eq!(export_pcap.file_name(), "sniffnet.pcapng");
+    }
+
+    #[test]
+    fn test_pcap_selection_file_name() {
+        let key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(50000),
+            "140.82.121.4".to_string(),
+            Some(443),
+            crate::Protocol::TCP,
+        );
+        assert_eq!(
+            PcapSelection::Connection(key).file_name(),
+            "sniffnet_140_82_121_4_443.pcap"
+        );
+        let host = Host {
+            domain: "github.com".to_string(),
+            ..Host::default()
+        };
+        assert_eq!(
+            PcapSelection::Host(host).file_name(),
+            "sniffnet_github_com.pcap"
+        );
+        assert_eq!(PcapSelection::SearchResults.file_name(), "sniffnet.pcap");
+    }
+
+    #[test]
+    fn test_full_path_for_adapter() {
+        let mut export_pcap = ExportPcap::default();
+        assert_eq!(export_pcap.full_path_for_adapter("eth0"), None);
+
+        export_pcap.toggle();
+        export_pcap.set_directory("/tmp".to_string());
+        let mut full_path = PathBuf::from("/tmp");
+        full_path.push("sniffnet_eth0.pcap");
+        assert_eq!(
+            export_pcap.full_path_for_adapter("eth0"),
+            Some(full_path.to_string_lossy().to_string())
+        );
+
+        export_pcap.set_file_name("capture".to_string());
+        let mut full_path = PathBuf::from("/tmp");
+        full_path.push("capture__Device_NPF__1234_");
+        assert_eq!(
+            export_pcap.full_path_for_adapter("\\Device\\NPF_{1234}"),
+            Some(full_path.to_string_lossy().to_string())
+        );
+    }
 }
@@ -0,0 +1,86 @@
+/// Information about the PCAP file to be analyzed in place of a network adapter
+#[derive(Default)]
+pub struct ImportPcap {
+    enabled: bool,
+    path: String,
+    real_time: bool,
+}
+
+impl ImportPcap {
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    pub fn toggle_real_time(&mut self) {
+        self.real_time = !self.real_time;
+    }
+
+    pub fn set_path(&mut self, path: String) {
+        self.path = path;
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Whether packets are replayed respecting their original timing,
+    /// instead of as fast as possible
+    pub fn real_time(&self) -> bool {
+        self.real_time
+    }
+
+    /// Returns the path of the file to analyze, if a file has been selected as capture source
+    pub fn file_path(&self) -> Option<&str> {
+        if self.enabled && !self.path.is_empty() {
+            Some(&self.path)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        let import_pcap = ImportPcap::default();
+        assert!(!import_pcap.enabled());
+        assert_eq!(import_pcap.path(), "");
+        assert!(!import_pcap.real_time());
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut import_pcap = ImportPcap::default();
+        import_pcap.toggle();
+        assert!(import_pcap.enabled());
+        import_pcap.toggle();
+        assert!(!import_pcap.enabled());
+
+        import_pcap.toggle_real_time();
+        assert!(import_pcap.real_time());
+        import_pcap.toggle_real_time();
+        assert!(!import_pcap.real_time());
+    }
+
+    #[test]
+    fn test_file_path() {
+        let mut import_pcap = ImportPcap::default();
+        assert_eq!(import_pcap.file_path(), None);
+
+        import_pcap.set_path("/tmp/trace.pcapng".to_string());
+        assert_eq!(import_pcap.file_path(), None);
+
+        import_pcap.toggle();
+        assert_eq!(import_pcap.file_path(), Some("/tmp/trace.pcapng"));
+
+        import_pcap.set_path(String::new());
+        assert_eq!(import_pcap.file_path(), None);
+    }
+}
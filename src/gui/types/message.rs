@@ -2,8 +2,10 @@
 use crate::gui::pages::types::running_page::RunningPage;
 use crate::gui::pages::types::settings_page::SettingsPage;
 use crate::gui::styles::types::gradient_type::GradientType;
+use crate::gui::types::export_pcap::PcapSelection;
 use crate::networking::types::host::Host;
 use crate::notifications::types::notifications::Notification;
+use crate::report::types::host_sort_metric::HostSortMetric;
 use crate::report::types::search_parameters::SearchParameters;
 use crate::report::types::sort_type::SortType;
 use crate::utils::types::file_info::FileInfo;
@@ -19,6 +21,8 @@ pub enum Message {
     TickRun,
     /// Select adapter
     AdapterSelection(String),
+    /// Add (true) or remove (false) an adapter to/from the ones captured at the same time
+    AdapterMultiSelection(String, bool),
     /// Select IP filter
     IpVersionSelection(IpVersion, bool),
     /// Select protocol filter
@@ -27,14 +31,30 @@ pub enum Message {
     AddressFilter(String),
     /// Changed port filter
     PortFilter(String),
+    /// Changed BPF filter expression
+    BpfFilter(String),
     /// Select chart type to be displayed
     ChartSelection(ChartType),
+    /// Whether the traffic chart should display a separate series for each network adapter
+    ChartSplitByAdapter(bool),
     /// Select report sort type to be displayed (inspect page)
     ReportSortSelection(ReportSortType),
+    /// Select report sort type to be displayed (hosts page)
+    HostsReportSortSelection(ReportSortType),
+    /// Select report sort type to be displayed (services page)
+    ServicesReportSortSelection(ReportSortType),
+    /// Select report sort type to be displayed (devices page)
+    DevicesReportSortSelection(ReportSortType),
     /// Select host sort type to be displayed (overview page)
     HostSortSelection(SortType),
+    /// Select the metric used to sort hosts (overview page)
+    HostSortMetricSelection(HostSortMetric),
     /// Select service sort type to be displayed (overview page)
     ServiceSortSelection(SortType),
+    /// Select program sort type to be displayed (overview page)
+    ProgramSortSelection(SortType),
+    /// Select non-IP protocol sort type to be displayed (overview page)
+    NonIpSortSelection(SortType),
     /// Adds or removes the given host into/from the favorites
     AddOrRemoveFavorite(Host, bool),
     /// Open the supplied web page
@@ -57,6 +77,10 @@ pub enum Message {
     OpenLastSettings,
     /// Hides the current modal
     HideModal,
+    /// Show the packets of the connection instead of its details (connection details modal)
+    ShowConnectionPackets(bool),
+    /// Select the packet to be inspected (connection details modal)
+    SelectPacket(u128),
     /// Hides the current setting page
     CloseSettings,
     /// Permits to change the current running page
@@ -81,7 +105,15 @@ pub enum Message {
     CtrlDPressed,
     /// Update search parameters of inspect page
     Search(SearchParameters),
-    /// Update page result number in inspect
+    /// Update search parameters of hosts and services pages
+    HostsSearch(SearchParameters),
+    /// Update search string of devices page
+    DevicesSearch(String),
+    /// Update search string of the non-IP traffic in the inspect page
+    NonIpSearch(String),
+    /// Whether the inspect page shows the non-IP traffic instead of the IP connections
+    ShowNonIpTraffic(bool),
+    /// Update page result number in inspect, hosts, or services page
     UpdatePageNumber(bool),
     /// Left (false) or Right (true) arrow key has been pressed
     ArrowPressed(bool),
@@ -99,6 +131,12 @@ pub enum Message {
     CustomCountryDb(String),
     /// The ASN MMDB custom path has been updated
     CustomAsnDb(String),
+    /// Set the path of the file defining custom services
+    CustomServicesFile(String),
+    /// Enable or disable active reverse DNS lookups
+    ToggleReverseDns(bool),
+    /// Select the maximum number of bytes captured for each packet
+    SnaplenSelection(u16),
     /// Save the configurations of the app and quit
     CloseRequested,
     /// Copies the given string to clipboard
@@ -111,6 +149,36 @@ pub enum Message {
     OutputPcapDir(String),
     /// The output PCAP file name has been updated
     OutputPcapFile(String),
+    /// Switch the output PCAP file between the PCAP and PCAPNG formats
+    TogglePcapng,
+    /// The megabytes after which the output PCAP file is rotated have been updated
+    PcapRotationSize(String),
+    /// The minutes after which the output PCAP file is rotated have been updated
+    PcapRotationMinutes(String),
+    /// The maximum number of rotated PCAP files kept has been updated
+    PcapMaxFiles(String),
+    /// Toggle import of a PCAP file in place of a network adapter
+    ToggleImportPcap,
+    /// The input PCAP file path has been updated
+    InputPcapFile(String),
+    /// Toggle real-time replay of the imported PCAP file
+    ToggleReplayRealTime,
+    /// Open the dialog to save the current session
+    SaveSession,
+    /// Save the current session to the given file
+    StoreSession(String),
+    /// Load a saved session from the given file
+    LoadSession(String),
+    /// Open the dialog to export the connections of the inspect page
+    ExportReport,
+    /// Export the connections of the inspect page to the given file
+    WriteReport(String),
+    /// Open the dialog to export the packets of the selected traffic to a PCAP file
+    ExportPcapSelection(PcapSelection),
+    /// Export the packets of the selected traffic to the given PCAP file
+    WritePcapSelection(PcapSelection, String),
+    /// Toggle whether the selected traffic is exported to a PCAP file for each host
+    ToggleSplitByHost,
     /// Toggle thumbnail mode
     ToggleThumbnail(bool),
     /// Drag the window
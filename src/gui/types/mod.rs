@@ -1,4 +1,5 @@
 pub mod export_pcap;
+pub mod import_pcap;
 pub mod message;
 pub mod runtime_data;
 pub mod sniffer;
@@ -1,7 +1,8 @@
 //! Module defining the `RunTimeData` struct, useful to to generate chart and to display statistics about network traffic
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
+use crate::networking::types::data_info::DataInfo;
 use crate::notifications::types::logged_notification::LoggedNotification;
 
 /// Struct containing useful data to display statistics about network traffic and the relative notifications
@@ -28,6 +29,10 @@ pub struct RunTimeData {
     pub tot_out_packets_prev: u128,
     /// Total received packets filtered before the current time interval
     pub tot_in_packets_prev: u128,
+    /// Filtered traffic of each second of the capture not displayed in the chart yet
+    pub traffic_per_second: BTreeMap<i64, DataInfo>,
+    /// Filtered traffic of each network adapter for each second not displayed in the chart yet
+    pub adapters_per_second: BTreeMap<i64, HashMap<String, DataInfo>>,
     /// Log of the received notifications
     pub logged_notifications: VecDeque<LoggedNotification>,
     /// The total number of emitted notifications
@@ -49,6 +54,8 @@ pub fn new() -> Self {
             tot_in_bytes_prev: 0,
             tot_out_packets_prev: 0,
             tot_in_packets_prev: 0,
+            traffic_per_second: BTreeMap::new(),
+            adapters_per_second: BTreeMap::new(),
             logged_notifications: VecDeque::default(),
             tot_emitted_notifications: 0,
         }
@@ -6,6 +6,7 @@
 use std::sync::{Arc, Mutex};
 use std::thread;
 
+use chrono::{DateTime, Local};
 use iced::window::{Id, Level};
 use iced::{window, Command};
 use pcap::Device;
@@ -18,27 +19,44 @@
 use crate::gui::pages::types::settings_page::SettingsPage;
 use crate::gui::styles::types::custom_palette::{CustomPalette, ExtraStyles};
 use crate::gui::styles::types::palette::Palette;
-use crate::gui::types::export_pcap::ExportPcap;
+use crate::gui::types::export_pcap::{ExportPcap, PcapSelection};
+use crate::gui::types::import_pcap::ImportPcap;
 use crate::gui::types::message::Message;
 use crate::gui::types::timing_events::TimingEvents;
 use crate::mmdb::asn::ASN_MMDB;
 use crate::mmdb::country::COUNTRY_MMDB;
 use crate::mmdb::types::mmdb_reader::MmdbReader;
+use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::capture_context::CaptureContext;
 use crate::networking::types::filters::Filters;
 use crate::networking::types::host::Host;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
 use crate::networking::types::ip_collection::AddressCollection;
 use crate::networking::types::my_device::MyDevice;
 use crate::networking::types::my_link_type::MyLinkType;
+use crate::networking::types::pcap_export::{PcapExport, PcapExportTarget};
+use crate::networking::types::pcap_recorder::{PcapRecorder, RecordedFiles};
 use crate::networking::types::port_collection::PortCollection;
+use crate::networking::types::session::{session_file_name, Session, SessionInfo};
+use crate::networking::user_services::load_user_services;
 use crate::notifications::notify_and_log::notify_and_log;
 use crate::notifications::types::notifications::Notification;
 use crate::notifications::types::sound::{play, Sound};
-use crate::report::get_report_entries::get_searched_entries;
+use crate::report::export_entries::{export_entries, report_file_name};
+use crate::report::get_report_entries::{
+    get_searched_devices, get_searched_entries, get_searched_hosts, get_searched_services,
+    get_sorted_searched_entries,
+};
+use crate::report::types::host_sort_metric::HostSortMetric;
 use crate::report::types::report_sort_type::ReportSortType;
 use crate::report::types::search_parameters::SearchParameters;
 use crate::report::types::sort_type::SortType;
+use crate::secondary_threads::export_recorded_packets::export_recorded_packets;
 use crate::secondary_threads::parse_packets::parse_packets;
+use crate::translations::translations_3::{
+    export_packets_translation, export_results_translation, save_session_translation,
+};
 use crate::translations::types::language::Language;
 use crate::utils::types::file_info::FileInfo;
 use crate::utils::types::web_page::WebPage;
@@ -60,6 +78,8 @@ pub struct Sniffer {
     pub runtime_data: RunTimeData,
     /// Network adapter to be analyzed
     pub device: MyDevice,
+    /// Other network adapters analyzed at the same time
+    pub additional_devices: Vec<MyDevice>,
     /// Active filters on the observed traffic
     pub filters: Filters,
     /// Signals if a pcap error occurred
@@ -72,8 +92,18 @@ pub struct Sniffer {
     pub report_sort_type: ReportSortType,
     /// Host sort type (overview page)
     pub host_sort_type: SortType,
+    /// Metric used to sort hosts in the overview page
+    pub host_sort_metric: HostSortMetric,
+    /// Whether the connection details modal shows the packets of the connection
+    pub show_connection_packets: bool,
+    /// Identifier of the packet inspected in the connection details modal
+    pub selected_packet: Option<u128>,
     /// Service sort type (overview page)
     pub service_sort_type: SortType,
+    /// Program sort type (overview page)
+    pub program_sort_type: SortType,
+    /// Non-IP protocol sort type (overview page)
+    pub non_ip_sort_type: SortType,
     /// Currently displayed modal; None if no modal is displayed
     pub modal: Option<MyModal>,
     /// Currently displayed settings page; None if settings is closed
@@ -88,16 +118,50 @@ pub struct Sniffer {
     pub search: SearchParameters,
     /// Current page number of inspect search results
     pub page_number: usize,
+    /// Search string of the non-IP traffic in the inspect page
+    pub non_ip_search: String,
+    /// Whether the inspect page shows the non-IP traffic instead of the IP connections
+    pub show_non_ip: bool,
+    /// Search parameters of hosts and services pages
+    pub hosts_search: SearchParameters,
+    /// Sort type of hosts page
+    pub hosts_sort_type: ReportSortType,
+    /// Sort type of services page
+    pub services_sort_type: ReportSortType,
+    /// Current page number of hosts page
+    pub hosts_page_number: usize,
+    /// Current page number of services page
+    pub services_page_number: usize,
+    /// Search string of devices page
+    pub devices_search: String,
+    /// Sort type of devices page
+    pub devices_sort_type: ReportSortType,
+    /// Current page number of devices page
+    pub devices_page_number: usize,
     /// MMDB reader for countries
     pub country_mmdb_reader: Arc<MmdbReader>,
     /// MMDB reader for ASN
     pub asn_mmdb_reader: Arc<MmdbReader>,
+    /// Errors found in the file defining custom services
+    pub services_file_errors: Vec<String>,
     /// Time-related events
     pub timing_events: TimingEvents,
     /// Information about PCAP file export
     pub export_pcap: ExportPcap,
+    /// Information about PCAP file import (offline analysis)
+    pub import_pcap: ImportPcap,
     /// Whether thumbnail mode is currently active
     pub thumbnail: bool,
+    /// Time the current capture was started
+    pub start_timestamp: DateTime<Local>,
+    /// Information about the saved session currently displayed; None if analyzing a capture
+    pub session: Option<SessionInfo>,
+    /// Signals if an error occurred while saving or loading a session
+    pub session_error: Option<String>,
+    /// Signals if an error occurred while exporting the inspect page results
+    pub export_report_error: Option<String>,
+    /// Signals if an error occurred while exporting the packets of the selected traffic
+    pub export_pcap_selection_error: Option<String>,
 }
 
 impl Sniffer {
@@ -110,6 +174,7 @@ pub fn new(
             language,
             mmdb_country,
             mmdb_asn,
+            services_file,
             ..
         } = configs.lock().unwrap().settings.clone();
         let device = configs.lock().unwrap().device.to_my_device();
@@ -120,13 +185,19 @@ pub fn new(
             newer_release_available,
             runtime_data: RunTimeData::new(),
             device,
+            additional_devices: Vec::new(),
             filters: Filters::default(),
             pcap_error: None,
             waiting: ".".to_string(),
             traffic_chart: TrafficChart::new(style, language),
             report_sort_type: ReportSortType::default(),
             host_sort_type: SortType::default(),
+            host_sort_metric: HostSortMetric::default(),
+            show_connection_packets: false,
+            selected_packet: None,
             service_sort_type: SortType::default(),
+            program_sort_type: SortType::default(),
+            non_ip_sort_type: SortType::default(),
             modal: None,
             settings_page: None,
             last_opened_setting: SettingsPage::Notifications,
@@ -134,18 +205,50 @@ pub fn new(
             unread_notifications: 0,
             search: SearchParameters::default(),
             page_number: 1,
+            non_ip_search: String::new(),
+            show_non_ip: false,
+            hosts_search: SearchParameters::default(),
+            hosts_sort_type: ReportSortType::default(),
+            services_sort_type: ReportSortType::default(),
+            hosts_page_number: 1,
+            services_page_number: 1,
+            devices_search: String::new(),
+            devices_sort_type: ReportSortType::default(),
+            devices_page_number: 1,
             country_mmdb_reader: Arc::new(MmdbReader::from(&mmdb_country, COUNTRY_MMDB)),
             asn_mmdb_reader: Arc::new(MmdbReader::from(&mmdb_asn, ASN_MMDB)),
+            services_file_errors: load_user_services(&services_file),
             timing_events: TimingEvents::default(),
             export_pcap: ExportPcap::default(),
+            import_pcap: ImportPcap::default(),
             thumbnail: false,
+            start_timestamp: Local::now(),
+            session: None,
+            session_error: None,
+            export_report_error: None,
+            export_pcap_selection_error: None,
         }
     }
 
     pub fn update(&mut self, message: Message) -> Command<Message> {
         match message {
-            Message::TickRun => return self.refresh_data(),
-            Message::AdapterSelection(name) => self.set_adapter(&name),
+            Message::TickRun => {
+                // saved sessions are not updated
+                if self.session.is_none() {
+                    return self.refresh_data();
+                }
+            }
+            Message::AdapterSelection(name) => {
+                self.additional_devices.clear();
+                self.set_adapter(&name);
+            }
+            Message::AdapterMultiSelection(name, insert) => {
+                if insert {
+                    self.add_adapter(&name);
+                } else {
+                    self.remove_adapter(&name);
+                }
+            }
             Message::IpVersionSelection(version, insert) => {
                 if insert {
                     self.filters.ip_versions.insert(version);
@@ -172,11 +275,25 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
                 }
                 self.filters.port_str = value;
             }
+            Message::BpfFilter(value) => self.filters.set_bpf(value),
             Message::ChartSelection(unit) => self.traffic_chart.change_kind(unit),
+            Message::ChartSplitByAdapter(split) => self.traffic_chart.split_by_adapter = split,
             Message::ReportSortSelection(sort) => {
                 self.page_number = 1;
                 self.report_sort_type = sort;
             }
+            Message::HostsReportSortSelection(sort) => {
+                self.hosts_page_number = 1;
+                self.hosts_sort_type = sort;
+            }
+            Message::ServicesReportSortSelection(sort) => {
+                self.services_page_number = 1;
+                self.services_sort_type = sort;
+            }
+            Message::DevicesReportSortSelection(sort) => {
+                self.devices_page_number = 1;
+                self.devices_sort_type = sort;
+            }
             Message::OpenWebPage(web_page) => Self::open_web(&web_page),
             Message::Start => self.start(),
             Message::Reset => return self.reset(),
@@ -199,9 +316,13 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
             Message::ShowModal(modal) => {
                 if self.settings_page.is_none() && self.modal.is_none() {
                     self.modal = Some(modal);
+                    self.show_connection_packets = false;
+                    self.selected_packet = None;
                 }
             }
             Message::HideModal => self.modal = None,
+            Message::ShowConnectionPackets(show) => self.show_connection_packets = show,
+            Message::SelectPacket(id) => self.selected_packet = Some(id),
             Message::OpenSettings(settings_page) => {
                 if self.modal.is_none() {
                     self.settings_page = Some(settings_page);
@@ -248,28 +369,62 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
             Message::Search(parameters) => {
                 self.page_number = 1;
                 self.running_page = RunningPage::Inspect;
+                self.show_non_ip = false;
                 self.search = parameters;
             }
+            Message::NonIpSearch(search) => {
+                self.running_page = RunningPage::Inspect;
+                self.show_non_ip = true;
+                self.non_ip_search = search;
+            }
+            Message::ShowNonIpTraffic(show) => self.show_non_ip = show,
+            Message::HostsSearch(parameters) => {
+                self.hosts_page_number = 1;
+                self.services_page_number = 1;
+                self.hosts_search = parameters;
+            }
+            Message::DevicesSearch(search) => {
+                self.devices_page_number = 1;
+                self.devices_search = search;
+            }
             Message::UpdatePageNumber(increment) => {
+                let page_number = match self.running_page {
+                    RunningPage::Hosts => &mut self.hosts_page_number,
+                    RunningPage::Services => &mut self.services_page_number,
+                    RunningPage::Devices => &mut self.devices_page_number,
+                    _ => &mut self.page_number,
+                };
                 let new_page = if increment {
-                    self.page_number.checked_add(1)
+                    page_number.checked_add(1)
                 } else {
-                    self.page_number.checked_sub(1)
+                    page_number.checked_sub(1)
                 }
                 .unwrap();
-                self.page_number = new_page;
+                *page_number = new_page;
             }
             Message::ArrowPressed(increment) => {
-                if self.running_page.eq(&RunningPage::Inspect)
-                    && self.settings_page.is_none()
-                    && self.modal.is_none()
-                {
-                    if increment {
-                        if self.page_number < (get_searched_entries(self).1 + 20 - 1) / 20 {
+                let page_info = match self.running_page {
+                    RunningPage::Inspect => Some((self.page_number, get_searched_entries(self).1)),
+                    RunningPage::Hosts => {
+                        Some((self.hosts_page_number, get_searched_hosts(self).1))
+                    }
+                    RunningPage::Services => {
+                        Some((self.services_page_number, get_searched_services(self).1))
+                    }
+                    RunningPage::Devices => {
+                        Some((self.devices_page_number, get_searched_devices(self).1))
+                    }
+                    _ => None,
+                };
+                if let Some((page_number, results_number)) = page_info {
+                    if self.settings_page.is_none() && self.modal.is_none() {
+                        if increment {
+                            if page_number < (results_number + 20 - 1) / 20 {
+                                return self.update(Message::UpdatePageNumber(increment));
+                            }
+                        } else if page_number > 1 {
                             return self.update(Message::UpdatePageNumber(increment));
                         }
-                    } else if self.page_number > 1 {
-                        return self.update(Message::UpdatePageNumber(increment));
                     }
                 }
             }
@@ -306,6 +461,16 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
                 self.configs.lock().unwrap().settings.mmdb_asn = db.clone();
                 self.asn_mmdb_reader = Arc::new(MmdbReader::from(&db, ASN_MMDB));
             }
+            Message::CustomServicesFile(path) => {
+                self.services_file_errors = load_user_services(&path);
+                self.configs.lock().unwrap().settings.services_file = path;
+            }
+            Message::SnaplenSelection(snaplen) => {
+                self.configs.lock().unwrap().settings.snaplen = snaplen;
+            }
+            Message::ToggleReverseDns(reverse_dns) => {
+                self.configs.lock().unwrap().settings.reverse_dns = reverse_dns;
+            }
             Message::CloseRequested => {
                 self.configs.lock().unwrap().clone().store();
                 return window::close(Id::MAIN);
@@ -327,9 +492,18 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
             Message::HostSortSelection(sort_type) => {
                 self.host_sort_type = sort_type;
             }
+            Message::HostSortMetricSelection(sort_metric) => {
+                self.host_sort_metric = sort_metric;
+            }
             Message::ServiceSortSelection(sort_type) => {
                 self.service_sort_type = sort_type;
             }
+            Message::ProgramSortSelection(sort_type) => {
+                self.program_sort_type = sort_type;
+            }
+            Message::NonIpSortSelection(sort_type) => {
+                self.non_ip_sort_type = sort_type;
+            }
             Message::ToggleExportPcap => {
                 self.export_pcap.toggle();
             }
@@ -339,6 +513,56 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
             Message::OutputPcapFile(name) => {
                 self.export_pcap.set_file_name(name);
             }
+            Message::TogglePcapng => self.export_pcap.toggle_pcapng(),
+            Message::PcapRotationSize(value) => self.export_pcap.set_rotation_size(&value),
+            Message::PcapRotationMinutes(value) => self.export_pcap.set_rotation_minutes(&value),
+            Message::PcapMaxFiles(value) => self.export_pcap.set_max_files(&value),
+            Message::ToggleImportPcap => {
+                self.import_pcap.toggle();
+            }
+            Message::InputPcapFile(path) => {
+                self.import_pcap.set_path(path);
+            }
+            Message::ToggleReplayRealTime => {
+                self.import_pcap.toggle_real_time();
+            }
+            Message::SaveSession => {
+                return Command::perform(
+                    Self::pick_file_destination(
+                        session_file_name(&self.start_timestamp),
+                        FileInfo::Session,
+                        save_session_translation(self.configs.lock().unwrap().settings.language),
+                    ),
+                    Message::StoreSession,
+                );
+            }
+            Message::StoreSession(path) => self.store_session(&path),
+            Message::ExportReport => {
+                return Command::perform(
+                    Self::pick_file_destination(
+                        report_file_name(&Local::now()),
+                        FileInfo::Report,
+                        export_results_translation(self.configs.lock().unwrap().settings.language),
+                    ),
+                    Message::WriteReport,
+                );
+            }
+            Message::WriteReport(path) => self.write_report(&path),
+            Message::ExportPcapSelection(selection) => {
+                return Command::perform(
+                    Self::pick_file_destination(
+                        selection.file_name(),
+                        FileInfo::Capture,
+                        export_packets_translation(self.configs.lock().unwrap().settings.language),
+                    ),
+                    move |path| Message::WritePcapSelection(selection.clone(), path),
+                );
+            }
+            Message::WritePcapSelection(selection, path) => {
+                self.write_pcap_selection(selection, &path);
+            }
+            Message::ToggleSplitByHost => self.export_pcap.toggle_split_by_host(),
+            Message::LoadSession(path) => self.load_session(&path),
             Message::ToggleThumbnail(triggered_by_resize) => {
                 self.thumbnail = !self.thumbnail;
                 self.traffic_chart.thumbnail = self.thumbnail;
@@ -381,8 +605,20 @@ pub fn update(&mut self, message: Message) -> Command<Message> {
     }
 
     fn refresh_data(&mut self) -> Command<Message> {
-        let info_traffic_lock = self.info_traffic.lock().unwrap();
+        let mut info_traffic_lock = self.info_traffic.lock().unwrap();
         self.runtime_data.all_packets = info_traffic_lock.all_packets;
+        // the capture may be stopped by an error (e.g., when reading a corrupted file)
+        if let Some(error) = info_traffic_lock.capture_error.take() {
+            self.pcap_error = Some(error);
+        }
+        // exports of the selected traffic may fail while the capture is running
+        if let Some(error) = info_traffic_lock
+            .pcap_exports
+            .iter()
+            .find_map(|pcap_export| pcap_export.lock().unwrap().error.clone())
+        {
+            self.export_pcap_selection_error = Some(error);
+        }
         if info_traffic_lock.tot_in_packets + info_traffic_lock.tot_out_packets == 0 {
             drop(info_traffic_lock);
             return self.update(Message::Waiting);
@@ -393,6 +629,24 @@ fn refresh_data(&mut self) -> Command<Message> {
         self.runtime_data.tot_in_bytes = info_traffic_lock.tot_in_bytes;
         self.runtime_data.tot_out_bytes = info_traffic_lock.tot_out_bytes;
         self.runtime_data.dropped_packets = info_traffic_lock.dropped_packets;
+        // traffic of the seconds captured since the last refresh, to be displayed in the chart
+        for (second, data_info) in std::mem::take(&mut info_traffic_lock.traffic_per_second) {
+            *self
+                .runtime_data
+                .traffic_per_second
+                .entry(second)
+                .or_default() += data_info;
+        }
+        for (second, adapters) in std::mem::take(&mut info_traffic_lock.adapters_per_second) {
+            let runtime_adapters = self
+                .runtime_data
+                .adapters_per_second
+                .entry(second)
+                .or_default();
+            for (adapter, data_info) in adapters {
+                *runtime_adapters.entry(adapter).or_default() += data_info;
+            }
+        }
         drop(info_traffic_lock);
         let emitted_notifications = notify_and_log(
             &mut self.runtime_data,
@@ -404,12 +658,22 @@ fn refresh_data(&mut self) -> Command<Message> {
         if self.thumbnail || self.running_page.ne(&RunningPage::Notifications) {
             self.unread_notifications += emitted_notifications;
         }
-        update_charts_data(&mut self.runtime_data, &mut self.traffic_chart);
+        // when sniffing live traffic, the chart keeps moving even if no packets are captured
+        let current_second = self
+            .import_pcap
+            .file_path()
+            .is_none()
+            .then(|| Local::now().timestamp());
+        update_charts_data(
+            &mut self.runtime_data,
+            &mut self.traffic_chart,
+            current_second,
+        );
 
         let current_device_name = self.device.name.clone();
         // update ConfigDevice stored if different from last sniffed device
         let last_device_name_sniffed = self.configs.lock().unwrap().device.device_name.clone();
-        if current_device_name.ne(&last_device_name_sniffed) {
+        if !self.import_pcap.enabled() && current_device_name.ne(&last_device_name_sniffed) {
             self.configs.lock().unwrap().device.device_name = current_device_name;
         }
         // waiting notifications
@@ -440,40 +704,91 @@ fn open_web(web_page: &WebPage) {
     fn start(&mut self) {
         let current_device_name = &*self.device.name.clone();
         self.set_adapter(current_device_name);
-        let device = self.device.clone();
-        let pcap_path = self.export_pcap.full_path();
-        let capture_context = CaptureContext::new(&device, &pcap_path);
-        self.pcap_error = capture_context.error().map(ToString::to_string);
+        let captures = if let Some(file_path) = self.import_pcap.file_path() {
+            vec![(
+                MyDevice::from_capture_file(file_path),
+                CaptureContext::new_offline(
+                    file_path,
+                    self.import_pcap.real_time(),
+                    &self.filters.bpf,
+                ),
+            )]
+        } else {
+            let snaplen = self.configs.lock().unwrap().settings.snaplen;
+            let is_multiple = !self.additional_devices.is_empty();
+            std::iter::once(&self.device)
+                .chain(&self.additional_devices)
+                .map(|device| {
+                    // each adapter is exported to its own file, since link types may differ
+                    let pcap_path = if is_multiple {
+                        self.export_pcap.full_path_for_adapter(&device.name)
+                    } else {
+                        self.export_pcap.full_path()
+                    };
+                    let capture_context = CaptureContext::new(
+                        device,
+                        &pcap_path,
+                        self.export_pcap.rotation(),
+                        &self.filters,
+                        snaplen,
+                    );
+                    (device.clone(), capture_context)
+                })
+                .collect::<Vec<_>>()
+        };
+        // nothing is captured if any of the adapters can't be opened
+        self.pcap_error = captures.iter().find_map(|(device, capture_context)| {
+            let error = capture_context.error()?;
+            Some(if captures.len() > 1 {
+                format!("{}: {error}", device.name)
+            } else {
+                error.to_string()
+            })
+        });
         let info_traffic_mutex = self.info_traffic.clone();
         *info_traffic_mutex.lock().unwrap() = InfoTraffic::new();
         self.runtime_data = RunTimeData::new();
         let ConfigSettings {
-            style, language, ..
+            style,
+            language,
+            reverse_dns,
+            ..
         } = self.configs.lock().unwrap().settings;
         self.traffic_chart = TrafficChart::new(style, language);
         self.running_page = RunningPage::Overview;
-
-        if capture_context.error().is_none() {
-            // no pcap error
-            let current_capture_id = self.current_capture_id.clone();
-            let filters = self.filters.clone();
-            let country_mmdb_reader = self.country_mmdb_reader.clone();
-            let asn_mmdb_reader = self.asn_mmdb_reader.clone();
-            self.device.link_type = capture_context.my_link_type();
-            thread::Builder::new()
-                .name("thread_parse_packets".to_string())
-                .spawn(move || {
-                    parse_packets(
-                        &current_capture_id,
-                        &device,
-                        &filters,
-                        &info_traffic_mutex,
-                        &country_mmdb_reader,
-                        &asn_mmdb_reader,
-                        capture_context,
-                    );
-                })
-                .unwrap();
+        self.start_timestamp = Local::now();
+        self.session = None;
+
+        if self.pcap_error.is_none() {
+            // no pcap error: one thread for each capture, all feeding the same traffic data
+            for (i, (device, capture_context)) in captures.into_iter().enumerate() {
+                let current_capture_id = self.current_capture_id.clone();
+                let filters = self.filters.clone();
+                let info_traffic_mutex = info_traffic_mutex.clone();
+                let country_mmdb_reader = self.country_mmdb_reader.clone();
+                let asn_mmdb_reader = self.asn_mmdb_reader.clone();
+                let link_type = capture_context.my_link_type();
+                if i == 0 {
+                    self.device.link_type = link_type;
+                } else {
+                    self.additional_devices[i - 1].link_type = link_type;
+                }
+                thread::Builder::new()
+                    .name("thread_parse_packets".to_string())
+                    .spawn(move || {
+                        parse_packets(
+                            &current_capture_id,
+                            &device,
+                            &filters,
+                            &info_traffic_mutex,
+                            &country_mmdb_reader,
+                            &asn_mmdb_reader,
+                            capture_context,
+                            reverse_dns,
+                        );
+                    })
+                    .unwrap();
+            }
         }
     }
 
@@ -485,9 +800,149 @@ fn reset(&mut self) -> Command<Message> {
         self.unread_notifications = 0;
         self.search = SearchParameters::default();
         self.page_number = 1;
+        self.non_ip_search = String::new();
+        self.show_non_ip = false;
+        self.hosts_search = SearchParameters::default();
+        self.hosts_sort_type = ReportSortType::default();
+        self.services_sort_type = ReportSortType::default();
+        self.hosts_page_number = 1;
+        self.services_page_number = 1;
+        self.devices_search = String::new();
+        self.devices_sort_type = ReportSortType::default();
+        self.devices_page_number = 1;
+        self.session = None;
+        // close the recorded files and the ones of the exported traffic
+        let mut info_traffic_lock = self.info_traffic.lock().unwrap();
+        info_traffic_lock.savefiles.clear();
+        info_traffic_lock.pcap_exports.clear();
+        drop(info_traffic_lock);
+        self.export_pcap_selection_error = None;
         self.update(Message::HideModal)
     }
 
+    fn store_session(&mut self, path: &str) {
+        if path.is_empty() {
+            // dialog closed without choosing a file
+            return;
+        }
+        let device = if let Some(file_path) = self.import_pcap.file_path() {
+            MyDevice::from_capture_file(file_path)
+        } else {
+            self.device.clone()
+        };
+        let session = Session::new(
+            &device,
+            &self.filters,
+            self.start_timestamp,
+            &self.info_traffic.lock().unwrap(),
+        );
+        self.session_error = session.store(path).err();
+    }
+
+    fn write_report(&mut self, path: &str) {
+        if path.is_empty() {
+            // dialog closed without choosing a file
+            return;
+        }
+        self.export_report_error = export_entries(
+            &self.info_traffic.lock().unwrap(),
+            &self.search,
+            self.report_sort_type,
+            path,
+        )
+        .err();
+    }
+
+    fn write_pcap_selection(&mut self, selection: PcapSelection, path: &str) {
+        if path.is_empty() {
+            // dialog closed without choosing a file
+            return;
+        }
+        let mut info_traffic = self.info_traffic.lock().unwrap();
+        let target = match selection {
+            PcapSelection::Host(host) => PcapExportTarget::Host(host),
+            PcapSelection::Connection(key) => {
+                connections_target(info_traffic.map.get_key_value(&key).into_iter())
+            }
+            PcapSelection::SearchResults => connections_target(
+                get_sorted_searched_entries(&info_traffic, &self.search, self.report_sort_type)
+                    .into_iter(),
+            ),
+        };
+        // packets recorded so far, read in the background
+        let recordings: Vec<RecordedFiles> = info_traffic
+            .savefiles
+            .values_mut()
+            .map(PcapRecorder::recorded_files)
+            .collect();
+        let mut pcap_export = PcapExport::new(
+            target,
+            path.to_string(),
+            self.export_pcap.split_by_host(),
+            !recordings.is_empty(),
+        );
+        pcap_export.create_file(self.device.link_type);
+        if recordings.is_empty() {
+            // the capture isn't recorded: only the packets kept in memory can be exported
+            pcap_export.write_buffered(&info_traffic.packets, &info_traffic.addresses_resolved);
+        }
+        self.export_pcap_selection_error
+            .clone_from(&pcap_export.error);
+        let pcap_export = Arc::new(Mutex::new(pcap_export));
+        info_traffic.pcap_exports.push(pcap_export.clone());
+        if recordings.is_empty() {
+            return;
+        }
+
+        let current_capture_id = self.current_capture_id.clone();
+        let info_traffic_mutex = self.info_traffic.clone();
+        let addresses_resolved = info_traffic.addresses_resolved.clone();
+        thread::Builder::new()
+            .name("thread_export_recorded_packets".to_string())
+            .spawn(move || {
+                export_recorded_packets(
+                    &current_capture_id,
+                    &info_traffic_mutex,
+                    &pcap_export,
+                    recordings,
+                    &addresses_resolved,
+                );
+            })
+            .unwrap();
+    }
+
+    fn load_session(&mut self, path: &str) {
+        if path.is_empty() {
+            // dialog closed without choosing a file
+            return;
+        }
+        let session = match Session::load(path) {
+            Ok(session) => session,
+            Err(error) => {
+                self.session_error = Some(error);
+                return;
+            }
+        };
+        let (session_info, info_traffic) = session.into_info_traffic();
+        self.runtime_data = RunTimeData::new();
+        self.runtime_data.all_packets = info_traffic.all_packets;
+        self.runtime_data.all_bytes = info_traffic.all_bytes;
+        self.runtime_data.tot_in_packets = info_traffic.tot_in_packets;
+        self.runtime_data.tot_out_packets = info_traffic.tot_out_packets;
+        self.runtime_data.tot_in_bytes = info_traffic.tot_in_bytes;
+        self.runtime_data.tot_out_bytes = info_traffic.tot_out_bytes;
+        self.runtime_data.dropped_packets = info_traffic.dropped_packets;
+        *self.info_traffic.lock().unwrap() = info_traffic;
+        let ConfigSettings {
+            style, language, ..
+        } = self.configs.lock().unwrap().settings;
+        self.traffic_chart = TrafficChart::new(style, language);
+        self.pcap_error = None;
+        self.session = Some(session_info);
+        self.session_error = None;
+        self.running_page = RunningPage::Overview;
+    }
+
     fn set_adapter(&mut self, name: &str) {
         for dev in Device::list().expect("Error retrieving device list\r\n") {
             if dev.name.eq(&name) {
@@ -505,6 +960,36 @@ fn set_adapter(&mut self, name: &str) {
         }
     }
 
+    /// Adds an adapter to the ones captured at the same time
+    fn add_adapter(&mut self, name: &str) {
+        if self.device.name.eq(name) || self.additional_devices.iter().any(|d| d.name.eq(name)) {
+            return;
+        }
+        if let Some(dev) = Device::list()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|dev| dev.name.eq(name))
+        {
+            self.additional_devices.push(MyDevice {
+                name: dev.name,
+                desc: dev.desc,
+                addresses: Arc::new(Mutex::new(dev.addresses)),
+                link_type: MyLinkType::default(),
+            });
+        }
+    }
+
+    /// Removes an adapter from the ones captured at the same time (at least one remains selected)
+    fn remove_adapter(&mut self, name: &str) {
+        if self.device.name.eq(name) {
+            if !self.additional_devices.is_empty() {
+                self.device = self.additional_devices.remove(0);
+            }
+        } else {
+            self.additional_devices.retain(|d| d.name.ne(name));
+        }
+    }
+
     fn update_waiting_dots(&mut self) {
         if self.waiting.len() > 2 {
             self.waiting = String::new();
@@ -581,7 +1066,12 @@ fn switch_page(&mut self, next: bool) {
                 }
             }
             (
-                RunningPage::Inspect | RunningPage::Notifications | RunningPage::Overview,
+                RunningPage::Inspect
+                | RunningPage::Hosts
+                | RunningPage::Services
+                | RunningPage::Devices
+                | RunningPage::Notifications
+                | RunningPage::Overview,
                 None,
                 true,
             ) => {
@@ -630,7 +1120,8 @@ fn shortcut_esc(&mut self) -> Command<Message> {
     // also called when backspace key is pressed on a running state
     fn reset_button_pressed(&mut self) -> Command<Message> {
         if self.running_page.ne(&RunningPage::Init) {
-            return if self.info_traffic.lock().unwrap().all_packets == 0
+            // saved sessions can be reopened at any time: no need to ask for confirmation
+            return if (self.session.is_some() || self.info_traffic.lock().unwrap().all_packets == 0)
                 && self.settings_page.is_none()
             {
                 self.update(Message::Reset)
@@ -667,9 +1158,11 @@ async fn open_file(old_file: String, file_info: FileInfo, language: Language) ->
 
         let picked = if file_info == FileInfo::Directory {
             dialog.pick_folder().await
+        } else if file_info.get_extensions().is_empty() {
+            dialog.pick_file().await
         } else {
             dialog
-                .add_filter(file_info.get_extension(), &[file_info.get_extension()])
+                .add_filter(file_info.get_extension(), file_info.get_extensions())
                 .pick_file()
                 .await
         }
@@ -677,6 +1170,36 @@ async fn open_file(old_file: String, file_info: FileInfo, language: Language) ->
 
         picked.path().to_string_lossy().to_string()
     }
+
+    /// Returns the path chosen to save a file, or an empty string if the dialog was closed
+    async fn pick_file_destination(file_name: String, file_info: FileInfo, title: &str) -> String {
+        rfd::AsyncFileDialog::new()
+            .set_title(title)
+            .set_directory(std::env::var("HOME").unwrap_or_default())
+            .set_file_name(file_name)
+            .add_filter(file_info.get_extension(), file_info.get_extensions())
+            .save_file()
+            .await
+            .map(|picked| picked.path().to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Builds the export target of the given connections, including both their directions
+fn connections_target<'a>(
+    connections: impl Iterator<Item = (&'a AddressPortPair, &'a InfoAddressPortPair)>,
+) -> PcapExportTarget {
+    PcapExportTarget::Connections(
+        connections
+            .flat_map(|(key, info)| {
+                let remote_address = get_address_to_lookup(key, info.traffic_direction);
+                [
+                    (key.clone(), remote_address.clone()),
+                    (key.reversed(), remote_address),
+                ]
+            })
+            .collect(),
+    )
 }
 
 #[cfg(test)]
@@ -690,6 +1213,7 @@ mod tests {
     use std::sync::{Arc, Mutex};
     use std::time::Duration;
 
+    use chrono::Local;
     use serial_test::{parallel, serial};
 
     use crate::countries::types::country::Country;
@@ -697,8 +1221,15 @@ mod tests {
     use crate::gui::pages::types::settings_page::SettingsPage;
     use crate::gui::styles::types::custom_palette::ExtraStyles;
     use crate::gui::styles::types::gradient_type::GradientType;
+    use crate::gui::types::export_pcap::PcapSelection;
     use crate::gui::types::message::Message;
+    use crate::networking::types::address_port_pair::AddressPortPair;
     use crate::networking::types::host::Host;
+    use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+    use crate::networking::types::my_device::MyDevice;
+    use crate::networking::types::my_link_type::MyLinkType;
+    use crate::networking::types::packet_buffer::CapturedPacket;
+    use crate::networking::types::traffic_direction::TrafficDirection;
     use crate::notifications::types::logged_notification::{
         LoggedNotification, PacketsThresholdExceeded,
     };
@@ -706,7 +1237,10 @@ mod tests {
         BytesNotification, FavoriteNotification, Notification, Notifications, PacketsNotification,
     };
     use crate::notifications::types::sound::Sound;
+    use crate::report::get_report_entries::get_searched_entries;
+    use crate::report::types::host_sort_metric::HostSortMetric;
     use crate::report::types::report_col::ReportCol;
+    use crate::report::types::search_parameters::SearchParameters;
     use crate::report::types::sort_type::SortType;
     use crate::{
         ByteMultiple, ChartType, ConfigDevice, ConfigSettings, ConfigWindow, Configs, IpVersion,
@@ -777,9 +1311,14 @@ fn test_correctly_update_protocol() {
         sniffer.update(Message::ProtocolSelection(Protocol::UDP, false));
         assert_eq!(
             sniffer.filters.protocols,
-            HashSet::from([Protocol::TCP, Protocol::ICMP])
+            HashSet::from([Protocol::TCP, Protocol::ICMP, Protocol::L2])
         );
         sniffer.update(Message::ProtocolSelection(Protocol::TCP, false));
+        assert_eq!(
+            sniffer.filters.protocols,
+            HashSet::from([Protocol::ICMP, Protocol::L2])
+        );
+        sniffer.update(Message::ProtocolSelection(Protocol::L2, false));
         assert_eq!(sniffer.filters.protocols, HashSet::from([Protocol::ICMP]));
         sniffer.update(Message::ProtocolSelection(Protocol::ICMP, false));
         assert_eq!(sniffer.filters.protocols, HashSet::new());
@@ -880,6 +1419,28 @@ fn test_correctly_update_host_sort_kind() {
         assert_eq!(sniffer.host_sort_type, SortType::Neutral);
     }
 
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_correctly_update_host_sort_metric() {
+        let mut sniffer = new_sniffer();
+
+        let mut metric = HostSortMetric::Traffic;
+
+        assert_eq!(sniffer.host_sort_metric, metric);
+
+        metric = metric.next();
+        sniffer.update(Message::HostSortMetricSelection(metric));
+        assert_eq!(sniffer.host_sort_metric, HostSortMetric::Latency);
+
+        metric = metric.next();
+        sniffer.update(Message::HostSortMetricSelection(metric));
+        assert_eq!(sniffer.host_sort_metric, HostSortMetric::Retransmissions);
+
+        metric = metric.next();
+        sniffer.update(Message::HostSortMetricSelection(metric));
+        assert_eq!(sniffer.host_sort_metric, HostSortMetric::Traffic);
+    }
+
     #[test]
     #[parallel] // needed to not collide with other tests generating configs files
     fn test_correctly_update_service_sort_kind() {
@@ -902,6 +1463,28 @@ fn test_correctly_update_service_sort_kind() {
         assert_eq!(sniffer.service_sort_type, SortType::Neutral);
     }
 
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_correctly_update_program_sort_kind() {
+        let mut sniffer = new_sniffer();
+
+        let mut sort = SortType::Neutral;
+
+        assert_eq!(sniffer.program_sort_type, sort);
+
+        sort = sort.next_sort();
+        sniffer.update(Message::ProgramSortSelection(sort));
+        assert_eq!(sniffer.program_sort_type, SortType::Descending);
+
+        sort = sort.next_sort();
+        sniffer.update(Message::ProgramSortSelection(sort));
+        assert_eq!(sniffer.program_sort_type, SortType::Ascending);
+
+        sort = sort.next_sort();
+        sniffer.update(Message::ProgramSortSelection(sort));
+        assert_eq!(sniffer.program_sort_type, SortType::Neutral);
+    }
+
     #[test]
     #[parallel] // needed to not collide with other tests generating configs files
     fn test_correctly_update_style() {
@@ -1140,6 +1723,35 @@ fn test_modify_favorite_connections() {
         );
     }
 
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_connection_packets_view() {
+        let mut sniffer = new_sniffer();
+        let key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(50000),
+            "140.82.121.4".to_string(),
+            Some(443),
+            Protocol::TCP,
+        );
+
+        assert!(!sniffer.show_connection_packets);
+        assert_eq!(sniffer.selected_packet, None);
+        sniffer.update(Message::ShowModal(MyModal::ConnectionDetails(key.clone())));
+        sniffer.update(Message::ShowConnectionPackets(true));
+        sniffer.update(Message::SelectPacket(7));
+        assert!(sniffer.show_connection_packets);
+        assert_eq!(sniffer.selected_packet, Some(7));
+        sniffer.update(Message::ShowConnectionPackets(false));
+        assert!(!sniffer.show_connection_packets);
+        sniffer.update(Message::ShowConnectionPackets(true));
+        // the view is reset when the details of another connection are shown
+        sniffer.update(Message::HideModal);
+        sniffer.update(Message::ShowModal(MyModal::ConnectionDetails(key)));
+        assert!(!sniffer.show_connection_packets);
+        assert_eq!(sniffer.selected_packet, None);
+    }
+
     #[test]
     #[parallel] // needed to not collide with other tests generating configs files
     fn test_show_and_hide_modal_and_settings() {
@@ -1659,7 +2271,10 @@ fn test_config_settings() {
                 scale_factor: 1.0,
                 mmdb_country: "".to_string(),
                 mmdb_asn: "".to_string(),
+                services_file: "".to_string(),
                 style_path: "".to_string(),
+                reverse_dns: true,
+                snaplen: 4096,
                 notifications: Notifications {
                     volume: 60,
                     packets_notification: Default::default(),
@@ -1676,12 +2291,15 @@ fn test_config_settings() {
         sniffer.update(Message::ChangeScaleFactor(0.65));
         sniffer.update(Message::CustomCountryDb("countrymmdb".to_string()));
         sniffer.update(Message::CustomAsnDb("asnmmdb".to_string()));
+        sniffer.update(Message::CustomServicesFile("servicesfile".to_string()));
         sniffer.update(Message::LoadStyle(format!(
             "{}/resources/themes/catppuccin.toml",
             env!("CARGO_MANIFEST_DIR")
         )));
         sniffer.update(Message::Style(StyleType::Custom(ExtraStyles::DraculaDark)));
         sniffer.update(Message::ChangeVolume(100));
+        sniffer.update(Message::ToggleReverseDns(false));
+        sniffer.update(Message::SnaplenSelection(1024));
 
         // quit the app by sending a CloseRequested message
         sniffer.update(Message::CloseRequested);
@@ -1703,10 +2321,13 @@ fn test_config_settings() {
                 scale_factor: 0.65,
                 mmdb_country: "countrymmdb".to_string(),
                 mmdb_asn: "asnmmdb".to_string(),
+                services_file: "servicesfile".to_string(),
                 style_path: format!(
                     "{}/resources/themes/catppuccin.toml",
                     env!("CARGO_MANIFEST_DIR")
                 ),
+                reverse_dns: false,
+                snaplen: 1024,
                 notifications: Notifications {
                     volume: 100,
                     packets_notification: Default::default(),
@@ -1882,4 +2503,399 @@ fn test_toggle_thumbnail() {
         sniffer.update(Message::ToggleThumbnail(false));
         assert_eq!(sniffer.unread_notifications, 0);
     }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_store_and_load_session() {
+        let path = "test_store_and_load_session.json";
+        let mut sniffer = new_sniffer();
+        let mut info_traffic = sniffer.info_traffic.lock().unwrap();
+        info_traffic.all_packets = 10;
+        info_traffic.all_bytes = 2000;
+        info_traffic.tot_in_packets = 4;
+        info_traffic.tot_out_packets = 3;
+        info_traffic.tot_in_bytes = 1000;
+        info_traffic.tot_out_bytes = 500;
+        info_traffic.map.insert(
+            AddressPortPair::new(
+                "192.168.1.10".to_string(),
+                Some(54321),
+                "8.8.8.8".to_string(),
+                Some(443),
+                Protocol::TCP,
+            ),
+            InfoAddressPortPair::default(),
+        );
+        drop(info_traffic);
+        sniffer.update(Message::StoreSession(path.to_string()));
+        assert_eq!(sniffer.session_error, None);
+        assert!(Path::new(path).exists());
+
+        let mut sniffer = new_sniffer();
+        sniffer.update(Message::LoadSession(path.to_string()));
+        remove_file(path).unwrap();
+        assert_eq!(sniffer.session_error, None);
+        assert_eq!(sniffer.running_page, RunningPage::Overview);
+        assert!(sniffer.session.is_some());
+        assert_eq!(sniffer.runtime_data.all_packets, 10);
+        assert_eq!(sniffer.runtime_data.all_bytes, 2000);
+        assert_eq!(sniffer.runtime_data.tot_in_packets, 4);
+        assert_eq!(sniffer.runtime_data.tot_out_packets, 3);
+        assert_eq!(sniffer.runtime_data.tot_in_bytes, 1000);
+        assert_eq!(sniffer.runtime_data.tot_out_bytes, 500);
+        assert_eq!(sniffer.info_traffic.lock().unwrap().map.len(), 1);
+
+        // saved sessions are not refreshed
+        sniffer.update(Message::TickRun);
+        assert_eq!(sniffer.traffic_chart.ticks, 0);
+
+        // no confirmation is needed to leave a saved session
+        sniffer.update(Message::ResetButtonPressed);
+        assert_eq!(sniffer.modal, None);
+        assert_eq!(sniffer.running_page, RunningPage::Init);
+        assert_eq!(sniffer.session, None);
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_load_session_errors() {
+        let mut sniffer = new_sniffer();
+
+        // dialog closed without choosing a file
+        sniffer.update(Message::LoadSession(String::new()));
+        assert_eq!(sniffer.session_error, None);
+        assert_eq!(sniffer.running_page, RunningPage::Init);
+
+        sniffer.update(Message::LoadSession(
+            "test_load_session_errors.json".to_string(),
+        ));
+        assert!(sniffer.session_error.is_some());
+        assert_eq!(sniffer.running_page, RunningPage::Init);
+        assert_eq!(sniffer.session, None);
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_write_report() {
+        let path = "test_write_report.csv";
+        let mut sniffer = new_sniffer();
+        sniffer.info_traffic.lock().unwrap().map.insert(
+            AddressPortPair::new(
+                "192.168.1.10".to_string(),
+                Some(54321),
+                "8.8.8.8".to_string(),
+                Some(443),
+                Protocol::TCP,
+            ),
+            InfoAddressPortPair::default(),
+        );
+
+        // dialog closed without choosing a file
+        sniffer.update(Message::WriteReport(String::new()));
+        assert_eq!(sniffer.export_report_error, None);
+
+        sniffer.update(Message::WriteReport(path.to_string()));
+        let content = std::fs::read_to_string(path).unwrap();
+        remove_file(path).unwrap();
+        assert_eq!(sniffer.export_report_error, None);
+        assert_eq!(content.lines().count(), 2);
+        assert!(content
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("192.168.1.10,54321,8.8.8.8,443,"));
+
+        sniffer.update(Message::WriteReport(
+            "not_existing_dir/test_write_report.csv".to_string(),
+        ));
+        assert!(sniffer.export_report_error.is_some());
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_write_pcap_selection() {
+        let path = "test_write_pcap_selection.pcap";
+        let mut sniffer = new_sniffer();
+        let ethernet = MyLinkType::Ethernet(pcap::Linktype::ETHERNET);
+        sniffer.device.link_type = ethernet;
+        let key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(54321),
+            "8.8.8.8".to_string(),
+            Some(443),
+            Protocol::TCP,
+        );
+        let other_key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(54322),
+            "8.8.4.4".to_string(),
+            Some(443),
+            Protocol::TCP,
+        );
+        {
+            let mut info_traffic = sniffer.info_traffic.lock().unwrap();
+            info_traffic.map.insert(
+                key.clone(),
+                InfoAddressPortPair {
+                    traffic_direction: TrafficDirection::Outgoing,
+                    ..InfoAddressPortPair::default()
+                },
+            );
+            info_traffic.map.insert(
+                other_key.clone(),
+                InfoAddressPortPair {
+                    traffic_direction: TrafficDirection::Outgoing,
+                    ..InfoAddressPortPair::default()
+                },
+            );
+            for key in [&key, &key.reversed(), &other_key] {
+                info_traffic.all_packets += 1;
+                let packet = CapturedPacket {
+                    id: 0,
+                    timestamp: Local::now(),
+                    original_len: 60,
+                    data: vec![0; 60],
+                    link_type: ethernet,
+                };
+                info_traffic.buffer_packet(key, packet);
+            }
+        }
+
+        // dialog closed without choosing a file
+        sniffer.update(Message::WritePcapSelection(
+            PcapSelection::Connection(key.clone()),
+            String::new(),
+        ));
+        assert!(sniffer.info_traffic.lock().unwrap().pcap_exports.is_empty());
+
+        // the capture isn't recorded: the buffered packets of both the directions are exported
+        sniffer.update(Message::WritePcapSelection(
+            PcapSelection::Connection(key),
+            path.to_string(),
+        ));
+        assert_eq!(sniffer.export_pcap_selection_error, None);
+        assert_eq!(sniffer.info_traffic.lock().unwrap().pcap_exports.len(), 1);
+        assert_eq!(std::fs::read(path).unwrap().len(), 24 + 2 * (16 + 60));
+        remove_file(path).unwrap();
+
+        // split by host
+        sniffer.update(Message::ToggleSplitByHost);
+        assert!(sniffer.export_pcap.split_by_host());
+        sniffer.update(Message::WritePcapSelection(
+            PcapSelection::SearchResults,
+            path.to_string(),
+        ));
+        for host_path in [
+            "test_write_pcap_selection_8_8_8_8.pcap",
+            "test_write_pcap_selection_8_8_4_4.pcap",
+        ] {
+            assert!(Path::new(host_path).exists());
+            remove_file(host_path).unwrap();
+        }
+        assert!(!Path::new(path).exists());
+
+        sniffer.update(Message::ToggleSplitByHost);
+        sniffer.update(Message::WritePcapSelection(
+            PcapSelection::SearchResults,
+            "not_existing_dir/test_write_pcap_selection.pcap".to_string(),
+        ));
+        assert!(sniffer.export_pcap_selection_error.is_some());
+
+        // exports are closed when the capture is reset
+        sniffer.update(Message::Reset);
+        assert!(sniffer.info_traffic.lock().unwrap().pcap_exports.is_empty());
+        assert_eq!(sniffer.export_pcap_selection_error, None);
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_hosts_and_services_pages() {
+        let mut sniffer = new_sniffer();
+        let sort = ReportSortType {
+            byte_sort: SortType::Descending,
+            packet_sort: SortType::Neutral,
+        };
+
+        // page numbers are updated for the page currently displayed
+        sniffer.running_page = RunningPage::Hosts;
+        sniffer.update(Message::UpdatePageNumber(true));
+        sniffer.update(Message::UpdatePageNumber(true));
+        assert_eq!(sniffer.hosts_page_number, 3);
+        assert_eq!(sniffer.services_page_number, 1);
+        assert_eq!(sniffer.page_number, 1);
+        sniffer.running_page = RunningPage::Services;
+        sniffer.update(Message::UpdatePageNumber(true));
+        assert_eq!(sniffer.hosts_page_number, 3);
+        assert_eq!(sniffer.services_page_number, 2);
+        assert_eq!(sniffer.page_number, 1);
+
+        // no results => arrows don't change page
+        sniffer.update(Message::ArrowPressed(true));
+        assert_eq!(sniffer.services_page_number, 2);
+        sniffer.update(Message::ArrowPressed(false));
+        assert_eq!(sniffer.services_page_number, 1);
+
+        // sorting restarts from the first page
+        sniffer.update(Message::HostsReportSortSelection(sort));
+        assert_eq!(sniffer.hosts_sort_type, sort);
+        assert_eq!(sniffer.hosts_page_number, 1);
+        sniffer.update(Message::ServicesReportSortSelection(sort));
+        assert_eq!(sniffer.services_sort_type, sort);
+
+        // searching restarts from the first page, without leaving the current page
+        sniffer.hosts_page_number = 2;
+        sniffer.services_page_number = 2;
+        let search = SearchParameters {
+            domain: "google".to_string(),
+            ..SearchParameters::default()
+        };
+        sniffer.update(Message::HostsSearch(search.clone()));
+        assert_eq!(sniffer.running_page, RunningPage::Services);
+        assert_eq!(sniffer.hosts_search.domain, "google");
+        assert_eq!(sniffer.hosts_page_number, 1);
+        assert_eq!(sniffer.services_page_number, 1);
+        assert_eq!(sniffer.search.domain, "");
+
+        // clicking on a host shows its connections in the inspect page
+        sniffer.update(Message::Search(search));
+        assert_eq!(sniffer.running_page, RunningPage::Inspect);
+        assert_eq!(sniffer.search.domain, "google");
+        assert_eq!(sniffer.hosts_search.domain, "google");
+
+        sniffer.update(Message::Reset);
+        assert_eq!(sniffer.hosts_search.domain, "");
+        assert_eq!(sniffer.hosts_sort_type, ReportSortType::default());
+        assert_eq!(sniffer.services_sort_type, ReportSortType::default());
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_multiple_adapters_selection() {
+        let mut sniffer = new_sniffer();
+        let device = |name: &str| MyDevice {
+            name: name.to_string(),
+            desc: None,
+            addresses: Arc::new(Mutex::new(Vec::new())),
+            link_type: MyLinkType::default(),
+        };
+        sniffer.device = device("eth0");
+        sniffer.additional_devices = vec![device("wlan0"), device("docker0")];
+
+        // adapters already selected or not existing are not added
+        sniffer.update(Message::AdapterMultiSelection("wlan0".to_string(), true));
+        sniffer.update(Message::AdapterMultiSelection(
+            "not-existing-adapter".to_string(),
+            true,
+        ));
+        assert_eq!(sniffer.additional_devices.len(), 2);
+
+        sniffer.update(Message::AdapterMultiSelection("wlan0".to_string(), false));
+        assert_eq!(sniffer.device.name, "eth0");
+        assert_eq!(sniffer.additional_devices.len(), 1);
+        assert_eq!(sniffer.additional_devices[0].name, "docker0");
+
+        // removing the main adapter promotes the next one
+        sniffer.update(Message::AdapterMultiSelection("eth0".to_string(), false));
+        assert_eq!(sniffer.device.name, "docker0");
+        assert!(sniffer.additional_devices.is_empty());
+
+        // the last adapter can't be removed
+        sniffer.update(Message::AdapterMultiSelection("docker0".to_string(), false));
+        assert_eq!(sniffer.device.name, "docker0");
+
+        assert!(!sniffer.traffic_chart.split_by_adapter);
+        sniffer.update(Message::ChartSplitByAdapter(true));
+        assert!(sniffer.traffic_chart.split_by_adapter);
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_non_ip_traffic_search() {
+        let mut sniffer = new_sniffer();
+        assert!(!sniffer.show_non_ip);
+
+        // clicking on a protocol shows its traffic in the inspect page
+        sniffer.update(Message::NonIpSearch("=ARP".to_string()));
+        assert_eq!(sniffer.running_page, RunningPage::Inspect);
+        assert!(sniffer.show_non_ip);
+        assert_eq!(sniffer.non_ip_search, "=ARP");
+
+        sniffer.update(Message::ShowNonIpTraffic(false));
+        assert!(!sniffer.show_non_ip);
+        sniffer.update(Message::ShowNonIpTraffic(true));
+        assert!(sniffer.show_non_ip);
+
+        // searching IP connections hides the non-IP traffic
+        sniffer.update(Message::Search(SearchParameters::default()));
+        assert!(!sniffer.show_non_ip);
+        assert_eq!(sniffer.non_ip_search, "=ARP");
+
+        sniffer.update(Message::NonIpSortSelection(SortType::Descending));
+        assert_eq!(sniffer.non_ip_sort_type, SortType::Descending);
+
+        sniffer.update(Message::Reset);
+        assert!(!sniffer.show_non_ip);
+        assert_eq!(sniffer.non_ip_search, "");
+    }
+
+    #[test]
+    #[parallel] // needed to not collide with other tests generating configs files
+    fn test_devices_page() {
+        let mut sniffer = new_sniffer();
+        let sort = ReportSortType {
+            byte_sort: SortType::Neutral,
+            packet_sort: SortType::Ascending,
+        };
+
+        sniffer.running_page = RunningPage::Devices;
+        sniffer.update(Message::UpdatePageNumber(true));
+        assert_eq!(sniffer.devices_page_number, 2);
+        assert_eq!(sniffer.page_number, 1);
+
+        // sorting and searching restart from the first page
+        sniffer.update(Message::DevicesReportSortSelection(sort));
+        assert_eq!(sniffer.devices_sort_type, sort);
+        assert_eq!(sniffer.devices_page_number, 1);
+        sniffer.devices_page_number = 2;
+        sniffer.update(Message::DevicesSearch("raspberry".to_string()));
+        assert_eq!(sniffer.devices_search, "raspberry");
+        assert_eq!(sniffer.devices_page_number, 1);
+        assert_eq!(sniffer.running_page, RunningPage::Devices);
+
+        // clicking on a device shows the connections involving its MAC address
+        let add_connection = |port: u16, mac_address1: &str, mac_address2: &str| {
+            sniffer.info_traffic.lock().unwrap().map.insert(
+                AddressPortPair::new(
+                    "192.168.1.10".to_string(),
+                    Some(port),
+                    "192.168.1.20".to_string(),
+                    Some(22),
+                    Protocol::TCP,
+                ),
+                InfoAddressPortPair {
+                    mac_address1: Some(mac_address1.to_string()),
+                    mac_address2: Some(mac_address2.to_string()),
+                    ..InfoAddressPortPair::default()
+                },
+            );
+        };
+        add_connection(50000, "aa:bb:cc:dd:ee:ff", "b8:27:eb:12:34:56");
+        add_connection(50001, "b8:27:eb:12:34:56", "aa:bb:cc:dd:ee:ff");
+        add_connection(50002, "aa:bb:cc:dd:ee:ff", "11:22:33:44:55:66");
+        sniffer.update(Message::Search(SearchParameters::new_device_search(
+            "b8:27:eb:12:34:56",
+        )));
+        assert_eq!(sniffer.running_page, RunningPage::Inspect);
+        assert_eq!(sniffer.search.mac, "=b8:27:eb:12:34:56");
+        assert_eq!(get_searched_entries(&sniffer).1, 2);
+        sniffer.update(Message::Search(SearchParameters::new_device_search(
+            "aa:bb:cc:dd:ee:ff",
+        )));
+        assert_eq!(get_searched_entries(&sniffer).1, 3);
+
+        sniffer.update(Message::Reset);
+        assert_eq!(sniffer.devices_search, "");
+        assert_eq!(sniffer.devices_sort_type, ReportSortType::default());
+        assert_eq!(sniffer.devices_page_number, 1);
+    }
 }
@@ -12,12 +12,14 @@
 
 use chart::types::chart_type::ChartType;
 use chart::types::traffic_chart::TrafficChart;
+use cli::headless::run_headless;
 use cli::parse_cli_args;
 use configs::types::config_device::ConfigDevice;
 use configs::types::config_settings::ConfigSettings;
 use gui::pages::types::running_page::RunningPage;
 use gui::styles::style_constants::FONT_SIZE_BODY;
 use gui::styles::types::style_type::StyleType;
+use gui::types::message::Message;
 use gui::types::runtime_data::RunTimeData;
 use gui::types::sniffer::Sniffer;
 use networking::types::byte_multiple::ByteMultiple;
@@ -55,7 +57,7 @@
 ///
 /// It initializes shared variables and loads configuration parameters
 pub fn main() -> iced::Result {
-    parse_cli_args();
+    let cli_args = parse_cli_args();
 
     let configs1 = Arc::new(Mutex::new(Configs::load()));
     let configs2 = configs1.clone();
@@ -78,6 +80,11 @@ pub fn main() -> iced::Result {
     })
     .expect("Error setting Ctrl-C handler");
 
+    if cli_args.headless {
+        let configs = configs1.lock().unwrap().clone();
+        run_headless(cli_args, &configs);
+    }
+
     thread::Builder::new()
         .name("thread_check_updates".to_string())
         .spawn(move || {
@@ -89,6 +96,41 @@ pub fn main() -> iced::Result {
 
     let ConfigWindow { size, position, .. } = configs1.lock().unwrap().window;
 
+    let mut sniffer = Sniffer::new(&configs1, newer_release_available1);
+    if let Some(name) = cli_args.interface {
+        let _ = sniffer.update(Message::AdapterSelection(name));
+    }
+    sniffer.filters = cli_args.filters;
+    if !cli_args.reverse_dns {
+        let _ = sniffer.update(Message::ToggleReverseDns(false));
+    }
+    if let Some(path) = cli_args.export_pcap {
+        let path = std::path::Path::new(&path);
+        sniffer.export_pcap.toggle();
+        sniffer.export_pcap.set_file_name(
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+        );
+        sniffer.export_pcap.set_directory(
+            path.parent()
+                .unwrap_or(std::path::Path::new(""))
+                .to_string_lossy()
+                .to_string(),
+        );
+        sniffer.export_pcap.set_rotation(cli_args.pcap_rotation);
+    }
+    if let Some(file_path) = cli_args.import_pcap {
+        // directly start the analysis of the supplied capture file
+        sniffer.import_pcap.toggle();
+        sniffer.import_pcap.set_path(file_path);
+        if cli_args.real_time {
+            sniffer.import_pcap.toggle_real_time();
+        }
+        let _ = sniffer.update(Message::Start);
+    }
+
     Sniffer::run(Settings {
         // id needed for Linux Wayland; should match StartupWMClass in .desktop file; see issue #292
         id: Some(String::from(SNIFFNET_LOWERCASE)),
@@ -109,7 +151,7 @@ pub fn main() -> iced::Result {
             exit_on_close_request: false,
             ..Default::default()
         },
-        flags: Sniffer::new(&configs1, newer_release_available1),
+        flags: sniffer,
         fonts: vec![
             Cow::Borrowed(SARASA_MONO_BYTES),
             Cow::Borrowed(SARASA_MONO_BOLD_BYTES),
@@ -0,0 +1,319 @@
+//! Module in charge of the inventory of the devices of the local network,
+//! built from the MAC addresses observed in the traffic exchanged by local IP addresses.
+//!
+//! The vendor of each device is looked up in `oui.txt`, a table of Organizationally Unique Identifiers.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Local};
+use pcap::Address;
+
+use crate::networking::manage_packets::is_local_connection;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::lan_device::LanDevice;
+use crate::networking::types::traffic_direction::TrafficDirection;
+
+include!(concat!(env!("OUT_DIR"), "/oui.rs"));
+
+/// A local device sending or receiving a packet
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanEndpoint {
+    /// MAC address of the device
+    pub mac_address: String,
+    /// IP address used by the device
+    pub address: IpAddr,
+    /// Whether the device is sending or receiving the packet
+    pub traffic_direction: TrafficDirection,
+}
+
+/// Returns the vendor of the device with the given MAC address, if its OUI is known.
+///
+/// Locally administered addresses (e.g., randomized by mobile devices) don't have any vendor.
+pub fn get_vendor(mac_address: &str) -> Option<&'static str> {
+    let oui_str: String = mac_address
+        .split(':')
+        .take(3)
+        .collect::<Vec<&str>>()
+        .concat();
+    if oui_str.len() != 6 {
+        return None;
+    }
+    let oui = u32::from_str_radix(&oui_str, 16).ok()?;
+    if oui & 0x02_0000 != 0 {
+        return None;
+    }
+    OUI_VENDORS.get(&oui).copied()
+}
+
+/// Returns the local devices sending and receiving the packet with the given addresses.
+///
+/// An endpoint is a local device if its IP address is link-local or belongs to the local network,
+/// and its MAC address is neither broadcast nor multicast.
+pub fn get_lan_endpoints(
+    key: &AddressPortPair,
+    mac_addresses: &(Option<String>, Option<String>),
+    my_interface_addresses: &Vec<Address>,
+) -> Vec<LanEndpoint> {
+    [
+        (&mac_addresses.0, &key.address1, TrafficDirection::Outgoing),
+        (&mac_addresses.1, &key.address2, TrafficDirection::Incoming),
+    ]
+    .into_iter()
+    .filter_map(|(mac_address, address, traffic_direction)| {
+        let mac_address = mac_address.as_ref()?;
+        let ip = address.parse::<IpAddr>().ok()?;
+        (is_unicast_mac(mac_address) && is_lan_address(address, ip, my_interface_addresses)).then(
+            || LanEndpoint {
+                mac_address: mac_address.clone(),
+                address: ip,
+                traffic_direction,
+            },
+        )
+    })
+    .collect()
+}
+
+/// Returns the local device sending an ARP message with the given MAC and IP addresses
+pub fn get_arp_lan_endpoint(
+    mac_address: &str,
+    arp_sender: IpAddr,
+    my_interface_addresses: &Vec<Address>,
+) -> Option<LanEndpoint> {
+    (is_unicast_mac(mac_address)
+        && is_lan_address(&arp_sender.to_string(), arp_sender, my_interface_addresses))
+    .then(|| LanEndpoint {
+        mac_address: mac_address.to_string(),
+        address: arp_sender,
+        traffic_direction: TrafficDirection::Outgoing,
+    })
+}
+
+/// Updates the devices of the local network with a packet exchanged by the given endpoints
+pub fn update_lan_devices(
+    devices: &mut HashMap<String, LanDevice>,
+    endpoints: impl IntoIterator<Item = LanEndpoint>,
+    exchanged_bytes: u128,
+    timestamp: DateTime<Local>,
+) {
+    for endpoint in endpoints {
+        devices
+            .entry(endpoint.mac_address)
+            .and_modify(|device| {
+                device.add_packet(
+                    endpoint.address,
+                    exchanged_bytes,
+                    endpoint.traffic_direction,
+                    timestamp,
+                );
+            })
+            .or_insert_with(|| {
+                LanDevice::new_with_first_packet(
+                    endpoint.address,
+                    exchanged_bytes,
+                    endpoint.traffic_direction,
+                    timestamp,
+                )
+            });
+    }
+}
+
+/// Unicast MAC addresses have the least significant bit of the first octet set to 0
+fn is_unicast_mac(mac_address: &str) -> bool {
+    mac_address
+        .get(0..2)
+        .and_then(|first_octet| u8::from_str_radix(first_octet, 16).ok())
+        .is_some_and(|first_octet| first_octet & 0x01 == 0)
+}
+
+/// Checks if the address belongs to the local network (including link-local addresses)
+pub fn is_lan_address(address: &str, ip: IpAddr, my_interface_addresses: &Vec<Address>) -> bool {
+    if ip.is_unspecified() || ip.is_loopback() || ip.is_multicast() {
+        return false;
+    }
+    let is_link_local = match ip {
+        IpAddr::V4(ipv4) => ipv4.is_link_local(),
+        IpAddr::V6(ipv6) => ipv6.segments()[0] & 0xffc0 == 0xfe80,
+    };
+    is_link_local || is_local_connection(address, my_interface_addresses)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Protocol;
+
+    use super::*;
+
+    fn addresses() -> Vec<Address> {
+        vec![Address {
+            addr: "192.168.1.10".parse().unwrap(),
+            netmask: Some("255.255.255.0".parse().unwrap()),
+            broadcast_addr: None,
+            dst_addr: None,
+        }]
+    }
+
+    fn key(address1: &str, address2: &str) -> AddressPortPair {
+        AddressPortPair::new(
+            address1.to_string(),
+            Some(50000),
+            address2.to_string(),
+            Some(443),
+            Protocol::TCP,
+        )
+    }
+
+    fn mac_addresses(mac1: &str, mac2: &str) -> (Option<String>, Option<String>) {
+        (Some(mac1.to_string()), Some(mac2.to_string()))
+    }
+
+    #[test]
+    fn test_get_vendor() {
+        assert_eq!(
+            get_vendor("b8:27:eb:12:34:56"),
+            Some("Raspberry Pi Foundation")
+        );
+        assert_eq!(get_vendor("00:0C:29:AB:CD:EF"), Some("VMware, Inc."));
+        assert_eq!(get_vendor("3c:5a:b4:00:00:01"), Some("Google, Inc."));
+        // not in the table
+        assert_eq!(get_vendor("00:11:22:33:44:55"), None);
+        // locally administered
+        assert_eq!(get_vendor("ba:27:eb:12:34:56"), None);
+        // invalid
+        assert_eq!(get_vendor("b8:27"), None);
+        assert_eq!(get_vendor("zz:27:eb:12:34:56"), None);
+    }
+
+    #[test]
+    fn test_get_lan_endpoints() {
+        // local to local
+        assert_eq!(
+            get_lan_endpoints(
+                &key("192.168.1.10", "192.168.1.20"),
+                &mac_addresses("aa:bb:cc:dd:ee:ff", "b8:27:eb:12:34:56"),
+                &addresses()
+            ),
+            vec![
+                LanEndpoint {
+                    mac_address: "aa:bb:cc:dd:ee:ff".to_string(),
+                    address: "192.168.1.10".parse().unwrap(),
+                    traffic_direction: TrafficDirection::Outgoing,
+                },
+                LanEndpoint {
+                    mac_address: "b8:27:eb:12:34:56".to_string(),
+                    address: "192.168.1.20".parse().unwrap(),
+                    traffic_direction: TrafficDirection::Incoming,
+                }
+            ]
+        );
+        // remote destination: the destination MAC is the one of the router
+        assert_eq!(
+            get_lan_endpoints(
+                &key("192.168.1.10", "140.82.121.4"),
+                &mac_addresses("aa:bb:cc:dd:ee:ff", "11:22:33:44:55:66"),
+                &addresses()
+            ),
+            vec![LanEndpoint {
+                mac_address: "aa:bb:cc:dd:ee:ff".to_string(),
+                address: "192.168.1.10".parse().unwrap(),
+                traffic_direction: TrafficDirection::Outgoing,
+            }]
+        );
+        // link-local IPv6 source and multicast destination
+        assert_eq!(
+            get_lan_endpoints(
+                &key("fe80::1", "ff02::fb"),
+                &mac_addresses("b8:27:eb:12:34:56", "33:33:00:00:00:fb"),
+                &addresses()
+            ),
+            vec![LanEndpoint {
+                mac_address: "b8:27:eb:12:34:56".to_string(),
+                address: "fe80::1".parse().unwrap(),
+                traffic_direction: TrafficDirection::Outgoing,
+            }]
+        );
+        // broadcast destination
+        assert_eq!(
+            get_lan_endpoints(
+                &key("192.168.1.20", "192.168.1.255"),
+                &mac_addresses("b8:27:eb:12:34:56", "ff:ff:ff:ff:ff:ff"),
+                &addresses()
+            )
+            .len(),
+            1
+        );
+        // no link layer header
+        assert!(get_lan_endpoints(
+            &key("192.168.1.10", "192.168.1.20"),
+            &(None, None),
+            &addresses()
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_get_arp_lan_endpoint() {
+        assert_eq!(
+            get_arp_lan_endpoint(
+                "b8:27:eb:12:34:56",
+                "192.168.1.20".parse().unwrap(),
+                &addresses()
+            ),
+            Some(LanEndpoint {
+                mac_address: "b8:27:eb:12:34:56".to_string(),
+                address: "192.168.1.20".parse().unwrap(),
+                traffic_direction: TrafficDirection::Outgoing,
+            })
+        );
+        // ARP probe
+        assert_eq!(
+            get_arp_lan_endpoint(
+                "b8:27:eb:12:34:56",
+                "0.0.0.0".parse().unwrap(),
+                &addresses()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_update_lan_devices() {
+        let first_time = DateTime::from_timestamp(1_700_000_000, 250_000_000)
+            .unwrap()
+            .with_timezone(&Local);
+        let second_time = first_time + chrono::TimeDelta::milliseconds(1500);
+        let mut devices = HashMap::new();
+        let endpoints = get_lan_endpoints(
+            &key("192.168.1.10", "192.168.1.20"),
+            &mac_addresses("aa:bb:cc:dd:ee:ff", "b8:27:eb:12:34:56"),
+            &addresses(),
+        );
+        update_lan_devices(&mut devices, endpoints, 100, first_time);
+        let endpoints = get_lan_endpoints(
+            &key("fe80::1", "fe80::2"),
+            &mac_addresses("b8:27:eb:12:34:56", "aa:bb:cc:dd:ee:ff"),
+            &addresses(),
+        );
+        update_lan_devices(&mut devices, endpoints, 50, second_time);
+
+        assert_eq!(devices.len(), 2);
+        let device = devices.get("b8:27:eb:12:34:56").unwrap();
+        assert_eq!(
+            device
+                .addresses
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["192.168.1.20", "fe80::1"]
+        );
+        assert_eq!(device.data_info.incoming_bytes(), 100);
+        assert_eq!(device.data_info.outgoing_bytes(), 50);
+        assert_eq!(device.data_info.tot_packets(), 2);
+        // times are the ones of the packets, not the ones they were processed at
+        assert_eq!(device.first_seen, first_time);
+        assert_eq!(device.last_seen(), second_time);
+        let device = devices.get("aa:bb:cc:dd:ee:ff").unwrap();
+        assert_eq!(device.data_info.incoming_bytes(), 50);
+        assert_eq!(device.data_info.outgoing_bytes(), 100);
+    }
+}
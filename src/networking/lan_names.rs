@@ -0,0 +1,588 @@
+//! Module in charge of learning the names of the hosts of the local network from the protocols
+//! they use to announce themselves: DHCP, mDNS, NetBIOS Name Service, and LLMNR.
+//!
+//! Reverse DNS lookups of local addresses rarely return anything but the address itself,
+//! while these names are broadcast on the wire.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use etherparse::{LaxPacketHeaders, TransportHeader};
+
+use crate::networking::passive_dns::{parse_dns_response, read_name, read_u16};
+use crate::networking::types::data_info_host::DataInfoHost;
+use crate::networking::types::host::Host;
+use crate::networking::types::name_source::NameSource;
+use crate::InfoTraffic;
+
+const DHCP_SERVER_PORT: u16 = 67;
+const DHCP_CLIENT_PORT: u16 = 68;
+const NBNS_PORT: u16 = 137;
+const MDNS_PORT: u16 = 5353;
+const LLMNR_PORT: u16 = 5355;
+
+/// Offset of the options in DHCP messages (after the fixed BOOTP fields and the magic cookie)
+const DHCP_OPTIONS_OFFSET: usize = 240;
+const DHCP_MAGIC_COOKIE: [u8; 4] = [99, 130, 83, 99];
+const DHCP_OPTION_HOST_NAME: u8 = 12;
+const DHCP_OPTION_REQUESTED_ADDRESS: u8 = 50;
+const DHCP_OPTION_MESSAGE_TYPE: u8 = 53;
+const DHCP_OPTION_CLIENT_FQDN: u8 = 81;
+const DHCP_REQUEST: u8 = 3;
+const DHCP_ACK: u8 = 5;
+const DHCP_INFORM: u8 = 8;
+
+/// Length of the NBNS message header
+const NBNS_HEADER_LEN: usize = 12;
+/// Record type of NetBIOS names
+const NBNS_TYPE_NB: u16 = 0x0020;
+/// Opcodes of queries and name registrations (refresh has two different values)
+const NBNS_OPCODES: [u16; 4] = [0, 5, 8, 9];
+/// Suffixes of the names of workstations and file servers
+const NBNS_HOST_SUFFIXES: [u8; 2] = [0x00, 0x20];
+
+/// Name of a host of the local network, learned from one of its announcements
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanName {
+    /// Address of the host
+    pub address: IpAddr,
+    /// Name of the host
+    pub name: String,
+    /// Protocol announcing the name
+    pub source: NameSource,
+}
+
+/// Returns the host names announced in the packet, if it's a DHCP, mDNS, NBNS, or LLMNR message
+pub fn get_lan_names(headers: &LaxPacketHeaders) -> Vec<LanName> {
+    let Some(TransportHeader::Udp(udp_header)) = &headers.transport else {
+        return Vec::new();
+    };
+    let payload = headers.payload.slice();
+    match (udp_header.source_port, udp_header.destination_port) {
+        (DHCP_CLIENT_PORT, DHCP_SERVER_PORT) | (DHCP_SERVER_PORT, DHCP_CLIENT_PORT) => {
+            parse_dhcp(payload).into_iter().collect()
+        }
+        (NBNS_PORT, _) => parse_nbns(payload).unwrap_or_default(),
+        (MDNS_PORT, _) => get_dns_names(payload, NameSource::Mdns),
+        (LLMNR_PORT, _) => get_dns_names(payload, NameSource::Llmnr),
+        _ => Vec::new(),
+    }
+}
+
+/// Updates the collections with the names announced by the hosts of the local network.
+///
+/// Hosts already resolved are renamed, unless their name was learned from DNS
+/// (the data they exchanged so far is moved to the renamed host).
+pub fn update_lan_names(info_traffic: &mut InfoTraffic, lan_names: Vec<LanName>) {
+    for LanName {
+        address,
+        name,
+        source,
+    } in lan_names
+    {
+        let address = address.to_string();
+        info_traffic
+            .lan_names
+            .insert(address.clone(), (name.clone(), source));
+
+        if info_traffic
+            .name_sources
+            .get(&address)
+            .is_some_and(|source| !source.is_lan())
+        {
+            continue;
+        }
+        let Some((r_dns, host)) = info_traffic.addresses_resolved.get(&address).cloned() else {
+            // the name will be used when the address is resolved
+            continue;
+        };
+        if r_dns.eq(&name) {
+            continue;
+        }
+
+        let new_host = Host {
+            domain: name.clone(),
+            ..host.clone()
+        };
+        // the previous host is kept if other addresses are still associated with it
+        let is_shared =
+            info_traffic
+                .addresses_resolved
+                .iter()
+                .any(|(other_address, (_, other_host))| {
+                    other_address.ne(&address) && other_host.eq(&host)
+                });
+        let data_info_host = if is_shared {
+            info_traffic
+                .hosts
+                .get(&host)
+                .map(|data_info_host| DataInfoHost {
+                    data_info: Default::default(),
+                    is_favorite: false,
+                    tcp_quality: Default::default(),
+                    ..*data_info_host
+                })
+        } else {
+            if info_traffic.favorite_hosts.remove(&host) {
+                info_traffic.favorite_hosts.insert(new_host.clone());
+            }
+            info_traffic.hosts.remove(&host)
+        };
+        if let Some(data_info_host) = data_info_host {
+            info_traffic
+                .hosts
+                .entry(new_host.clone())
+                .and_modify(|existing| {
+                    existing.data_info += data_info_host.data_info;
+                    existing.tcp_quality += data_info_host.tcp_quality;
+                })
+                .or_insert(data_info_host);
+        }
+        info_traffic
+            .addresses_resolved
+            .insert(address.clone(), (name, new_host));
+        info_traffic.name_sources.insert(address, source);
+    }
+}
+
+/// Returns the host name declared in a DHCP request (or acknowledged by the server),
+/// with the address the client is using or requesting
+fn parse_dhcp(message: &[u8]) -> Option<LanName> {
+    if message.get(236..DHCP_OPTIONS_OFFSET)? != DHCP_MAGIC_COOKIE {
+        return None;
+    }
+    let op = *message.first()?;
+    let ciaddr = read_ipv4(message, 12)?;
+    let yiaddr = read_ipv4(message, 16)?;
+
+    let mut message_type = None;
+    let mut host_name = None;
+    let mut fqdn = None;
+    let mut requested_address = None;
+    let mut offset = DHCP_OPTIONS_OFFSET;
+    while let Some(&code) = message.get(offset) {
+        match code {
+            0 => {
+                offset += 1;
+                continue;
+            }
+            255 => break,
+            _ => {}
+        }
+        let len = usize::from(*message.get(offset + 1)?);
+        let data = message.get(offset + 2..offset + 2 + len)?;
+        match code {
+            DHCP_OPTION_MESSAGE_TYPE => message_type = data.first().copied(),
+            DHCP_OPTION_HOST_NAME => host_name = get_valid_name(data),
+            DHCP_OPTION_CLIENT_FQDN => fqdn = parse_client_fqdn(data),
+            DHCP_OPTION_REQUESTED_ADDRESS => requested_address = read_ipv4(data, 0),
+            _ => {}
+        }
+        offset += 2 + len;
+    }
+
+    let address = match (op, message_type?) {
+        // client renewing (ciaddr) or selecting (requested address) its lease
+        (1, DHCP_REQUEST | DHCP_INFORM) => Some(ciaddr)
+            .filter(|a| !a.is_unspecified())
+            .or(requested_address),
+        // server acknowledging the lease
+        (2, DHCP_ACK) => Some(yiaddr).filter(|a| !a.is_unspecified()),
+        _ => None,
+    }?;
+    Some(LanName {
+        address: IpAddr::V4(address),
+        name: fqdn.or(host_name)?,
+        source: NameSource::Dhcp,
+    })
+}
+
+/// Parses the Client FQDN option: flags, two deprecated result codes, and the domain name
+/// (in DNS wire format if the E flag is set, otherwise in ASCII)
+fn parse_client_fqdn(data: &[u8]) -> Option<String> {
+    let flags = *data.first()?;
+    let name = data.get(3..)?;
+    if flags & 0x04 == 0 {
+        return get_valid_name(name);
+    }
+    let mut name_with_terminator = name.to_vec();
+    name_with_terminator.push(0);
+    // names are made of labels only (without compression)
+    let (name, _) = read_name(&name_with_terminator, 0)?;
+    get_valid_name(name.as_bytes())
+}
+
+/// Returns the names of the NetBIOS records (workstations and file servers) with their addresses
+fn parse_nbns(message: &[u8]) -> Option<Vec<LanName>> {
+    let flags = read_u16(message, 2)?;
+    let opcode = (flags >> 11) & 0x000F;
+    let response_code = flags & 0x000F;
+    if !NBNS_OPCODES.contains(&opcode) || response_code != 0 {
+        return None;
+    }
+    let questions = read_u16(message, 4)?;
+    let records = [6, 8, 10]
+        .iter()
+        .map(|offset| read_u16(message, *offset).map(usize::from))
+        .sum::<Option<usize>>()?;
+
+    let mut offset = NBNS_HEADER_LEN;
+    for _ in 0..questions {
+        // skip name, type, and class
+        offset = read_name(message, offset)?.1 + 4;
+    }
+
+    let mut lan_names = Vec::new();
+    for _ in 0..records {
+        let (encoded_name, after_name) = read_name(message, offset)?;
+        let record_type = read_u16(message, after_name)?;
+        let data_len = usize::from(read_u16(message, after_name + 8)?);
+        let data_offset = after_name + 10;
+        let data = message.get(data_offset..data_offset + data_len)?;
+        if record_type == NBNS_TYPE_NB {
+            if let Some(name) = decode_netbios_name(&encoded_name) {
+                // each entry is made of flags (the most significant bit is set for group names) and address
+                for entry in data.chunks_exact(6) {
+                    let address = read_ipv4(entry, 2)?;
+                    if entry[0] & 0x80 == 0 && !address.is_unspecified() {
+                        lan_names.push(LanName {
+                            address: IpAddr::V4(address),
+                            name: name.clone(),
+                            source: NameSource::Nbns,
+                        });
+                    }
+                }
+            }
+        }
+        offset = data_offset + data_len;
+    }
+    Some(lan_names)
+}
+
+/// Decodes a NetBIOS name (first-level encoding of the 15 characters of the name and its suffix),
+/// returning None if it doesn't belong to a workstation or a file server
+fn decode_netbios_name(encoded_name: &str) -> Option<String> {
+    let encoded_name = encoded_name.split('.').next()?.as_bytes();
+    if encoded_name.len() != 32 {
+        return None;
+    }
+    let mut decoded = Vec::with_capacity(16);
+    for pair in encoded_name.chunks_exact(2) {
+        // names read from the message are lowercase
+        let (high, low) = (pair[0].checked_sub(b'a')?, pair[1].checked_sub(b'a')?);
+        if high > 0x0F || low > 0x0F {
+            return None;
+        }
+        decoded.push((high << 4) | low);
+    }
+    if !NBNS_HOST_SUFFIXES.contains(&decoded[15]) {
+        return None;
+    }
+    get_valid_name(&decoded[..15])
+}
+
+/// Returns the A and AAAA records of an mDNS or LLMNR response
+fn get_dns_names(message: &[u8], source: NameSource) -> Vec<LanName> {
+    parse_dns_response(message)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(address, name)| {
+            Some(LanName {
+                address,
+                name: get_valid_name(name.as_bytes())?,
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Returns the lowercase name without padding, if it's made of printable ASCII characters
+fn get_valid_name(name: &[u8]) -> Option<String> {
+    let name = std::str::from_utf8(name)
+        .ok()?
+        .trim_matches(|c: char| c.is_whitespace() || c == '\0' || c == '.');
+    if name.is_empty() || name == "*" || !name.chars().all(|c| c.is_ascii_graphic()) {
+        return None;
+    }
+    Some(name.to_lowercase())
+}
+
+fn read_ipv4(message: &[u8], offset: usize) -> Option<Ipv4Addr> {
+    let octets: [u8; 4] = message.get(offset..offset + 4)?.try_into().ok()?;
+    Some(Ipv4Addr::from(octets))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::Local;
+
+    use crate::countries::types::country::Country;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::data_info::DataInfo;
+    use crate::networking::types::tcp_quality::TcpQuality;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::networking::types::traffic_type::TrafficType;
+
+    use super::*;
+
+    fn dhcp_message(op: u8, ciaddr: [u8; 4], yiaddr: [u8; 4], options: &[u8]) -> Vec<u8> {
+        let mut message = vec![op, 1, 6, 0];
+        message.extend_from_slice(&[0x12, 0x34, 0x56, 0x78, 0, 0, 0, 0]);
+        message.extend_from_slice(&ciaddr);
+        message.extend_from_slice(&yiaddr);
+        message.extend_from_slice(&[0; 8]);
+        message.extend_from_slice(&[0xb8, 0x27, 0xeb, 0x12, 0x34, 0x56]);
+        message.extend_from_slice(&[0; 202]);
+        message.extend_from_slice(&DHCP_MAGIC_COOKIE);
+        message.extend_from_slice(options);
+        message.push(255);
+        message
+    }
+
+    /// Encodes a NetBIOS name with the given suffix (first-level encoding)
+    fn netbios_name(name: &str, suffix: u8) -> Vec<u8> {
+        let mut padded = format!("{name:<15}").into_bytes();
+        padded.push(suffix);
+        let mut encoded = vec![32];
+        for byte in padded {
+            encoded.push(b'A' + (byte >> 4));
+            encoded.push(b'A' + (byte & 0x0F));
+        }
+        encoded.push(0);
+        encoded
+    }
+
+    fn nbns_registration(name: &[u8], nb_flags: u8, address: [u8; 4]) -> Vec<u8> {
+        // opcode 5 (registration), recursion desired, broadcast
+        let mut message = vec![0x80, 0x01, 0x29, 0x10, 0, 1, 0, 0, 0, 0, 0, 1];
+        message.extend_from_slice(name);
+        message.extend_from_slice(&[0, 0x20, 0, 1]);
+        // additional record pointing to the name of the question
+        message.extend_from_slice(&[0xC0, 0x0C, 0, 0x20, 0, 1, 0, 0x04, 0x93, 0xE0, 0, 6]);
+        message.extend_from_slice(&[nb_flags, 0]);
+        message.extend_from_slice(&address);
+        message
+    }
+
+    #[test]
+    fn test_parse_dhcp_request() {
+        // selecting: requested address and host name
+        let mut options = vec![53, 1, DHCP_REQUEST, 50, 4, 192, 168, 1, 20];
+        options.extend_from_slice(&[12, 11]);
+        options.extend_from_slice(b"Pixel-7-Pro");
+        let message = dhcp_message(1, [0; 4], [0; 4], &options);
+        assert_eq!(
+            parse_dhcp(&message),
+            Some(LanName {
+                address: "192.168.1.20".parse().unwrap(),
+                name: "pixel-7-pro".to_string(),
+                source: NameSource::Dhcp,
+            })
+        );
+
+        // renewing: current address and FQDN (preferred to the host name)
+        let mut options = vec![53, 1, DHCP_REQUEST, 12, 6];
+        options.extend_from_slice(b"laptop");
+        options.extend_from_slice(&[81, 19, 0x01, 0, 0]);
+        options.extend_from_slice(b"laptop.home.lan\0");
+        let message = dhcp_message(1, [192, 168, 1, 30], [0; 4], &options);
+        assert_eq!(
+            parse_dhcp(&message).map(|lan_name| (lan_name.address, lan_name.name)),
+            Some((
+                "192.168.1.30".parse().unwrap(),
+                "laptop.home.lan".to_string()
+            ))
+        );
+
+        // discover: no address assigned yet
+        let mut options = vec![53, 1, 1, 12, 6];
+        options.extend_from_slice(b"laptop");
+        let message = dhcp_message(1, [0; 4], [0; 4], &options);
+        assert_eq!(parse_dhcp(&message), None);
+    }
+
+    #[test]
+    fn test_parse_dhcp_ack_and_fqdn_wire_format() {
+        let mut options = vec![53, 1, DHCP_ACK, 0, 0, 81, 14, 0x05, 0, 0];
+        options.extend_from_slice(b"\x03nas\x04home\x00");
+        let message = dhcp_message(2, [0; 4], [192, 168, 1, 40], &options);
+        assert_eq!(
+            parse_dhcp(&message).map(|lan_name| (lan_name.address, lan_name.name)),
+            Some(("192.168.1.40".parse().unwrap(), "nas.home".to_string()))
+        );
+
+        // without host name
+        let message = dhcp_message(2, [0; 4], [192, 168, 1, 40], &[53, 1, DHCP_ACK]);
+        assert_eq!(parse_dhcp(&message), None);
+        // not a DHCP message
+        let mut message = dhcp_message(2, [0; 4], [192, 168, 1, 40], &options);
+        message[236] = 0;
+        assert_eq!(parse_dhcp(&message), None);
+        // truncated option
+        let message = dhcp_message(1, [192, 168, 1, 30], [0; 4], &[53, 1, DHCP_REQUEST, 12]);
+        assert_eq!(parse_dhcp(&message[..message.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_parse_nbns() {
+        let message =
+            nbns_registration(&netbios_name("DESKTOP-4F2K", 0x00), 0x00, [192, 168, 1, 50]);
+        assert_eq!(
+            parse_nbns(&message),
+            Some(vec![LanName {
+                address: "192.168.1.50".parse().unwrap(),
+                name: "desktop-4f2k".to_string(),
+                source: NameSource::Nbns,
+            }])
+        );
+
+        // group name
+        let message = nbns_registration(&netbios_name("WORKGROUP", 0x00), 0x80, [192, 168, 1, 50]);
+        assert_eq!(parse_nbns(&message), Some(Vec::new()));
+        // domain master browser suffix
+        let message = nbns_registration(&netbios_name("WORKGROUP", 0x1B), 0x00, [192, 168, 1, 50]);
+        assert_eq!(parse_nbns(&message), Some(Vec::new()));
+        // truncated
+        let message =
+            nbns_registration(&netbios_name("DESKTOP-4F2K", 0x20), 0x00, [192, 168, 1, 50]);
+        assert_eq!(parse_nbns(&message[..message.len() - 2]), None);
+    }
+
+    #[test]
+    fn test_decode_netbios_name() {
+        let encoded = netbios_name("NAS", 0x20);
+        let (name, _) = read_name(&encoded, 0).unwrap();
+        assert_eq!(decode_netbios_name(&name), Some("nas".to_string()));
+        assert_eq!(decode_netbios_name("abcd"), None);
+        assert_eq!(decode_netbios_name(&"z".repeat(32)), None);
+    }
+
+    #[test]
+    fn test_get_lan_names_from_packets() {
+        let packet = |source_port: u16, destination_port: u16, payload: &[u8]| {
+            let builder = etherparse::PacketBuilder::ipv4([192, 168, 1, 60], [224, 0, 0, 251], 255)
+                .udp(source_port, destination_port);
+            let mut packet = Vec::new();
+            builder.write(&mut packet, payload).unwrap();
+            packet
+        };
+        // response with an A record for the responder's name
+        let mut response = vec![0, 0, 0x84, 0, 0, 0, 0, 1, 0, 0, 0, 0];
+        response.extend_from_slice(b"\x0bliving-room\x05local\x00");
+        response.extend_from_slice(&[0, 1, 0x80, 1, 0, 0, 0, 120, 0, 4, 192, 168, 1, 60]);
+
+        let mdns_packet = packet(MDNS_PORT, MDNS_PORT, &response);
+        let headers = LaxPacketHeaders::from_ip(&mdns_packet).unwrap();
+        assert_eq!(
+            get_lan_names(&headers),
+            vec![LanName {
+                address: "192.168.1.60".parse().unwrap(),
+                name: "living-room.local".to_string(),
+                source: NameSource::Mdns,
+            }]
+        );
+
+        let llmnr_packet = packet(LLMNR_PORT, 50000, &response);
+        let headers = LaxPacketHeaders::from_ip(&llmnr_packet).unwrap();
+        assert_eq!(get_lan_names(&headers)[0].source, NameSource::Llmnr);
+
+        let other_packet = packet(8080, 50000, &response);
+        let headers = LaxPacketHeaders::from_ip(&other_packet).unwrap();
+        assert!(get_lan_names(&headers).is_empty());
+    }
+
+    #[test]
+    fn test_update_lan_names() {
+        let mut info_traffic = InfoTraffic::new();
+        let unresolved_host = |address: &str| Host {
+            domain: address.to_string(),
+            asn: Asn::default(),
+            country: Country::ZZ,
+        };
+        let data_info_host = |bytes: u128| DataInfoHost {
+            data_info: DataInfo::new_with_first_packet(
+                bytes,
+                TrafficDirection::Incoming,
+                Local::now(),
+            ),
+            is_favorite: false,
+            is_loopback: false,
+            is_local: true,
+            traffic_type: TrafficType::Unicast,
+            tcp_quality: TcpQuality::default(),
+        };
+        for (address, bytes) in [("192.168.1.20", 100), ("192.168.1.30", 200)] {
+            info_traffic.addresses_resolved.insert(
+                address.to_string(),
+                (address.to_string(), unresolved_host(address)),
+            );
+            info_traffic
+                .hosts
+                .insert(unresolved_host(address), data_info_host(bytes));
+        }
+        info_traffic
+            .favorite_hosts
+            .insert(unresolved_host("192.168.1.20"));
+        info_traffic
+            .name_sources
+            .insert("192.168.1.30".to_string(), NameSource::ReverseDns);
+        let lan_name = |address: &str, name: &str| LanName {
+            address: address.parse().unwrap(),
+            name: name.to_string(),
+            source: NameSource::Dhcp,
+        };
+
+        update_lan_names(
+            &mut info_traffic,
+            vec![
+                lan_name("192.168.1.20", "pixel-7"),
+                lan_name("192.168.1.30", "laptop"),
+                lan_name("192.168.1.40", "nas"),
+            ],
+        );
+
+        // the unresolved host is renamed, keeping its data
+        let renamed_host = Host {
+            domain: "pixel-7".to_string(),
+            ..unresolved_host("192.168.1.20")
+        };
+        assert_eq!(
+            info_traffic.addresses_resolved["192.168.1.20"],
+            ("pixel-7".to_string(), renamed_host.clone())
+        );
+        assert_eq!(
+            info_traffic.hosts[&renamed_host].data_info.incoming_bytes(),
+            100
+        );
+        assert!(!info_traffic
+            .hosts
+            .contains_key(&unresolved_host("192.168.1.20")));
+        assert!(info_traffic.favorite_hosts.contains(&renamed_host));
+        assert_eq!(info_traffic.name_sources["192.168.1.20"], NameSource::Dhcp);
+        // names learned from DNS are kept
+        assert_eq!(
+            info_traffic.addresses_resolved["192.168.1.30"].0,
+            "192.168.1.30"
+        );
+        // addresses not resolved yet only remember the name
+        assert!(!info_traffic.addresses_resolved.contains_key("192.168.1.40"));
+        assert_eq!(
+            info_traffic.lan_names,
+            HashMap::from([
+                (
+                    "192.168.1.20".to_string(),
+                    ("pixel-7".to_string(), NameSource::Dhcp)
+                ),
+                (
+                    "192.168.1.30".to_string(),
+                    ("laptop".to_string(), NameSource::Dhcp)
+                ),
+                (
+                    "192.168.1.40".to_string(),
+                    ("nas".to_string(), NameSource::Dhcp)
+                ),
+            ])
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Module in charge of decoding the Linux "cooked" capture headers (`LINUX_SLL` and `LINUX_SLL2`),
+//! used by the `any` pseudo-device and by interfaces without a link-layer header of their own.
+//!
+//! Besides the protocol of the encapsulated packet, these headers tell whether the packet
+//! was sent or received by this host and (SLL2 only) the index of the interface it was captured on.
+
+use etherparse::err::ip::{HeaderError, LaxHeaderSliceError};
+use etherparse::err::{Layer, LenError};
+use etherparse::{EtherType, LaxPacketHeaders, LenSource};
+
+use crate::networking::types::traffic_direction::TrafficDirection;
+
+/// Length of the `LINUX_SLL` header
+const SLL_HEADER_LEN: usize = 16;
+/// Length of the `LINUX_SLL2` header
+const SLL2_HEADER_LEN: usize = 20;
+/// Protocol values lower than this are not `EtherType`s (e.g., raw 802.3 or 802.2 LLC frames)
+const MIN_ETHER_TYPE: u16 = 0x0600;
+
+/// Information carried by a Linux cooked capture header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinuxCookedHeader {
+    /// Type of the packet, as classified by the kernel
+    pub packet_type: LinuxPacketType,
+    /// Index of the interface the packet was captured on (only available with `LINUX_SLL2`)
+    pub interface_index: Option<u32>,
+}
+
+/// Packet types of the Linux cooked capture headers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinuxPacketType {
+    /// Sent to this host
+    Host,
+    /// Broadcast by another host
+    Broadcast,
+    /// Multicast by another host
+    Multicast,
+    /// Sent by another host to another host (seen in promiscuous mode)
+    OtherHost,
+    /// Sent by this host
+    Outgoing,
+    /// Any other value
+    Other(u16),
+}
+
+impl LinuxPacketType {
+    fn from_u16(value: u16) -> Self {
+        match value {
+            0 => Self::Host,
+            1 => Self::Broadcast,
+            2 => Self::Multicast,
+            3 => Self::OtherHost,
+            4 => Self::Outgoing,
+            _ => Self::Other(value),
+        }
+    }
+
+    /// Returns the traffic direction of the packet, if the kernel knows it
+    pub fn traffic_direction(self) -> Option<TrafficDirection> {
+        match self {
+            Self::Host | Self::Broadcast | Self::Multicast => Some(TrafficDirection::Incoming),
+            Self::Outgoing => Some(TrafficDirection::Outgoing),
+            Self::OtherHost | Self::Other(_) => None,
+        }
+    }
+}
+
+/// Parses a packet captured with link type `LINUX_SLL`.
+///
+/// Header layout: packet type (2 bytes), ARPHRD type (2), address length (2),
+/// address (8), protocol (2).
+pub fn from_sll(
+    packet: &[u8],
+) -> Result<(LaxPacketHeaders<'_>, LinuxCookedHeader), LaxHeaderSliceError> {
+    check_len(packet, SLL_HEADER_LEN)?;
+    let header = LinuxCookedHeader {
+        packet_type: LinuxPacketType::from_u16(u16::from_be_bytes([packet[0], packet[1]])),
+        interface_index: None,
+    };
+    let protocol = u16::from_be_bytes([packet[14], packet[15]]);
+    let headers = from_protocol(protocol, &packet[SLL_HEADER_LEN..])?;
+    Ok((headers, header))
+}
+
+/// Parses a packet captured with link type `LINUX_SLL2`.
+///
+/// Header layout: protocol (2 bytes), reserved (2), interface index (4), ARPHRD type (2),
+/// packet type (1), address length (1), address (8).
+pub fn from_sll2(
+    packet: &[u8],
+) -> Result<(LaxPacketHeaders<'_>, LinuxCookedHeader), LaxHeaderSliceError> {
+    check_len(packet, SLL2_HEADER_LEN)?;
+    let header = LinuxCookedHeader {
+        packet_type: LinuxPacketType::from_u16(u16::from(packet[10])),
+        interface_index: Some(u32::from_be_bytes([
+            packet[4], packet[5], packet[6], packet[7],
+        ])),
+    };
+    let protocol = u16::from_be_bytes([packet[0], packet[1]]);
+    let headers = from_protocol(protocol, &packet[SLL2_HEADER_LEN..])?;
+    Ok((headers, header))
+}
+
+fn check_len(packet: &[u8], required_len: usize) -> Result<(), LaxHeaderSliceError> {
+    if packet.len() < required_len {
+        return Err(LaxHeaderSliceError::Len(LenError {
+            required_len,
+            len: packet.len(),
+            len_source: LenSource::Slice,
+            layer: Layer::Ethernet2Header,
+            layer_start_offset: 0,
+        }));
+    }
+    Ok(())
+}
+
+fn from_protocol(
+    protocol: u16,
+    payload: &[u8],
+) -> Result<LaxPacketHeaders<'_>, LaxHeaderSliceError> {
+    if protocol < MIN_ETHER_TYPE {
+        return Err(LaxHeaderSliceError::Content(
+            HeaderError::UnsupportedIpVersion { version_number: 0 },
+        ));
+    }
+    Ok(LaxPacketHeaders::from_ether_type(
+        EtherType(protocol),
+        payload,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use etherparse::{NetHeaders, PacketBuilder};
+
+    use super::*;
+
+    fn ip_packet() -> Vec<u8> {
+        let builder = PacketBuilder::ipv4([192, 168, 1, 10], [140, 82, 121, 4], 64).udp(50000, 53);
+        let mut packet = Vec::new();
+        builder.write(&mut packet, &[1, 2, 3, 4]).unwrap();
+        packet
+    }
+
+    fn assert_ip_headers(headers: &LaxPacketHeaders) {
+        assert!(headers.link.is_none());
+        let Some(NetHeaders::Ipv4(ipv4_header, _)) = &headers.net else {
+            panic!("expected IPv4 header");
+        };
+        assert_eq!(ipv4_header.source, [192, 168, 1, 10]);
+        assert_eq!(ipv4_header.destination, [140, 82, 121, 4]);
+        assert_eq!(headers.payload.slice(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_from_sll() {
+        let mut packet = vec![0, 4, 0, 1, 0, 6, 1, 2, 3, 4, 5, 6, 0, 0, 0x08, 0x00];
+        packet.extend(ip_packet());
+        let (headers, cooked_header) = from_sll(&packet).unwrap();
+        assert_ip_headers(&headers);
+        assert_eq!(
+            cooked_header,
+            LinuxCookedHeader {
+                packet_type: LinuxPacketType::Outgoing,
+                interface_index: None,
+            }
+        );
+
+        // 802.2 LLC frame
+        packet[14..16].copy_from_slice(&[0, 4]);
+        assert!(from_sll(&packet).is_err());
+        // truncated header
+        assert!(from_sll(&packet[..15]).is_err());
+    }
+
+    #[test]
+    fn test_from_sll2() {
+        let mut packet = vec![
+            0x08, 0x00, 0, 0, 0, 0, 0, 3, 0, 1, 0, 6, 1, 2, 3, 4, 5, 6, 0, 0,
+        ];
+        packet.extend(ip_packet());
+        let (headers, cooked_header) = from_sll2(&packet).unwrap();
+        assert_ip_headers(&headers);
+        assert_eq!(
+            cooked_header,
+            LinuxCookedHeader {
+                packet_type: LinuxPacketType::Host,
+                interface_index: Some(3),
+            }
+        );
+
+        // truncated header
+        assert!(from_sll2(&packet[..19]).is_err());
+    }
+
+    #[test]
+    fn test_packet_type_traffic_direction() {
+        assert_eq!(
+            LinuxPacketType::from_u16(0).traffic_direction(),
+            Some(TrafficDirection::Incoming)
+        );
+        assert_eq!(
+            LinuxPacketType::from_u16(1).traffic_direction(),
+            Some(TrafficDirection::Incoming)
+        );
+        assert_eq!(
+            LinuxPacketType::from_u16(2).traffic_direction(),
+            Some(TrafficDirection::Incoming)
+        );
+        assert_eq!(LinuxPacketType::from_u16(3).traffic_direction(), None);
+        assert_eq!(
+            LinuxPacketType::from_u16(4).traffic_direction(),
+            Some(TrafficDirection::Outgoing)
+        );
+        assert_eq!(LinuxPacketType::from_u16(7), LinuxPacketType::Other(7));
+    }
+}
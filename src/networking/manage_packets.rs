@@ -1,28 +1,36 @@
+use std::cmp::max;
 use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
-use chrono::Local;
-use dns_lookup::lookup_addr;
+use chrono::{DateTime, Local};
 use etherparse::{Ethernet2Header, LaxPacketHeaders, NetHeaders, TransportHeader};
-use pcap::{Address, Device};
+use pcap::{Address, Device, Packet};
 
 use crate::mmdb::asn::get_asn;
 use crate::mmdb::country::get_country;
 use crate::mmdb::types::mmdb_reader::MmdbReader;
+use crate::networking::linux_cooked::LinuxCookedHeader;
+use crate::networking::payload_classifier::{MAX_CLASSIFIED_PACKETS, PAYLOAD_ONLY_SERVICES};
+use crate::networking::program_lookup::ProgramLookup;
 use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::data_info_host::DataInfoHost;
 use crate::networking::types::host::Host;
 use crate::networking::types::icmp_type::{IcmpType, IcmpTypeV4, IcmpTypeV6};
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
 use crate::networking::types::my_device::MyDevice;
+use crate::networking::types::name_source::NameSource;
 use crate::networking::types::packet_filters_fields::PacketFiltersFields;
+use crate::networking::types::program::Program;
 use crate::networking::types::service::Service;
 use crate::networking::types::service_query::ServiceQuery;
+use crate::networking::types::tcp_connection::{TcpConnection, TcpSegment, TcpState};
+use crate::networking::types::tcp_quality::{TcpQuality, TcpTracker};
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::networking::types::traffic_type::TrafficType;
-use crate::utils::formatted_strings::get_domain_from_r_dns;
+use crate::networking::user_services::{get_user_service_by_name, USER_SERVICES};
+use crate::utils::formatted_strings::{get_domain_from_r_dns, get_socket_address};
 use crate::IpVersion::{IPv4, IPv6};
 use crate::{InfoTraffic, IpVersion, Protocol};
 
@@ -32,11 +40,17 @@
 /// Returns the relevant collected information.
 pub fn analyze_headers(
     headers: LaxPacketHeaders,
+    interface: Option<&String>,
     mac_addresses: &mut (Option<String>, Option<String>),
     exchanged_bytes: &mut u128,
     icmp_type: &mut IcmpType,
+    tcp_segment: &mut Option<TcpSegment>,
     packet_filters_fields: &mut PacketFiltersFields,
 ) -> Option<AddressPortPair> {
+    // the captured payload may be truncated by the snapshot length,
+    // while the length declared in the IP header may be missing with segmentation offload
+    let payload_len = max(headers.payload.slice().len(), get_payload_len(&headers));
+
     analyze_link_header(
         headers.link,
         &mut mac_addresses.0,
@@ -60,17 +74,22 @@ pub fn analyze_headers(
         &mut packet_filters_fields.dport,
         &mut packet_filters_fields.protocol,
         icmp_type,
+        tcp_segment,
+        payload_len,
     ) {
         return None;
     }
 
-    Some(AddressPortPair::new(
-        packet_filters_fields.source.to_string(),
-        packet_filters_fields.sport,
-        packet_filters_fields.dest.to_string(),
-        packet_filters_fields.dport,
-        packet_filters_fields.protocol,
-    ))
+    Some(
+        AddressPortPair::new(
+            packet_filters_fields.source.to_string(),
+            packet_filters_fields.sport,
+            packet_filters_fields.dest.to_string(),
+            packet_filters_fields.dport,
+            packet_filters_fields.protocol,
+        )
+        .with_interface(interface.cloned()),
+    )
 }
 
 /// This function analyzes the data link layer header passed as parameter and updates variables
@@ -121,6 +140,24 @@ fn analyze_network_header(
     }
 }
 
+/// Returns the length of the transport layer payload, as declared in the IP header
+fn get_payload_len(headers: &LaxPacketHeaders) -> usize {
+    let ip_payload_len = match &headers.net {
+        Some(NetHeaders::Ipv4(ipv4_header, extensions)) => usize::from(ipv4_header.total_len)
+            .saturating_sub(ipv4_header.header_len() + extensions.header_len()),
+        Some(NetHeaders::Ipv6(ipv6_header, extensions)) => {
+            usize::from(ipv6_header.payload_length).saturating_sub(extensions.header_len())
+        }
+        None => 0,
+    };
+    let transport_header_len = match &headers.transport {
+        Some(TransportHeader::Tcp(tcp_header)) => tcp_header.header_len(),
+        Some(TransportHeader::Udp(_)) => 8,
+        _ => 0,
+    };
+    ip_payload_len.saturating_sub(transport_header_len)
+}
+
 /// This function analyzes the transport layer header passed as parameter and updates variables
 /// passed by reference on the basis of the packet header content.
 /// Returns false if packet has to be skipped.
@@ -130,6 +167,8 @@ fn analyze_transport_header(
     port2: &mut Option<u16>,
     protocol: &mut Protocol,
     icmp_type: &mut IcmpType,
+    tcp_segment: &mut Option<TcpSegment>,
+    payload_len: usize,
 ) -> bool {
     match transport_header {
         Some(TransportHeader::Udp(udp_header)) => {
@@ -142,6 +181,7 @@ fn analyze_transport_header(
             *port1 = Some(tcp_header.source_port);
             *port2 = Some(tcp_header.destination_port);
             *protocol = Protocol::TCP;
+            *tcp_segment = Some(TcpSegment::from_etherparse(&tcp_header, payload_len));
             true
         }
         Some(TransportHeader::Icmpv4(icmpv4_header)) => {
@@ -170,58 +210,95 @@ pub fn get_service(key: &AddressPortPair, traffic_direction: TrafficDirection) -
     // to return the service associated with the highest score:
     // score = service_is_some * (port_is_well_known + bonus_direction)
     // service_is_some: 1 if some, 0 if unknown
-    // port_is_well_known: 3 if well known, 1 if not
+    // port_is_well_known: 3 if well known or defined by the user, 1 if not
     // bonus_direction: +1 assigned to remote port
-    let compute_service_score = |service: &Service, port: u16, bonus_direction: bool| {
-        let service_is_some = u8::from(matches!(service, Service::Name(_)));
-        let port_is_well_known = if port < 1024 { 3 } else { 1 };
-        let bonus_direction = u8::from(bonus_direction);
-        service_is_some * (port_is_well_known + bonus_direction)
-    };
+    let compute_service_score =
+        |service: &Service, port: u16, is_user_service: bool, bonus_direction: bool| {
+            let service_is_some = u8::from(matches!(service, Service::Name(_)));
+            let port_is_well_known = if port < 1024 || is_user_service { 3 } else { 1 };
+            let bonus_direction = u8::from(bonus_direction);
+            service_is_some * (port_is_well_known + bonus_direction)
+        };
 
     let port1 = key.port1.unwrap();
     let port2 = key.port2.unwrap();
 
-    let unknown = Service::Unknown;
-    let service1 = SERVICES
-        .get(&ServiceQuery(port1, key.protocol))
-        .unwrap_or(&unknown);
-    let service2 = SERVICES
-        .get(&ServiceQuery(port2, key.protocol))
-        .unwrap_or(&unknown);
+    // services defined by the user are consulted before the built-in ones
+    let user_services = USER_SERVICES.read().unwrap();
+    let get_port_service = |port: u16| {
+        let query = ServiceQuery(port, key.protocol);
+        match user_services.get(&query) {
+            Some(service) => (*service, true),
+            None => (*SERVICES.get(&query).unwrap_or(&Service::Unknown), false),
+        }
+    };
+    let (service1, is_user_service1) = get_port_service(port1);
+    let (service2, is_user_service2) = get_port_service(port2);
 
     let score1 = compute_service_score(
-        service1,
+        &service1,
         port1,
+        is_user_service1,
         traffic_direction.ne(&TrafficDirection::Outgoing),
     );
     let score2 = compute_service_score(
-        service2,
+        &service2,
         port2,
+        is_user_service2,
         traffic_direction.eq(&TrafficDirection::Outgoing),
     );
 
     if score1 > score2 {
-        *service1
+        service1
     } else {
-        *service2
+        service2
     }
 }
 
+/// Returns the known service with the given name, if any
+pub fn get_service_by_name(name: &str) -> Option<Service> {
+    SERVICES
+        .values()
+        .find(|service| matches!(service, Service::Name(n) if *n == name))
+        .copied()
+        .or_else(|| get_user_service_by_name(name))
+        .or_else(|| {
+            PAYLOAD_ONLY_SERVICES
+                .into_iter()
+                .find(|n| *n == name)
+                .map(Service::Name)
+        })
+}
+
+/// Returns the time the packet was captured, as recorded in its header
+pub fn get_packet_timestamp(packet: &Packet) -> DateTime<Local> {
+    let ts = packet.header.ts;
+    // the seconds are not represented with an i64 on every platform
+    #[allow(clippy::useless_conversion)]
+    let secs = i64::from(ts.tv_sec);
+    DateTime::from_timestamp(secs, u32::try_from(ts.tv_usec).unwrap_or_default() * 1000)
+        .map_or_else(Local::now, |timestamp| timestamp.with_timezone(&Local))
+}
+
 /// Function to insert the source and destination of a packet into the shared map containing the analyzed traffic.
+#[allow(clippy::too_many_arguments)]
 pub fn modify_or_insert_in_map(
     info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
     key: &AddressPortPair,
     my_device: &MyDevice,
     mac_addresses: (Option<String>, Option<String>),
     icmp_type: IcmpType,
+    tcp_segment: Option<TcpSegment>,
     exchanged_bytes: u128,
+    program_lookup: Option<&ProgramLookup>,
+    cooked_header: Option<LinuxCookedHeader>,
+    timestamp: DateTime<Local>,
 ) -> InfoAddressPortPair {
-    let now = Local::now();
     let mut traffic_direction = TrafficDirection::default();
     let mut service = Service::Unknown;
 
-    if !info_traffic_mutex.lock().unwrap().map.contains_key(key) {
+    let is_new = !info_traffic_mutex.lock().unwrap().map.contains_key(key);
+    if is_new {
         // first occurrence of key
 
         // update device addresses
@@ -244,6 +321,7 @@ pub fn modify_or_insert_in_map(
             key.port1,
             key.port2,
             &my_interface_addresses,
+            cooked_header.and_then(|header| header.packet_type.traffic_direction()),
         );
         // determine upper layer service
         service = get_service(key, traffic_direction);
@@ -253,13 +331,13 @@ pub fn modify_or_insert_in_map(
         .lock()
         .expect("Error acquiring mutex\n\r");
 
-    let new_info: InfoAddressPortPair = info_traffic
+    info_traffic
         .map
         .entry(key.clone())
         .and_modify(|info| {
             info.transmitted_bytes += exchanged_bytes;
             info.transmitted_packets += 1;
-            info.final_timestamp = now;
+            info.final_timestamp = info.final_timestamp.max(timestamp);
             if key.protocol.eq(&Protocol::ICMP) {
                 info.icmp_types
                     .entry(icmp_type)
@@ -272,17 +350,35 @@ pub fn modify_or_insert_in_map(
             mac_address2: mac_addresses.1,
             transmitted_bytes: exchanged_bytes,
             transmitted_packets: 1,
-            initial_timestamp: now,
-            final_timestamp: now,
+            initial_timestamp: timestamp,
+            final_timestamp: timestamp,
             service,
+            program: Program::Unknown,
             traffic_direction,
+            server_name: None,
+            service_from_payload: false,
+            interface_index: cooked_header.and_then(|header| header.interface_index),
             icmp_types: if key.protocol.eq(&Protocol::ICMP) {
                 HashMap::from([(icmp_type, 1)])
             } else {
                 HashMap::new()
             },
-        })
-        .clone();
+            tcp_connection: None,
+            tcp_quality: TcpQuality::default(),
+            tcp_tracker: TcpTracker::default(),
+        });
+    if let Some(tcp_segment) = tcp_segment {
+        update_tcp_connection(&mut info_traffic, key, &tcp_segment, timestamp);
+    }
+    // the program owning the local socket is identified in the background
+    let program_lookup = program_lookup.filter(|_| is_new);
+    if program_lookup.is_some() {
+        info_traffic
+            .programs_waiting_resolution
+            .entry(key.clone())
+            .or_default();
+    }
+    let new_info = info_traffic.map.get(key).cloned().unwrap_or_default();
 
     if let Some(host_info) = info_traffic
         .addresses_resolved
@@ -293,10 +389,112 @@ pub fn modify_or_insert_in_map(
             info_traffic.favorites_last_interval.insert(host_info.1);
         }
     }
+    drop(info_traffic);
+
+    if let Some(program_lookup) = program_lookup {
+        program_lookup.request(key, new_info.traffic_direction);
+    }
 
     new_info
 }
 
+/// Updates the lifecycle of the TCP connection the segment belongs to,
+/// which is shared by the entries of the two directions of the flow,
+/// and the quality metrics of the connection and of the remote host.
+pub fn update_tcp_connection(
+    info_traffic: &mut InfoTraffic,
+    key: &AddressPortPair,
+    tcp_segment: &TcpSegment,
+    timestamp: DateTime<Local>,
+) {
+    let reversed_key = key.reversed();
+    let sender = get_socket_address(&key.address1, key.port1);
+    let previous = [key, &reversed_key].into_iter().find_map(|key| {
+        info_traffic
+            .map
+            .get(key)
+            .and_then(|info| info.tcp_connection.clone())
+    });
+    let previous_state = previous.as_ref().map(|connection| connection.state);
+    let connection = if let Some(mut connection) = previous {
+        connection.update(tcp_segment.flags, &sender, timestamp);
+        connection
+    } else {
+        TcpConnection::new(tcp_segment.flags, &sender, timestamp)
+    };
+    info_traffic
+        .tcp_stats
+        .update(previous_state, connection.state);
+    for key in [key, &reversed_key] {
+        if let Some(info) = info_traffic.map.get_mut(key) {
+            info.tcp_connection = Some(connection.clone());
+        }
+    }
+
+    // quality of the segments sent in this direction
+    let Some(info) = info_traffic.map.get_mut(key) else {
+        return;
+    };
+    let rtt = connection.handshake_rtt();
+    let mut quality = info.tcp_tracker.update(tcp_segment, timestamp, rtt);
+    if previous_state.is_some_and(TcpState::is_half_open)
+        && connection.state == TcpState::Established
+    {
+        quality.max_handshake_rtt = rtt;
+    }
+    info.tcp_quality += quality;
+
+    // the host may still be waiting for its name to be resolved
+    let address = get_address_to_lookup(key, info.traffic_direction);
+    if let Some((_, host)) = info_traffic.addresses_resolved.get(&address) {
+        if let Some(data_info_host) = info_traffic.hosts.get_mut(host) {
+            data_info_host.tcp_quality += quality;
+        }
+    } else {
+        *info_traffic
+            .tcp_quality_waiting_resolution
+            .entry(address)
+            .or_default() += quality;
+    }
+}
+
+/// Overrides the service of the connection with the one detected from its payload,
+/// moving the data exchanged so far (except for the current packet) to the detected service.
+///
+/// Returns the service of the connection.
+pub fn set_service_from_payload(
+    info_traffic: &mut InfoTraffic,
+    key: &AddressPortPair,
+    service: Service,
+    exchanged_bytes: u128,
+) -> Option<Service> {
+    let info = info_traffic.map.get_mut(key)?;
+    if info.service_from_payload || info.transmitted_packets > MAX_CLASSIFIED_PACKETS {
+        return Some(info.service);
+    }
+    let guessed_service = info.service;
+    info.service = service;
+    info.service_from_payload = true;
+
+    let mut previous_data = info.clone();
+    previous_data.transmitted_packets -= 1;
+    previous_data.transmitted_bytes -= exchanged_bytes;
+    if guessed_service != service && previous_data.transmitted_packets > 0 {
+        if let Some(data_info) = info_traffic.services.get_mut(&guessed_service) {
+            data_info.remove_connection(&previous_data);
+            if data_info.tot_packets() == 0 {
+                info_traffic.services.remove(&guessed_service);
+            }
+        }
+        info_traffic
+            .services
+            .entry(service)
+            .or_default()
+            .add_connection(&previous_data);
+    }
+    Some(service)
+}
+
 pub fn reverse_dns_lookup(
     info_traffic: &Arc<Mutex<InfoTraffic>>,
     key: &AddressPortPair,
@@ -304,12 +502,36 @@ pub fn reverse_dns_lookup(
     my_device: &MyDevice,
     country_db_reader: &Arc<MmdbReader>,
     asn_db_reader: &Arc<MmdbReader>,
+    lookup: impl FnOnce(&str) -> Option<String>,
 ) {
     let address_to_lookup = get_address_to_lookup(key, traffic_direction);
     let my_interface_addresses = my_device.addresses.lock().unwrap().clone();
 
-    // perform rDNS lookup
-    let lookup_result = lookup_addr(&address_to_lookup.parse().unwrap());
+    // prefer the name announced by the host if it belongs to the local network (e.g., via DHCP or mDNS),
+    // then the domain name queried via DNS if observed on the wire, otherwise perform rDNS lookup
+    // (if the lookup is disabled or fails, the host is identified by its address)
+    let observed_name = || {
+        let info_traffic = info_traffic.lock().unwrap();
+        info_traffic
+            .lan_names
+            .get(&address_to_lookup)
+            .cloned()
+            .or_else(|| {
+                info_traffic
+                    .dns_names
+                    .get(&address_to_lookup)
+                    .map(|name| (name.clone(), NameSource::Traffic))
+            })
+    };
+    let name = observed_name().or_else(|| {
+        let looked_up = lookup(&address_to_lookup).map(|name| (name, NameSource::ReverseDns));
+        // a name may have been observed while waiting for the lookup (e.g., in a TLS handshake)
+        observed_name().or(looked_up)
+    });
+    let (r_dns, name_source) = match name {
+        Some((name, source)) => (name, Some(source)),
+        None => (address_to_lookup.clone(), None),
+    };
 
     // get new host info and build the new host
     let traffic_type = get_traffic_type(
@@ -321,17 +543,14 @@ pub fn reverse_dns_lookup(
     let is_local = is_local_connection(&address_to_lookup, &my_interface_addresses);
     let country = get_country(&address_to_lookup, country_db_reader);
     let asn = get_asn(&address_to_lookup, asn_db_reader);
-    let r_dns = if let Ok(result) = lookup_result {
-        if result.is_empty() {
-            address_to_lookup.clone()
-        } else {
-            result
-        }
+    // names of the hosts of the local network are kept in full
+    let domain = if name_source.is_some_and(|source| source.is_lan()) {
+        r_dns.clone()
     } else {
-        address_to_lookup.clone()
+        get_domain_from_r_dns(r_dns.clone())
     };
     let new_host = Host {
-        domain: get_domain_from_r_dns(r_dns.clone()),
+        domain,
         asn,
         country,
     };
@@ -342,7 +561,16 @@ pub fn reverse_dns_lookup(
         .addresses_waiting_resolution
         .remove(&address_to_lookup)
         .unwrap_or_default();
+    let tcp_quality = info_traffic_lock
+        .tcp_quality_waiting_resolution
+        .remove(&address_to_lookup)
+        .unwrap_or_default();
     // insert the newly resolved host in the collections, with the data it exchanged so far
+    if let Some(name_source) = name_source {
+        info_traffic_lock
+            .name_sources
+            .insert(address_to_lookup.clone(), name_source);
+    }
     info_traffic_lock
         .addresses_resolved
         .insert(address_to_lookup, (r_dns, new_host.clone()));
@@ -351,6 +579,7 @@ pub fn reverse_dns_lookup(
         .entry(new_host.clone())
         .and_modify(|data_info_host| {
             data_info_host.data_info += other_data;
+            data_info_host.tcp_quality += tcp_quality;
         })
         .or_insert_with(|| DataInfoHost {
             data_info: other_data,
@@ -358,6 +587,7 @@ pub fn reverse_dns_lookup(
             is_loopback,
             is_local,
             traffic_type,
+            tcp_quality,
         });
     // check if the newly resolved host was featured in the favorites (possible in case of already existing host)
     if info_traffic_lock.favorite_hosts.contains(&new_host) {
@@ -368,13 +598,21 @@ pub fn reverse_dns_lookup(
 }
 
 /// Returns the traffic direction observed (incoming or outgoing)
+///
+/// The direction reported by the link layer (Linux cooked captures), if any, is authoritative;
+/// otherwise it's inferred from the addresses of the capturing interface.
 fn get_traffic_direction(
     source_ip: &String,
     destination_ip: &String,
     source_port: Option<u16>,
     dest_port: Option<u16>,
     my_interface_addresses: &[Address],
+    link_direction: Option<TrafficDirection>,
 ) -> TrafficDirection {
+    if let Some(traffic_direction) = link_direction {
+        return traffic_direction;
+    }
+
     let my_interface_addresses_string: Vec<String> = my_interface_addresses
         .iter()
         .map(|address| address.addr.to_string())
@@ -564,7 +802,7 @@ pub fn is_my_address(local_address: &String, my_interface_addresses: &Vec<Addres
 }
 
 /// Converts a MAC address in its hexadecimal form
-fn mac_from_dec_to_hex(mac_dec: [u8; 6]) -> String {
+pub fn mac_from_dec_to_hex(mac_dec: [u8; 6]) -> String {
     let mut mac_hex = String::new();
     for n in &mac_dec {
         mac_hex.push_str(&format!("{n:02x}:"));
@@ -582,21 +820,46 @@ pub fn get_address_to_lookup(key: &AddressPortPair, traffic_direction: TrafficDi
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
     use std::net::IpAddr;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
+    use chrono::{Local, TimeDelta};
+    use etherparse::LaxPacketHeaders;
     use pcap::Address;
+    use serial_test::serial;
 
+    use crate::countries::types::country::Country;
+    use crate::mmdb::asn::ASN_MMDB;
+    use crate::mmdb::country::COUNTRY_MMDB;
+    use crate::mmdb::types::mmdb_reader::MmdbReader;
     use crate::networking::manage_packets::{
-        get_service, get_traffic_direction, get_traffic_type, is_local_connection,
-        mac_from_dec_to_hex,
+        analyze_headers, get_service, get_service_by_name, get_traffic_direction, get_traffic_type,
+        is_local_connection, mac_from_dec_to_hex, reverse_dns_lookup, set_service_from_payload,
+        update_tcp_connection,
     };
     use crate::networking::types::address_port_pair::AddressPortPair;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::data_info::DataInfo;
+    use crate::networking::types::data_info_host::DataInfoHost;
+    use crate::networking::types::host::Host;
+    use crate::networking::types::icmp_type::IcmpType;
+    use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+    use crate::networking::types::my_device::MyDevice;
+    use crate::networking::types::my_link_type::MyLinkType;
+    use crate::networking::types::name_source::NameSource;
+    use crate::networking::types::packet_filters_fields::PacketFiltersFields;
     use crate::networking::types::service_query::ServiceQuery;
+    use crate::networking::types::tcp_connection::{
+        TcpConnection, TcpFlags, TcpSegment, TcpState, TcpStats,
+    };
+    use crate::networking::types::tcp_quality::TcpQuality;
     use crate::networking::types::traffic_direction::TrafficDirection;
     use crate::networking::types::traffic_type::TrafficType;
-    use crate::Protocol;
+    use crate::networking::user_services::load_user_services;
     use crate::Service;
+    use crate::{InfoTraffic, Protocol};
 
     include!(concat!(env!("OUT_DIR"), "/services.rs"));
 
@@ -729,6 +992,7 @@ fn traffic_direction_ipv4_test() {
             Some(99),
             Some(99),
             &address_vec,
+            None,
         );
         assert_eq!(result1, TrafficDirection::Outgoing);
         let result2 = get_traffic_direction(
@@ -737,6 +1001,7 @@ fn traffic_direction_ipv4_test() {
             Some(99),
             Some(99),
             &address_vec,
+            None,
         );
         assert_eq!(result2, TrafficDirection::Incoming);
         let result3 = get_traffic_direction(
@@ -745,6 +1010,7 @@ fn traffic_direction_ipv4_test() {
             Some(99),
             Some(99),
             &address_vec,
+            None,
         );
         assert_eq!(result3, TrafficDirection::Outgoing);
         let result4 = get_traffic_direction(
@@ -753,6 +1019,7 @@ fn traffic_direction_ipv4_test() {
             Some(99),
             Some(99),
             &address_vec,
+            None,
         );
         assert_eq!(result4, TrafficDirection::Incoming);
         let result4 = get_traffic_direction(
@@ -761,8 +1028,28 @@ fn traffic_direction_ipv4_test() {
             Some(99),
             Some(99),
             &address_vec,
+            None,
         );
         assert_eq!(result4, TrafficDirection::Outgoing);
+        // the direction reported by the link layer takes precedence
+        let result5 = get_traffic_direction(
+            &"172.20.10.9".to_string(),
+            &"99.88.77.00".to_string(),
+            Some(99),
+            Some(99),
+            &address_vec,
+            Some(TrafficDirection::Incoming),
+        );
+        assert_eq!(result5, TrafficDirection::Incoming);
+        let result6 = get_traffic_direction(
+            &"99.88.77.00".to_string(),
+            &"172.20.10.9".to_string(),
+            Some(99),
+            Some(99),
+            &[],
+            Some(TrafficDirection::Outgoing),
+        );
+        assert_eq!(result6, TrafficDirection::Outgoing);
     }
 
     #[test]
@@ -1231,7 +1518,7 @@ fn test_get_service_different_tcp_udp() {
                     Service::Name(match p {
                         Protocol::TCP => "mdns",
                         Protocol::UDP => "zeroconf",
-                        Protocol::ICMP => panic!(),
+                        Protocol::ICMP | Protocol::L2 => panic!(),
                     })
                 );
 
@@ -1241,7 +1528,7 @@ fn test_get_service_different_tcp_udp() {
                     match p {
                         Protocol::TCP => Service::Name("netstat"),
                         Protocol::UDP => Service::Unknown,
-                        Protocol::ICMP => panic!(),
+                        Protocol::ICMP | Protocol::L2 => panic!(),
                     }
                 );
 
@@ -1252,7 +1539,7 @@ fn test_get_service_different_tcp_udp() {
                     match p {
                         Protocol::TCP => Service::Unknown,
                         Protocol::UDP => Service::Name("murmur"),
-                        Protocol::ICMP => panic!(),
+                        Protocol::ICMP | Protocol::L2 => panic!(),
                     }
                 );
 
@@ -1301,6 +1588,55 @@ fn test_get_service_unknown() {
         }
     }
 
+    #[test]
+    #[serial] // needed to not collide with other tests loading user services
+    fn test_get_service_user_defined() {
+        let user_services_path = format!("{}/user_services.txt", env!("CARGO_MANIFEST_DIR"));
+        std::fs::write(
+            &user_services_path,
+            "vault\t7443/tcp\nhttps-internal\t8443/tcp\n",
+        )
+        .unwrap();
+        assert!(load_user_services(&user_services_path).is_empty());
+        std::fs::remove_file(&user_services_path).unwrap();
+
+        for d in [TrafficDirection::Incoming, TrafficDirection::Outgoing] {
+            // user services are preferred to unknown and built-in ones
+            for (p1, p2, service) in [
+                (Some(7443), Some(51000), "vault"),
+                (Some(51000), Some(7443), "vault"),
+                (Some(8443), Some(51000), "https-internal"),
+            ] {
+                let key = AddressPortPair::new(String::new(), p1, String::new(), p2, Protocol::TCP);
+                assert_eq!(get_service(&key, d), Service::Name(service));
+            }
+            // only for the given protocol
+            let key = AddressPortPair::new(
+                String::new(),
+                Some(7443),
+                String::new(),
+                Some(51000),
+                Protocol::UDP,
+            );
+            assert_eq!(get_service(&key, d), Service::Name("oracleas-https"));
+        }
+        assert_eq!(get_service_by_name("vault"), Some(Service::Name("vault")));
+
+        // an empty path removes the user services
+        assert!(load_user_services("").is_empty());
+        let key = AddressPortPair::new(
+            String::new(),
+            Some(7443),
+            String::new(),
+            Some(51000),
+            Protocol::TCP,
+        );
+        assert_eq!(
+            get_service(&key, TrafficDirection::Outgoing),
+            Service::Name("oracleas-https")
+        );
+    }
+
     #[test]
     fn test_all_services_map_key_and_values_are_valid() {
         assert_eq!(SERVICES.len(), 12066);
@@ -1647,4 +1983,395 @@ fn test_other_service_names() {
             &Service::Name("murmur")
         );
     }
+
+    #[test]
+    fn test_reverse_dns_lookup() {
+        let info_traffic = Arc::new(Mutex::new(InfoTraffic::new()));
+        let device = MyDevice {
+            name: "en0".to_string(),
+            desc: None,
+            addresses: Arc::new(Mutex::new(vec![Address {
+                addr: "192.168.1.10".parse().unwrap(),
+                netmask: Some("255.255.255.0".parse().unwrap()),
+                broadcast_addr: None,
+                dst_addr: None,
+            }])),
+            link_type: MyLinkType::default(),
+        };
+        let country_db_reader = Arc::new(MmdbReader::from(&String::new(), COUNTRY_MMDB));
+        let asn_db_reader = Arc::new(MmdbReader::from(&String::new(), ASN_MMDB));
+        let key = |address: &str| {
+            AddressPortPair::new(
+                "192.168.1.10".to_string(),
+                Some(50000),
+                address.to_string(),
+                Some(443),
+                Protocol::TCP,
+            )
+        };
+        info_traffic
+            .lock()
+            .unwrap()
+            .dns_names
+            .insert("140.82.121.4".to_string(), "github.com".to_string());
+        info_traffic.lock().unwrap().lan_names.insert(
+            "192.168.1.20".to_string(),
+            ("pixel-7.home.lan".to_string(), NameSource::Dhcp),
+        );
+
+        // lookup disabled: the host is identified by its address
+        reverse_dns_lookup(
+            &info_traffic,
+            &key("8.8.8.8"),
+            TrafficDirection::Outgoing,
+            &device,
+            &country_db_reader,
+            &asn_db_reader,
+            |_| None,
+        );
+        // names observed in DNS responses are preferred to the lookup result
+        reverse_dns_lookup(
+            &info_traffic,
+            &key("140.82.121.4"),
+            TrafficDirection::Outgoing,
+            &device,
+            &country_db_reader,
+            &asn_db_reader,
+            |_| Some("lb-140-82-121-4-fra.github.com".to_string()),
+        );
+        reverse_dns_lookup(
+            &info_traffic,
+            &key("1.1.1.1"),
+            TrafficDirection::Outgoing,
+            &device,
+            &country_db_reader,
+            &asn_db_reader,
+            |_| Some("one.one.one.one".to_string()),
+        );
+        // names announced by the hosts of the local network are preferred and kept in full
+        reverse_dns_lookup(
+            &info_traffic,
+            &key("192.168.1.20"),
+            TrafficDirection::Outgoing,
+            &device,
+            &country_db_reader,
+            &asn_db_reader,
+            |_| Some("192-168-1-20.isp.net".to_string()),
+        );
+
+        let info_traffic = info_traffic.lock().unwrap();
+        let resolved = |address: &str| {
+            let (r_dns, host) = info_traffic.addresses_resolved.get(address).unwrap();
+            (r_dns.as_str(), host.domain.as_str())
+        };
+        assert_eq!(resolved("8.8.8.8"), ("8.8.8.8", "8.8.8.8"));
+        assert_eq!(resolved("140.82.121.4"), ("github.com", "github.com"));
+        assert_eq!(resolved("1.1.1.1"), ("one.one.one.one", "one.one"));
+        assert_eq!(
+            resolved("192.168.1.20"),
+            ("pixel-7.home.lan", "pixel-7.home.lan")
+        );
+        assert_eq!(info_traffic.hosts.len(), 4);
+        assert_eq!(
+            info_traffic.name_sources,
+            HashMap::from([
+                ("140.82.121.4".to_string(), NameSource::Traffic),
+                ("1.1.1.1".to_string(), NameSource::ReverseDns),
+                ("192.168.1.20".to_string(), NameSource::Dhcp),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_update_tcp_connection() {
+        let mut info_traffic = InfoTraffic::new();
+        let client_key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(50000),
+            "140.82.121.4".to_string(),
+            Some(443),
+            Protocol::TCP,
+        );
+        let server_key = client_key.reversed();
+        let flags = |syn, ack, fin, rst| TcpSegment {
+            flags: TcpFlags { syn, ack, fin, rst },
+            ..TcpSegment::default()
+        };
+        let now = Local::now();
+        let send = |info_traffic: &mut InfoTraffic, key: &AddressPortPair, tcp_segment| {
+            info_traffic.map.entry(key.clone()).or_default();
+            update_tcp_connection(info_traffic, key, &tcp_segment, now);
+        };
+
+        send(
+            &mut info_traffic,
+            &client_key,
+            flags(true, false, false, false),
+        );
+        send(
+            &mut info_traffic,
+            &server_key,
+            flags(true, true, false, false),
+        );
+        send(
+            &mut info_traffic,
+            &client_key,
+            flags(false, true, false, false),
+        );
+        send(
+            &mut info_traffic,
+            &client_key,
+            flags(false, true, true, false),
+        );
+        send(
+            &mut info_traffic,
+            &server_key,
+            flags(false, true, false, true),
+        );
+
+        // the two directions share the same lifecycle
+        let connection = TcpConnection {
+            state: TcpState::Reset,
+            syn_at: Some(now),
+            opened_at: Some(now),
+            closed_at: Some(now),
+            closed_by: Some("140.82.121.4:443".to_string()),
+        };
+        assert_eq!(
+            info_traffic.map[&client_key].tcp_connection,
+            Some(connection.clone())
+        );
+        assert_eq!(
+            info_traffic.map[&server_key].tcp_connection,
+            Some(connection)
+        );
+
+        // a connection request never answered, and one rejected
+        let mut other_key = client_key.clone();
+        other_key.port1 = Some(50001);
+        send(
+            &mut info_traffic,
+            &other_key,
+            flags(true, false, false, false),
+        );
+        other_key.port1 = Some(50002);
+        send(
+            &mut info_traffic,
+            &other_key,
+            flags(true, false, false, false),
+        );
+        send(
+            &mut info_traffic,
+            &other_key.reversed(),
+            flags(false, true, false, true),
+        );
+        assert_eq!(
+            info_traffic.map[&other_key.reversed()]
+                .tcp_connection
+                .as_ref()
+                .map(|connection| connection.state),
+            Some(TcpState::Refused)
+        );
+
+        assert_eq!(
+            info_traffic.tcp_stats,
+            TcpStats {
+                failed_handshakes: 1,
+                resets: 1,
+                half_open: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_update_tcp_quality() {
+        let mut info_traffic = InfoTraffic::new();
+        let client_key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(50000),
+            "140.82.121.4".to_string(),
+            Some(443),
+            Protocol::TCP,
+        );
+        let server_key = client_key.reversed();
+        let start = Local::now();
+        let send = |info_traffic: &mut InfoTraffic,
+                    key: &AddressPortPair,
+                    flags: &str,
+                    sequence_number: u32,
+                    payload_len: u32,
+                    millis: i64| {
+            let traffic_direction = if key.eq(&client_key) {
+                TrafficDirection::Outgoing
+            } else {
+                TrafficDirection::Incoming
+            };
+            info_traffic
+                .map
+                .entry(key.clone())
+                .or_insert_with(|| InfoAddressPortPair {
+                    traffic_direction,
+                    ..InfoAddressPortPair::default()
+                });
+            let tcp_segment = TcpSegment {
+                flags: TcpFlags {
+                    syn: flags.contains('S'),
+                    ack: flags.contains('A'),
+                    fin: false,
+                    rst: false,
+                },
+                sequence_number,
+                acknowledgment_number: 1,
+                window_size: 1000,
+                payload_len,
+            };
+            let timestamp = start + TimeDelta::milliseconds(millis);
+            update_tcp_connection(info_traffic, key, &tcp_segment, timestamp);
+        };
+
+        // handshake completed in 40 ms
+        send(&mut info_traffic, &client_key, "S", 100, 0, 0);
+        send(&mut info_traffic, &server_key, "SA", 500, 0, 20);
+        send(&mut info_traffic, &client_key, "A", 101, 0, 40);
+        // the second data segment is retransmitted
+        send(&mut info_traffic, &client_key, "A", 101, 100, 50);
+        send(&mut info_traffic, &client_key, "A", 201, 100, 60);
+        send(&mut info_traffic, &client_key, "A", 201, 100, 400);
+
+        let expected = TcpQuality {
+            segments: 6,
+            retransmissions: 1,
+            max_handshake_rtt: Some(Duration::from_millis(40)),
+            ..TcpQuality::default()
+        };
+        assert_eq!(
+            info_traffic.map[&client_key]
+                .tcp_connection
+                .as_ref()
+                .and_then(TcpConnection::handshake_rtt),
+            Some(Duration::from_millis(40))
+        );
+        // the server sent its SYN-ACK only
+        assert_eq!(info_traffic.map[&server_key].tcp_quality.segments, 1);
+        assert_eq!(
+            info_traffic.map[&client_key].tcp_quality,
+            TcpQuality {
+                segments: 5,
+                ..expected
+            }
+        );
+
+        // the remote host is not resolved yet...
+        assert_eq!(
+            info_traffic.tcp_quality_waiting_resolution["140.82.121.4"],
+            expected
+        );
+        // ...then its metrics are aggregated with the host ones
+        let host = Host {
+            domain: "github.com".to_string(),
+            asn: Asn::default(),
+            country: Country::ZZ,
+        };
+        info_traffic.addresses_resolved.insert(
+            "140.82.121.4".to_string(),
+            ("lb-140-82-121-4.github.com".to_string(), host.clone()),
+        );
+        info_traffic
+            .hosts
+            .insert(host.clone(), DataInfoHost::default());
+        send(&mut info_traffic, &client_key, "A", 301, 100, 410);
+        assert_eq!(info_traffic.hosts[&host].tcp_quality.segments, 1);
+    }
+
+    #[test]
+    fn test_set_service_from_payload() {
+        let mut info_traffic = InfoTraffic::new();
+        let key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(50000),
+            "10.0.0.5".to_string(),
+            Some(2222),
+            Protocol::TCP,
+        );
+        // three packets guessed as "EtherNetIP-1", the third carrying the SSH banner
+        info_traffic.map.insert(
+            key.clone(),
+            InfoAddressPortPair {
+                transmitted_bytes: 200,
+                transmitted_packets: 3,
+                service: Service::Name("EtherNetIP-1"),
+                traffic_direction: TrafficDirection::Outgoing,
+                ..InfoAddressPortPair::default()
+            },
+        );
+        info_traffic.services.insert(
+            Service::Name("EtherNetIP-1"),
+            DataInfo::new_for_tests(0, 2, 0, 120),
+        );
+
+        assert_eq!(
+            set_service_from_payload(&mut info_traffic, &key, Service::Name("ssh"), 80),
+            Some(Service::Name("ssh"))
+        );
+        let info = info_traffic.map.get(&key).unwrap();
+        assert_eq!(info.service, Service::Name("ssh"));
+        assert!(info.service_from_payload);
+        // data exchanged before the current packet moved to the detected service
+        assert!(!info_traffic
+            .services
+            .contains_key(&Service::Name("EtherNetIP-1")));
+        let ssh_data = info_traffic.services.get(&Service::Name("ssh")).unwrap();
+        assert_eq!(ssh_data.outgoing_packets(), 2);
+        assert_eq!(ssh_data.outgoing_bytes(), 120);
+
+        // the detected service is not overridden by later packets
+        assert_eq!(
+            set_service_from_payload(&mut info_traffic, &key, Service::Name("https"), 80),
+            Some(Service::Name("ssh"))
+        );
+        // connections not in the map are ignored
+        let other_key = AddressPortPair::new(
+            "10.0.0.5".to_string(),
+            Some(2222),
+            "192.168.1.10".to_string(),
+            Some(50000),
+            Protocol::TCP,
+        );
+        assert_eq!(
+            set_service_from_payload(&mut info_traffic, &other_key, Service::Name("ssh"), 80),
+            None
+        );
+    }
+
+    #[test]
+    fn test_analyze_headers_same_packet_from_two_adapters() {
+        let builder =
+            etherparse::PacketBuilder::ipv4([10, 0, 0, 2], [10, 0, 0, 1], 64).udp(51820, 51820);
+        let mut packet = Vec::new();
+        builder.write(&mut packet, &[0; 32]).unwrap();
+        let key = |interface: Option<&String>| {
+            analyze_headers(
+                LaxPacketHeaders::from_ip(&packet).unwrap(),
+                interface,
+                &mut (None, None),
+                &mut 0,
+                &mut IcmpType::default(),
+                &mut None,
+                &mut PacketFiltersFields::default(),
+            )
+            .unwrap()
+        };
+
+        // the same traffic seen on two adapters belongs to distinct connections
+        let eth0 = key(Some(&"eth0".to_string()));
+        let wg0 = key(Some(&"wg0".to_string()));
+        assert_ne!(eth0, wg0);
+        assert_eq!(eth0.interface.as_deref(), Some("eth0"));
+        assert_eq!(wg0.interface.as_deref(), Some("wg0"));
+        assert_eq!(eth0.clone().with_interface(None), wg0.with_interface(None));
+        assert_eq!(eth0.reversed().interface.as_deref(), Some("eth0"));
+
+        // packets of capture files are not associated with any adapter
+        assert_eq!(key(None).interface, None);
+    }
 }
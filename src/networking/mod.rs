@@ -1,2 +1,15 @@
+pub mod lan_devices;
+pub mod lan_names;
+pub mod linux_cooked;
 pub mod manage_packets;
+pub mod non_ip;
+pub mod packet_dissector;
+pub mod passive_dns;
+pub mod payload_classifier;
+pub mod pcap_writer;
+pub mod pcapng_writer;
+pub mod program_lookup;
+pub mod reverse_dns_resolver;
+pub mod server_name;
 pub mod types;
+pub mod user_services;
@@ -0,0 +1,212 @@
+//! Module in charge of the frames not carrying IP packets (e.g., ARP, LLDP, STP, EAPOL),
+//! which are aggregated by protocol and pair of MAC addresses instead of being discarded.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use etherparse::{LaxPacketHeaders, LaxPayloadSlice, VlanHeader};
+use pcap::Address;
+
+use crate::networking::manage_packets::mac_from_dec_to_hex;
+use crate::networking::types::l2_pair::L2Pair;
+use crate::networking::types::l2_protocol::L2Protocol;
+use crate::networking::types::traffic_direction::TrafficDirection;
+
+/// Length of the Ethernet II header
+const ETHERNET_HEADER_LEN: u128 = 14;
+/// Beginning of ARP messages for IPv4 over Ethernet (hardware type, protocol type, lengths)
+const ARP_IPV4_OVER_ETHERNET: [u8; 6] = [0, 1, 0x08, 0x00, 6, 4];
+
+/// Information about a frame not carrying an IP packet
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonIpFrame {
+    /// MAC addresses and protocol of the frame
+    pub key: L2Pair,
+    /// Length of the frame
+    pub exchanged_bytes: u128,
+    /// Address of the sender of ARP messages
+    pub arp_sender: Option<IpAddr>,
+}
+
+/// Returns the information about the frame if it's an Ethernet frame not carrying an IP packet
+pub fn get_non_ip_frame(headers: &LaxPacketHeaders) -> Option<NonIpFrame> {
+    if headers.net.is_some() {
+        return None;
+    }
+    let link_header = headers.link.as_ref()?;
+    let LaxPayloadSlice::Ether(ether_payload) = &headers.payload else {
+        return None;
+    };
+    let payload = ether_payload.payload;
+    let protocol = L2Protocol::from_ether_type(ether_payload.ether_type.0, payload)?;
+
+    let vlan_len = headers.vlan.as_ref().map_or(0, VlanHeader::header_len);
+    let arp_sender = if protocol == L2Protocol::ARP && payload.starts_with(&ARP_IPV4_OVER_ETHERNET)
+    {
+        payload
+            .get(14..18)
+            .map(|a| IpAddr::V4(Ipv4Addr::new(a[0], a[1], a[2], a[3])))
+    } else {
+        None
+    };
+
+    Some(NonIpFrame {
+        key: L2Pair {
+            mac_address1: mac_from_dec_to_hex(link_header.source),
+            mac_address2: mac_from_dec_to_hex(link_header.destination),
+            protocol,
+        },
+        exchanged_bytes: ETHERNET_HEADER_LEN + (vlan_len + payload.len()) as u128,
+        arp_sender,
+    })
+}
+
+/// Determines if the frame is outgoing or incoming.
+///
+/// A frame is outgoing if it's sent from the MAC address of the adapter (when known),
+/// or if it's an ARP message whose sender is one of the adapter's addresses.
+pub fn get_non_ip_traffic_direction(
+    frame: &NonIpFrame,
+    my_interface_addresses: &[Address],
+    local_mac_address: Option<&str>,
+) -> TrafficDirection {
+    let is_local_mac = local_mac_address.is_some_and(|mac| frame.key.mac_address1.eq(mac));
+    let is_local_arp_sender = frame
+        .arp_sender
+        .is_some_and(|sender| my_interface_addresses.iter().any(|a| a.addr == sender));
+    if is_local_mac || is_local_arp_sender {
+        TrafficDirection::Outgoing
+    } else {
+        TrafficDirection::Incoming
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCAL_MAC: [u8; 6] = [0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+    const BROADCAST_MAC: [u8; 6] = [0xff; 6];
+
+    fn frame(
+        source: [u8; 6],
+        destination: [u8; 6],
+        ether_type: [u8; 2],
+        payload: &[u8],
+    ) -> Vec<u8> {
+        let mut frame = destination.to_vec();
+        frame.extend_from_slice(&source);
+        frame.extend_from_slice(&ether_type);
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    fn arp_request(sender: [u8; 4]) -> Vec<u8> {
+        let mut arp = ARP_IPV4_OVER_ETHERNET.to_vec();
+        // operation (request), sender MAC and IP, target MAC and IP
+        arp.extend_from_slice(&[0, 1]);
+        arp.extend_from_slice(&LOCAL_MAC);
+        arp.extend_from_slice(&sender);
+        arp.extend_from_slice(&[0; 6]);
+        arp.extend_from_slice(&[192, 168, 1, 1]);
+        arp
+    }
+
+    fn addresses() -> Vec<Address> {
+        vec![Address {
+            addr: "192.168.1.10".parse().unwrap(),
+            netmask: None,
+            broadcast_addr: None,
+            dst_addr: None,
+        }]
+    }
+
+    #[test]
+    fn test_get_non_ip_frame_arp() {
+        let packet = frame(
+            LOCAL_MAC,
+            BROADCAST_MAC,
+            [0x08, 0x06],
+            &arp_request([192, 168, 1, 10]),
+        );
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(
+            get_non_ip_frame(&headers),
+            Some(NonIpFrame {
+                key: L2Pair {
+                    mac_address1: "aa:bb:cc:dd:ee:ff".to_string(),
+                    mac_address2: "ff:ff:ff:ff:ff:ff".to_string(),
+                    protocol: L2Protocol::ARP,
+                },
+                exchanged_bytes: 42,
+                arp_sender: Some("192.168.1.10".parse().unwrap()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_non_ip_frame_stp() {
+        let mut payload = vec![0x42, 0x42, 0x03];
+        payload.extend_from_slice(&[0; 35]);
+        let packet = frame(
+            [1, 2, 3, 4, 5, 6],
+            [1, 0x80, 0xc2, 0, 0, 0],
+            [0, 38],
+            &payload,
+        );
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        let non_ip_frame = get_non_ip_frame(&headers).unwrap();
+        assert_eq!(non_ip_frame.key.protocol, L2Protocol::STP);
+        assert_eq!(non_ip_frame.key.mac_address2, "01:80:c2:00:00:00");
+        assert_eq!(non_ip_frame.exchanged_bytes, 52);
+        assert_eq!(non_ip_frame.arp_sender, None);
+    }
+
+    #[test]
+    fn test_get_non_ip_frame_ip() {
+        let builder = etherparse::PacketBuilder::ethernet2(LOCAL_MAC, BROADCAST_MAC)
+            .ipv4([192, 168, 1, 10], [192, 168, 1, 255], 64)
+            .udp(137, 137);
+        let mut packet = Vec::new();
+        builder.write(&mut packet, &[1, 2, 3]).unwrap();
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        assert_eq!(get_non_ip_frame(&headers), None);
+    }
+
+    #[test]
+    fn test_get_non_ip_traffic_direction() {
+        let packet = frame(
+            LOCAL_MAC,
+            BROADCAST_MAC,
+            [0x08, 0x06],
+            &arp_request([192, 168, 1, 10]),
+        );
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        let arp_frame = get_non_ip_frame(&headers).unwrap();
+        assert_eq!(
+            get_non_ip_traffic_direction(&arp_frame, &addresses(), None),
+            TrafficDirection::Outgoing
+        );
+        assert_eq!(
+            get_non_ip_traffic_direction(&arp_frame, &[], None),
+            TrafficDirection::Incoming
+        );
+
+        let packet = frame(
+            LOCAL_MAC,
+            [1, 0x80, 0xc2, 0, 0, 0x0e],
+            [0x88, 0xcc],
+            &[0; 30],
+        );
+        let headers = LaxPacketHeaders::from_ethernet(&packet).unwrap();
+        let lldp_frame = get_non_ip_frame(&headers).unwrap();
+        assert_eq!(lldp_frame.key.protocol, L2Protocol::LLDP);
+        assert_eq!(
+            get_non_ip_traffic_direction(&lldp_frame, &addresses(), Some("aa:bb:cc:dd:ee:ff")),
+            TrafficDirection::Outgoing
+        );
+        assert_eq!(
+            get_non_ip_traffic_direction(&lldp_frame, &addresses(), Some("11:22:33:44:55:66")),
+            TrafficDirection::Incoming
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Module containing functions to describe the headers and the payload of the buffered packets,
+//! to allow inspecting them.
+
+use std::fmt::Write;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use etherparse::{LaxPacketHeaders, NetHeaders, TransportHeader, VlanHeader};
+
+use crate::networking::linux_cooked::LinuxCookedHeader;
+use crate::networking::manage_packets::mac_from_dec_to_hex;
+use crate::networking::types::icmp_type::{IcmpTypeV4, IcmpTypeV6};
+use crate::networking::types::packet_buffer::CapturedPacket;
+use crate::secondary_threads::parse_packets::get_sniffable_headers;
+
+/// Number of bytes shown in each line of the hex dump
+const BYTES_PER_LINE: usize = 16;
+
+/// Header of a protocol layer, with its fields
+#[derive(Debug, PartialEq, Eq)]
+pub struct PacketLayer {
+    pub name: String,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+/// Describes the headers of the packet, from the link layer to the transport layer
+pub fn get_packet_layers(packet: &CapturedPacket) -> Vec<PacketLayer> {
+    let mut layers = Vec::new();
+    let Ok((headers, cooked_header)) = get_sniffable_headers(&packet.data, packet.link_type) else {
+        return layers;
+    };
+    if let Some(layer) = get_link_layer(&headers, cooked_header) {
+        layers.push(layer);
+    }
+    layers.extend(get_vlan_layers(&headers));
+    if let Some(layer) = get_network_layer(&headers) {
+        layers.push(layer);
+    }
+    if let Some(layer) = get_transport_layer(&headers) {
+        layers.push(layer);
+    }
+    layers
+}
+
+/// Returns the payload carried by the packet (empty if the headers can't be parsed)
+pub fn get_packet_payload(packet: &CapturedPacket) -> &[u8] {
+    get_sniffable_headers(&packet.data, packet.link_type)
+        .map_or(&[], |(headers, _)| headers.payload.slice())
+}
+
+/// Short description of the packet, to be shown in the packet list
+pub fn get_packet_summary(packet: &CapturedPacket) -> String {
+    let Ok((headers, _)) = get_sniffable_headers(&packet.data, packet.link_type) else {
+        return "-".to_string();
+    };
+    match headers.transport {
+        Some(TransportHeader::Tcp(tcp_header)) => {
+            let flags = [
+                (tcp_header.syn, "SYN"),
+                (tcp_header.fin, "FIN"),
+                (tcp_header.rst, "RST"),
+                (tcp_header.psh, "PSH"),
+                (tcp_header.ack, "ACK"),
+                (tcp_header.urg, "URG"),
+            ]
+            .iter()
+            .filter(|(is_set, _)| *is_set)
+            .map(|(_, flag)| *flag)
+            .collect::<Vec<_>>()
+            .join(", ");
+            format!("[{flags}]")
+        }
+        Some(TransportHeader::Udp(_)) => "UDP".to_string(),
+        Some(TransportHeader::Icmpv4(icmpv4_header)) => {
+            IcmpTypeV4::from_etherparse(&icmpv4_header.icmp_type).to_string()
+        }
+        Some(TransportHeader::Icmpv6(icmpv6_header)) => {
+            IcmpTypeV6::from_etherparse(&icmpv6_header.icmp_type).to_string()
+        }
+        None => "-".to_string(),
+    }
+}
+
+/// Formats the bytes as lines of offset, hexadecimal values, and printable ASCII characters
+pub fn hex_dump(data: &[u8]) -> String {
+    let mut dump = String::new();
+    for (i, line) in data.chunks(BYTES_PER_LINE).enumerate() {
+        let _ = write!(dump, "{:04x}  ", i * BYTES_PER_LINE);
+        for j in 0..BYTES_PER_LINE {
+            match line.get(j) {
+                Some(byte) => {
+                    let _ = write!(dump, "{byte:02x} ");
+                }
+                None => dump.push_str("   "),
+            }
+            if j == BYTES_PER_LINE / 2 - 1 {
+                dump.push(' ');
+            }
+        }
+        dump.push(' ');
+        for byte in line {
+            dump.push(if byte.is_ascii_graphic() || *byte == b' ' {
+                char::from(*byte)
+            } else {
+                '.'
+            });
+        }
+        dump.push('\n');
+    }
+    dump.pop();
+    dump
+}
+
+fn get_link_layer(
+    headers: &LaxPacketHeaders,
+    cooked_header: Option<LinuxCookedHeader>,
+) -> Option<PacketLayer> {
+    if let Some(ethernet_header) = &headers.link {
+        return Some(PacketLayer {
+            name: "Ethernet II".to_string(),
+            fields: vec![
+                ("Source", mac_from_dec_to_hex(ethernet_header.source)),
+                (
+                    "Destination",
+                    mac_from_dec_to_hex(ethernet_header.destination),
+                ),
+                (
+                    "EtherType",
+                    format!("{:#06x}", ethernet_header.ether_type.0),
+                ),
+            ],
+        });
+    }
+    let cooked_header = cooked_header?;
+    let mut fields = vec![("Packet type", format!("{:?}", cooked_header.packet_type))];
+    if let Some(interface_index) = cooked_header.interface_index {
+        fields.push(("Interface index", interface_index.to_string()));
+    }
+    Some(PacketLayer {
+        name: "Linux cooked capture".to_string(),
+        fields,
+    })
+}
+
+fn get_vlan_layers(headers: &LaxPacketHeaders) -> Vec<PacketLayer> {
+    let vlan_headers = match &headers.vlan {
+        Some(VlanHeader::Single(vlan_header)) => vec![vlan_header],
+        Some(VlanHeader::Double(double_vlan_header)) => {
+            vec![&double_vlan_header.outer, &double_vlan_header.inner]
+        }
+        None => Vec::new(),
+    };
+    vlan_headers
+        .into_iter()
+        .map(|vlan_header| PacketLayer {
+            name: "802.1Q VLAN".to_string(),
+            fields: vec![
+                ("ID", vlan_header.vlan_id.value().to_string()),
+                ("EtherType", format!("{:#06x}", vlan_header.ether_type.0)),
+            ],
+        })
+        .collect()
+}
+
+fn get_network_layer(headers: &LaxPacketHeaders) -> Option<PacketLayer> {
+    match &headers.net {
+        Some(NetHeaders::Ipv4(ipv4_header, _)) => Some(PacketLayer {
+            name: "IPv4".to_string(),
+            fields: vec![
+                ("Source", Ipv4Addr::from(ipv4_header.source).to_string()),
+                (
+                    "Destination",
+                    Ipv4Addr::from(ipv4_header.destination).to_string(),
+                ),
+                ("Total length", ipv4_header.total_len.to_string()),
+                (
+                    "Identification",
+                    format!("{:#06x}", ipv4_header.identification),
+                ),
+                ("TTL", ipv4_header.time_to_live.to_string()),
+                ("Protocol", ipv4_header.protocol.0.to_string()),
+            ],
+        }),
+        Some(NetHeaders::Ipv6(ipv6_header, _)) => Some(PacketLayer {
+            name: "IPv6".to_string(),
+            fields: vec![
+                ("Source", Ipv6Addr::from(ipv6_header.source).to_string()),
+                (
+                    "Destination",
+                    Ipv6Addr::from(ipv6_header.destination).to_string(),
+                ),
+                ("Payload length", ipv6_header.payload_length.to_string()),
+                ("Hop limit", ipv6_header.hop_limit.to_string()),
+                ("Next header", ipv6_header.next_header.0.to_string()),
+            ],
+        }),
+        None => None,
+    }
+}
+
+fn get_transport_layer(headers: &LaxPacketHeaders) -> Option<PacketLayer> {
+    match &headers.transport {
+        Some(TransportHeader::Tcp(tcp_header)) => Some(PacketLayer {
+            name: "TCP".to_string(),
+            fields: vec![
+                ("Source port", tcp_header.source_port.to_string()),
+                ("Destination port", tcp_header.destination_port.to_string()),
+                ("Sequence number", tcp_header.sequence_number.to_string()),
+                (
+                    "Acknowledgment number",
+                    tcp_header.acknowledgment_number.to_string(),
+                ),
+                ("Window size", tcp_header.window_size.to_string()),
+                ("Header length", tcp_header.header_len().to_string()),
+            ],
+        }),
+        Some(TransportHeader::Udp(udp_header)) => Some(PacketLayer {
+            name: "UDP".to_string(),
+            fields: vec![
+                ("Source port", udp_header.source_port.to_string()),
+                ("Destination port", udp_header.destination_port.to_string()),
+                ("Length", udp_header.length.to_string()),
+            ],
+        }),
+        Some(TransportHeader::Icmpv4(icmpv4_header)) => Some(PacketLayer {
+            name: "ICMPv4".to_string(),
+            fields: vec![(
+                "Type",
+                IcmpTypeV4::from_etherparse(&icmpv4_header.icmp_type).to_string(),
+            )],
+        }),
+        Some(TransportHeader::Icmpv6(icmpv6_header)) => Some(PacketLayer {
+            name: "ICMPv6".to_string(),
+            fields: vec![(
+                "Type",
+                IcmpTypeV6::from_etherparse(&icmpv6_header.icmp_type).to_string(),
+            )],
+        }),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+    use pcap::Linktype;
+
+    use crate::networking::types::my_link_type::MyLinkType;
+
+    use super::*;
+
+    // Ethernet + IPv4 + TCP (SYN, ACK) carrying "hello"
+    const TCP_PACKET: [u8; 59] = [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0x08, 0x00, 0x45,
+        0x00, 0x00, 0x2d, 0x12, 0x34, 0x40, 0x00, 0x40, 0x06, 0x00, 0x00, 0xc0, 0xa8, 0x01, 0x0a,
+        0x8c, 0x52, 0x79, 0x04, 0xc3, 0x50, 0x01, 0xbb, 0x00, 0x00, 0x03, 0xe8, 0x00, 0x00, 0x00,
+        0x01, 0x50, 0x12, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, b'h', b'e', b'l', b'l', b'o',
+    ];
+
+    fn captured_packet(data: &[u8]) -> CapturedPacket {
+        CapturedPacket {
+            id: 1,
+            timestamp: Local::now(),
+            original_len: u32::try_from(data.len()).unwrap(),
+            data: data.to_vec(),
+            link_type: MyLinkType::Ethernet(Linktype::ETHERNET),
+        }
+    }
+
+    #[test]
+    fn test_get_packet_layers() {
+        let packet = captured_packet(&TCP_PACKET);
+        let layers = get_packet_layers(&packet);
+        assert_eq!(
+            layers
+                .iter()
+                .map(|layer| layer.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Ethernet II", "IPv4", "TCP"]
+        );
+        assert_eq!(
+            layers[0].fields[0],
+            ("Source", "66:77:88:99:aa:bb".to_string())
+        );
+        assert_eq!(
+            layers[1].fields[1],
+            ("Destination", "140.82.121.4".to_string())
+        );
+        assert_eq!(layers[1].fields[4], ("TTL", "64".to_string()));
+        assert_eq!(layers[2].fields[0], ("Source port", "50000".to_string()));
+        assert_eq!(layers[2].fields[2], ("Sequence number", "1000".to_string()));
+        assert_eq!(get_packet_summary(&packet), "[SYN, ACK]");
+        assert_eq!(get_packet_payload(&packet), b"hello");
+
+        // not parsable
+        let packet = captured_packet(&TCP_PACKET[..10]);
+        assert!(get_packet_layers(&packet).is_empty());
+        assert_eq!(get_packet_summary(&packet), "-");
+        assert!(get_packet_payload(&packet).is_empty());
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        assert_eq!(hex_dump(&[]), "");
+        assert_eq!(
+            hex_dump(b"GET / HTTP/1.1\r\nHost"),
+            "0000  47 45 54 20 2f 20 48 54  54 50 2f 31 2e 31 0d 0a  GET / HTTP/1.1..\n\
+             0010  48 6f 73 74                                       Host"
+        );
+    }
+}
@@ -0,0 +1,413 @@
+//! Module in charge of extracting domain names from the DNS responses observed on the wire.
+//!
+//! Names obtained this way are the ones actually queried by the local applications,
+//! and are often more meaningful than the PTR records returned by reverse DNS lookups.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use etherparse::{LaxPacketHeaders, TransportHeader};
+
+/// Port used by DNS servers
+const DNS_PORT: u16 = 53;
+/// Port used by mDNS responders
+const MDNS_PORT: u16 = 5353;
+/// Length of the DNS message header
+const HEADER_LEN: usize = 12;
+/// Maximum number of compression pointers followed when reading a name (to avoid loops)
+const MAX_POINTERS: usize = 16;
+/// Maximum length of a CNAME chain followed to find the name originally queried
+const MAX_CNAME_CHAIN: usize = 16;
+
+/// Class of the records of the Internet (the most significant bit is the mDNS unicast-response flag
+/// in questions, and the mDNS cache-flush flag in records)
+const CLASS_IN: u16 = 1;
+
+const TYPE_A: u16 = 1;
+const TYPE_CNAME: u16 = 5;
+const TYPE_AAAA: u16 = 28;
+
+/// Returns the addresses contained in the DNS response carried by the packet,
+/// each associated with the name originally queried for it.
+///
+/// mDNS responses are also considered, even if not solicited (i.e., announcements).
+///
+/// The returned list is empty if the packet is not a valid DNS response.
+pub fn get_dns_answers(headers: &LaxPacketHeaders) -> Vec<(String, String)> {
+    let message = match &headers.transport {
+        Some(TransportHeader::Udp(udp_header))
+            if udp_header.source_port == DNS_PORT || udp_header.source_port == MDNS_PORT =>
+        {
+            headers.payload.slice()
+        }
+        // over TCP, each DNS message is preceded by its length
+        Some(TransportHeader::Tcp(tcp_header)) if tcp_header.source_port == DNS_PORT => {
+            headers.payload.slice().get(2..).unwrap_or_default()
+        }
+        _ => return Vec::new(),
+    };
+
+    parse_dns_response(message)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(address, name)| (address.to_string(), name))
+        .collect()
+}
+
+/// Parses a DNS response, returning the resolved addresses with the corresponding queried names.
+///
+/// Returns None if the message is not a response or is malformed.
+pub fn parse_dns_response(message: &[u8]) -> Option<Vec<(IpAddr, String)>> {
+    let flags = read_u16(message, 2)?;
+    let is_response = flags & 0x8000 != 0;
+    let response_code = flags & 0x000F;
+    if !is_response || response_code != 0 {
+        return None;
+    }
+
+    let questions = read_u16(message, 4)?;
+    let records = [6, 8, 10]
+        .iter()
+        .map(|offset| read_u16(message, *offset).map(usize::from))
+        .sum::<Option<usize>>()?;
+
+    let mut offset = HEADER_LEN;
+    for _ in 0..questions {
+        // skip name, type, and class
+        offset = read_name(message, offset)?.1 + 4;
+    }
+
+    let mut addresses = Vec::new();
+    // alias associated with each canonical name
+    let mut aliases: HashMap<String, String> = HashMap::new();
+    for _ in 0..records {
+        let (name, after_name) = read_name(message, offset)?;
+        let record_type = read_u16(message, after_name)?;
+        let class = read_u16(message, after_name + 2)? & 0x7FFF;
+        let data_len = usize::from(read_u16(message, after_name + 8)?);
+        let data_offset = after_name + 10;
+        let data = message.get(data_offset..data_offset + data_len)?;
+        match record_type {
+            // records of other classes (e.g., CHAOS) don't carry Internet addresses
+            _ if class != CLASS_IN => {}
+            TYPE_A => {
+                let octets: [u8; 4] = data.try_into().ok()?;
+                addresses.push((IpAddr::V4(Ipv4Addr::from(octets)), name));
+            }
+            TYPE_AAAA => {
+                let octets: [u8; 16] = data.try_into().ok()?;
+                addresses.push((IpAddr::V6(Ipv6Addr::from(octets)), name));
+            }
+            TYPE_CNAME => {
+                let canonical_name = read_name(message, data_offset)?.0;
+                aliases.insert(canonical_name, name);
+            }
+            _ => {}
+        }
+        offset = data_offset + data_len;
+    }
+
+    Some(
+        addresses
+            .into_iter()
+            .filter(|(_, name)| !name.is_empty())
+            .map(|(address, name)| (address, get_queried_name(name, &aliases)))
+            .collect(),
+    )
+}
+
+/// Checks if the given message looks like a DNS query or response:
+/// a standard query with a single well-formed question of class IN.
+pub fn is_dns_message(message: &[u8]) -> bool {
+    let (Some(flags), Some(questions)) = (read_u16(message, 2), read_u16(message, 4)) else {
+        return false;
+    };
+    let opcode = (flags >> 11) & 0x000F;
+    let z = flags & 0x0040;
+    if opcode != 0 || z != 0 || questions != 1 {
+        return false;
+    }
+    read_name(message, HEADER_LEN)
+        .and_then(|(name, offset)| Some((name, read_u16(message, offset + 2)?)))
+        .is_some_and(|(name, class)| !name.is_empty() && class & 0x7FFF == CLASS_IN)
+}
+
+/// Follows the CNAME chain backwards, to find the name originally queried
+fn get_queried_name(mut name: String, aliases: &HashMap<String, String>) -> String {
+    for _ in 0..MAX_CNAME_CHAIN {
+        match aliases.get(&name) {
+            Some(alias) if alias.ne(&name) => name.clone_from(alias),
+            _ => break,
+        }
+    }
+    name
+}
+
+/// Reads a (possibly compressed) domain name starting at the given offset.
+///
+/// Returns the lowercase name and the offset right after the name in its original position.
+pub fn read_name(message: &[u8], mut offset: usize) -> Option<(String, usize)> {
+    let mut labels: Vec<String> = Vec::new();
+    let mut end_offset = None;
+    let mut pointers = 0;
+    loop {
+        let len = *message.get(offset)?;
+        match len & 0xC0 {
+            0x00 if len == 0 => {
+                let end = end_offset.unwrap_or(offset + 1);
+                return Some((labels.join("."), end));
+            }
+            0x00 => {
+                let label = message.get(offset + 1..offset + 1 + usize::from(len))?;
+                labels.push(String::from_utf8_lossy(label).to_lowercase());
+                offset += 1 + usize::from(len);
+            }
+            0xC0 => {
+                pointers += 1;
+                if pointers > MAX_POINTERS {
+                    return None;
+                }
+                end_offset.get_or_insert(offset + 2);
+                offset = usize::from(read_u16(message, offset)? & 0x3FFF);
+            }
+            _ => return None,
+        }
+    }
+}
+
+pub fn read_u16(message: &[u8], offset: usize) -> Option<u16> {
+    let bytes = message.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::*;
+
+    /// Encodes a domain name without compression
+    fn name(domain: &str) -> Vec<u8> {
+        let mut ret_val = Vec::new();
+        for label in domain.split('.') {
+            ret_val.push(u8::try_from(label.len()).unwrap());
+            ret_val.extend_from_slice(label.as_bytes());
+        }
+        ret_val.push(0);
+        ret_val
+    }
+
+    fn record(owner: &[u8], record_type: u16, data: &[u8]) -> Vec<u8> {
+        record_with_class(owner, record_type, CLASS_IN, data)
+    }
+
+    fn record_with_class(owner: &[u8], record_type: u16, class: u16, data: &[u8]) -> Vec<u8> {
+        let mut ret_val = owner.to_vec();
+        ret_val.extend_from_slice(&record_type.to_be_bytes());
+        ret_val.extend_from_slice(&class.to_be_bytes());
+        ret_val.extend_from_slice(&[0, 0, 0, 60]);
+        ret_val.extend_from_slice(&u16::try_from(data.len()).unwrap().to_be_bytes());
+        ret_val.extend_from_slice(data);
+        ret_val
+    }
+
+    fn response(flags: u16, questions: &[&str], answers: &[Vec<u8>]) -> Vec<u8> {
+        let mut ret_val = vec![0x12, 0x34];
+        ret_val.extend_from_slice(&flags.to_be_bytes());
+        ret_val.extend_from_slice(&u16::try_from(questions.len()).unwrap().to_be_bytes());
+        ret_val.extend_from_slice(&u16::try_from(answers.len()).unwrap().to_be_bytes());
+        ret_val.extend_from_slice(&[0, 0, 0, 0]);
+        for question in questions {
+            ret_val.extend_from_slice(&name(question));
+            ret_val.extend_from_slice(&[0, 1, 0, 1]);
+        }
+        for answer in answers {
+            ret_val.extend_from_slice(answer);
+        }
+        ret_val
+    }
+
+    #[test]
+    fn test_parse_a_and_aaaa_records() {
+        let message = response(
+            0x8180,
+            &["GitHub.com"],
+            &[
+                // compressed owner name pointing to the question
+                record(&[0xC0, 0x0C], TYPE_A, &[140, 82, 121, 4]),
+                record(
+                    &[0xC0, 0x0C],
+                    TYPE_AAAA,
+                    &[
+                        0x20, 0x01, 0x48, 0x60, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x88, 0x88,
+                    ],
+                ),
+            ],
+        );
+        assert_eq!(
+            parse_dns_response(&message),
+            Some(vec![
+                (
+                    "140.82.121.4".parse::<IpAddr>().unwrap(),
+                    "github.com".to_string()
+                ),
+                (
+                    "2001:4860::8888".parse::<IpAddr>().unwrap(),
+                    "github.com".to_string()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_cname_chain() {
+        let message = response(
+            0x8180,
+            &["www.sniffnet.net"],
+            &[
+                record(
+                    &name("www.sniffnet.net"),
+                    TYPE_CNAME,
+                    &name("sniffnet.cdn.net"),
+                ),
+                record(
+                    &name("sniffnet.cdn.net"),
+                    TYPE_CNAME,
+                    &name("edge-1.fra.cdn.net"),
+                ),
+                record(&name("edge-1.fra.cdn.net"), TYPE_A, &[13, 32, 1, 1]),
+            ],
+        );
+        assert_eq!(
+            parse_dns_response(&message),
+            Some(vec![(
+                "13.32.1.1".parse::<IpAddr>().unwrap(),
+                "www.sniffnet.net".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_parse_mdns_response_without_questions() {
+        let message = response(
+            0x8400,
+            &[],
+            &[record(&name("printer.local"), TYPE_A, &[192, 168, 1, 20])],
+        );
+        assert_eq!(
+            parse_dns_response(&message),
+            Some(vec![(
+                "192.168.1.20".parse::<IpAddr>().unwrap(),
+                "printer.local".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_ignore_queries_errors_and_malformed_messages() {
+        let answer = record(&[0xC0, 0x0C], TYPE_A, &[140, 82, 121, 4]);
+        // query
+        let message = response(0x0100, &["github.com"], std::slice::from_ref(&answer));
+        assert_eq!(parse_dns_response(&message), None);
+        // NXDOMAIN
+        let message = response(0x8183, &["github.com"], std::slice::from_ref(&answer));
+        assert_eq!(parse_dns_response(&message), None);
+        // truncated
+        let message = response(0x8180, &["github.com"], &[answer]);
+        assert_eq!(parse_dns_response(&message[..message.len() - 1]), None);
+        assert_eq!(parse_dns_response(&message[..5]), None);
+        // pointer loop
+        let message = response(0x8180, &[], &[record(&[0xC0, 0x0C], TYPE_A, &[1, 1, 1, 1])]);
+        assert_eq!(parse_dns_response(&message), None);
+    }
+
+    #[test]
+    fn test_is_dns_message() {
+        let query = response(0x0100, &["github.com"], &[]);
+        assert!(is_dns_message(&query));
+        let message = response(
+            0x8180,
+            &["github.com"],
+            &[record(&[0xC0, 0x0C], TYPE_A, &[140, 82, 121, 4])],
+        );
+        assert!(is_dns_message(&message));
+        // no questions
+        assert!(!is_dns_message(&response(0x8400, &[], &[])));
+        // truncated question
+        assert!(!is_dns_message(&query[..query.len() - 2]));
+        // random bytes
+        assert!(!is_dns_message(b"SSH-2.0-OpenSSH_9.6"));
+    }
+
+    #[test]
+    fn test_get_dns_answers_from_packet() {
+        let message = response(
+            0x8180,
+            &["github.com"],
+            &[record(&[0xC0, 0x0C], TYPE_A, &[140, 82, 121, 4])],
+        );
+        let packet = |source_port: u16| {
+            let builder = etherparse::PacketBuilder::ipv4([8, 8, 8, 8], [192, 168, 1, 10], 64)
+                .udp(source_port, 50000);
+            let mut packet = Vec::new();
+            builder.write(&mut packet, &message).unwrap();
+            packet
+        };
+
+        let dns_packet = packet(DNS_PORT);
+        let headers = LaxPacketHeaders::from_ip(&dns_packet).unwrap();
+        assert_eq!(
+            get_dns_answers(&headers),
+            vec![("140.82.121.4".to_string(), "github.com".to_string())]
+        );
+
+        // same payload, but not from a DNS server
+        let other_packet = packet(8080);
+        let headers = LaxPacketHeaders::from_ip(&other_packet).unwrap();
+        assert!(get_dns_answers(&headers).is_empty());
+    }
+
+    #[test]
+    fn test_get_dns_answers_from_mdns_announcement() {
+        // unsolicited response, with the cache-flush flag set in the class of the records
+        let message = response(
+            0x8400,
+            &[],
+            &[
+                record_with_class(
+                    &name("printer.local"),
+                    TYPE_A,
+                    0x8000 | CLASS_IN,
+                    &[192, 168, 1, 20],
+                ),
+                record_with_class(
+                    &name("printer.local"),
+                    TYPE_AAAA,
+                    0x8000 | CLASS_IN,
+                    &[
+                        0xFE, 0x80, 0, 0, 0, 0, 0, 0, 0x02, 0x11, 0x22, 0xFF, 0xFE, 0x33, 0x44,
+                        0x55,
+                    ],
+                ),
+                // records of other classes are ignored
+                record_with_class(&name("other.local"), TYPE_A, 3, &[192, 168, 1, 21]),
+            ],
+        );
+        let builder = etherparse::PacketBuilder::ipv4([192, 168, 1, 20], [224, 0, 0, 251], 255)
+            .udp(MDNS_PORT, MDNS_PORT);
+        let mut packet = Vec::new();
+        builder.write(&mut packet, &message).unwrap();
+
+        let headers = LaxPacketHeaders::from_ip(&packet).unwrap();
+        assert_eq!(
+            get_dns_answers(&headers),
+            vec![
+                ("192.168.1.20".to_string(), "printer.local".to_string()),
+                (
+                    "fe80::211:22ff:fe33:4455".to_string(),
+                    "printer.local".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Module in charge of detecting the application protocol of a flow from the first bytes of its payload.
+//!
+//! This complements the port-based guess of `get_service`, which fails for services
+//! running on non-standard ports and can't tell apart protocols sharing the same port.
+
+use etherparse::{LaxPacketHeaders, TransportHeader};
+
+use crate::networking::passive_dns::is_dns_message;
+use crate::networking::server_name::HTTP_METHODS;
+use crate::Service;
+
+/// Number of packets of each flow whose payload is inspected
+pub const MAX_CLASSIFIED_PACKETS: u128 = 10;
+
+/// Names of the services detected from the payload which are not associated with any port
+pub const PAYLOAD_ONLY_SERVICES: [&str; 2] = ["quic", "bittorrent"];
+
+/// Connection preface sent by HTTP/2 clients
+const HTTP2_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+/// Handshake sent by BitTorrent peers
+const BITTORRENT_HANDSHAKE: &[u8] = b"\x13BitTorrent protocol";
+/// QUIC versions recognised in long headers
+const QUIC_VERSIONS: [u32; 2] = [0x0000_0001, 0x6b33_43cf];
+
+/// Returns the service detected from the payload of the packet, if any
+pub fn classify_payload(headers: &LaxPacketHeaders) -> Option<Service> {
+    let payload = headers.payload.slice();
+    if payload.is_empty() {
+        return None;
+    }
+    let name = match headers.transport {
+        Some(TransportHeader::Tcp(_)) => classify_tcp_payload(payload),
+        Some(TransportHeader::Udp(_)) => classify_udp_payload(payload),
+        _ => None,
+    }?;
+    Some(Service::Name(name))
+}
+
+fn classify_tcp_payload(payload: &[u8]) -> Option<&'static str> {
+    if is_tls_handshake(payload) {
+        Some("https")
+    } else if is_http(payload) {
+        Some("http")
+    } else if payload.starts_with(b"SSH-") {
+        Some("ssh")
+    } else if is_smb(payload) {
+        Some("microsoft-ds")
+    } else if is_rdp(payload) {
+        Some("ms-wbt-server")
+    } else if payload.starts_with(BITTORRENT_HANDSHAKE) {
+        Some("bittorrent")
+    } else if payload.get(2..).is_some_and(is_dns_message) {
+        // over TCP, each DNS message is preceded by its length
+        Some("domain")
+    } else {
+        None
+    }
+}
+
+fn classify_udp_payload(payload: &[u8]) -> Option<&'static str> {
+    if is_quic_long_header(payload) {
+        Some("quic")
+    } else if is_dns_message(payload) {
+        Some("domain")
+    } else {
+        None
+    }
+}
+
+/// TLS record containing a `ClientHello` or a `ServerHello`
+fn is_tls_handshake(payload: &[u8]) -> bool {
+    matches!(payload, [22, 3, 0..=4, _, _, 1 | 2, ..])
+}
+
+/// HTTP/1.x request or response, or HTTP/2 connection preface
+fn is_http(payload: &[u8]) -> bool {
+    if payload.starts_with(HTTP2_PREFACE) || payload.starts_with(b"HTTP/1.") {
+        return true;
+    }
+    HTTP_METHODS
+        .iter()
+        .any(|method| payload.starts_with(method))
+        && payload
+            .split(|b| *b == b'\r')
+            .next()
+            .is_some_and(|request_line| {
+                request_line.ends_with(b" HTTP/1.1") || request_line.ends_with(b" HTTP/1.0")
+            })
+}
+
+/// SMB message (version 1, 2, or encrypted) preceded by the NetBIOS session header
+fn is_smb(payload: &[u8]) -> bool {
+    matches!(payload, [0, _, _, _, 0xFD..=0xFF, b'S', b'M', b'B', ..])
+}
+
+/// X.224 connection request or confirm carried by TPKT, as sent at the beginning of RDP sessions
+fn is_rdp(payload: &[u8]) -> bool {
+    matches!(payload, [3, 0, _, _, _, 0xE0 | 0xD0, ..])
+        && usize::from(u16::from_be_bytes([payload[2], payload[3]])) == payload.len()
+}
+
+/// QUIC packet with long header (used during the handshake)
+fn is_quic_long_header(payload: &[u8]) -> bool {
+    let [first_byte, v1, v2, v3, v4, dcid_len, ..] = payload else {
+        return false;
+    };
+    let version = u32::from_be_bytes([*v1, *v2, *v3, *v4]);
+    first_byte & 0xC0 == 0xC0 && QUIC_VERSIONS.contains(&version) && *dcid_len <= 20
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_tcp_payload() {
+        assert_eq!(
+            classify_tcp_payload(&[22, 3, 1, 0, 200, 1, 0, 0, 196, 3, 3]),
+            Some("https")
+        );
+        assert_eq!(
+            classify_tcp_payload(b"GET /index.html HTTP/1.1\r\nHost: example.com\r\n\r\n"),
+            Some("http")
+        );
+        assert_eq!(
+            classify_tcp_payload(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n"),
+            Some("http")
+        );
+        assert_eq!(
+            classify_tcp_payload(b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n\x00\x00\x12\x04"),
+            Some("http")
+        );
+        assert_eq!(
+            classify_tcp_payload(b"SSH-2.0-OpenSSH_9.6\r\n"),
+            Some("ssh")
+        );
+        assert_eq!(
+            classify_tcp_payload(&[0, 0, 0, 68, 0xFE, b'S', b'M', b'B', 64, 0]),
+            Some("microsoft-ds")
+        );
+        let mut rdp = vec![3, 0, 0, 19, 14, 0xE0, 0, 0, 0, 0, 0];
+        rdp.extend_from_slice(&[1, 0, 8, 0, 3, 0, 0, 0]);
+        assert_eq!(classify_tcp_payload(&rdp), Some("ms-wbt-server"));
+        let mut bittorrent = BITTORRENT_HANDSHAKE.to_vec();
+        bittorrent.extend_from_slice(&[0; 8]);
+        assert_eq!(classify_tcp_payload(&bittorrent), Some("bittorrent"));
+        let mut dns = vec![0, 28, 0x12, 0x34, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+        dns.extend_from_slice(b"\x06github\x03com\x00\x00\x01\x00\x01");
+        assert_eq!(classify_tcp_payload(&dns), Some("domain"));
+
+        // application data in the middle of a TLS session
+        assert_eq!(classify_tcp_payload(&[23, 3, 3, 0, 40, 1, 2, 3]), None);
+        // methods not followed by an HTTP version
+        assert_eq!(classify_tcp_payload(b"GET something\r\n"), None);
+        assert_eq!(classify_tcp_payload(b"hello"), None);
+    }
+
+    #[test]
+    fn test_classify_udp_payload() {
+        let mut quic = vec![0xC3, 0, 0, 0, 1, 8];
+        quic.extend_from_slice(&[0xAB; 8]);
+        assert_eq!(classify_udp_payload(&quic), Some("quic"));
+        // unknown version
+        quic[1] = 0x12;
+        assert_eq!(classify_udp_payload(&quic), None);
+        // short header
+        assert_eq!(classify_udp_payload(&[0x43, 0, 0, 0, 1, 8]), None);
+
+        let mut dns = vec![0x12, 0x34, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+        dns.extend_from_slice(b"\x06github\x03com\x00\x00\x01\x00\x01");
+        assert_eq!(classify_udp_payload(&dns), Some("domain"));
+        assert_eq!(classify_udp_payload(b"M-SEARCH * HTTP/1.1\r\n"), None);
+    }
+
+    #[test]
+    fn test_classify_payload_from_packet() {
+        let builder = etherparse::PacketBuilder::ipv4([192, 168, 1, 10], [10, 0, 0, 5], 64)
+            .tcp(50000, 2222, 1, 64240);
+        let mut packet = Vec::new();
+        builder
+            .write(&mut packet, b"SSH-2.0-OpenSSH_9.6\r\n")
+            .unwrap();
+        let headers = LaxPacketHeaders::from_ip(&packet).unwrap();
+        assert_eq!(classify_payload(&headers), Some(Service::Name("ssh")));
+
+        let builder = etherparse::PacketBuilder::ipv4([192, 168, 1, 10], [10, 0, 0, 5], 64)
+            .tcp(50000, 2222, 1, 64240);
+        let mut packet = Vec::new();
+        builder.write(&mut packet, &[]).unwrap();
+        let headers = LaxPacketHeaders::from_ip(&packet).unwrap();
+        assert_eq!(classify_payload(&headers), None);
+    }
+}
@@ -0,0 +1,134 @@
+//! Module containing a minimal writer of PCAP files, used to export the packets
+//! of the selected connections and hosts.
+
+use std::io::Write;
+
+use pcap::Linktype;
+
+use crate::networking::types::packet_buffer::CapturedPacket;
+
+/// Magic number of PCAP files with timestamps in microseconds
+const PCAP_MAGIC_NUMBER: u32 = 0xa1b2_c3d4;
+
+/// Snapshot length declared in the file header
+const PCAP_SNAPLEN: u32 = 65535;
+
+/// Length of the global header of the file
+pub const PCAP_HEADER_LEN: usize = 24;
+
+/// Length of the header of each packet record
+pub const PCAP_RECORD_HEADER_LEN: usize = 16;
+
+/// Writes packets in the classic PCAP format (version 2.4)
+pub struct PcapWriter<W: Write> {
+    writer: W,
+    link_type: Linktype,
+}
+
+impl<W: Write> PcapWriter<W> {
+    /// Writes the global header of the file, declaring the link type of all the packets
+    pub fn new(mut writer: W, link_type: Linktype) -> std::io::Result<Self> {
+        let mut header = Vec::with_capacity(PCAP_HEADER_LEN);
+        header.extend_from_slice(&PCAP_MAGIC_NUMBER.to_le_bytes());
+        header.extend_from_slice(&2_u16.to_le_bytes());
+        header.extend_from_slice(&4_u16.to_le_bytes());
+        // timezone offset and timestamps accuracy
+        header.extend_from_slice(&[0; 8]);
+        header.extend_from_slice(&PCAP_SNAPLEN.to_le_bytes());
+        header.extend_from_slice(&link_type.0.to_le_bytes());
+        writer.write_all(&header)?;
+        writer.flush()?;
+        Ok(Self { writer, link_type })
+    }
+
+    pub fn link_type(&self) -> Linktype {
+        self.link_type
+    }
+
+    /// Appends a packet record to the file
+    pub fn write_packet(&mut self, packet: &CapturedPacket) -> std::io::Result<()> {
+        let ts_sec = u32::try_from(packet.timestamp.timestamp()).unwrap_or_default();
+        let ts_usec = packet.timestamp.timestamp_subsec_micros();
+        self.write_record(ts_sec, ts_usec, packet.original_len, &packet.data)
+    }
+
+    /// Appends a record with the given timestamp, original length, and captured bytes
+    pub fn write_record(
+        &mut self,
+        ts_sec: u32,
+        ts_usec: u32,
+        original_len: u32,
+        data: &[u8],
+    ) -> std::io::Result<()> {
+        let caplen = u32::try_from(data.len()).unwrap_or(u32::MAX);
+        let mut record = Vec::with_capacity(PCAP_RECORD_HEADER_LEN + data.len());
+        record.extend_from_slice(&ts_sec.to_le_bytes());
+        record.extend_from_slice(&ts_usec.to_le_bytes());
+        record.extend_from_slice(&caplen.to_le_bytes());
+        record.extend_from_slice(&original_len.max(caplen).to_le_bytes());
+        record.extend_from_slice(data);
+        self.writer.write_all(&record)
+    }
+
+    /// Writes to the underlying writer the records still buffered
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Local};
+
+    use crate::networking::types::my_link_type::MyLinkType;
+
+    use super::*;
+
+    fn captured_packet(data: &[u8], original_len: u32) -> CapturedPacket {
+        CapturedPacket {
+            id: 0,
+            timestamp: DateTime::from_timestamp(1_700_000_000, 123_456_000)
+                .unwrap()
+                .with_timezone(&Local),
+            original_len,
+            data: data.to_vec(),
+            link_type: MyLinkType::Ethernet(Linktype::ETHERNET),
+        }
+    }
+
+    #[test]
+    fn test_pcap_writer_header_and_records() {
+        let mut writer = PcapWriter::new(Vec::new(), Linktype::ETHERNET).unwrap();
+        assert_eq!(writer.link_type(), Linktype::ETHERNET);
+        writer
+            .write_packet(&captured_packet(&[1, 2, 3], 100))
+            .unwrap();
+        let bytes = writer.writer;
+
+        assert_eq!(bytes.len(), 24 + 16 + 3);
+        assert_eq!(bytes[0..4], [0xd4, 0xc3, 0xb2, 0xa1]);
+        assert_eq!(bytes[4..8], [2, 0, 4, 0]);
+        assert_eq!(bytes[16..20], 65535_u32.to_le_bytes());
+        assert_eq!(bytes[20..24], 1_u32.to_le_bytes());
+        assert_eq!(bytes[24..28], 1_700_000_000_u32.to_le_bytes());
+        assert_eq!(bytes[28..32], 123_456_u32.to_le_bytes());
+        assert_eq!(bytes[32..36], 3_u32.to_le_bytes());
+        assert_eq!(bytes[36..40], 100_u32.to_le_bytes());
+        assert_eq!(bytes[40..], [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_pcap_writer_original_len() {
+        let mut writer = PcapWriter::new(Vec::new(), Linktype::LINUX_SLL).unwrap();
+        writer
+            .write_packet(&captured_packet(&[0xbb; 42], 0))
+            .unwrap();
+        let bytes = writer.writer;
+
+        assert_eq!(bytes[20..24], 113_u32.to_le_bytes());
+        assert_eq!(bytes[32..36], 42_u32.to_le_bytes());
+        // the original length is never lower than the captured one
+        assert_eq!(bytes[36..40], 42_u32.to_le_bytes());
+        assert_eq!(bytes[40..], [0xbb; 42]);
+    }
+}
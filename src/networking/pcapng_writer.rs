@@ -0,0 +1,321 @@
+//! Module containing a minimal writer of PCAPNG files, used to record the filtered packets
+//! together with the description of the adapter and the information computed by Sniffnet.
+
+use std::io::Write;
+
+use pcap::Linktype;
+
+use crate::networking::types::traffic_direction::TrafficDirection;
+
+const SECTION_HEADER_BLOCK: u32 = 0x0a0d_0d0a;
+const INTERFACE_DESCRIPTION_BLOCK: u32 = 0x0000_0001;
+const ENHANCED_PACKET_BLOCK: u32 = 0x0000_0006;
+
+/// Magic number telling the byte order of the section
+const BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+
+/// Snapshot length declared for the interface
+const PCAPNG_SNAPLEN: u32 = 65535;
+
+const OPT_END_OF_OPT: u16 = 0;
+const OPT_COMMENT: u16 = 1;
+const SHB_OS: u16 = 3;
+const SHB_USER_APPL: u16 = 4;
+const IF_NAME: u16 = 2;
+const IF_DESCRIPTION: u16 = 3;
+const EPB_FLAGS: u16 = 2;
+
+/// Length of an Enhanced Packet Block without captured bytes and options
+const EPB_FIXED_LEN: usize = 32;
+
+/// Information written at the beginning of each PCAPNG file
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PcapngSection {
+    /// Application that wrote the file, with its version
+    pub application: String,
+    /// Free-text comment of the section (e.g., the active filters)
+    pub comment: String,
+    /// Name of the network adapter
+    pub interface_name: String,
+    /// Description of the network adapter
+    pub interface_description: Option<String>,
+}
+
+/// A packet to be written, with the information computed by the app
+pub struct PacketRecord<'a> {
+    pub ts_sec: u32,
+    pub ts_usec: u32,
+    pub original_len: u32,
+    pub data: &'a [u8],
+    pub direction: TrafficDirection,
+    /// Comment attached to the packet
+    pub comment: Option<&'a str>,
+}
+
+/// Writes packets in the PCAPNG format, with a single section and a single interface
+pub struct PcapngWriter<W: Write> {
+    writer: W,
+    /// Length of the Section Header Block and Interface Description Block
+    header_len: usize,
+}
+
+impl<W: Write> PcapngWriter<W> {
+    /// Writes the Section Header Block and the Interface Description Block of the file
+    pub fn new(
+        mut writer: W,
+        link_type: Linktype,
+        section: &PcapngSection,
+    ) -> std::io::Result<Self> {
+        let mut shb_body = Vec::new();
+        shb_body.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        // version 1.0
+        shb_body.extend_from_slice(&1_u16.to_le_bytes());
+        shb_body.extend_from_slice(&0_u16.to_le_bytes());
+        // section length not specified
+        shb_body.extend_from_slice(&(-1_i64).to_le_bytes());
+        let mut shb_options = vec![(SHB_USER_APPL, section.application.as_bytes())];
+        if !section.comment.is_empty() {
+            shb_options.push((OPT_COMMENT, section.comment.as_bytes()));
+        }
+        shb_options.push((SHB_OS, std::env::consts::OS.as_bytes()));
+        push_options(&mut shb_body, &shb_options);
+
+        let mut idb_body = Vec::new();
+        // the link type is a 16-bit field in PCAPNG
+        idb_body.extend_from_slice(&u16::try_from(link_type.0).unwrap_or_default().to_le_bytes());
+        idb_body.extend_from_slice(&0_u16.to_le_bytes());
+        idb_body.extend_from_slice(&PCAPNG_SNAPLEN.to_le_bytes());
+        let mut idb_options = vec![(IF_NAME, section.interface_name.as_bytes())];
+        if let Some(description) = &section.interface_description {
+            idb_options.push((IF_DESCRIPTION, description.as_bytes()));
+        }
+        push_options(&mut idb_body, &idb_options);
+
+        let mut header = block(SECTION_HEADER_BLOCK, &shb_body);
+        header.extend(block(INTERFACE_DESCRIPTION_BLOCK, &idb_body));
+        writer.write_all(&header)?;
+        writer.flush()?;
+        Ok(Self {
+            writer,
+            header_len: header.len(),
+        })
+    }
+
+    /// Length of the blocks written at the beginning of the file
+    pub fn header_len(&self) -> usize {
+        self.header_len
+    }
+
+    /// Length of the Enhanced Packet Block of a packet with the given captured bytes and comment
+    pub fn record_len(data_len: usize, comment: Option<&str>) -> usize {
+        let flags_len = 4 + 4;
+        let comment_len = comment.map_or(0, |comment| 4 + padded_len(comment.len()));
+        let end_of_opt_len = 4;
+        EPB_FIXED_LEN + padded_len(data_len) + flags_len + comment_len + end_of_opt_len
+    }
+
+    /// Appends an Enhanced Packet Block to the file
+    pub fn write_record(&mut self, record: &PacketRecord) -> std::io::Result<()> {
+        let caplen = u32::try_from(record.data.len()).unwrap_or(u32::MAX);
+        let ts = u64::from(record.ts_sec) * 1_000_000 + u64::from(record.ts_usec);
+        let mut body = Vec::with_capacity(Self::record_len(record.data.len(), record.comment));
+        // interface ID
+        body.extend_from_slice(&0_u32.to_le_bytes());
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            body.extend_from_slice(&((ts >> 32) as u32).to_le_bytes());
+            body.extend_from_slice(&(ts as u32).to_le_bytes());
+        }
+        body.extend_from_slice(&caplen.to_le_bytes());
+        body.extend_from_slice(&record.original_len.max(caplen).to_le_bytes());
+        body.extend_from_slice(record.data);
+        pad(&mut body);
+        // the two lowest bits of the flags word tell whether the packet is inbound or outbound
+        let flags: u32 = match record.direction {
+            TrafficDirection::Incoming => 0b01,
+            TrafficDirection::Outgoing => 0b10,
+        };
+        let flags = flags.to_le_bytes();
+        let mut options = vec![(EPB_FLAGS, &flags[..])];
+        if let Some(comment) = record.comment {
+            options.push((OPT_COMMENT, comment.as_bytes()));
+        }
+        push_options(&mut body, &options);
+        self.writer.write_all(&block(ENHANCED_PACKET_BLOCK, &body))
+    }
+
+    /// Writes to the underlying writer the blocks still buffered
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Wraps the body in a block, with its type and total length at both ends
+fn block(block_type: u32, body: &[u8]) -> Vec<u8> {
+    let total_len = u32::try_from(body.len() + 12).unwrap_or(u32::MAX);
+    let mut block = Vec::with_capacity(body.len() + 12);
+    block.extend_from_slice(&block_type.to_le_bytes());
+    block.extend_from_slice(&total_len.to_le_bytes());
+    block.extend_from_slice(body);
+    block.extend_from_slice(&total_len.to_le_bytes());
+    block
+}
+
+/// Appends the options, followed by the end of options marker
+fn push_options(body: &mut Vec<u8>, options: &[(u16, &[u8])]) {
+    for (code, value) in options {
+        body.extend_from_slice(&code.to_le_bytes());
+        body.extend_from_slice(&u16::try_from(value.len()).unwrap_or(u16::MAX).to_le_bytes());
+        body.extend_from_slice(&value[..value.len().min(usize::from(u16::MAX))]);
+        pad(body);
+    }
+    body.extend_from_slice(&OPT_END_OF_OPT.to_le_bytes());
+    body.extend_from_slice(&0_u16.to_le_bytes());
+}
+
+/// Pads the buffer to a multiple of 32 bits
+fn pad(buffer: &mut Vec<u8>) {
+    buffer.resize(padded_len(buffer.len()), 0);
+}
+
+fn padded_len(len: usize) -> usize {
+    len.div_ceil(4) * 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    /// Returns the options of a block body, starting at the given offset
+    fn options(body: &[u8], mut offset: usize) -> Vec<(u16, Vec<u8>)> {
+        let mut options = Vec::new();
+        loop {
+            let code = u16::from_le_bytes(body[offset..offset + 2].try_into().unwrap());
+            let len = usize::from(u16::from_le_bytes(
+                body[offset + 2..offset + 4].try_into().unwrap(),
+            ));
+            if code == OPT_END_OF_OPT {
+                return options;
+            }
+            options.push((code, body[offset + 4..offset + 4 + len].to_vec()));
+            offset += 4 + padded_len(len);
+        }
+    }
+
+    fn section() -> PcapngSection {
+        PcapngSection {
+            application: "Sniffnet 1.3.0".to_string(),
+            comment: "BPF: tcp port 443".to_string(),
+            interface_name: "eth0".to_string(),
+            interface_description: Some("Ethernet adapter".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_pcapng_writer_header() {
+        let writer = PcapngWriter::new(Vec::new(), Linktype::ETHERNET, &section()).unwrap();
+        let header_len = writer.header_len();
+        let bytes = writer.writer;
+        assert_eq!(bytes.len(), header_len);
+
+        // Section Header Block
+        assert_eq!(u32_at(&bytes, 0), SECTION_HEADER_BLOCK);
+        let shb_len = u32_at(&bytes, 4) as usize;
+        assert_eq!(u32_at(&bytes, shb_len - 4) as usize, shb_len);
+        assert_eq!(u32_at(&bytes, 8), BYTE_ORDER_MAGIC);
+        assert_eq!(bytes[12..16], [1, 0, 0, 0]);
+        assert_eq!(bytes[16..24], [0xff; 8]);
+        assert_eq!(
+            options(&bytes, 24),
+            vec![
+                (SHB_USER_APPL, b"Sniffnet 1.3.0".to_vec()),
+                (OPT_COMMENT, b"BPF: tcp port 443".to_vec()),
+                (SHB_OS, std::env::consts::OS.as_bytes().to_vec()),
+            ]
+        );
+
+        // Interface Description Block
+        let idb = &bytes[shb_len..];
+        assert_eq!(u32_at(idb, 0), INTERFACE_DESCRIPTION_BLOCK);
+        assert_eq!(u32_at(idb, 4) as usize, idb.len());
+        assert_eq!(idb[8..12], [1, 0, 0, 0]);
+        assert_eq!(u32_at(idb, 12), 65535);
+        assert_eq!(
+            options(idb, 16),
+            vec![
+                (IF_NAME, b"eth0".to_vec()),
+                (IF_DESCRIPTION, b"Ethernet adapter".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pcapng_writer_records() {
+        let mut writer = PcapngWriter::new(
+            Vec::new(),
+            Linktype::LINUX_SLL,
+            &PcapngSection {
+                interface_description: None,
+                ..section()
+            },
+        )
+        .unwrap();
+        let header_len = writer.header_len();
+        let records = [
+            PacketRecord {
+                ts_sec: 1_700_000_000,
+                ts_usec: 123_456,
+                original_len: 100,
+                data: &[1, 2, 3],
+                direction: TrafficDirection::Outgoing,
+                comment: None,
+            },
+            PacketRecord {
+                ts_sec: 1_700_000_001,
+                ts_usec: 0,
+                original_len: 0,
+                data: &[0xbb; 8],
+                direction: TrafficDirection::Incoming,
+                comment: Some("Favorite: one.one.one.one"),
+            },
+        ];
+        for record in &records {
+            writer.write_record(record).unwrap();
+        }
+        let bytes = writer.writer;
+        let idb = &bytes[u32_at(&bytes, 4) as usize..header_len];
+        assert_eq!(idb[8..10], 113_u16.to_le_bytes());
+        assert_eq!(options(idb, 16), vec![(IF_NAME, b"eth0".to_vec())]);
+
+        let epb = &bytes[header_len..];
+        let first_len = PcapngWriter::<Vec<u8>>::record_len(3, None);
+        assert_eq!(u32_at(epb, 0), ENHANCED_PACKET_BLOCK);
+        assert_eq!(u32_at(epb, 4) as usize, first_len);
+        assert_eq!(u32_at(epb, first_len - 4) as usize, first_len);
+        assert_eq!(u32_at(epb, 8), 0);
+        let ts = (u64::from(u32_at(epb, 12)) << 32) + u64::from(u32_at(epb, 16));
+        assert_eq!(ts, 1_700_000_000_123_456);
+        assert_eq!(u32_at(epb, 20), 3);
+        assert_eq!(u32_at(epb, 24), 100);
+        assert_eq!(epb[28..32], [1, 2, 3, 0]);
+        assert_eq!(options(epb, 32), vec![(EPB_FLAGS, vec![2, 0, 0, 0])]);
+
+        let epb = &epb[first_len..];
+        let second_len = PcapngWriter::<Vec<u8>>::record_len(8, Some("Favorite: one.one.one.one"));
+        assert_eq!(epb.len(), second_len);
+        assert_eq!(u32_at(epb, 4) as usize, second_len);
+        // the original length is never lower than the captured one
+        assert_eq!(u32_at(epb, 24), 8);
+        assert_eq!(
+            options(epb, 36),
+            vec![
+                (EPB_FLAGS, vec![1, 0, 0, 0]),
+                (OPT_COMMENT, b"Favorite: one.one.one.one".to_vec()),
+            ]
+        );
+    }
+}
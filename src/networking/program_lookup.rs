@@ -0,0 +1,459 @@
+//! Module in charge of attributing connections to the programs owning their local sockets.
+//!
+//! Sockets are identified by matching local port and protocol against the tables in `/proc/net`,
+//! and their inodes are then searched among the file descriptors of the running processes.
+//! This is only supported on Linux: on other platforms programs are always unknown.
+
+use std::collections::HashMap;
+use std::fs;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::program::Program;
+use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::{InfoTraffic, Protocol};
+
+/// Minimum interval between two scans of the running processes
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_millis(200);
+/// Connections whose program isn't found within this time are attributed to an unknown program
+const RESOLUTION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Handle to the thread identifying the programs owning the connections of a capture.
+///
+/// New connections are counted as waiting for a program until the thread finds it,
+/// so that the scans of the running processes don't slow down the parsing of packets.
+pub struct ProgramLookup {
+    /// Queue of the connections whose program has to be identified
+    sender: Sender<ProgramRequest>,
+}
+
+impl ProgramLookup {
+    pub fn new(info_traffic: Arc<Mutex<InfoTraffic>>) -> Self {
+        let (sender, receiver) = channel();
+        thread::Builder::new()
+            .name("thread_program_lookup".to_string())
+            .spawn(move || serve_requests(&receiver, &info_traffic))
+            .unwrap();
+        Self { sender }
+    }
+
+    /// Queues the identification of the program owning the local socket of a new connection
+    pub fn request(&self, key: &AddressPortPair, traffic_direction: TrafficDirection) {
+        // the thread only stops when the capture is over
+        let _ = self.sender.send(ProgramRequest {
+            key: key.clone(),
+            traffic_direction,
+            requested_at: Instant::now(),
+        });
+    }
+}
+
+/// Request to identify the program owning the local socket of a connection
+struct ProgramRequest {
+    key: AddressPortPair,
+    traffic_direction: TrafficDirection,
+    requested_at: Instant,
+}
+
+impl ProgramRequest {
+    /// Returns the local and remote ports of the connection
+    fn ports(&self) -> Option<(u16, u16)> {
+        let (local_port, remote_port) = if self.traffic_direction == TrafficDirection::Outgoing {
+            (self.key.port1, self.key.port2)
+        } else {
+            (self.key.port2, self.key.port1)
+        };
+        Some((local_port?, remote_port?))
+    }
+}
+
+/// Connections waiting for their program to be identified
+#[derive(Default)]
+struct ProgramResolver {
+    pending: Vec<ProgramRequest>,
+}
+
+impl ProgramResolver {
+    /// Tries to identify the programs of the pending connections,
+    /// scanning the running processes if some of their sockets are not known yet.
+    ///
+    /// Returns the connections identified or given up on;
+    /// the others are retried at the next call, since their sockets may not be listed yet.
+    fn resolve(
+        &mut self,
+        get_inode: impl Fn(&ProgramRequest) -> Option<u64>,
+        get_sockets: impl FnOnce() -> HashMap<u64, Program>,
+    ) -> Vec<(AddressPortPair, Program)> {
+        let mut resolved = Vec::new();
+        let mut inodes = Vec::new();
+        for request in std::mem::take(&mut self.pending) {
+            if !cfg!(target_os = "linux") || request.ports().is_none() {
+                // unsupported platform, or ICMP
+                resolved.push((request.key, Program::Unknown));
+                continue;
+            }
+            let inode = get_inode(&request);
+            inodes.push((request, inode));
+        }
+        if inodes.is_empty() {
+            return resolved;
+        }
+
+        let sockets = get_sockets();
+        for (request, inode) in inodes {
+            match inode.and_then(|inode| sockets.get(&inode)) {
+                Some(program) => resolved.push((request.key, program.clone())),
+                None if request.requested_at.elapsed() >= RESOLUTION_TIMEOUT => {
+                    resolved.push((request.key, Program::Unknown));
+                }
+                None => self.pending.push(request),
+            }
+        }
+        resolved
+    }
+}
+
+/// The calling thread collects the requests of a capture and periodically tries to resolve them,
+/// until the capture is over
+fn serve_requests(receiver: &Receiver<ProgramRequest>, info_traffic: &Mutex<InfoTraffic>) {
+    let mut resolver = ProgramResolver::default();
+    loop {
+        // collect the requests received until the next scan
+        let next_refresh = Instant::now() + MIN_REFRESH_INTERVAL;
+        loop {
+            match receiver.recv_timeout(next_refresh.saturating_duration_since(Instant::now())) {
+                Ok(request) => resolver.pending.push(request),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        if resolver.pending.is_empty() {
+            continue;
+        }
+
+        let tables = SocketTables::read();
+        let resolved = resolver.resolve(
+            |request| {
+                let (local_port, remote_port) = request.ports()?;
+                tables.get_inode(request.key.protocol, local_port, remote_port)
+            },
+            get_process_sockets,
+        );
+        let mut info_traffic = info_traffic.lock().expect("Error acquiring mutex\n\r");
+        for (key, program) in resolved {
+            set_program(&mut info_traffic, &key, program);
+        }
+    }
+}
+
+/// Attributes a connection to its program,
+/// moving the traffic exchanged while waiting for it to the program data info
+fn set_program(info_traffic: &mut InfoTraffic, key: &AddressPortPair, program: Program) {
+    let name = program.to_string();
+    if let Some(info) = info_traffic.map.get_mut(key) {
+        info.program = program;
+    }
+    if let Some(data_info) = info_traffic.programs_waiting_resolution.remove(key) {
+        *info_traffic.programs.entry(name).or_default() += data_info;
+    }
+}
+
+/// Contents of the `/proc/net` socket tables, read once for all the pending connections
+struct SocketTables {
+    tcp: Vec<String>,
+    udp: Vec<String>,
+}
+
+impl SocketTables {
+    fn read() -> Self {
+        let read_tables = |tables: [&str; 2]| {
+            tables
+                .iter()
+                .filter_map(|table| fs::read_to_string(table).ok())
+                .collect()
+        };
+        Self {
+            tcp: read_tables(["/proc/net/tcp", "/proc/net/tcp6"]),
+            udp: read_tables(["/proc/net/udp", "/proc/net/udp6"]),
+        }
+    }
+
+    /// Searches the socket tables for the inode of the socket bound to the given local port
+    fn get_inode(&self, protocol: Protocol, local_port: u16, remote_port: u16) -> Option<u64> {
+        let tables = match protocol {
+            Protocol::TCP => &self.tcp,
+            Protocol::UDP => &self.udp,
+            Protocol::ICMP | Protocol::L2 => return None,
+        };
+        let mut fallback = None;
+        for content in tables {
+            match parse_socket_table(content, local_port, remote_port) {
+                Some((inode, true)) => return Some(inode),
+                Some((inode, false)) => fallback = fallback.or(Some(inode)),
+                None => {}
+            }
+        }
+        fallback
+    }
+}
+
+/// Parses the content of a `/proc/net` socket table,
+/// returning the inode of a socket bound to the given local port.
+///
+/// Sockets also connected to the given remote port are preferred (e.g., over a listening socket);
+/// the returned flag reports whether the remote port matched as well.
+fn parse_socket_table(content: &str, local_port: u16, remote_port: u16) -> Option<(u64, bool)> {
+    let mut fallback = None;
+    // the first line is the header
+    for line in content.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (Some(local), Some(remote), Some(inode)) =
+            (fields.get(1), fields.get(2), fields.get(9))
+        else {
+            continue;
+        };
+        let Ok(inode) = inode.parse::<u64>() else {
+            continue;
+        };
+        if inode == 0 || parse_hex_port(local) != Some(local_port) {
+            continue;
+        }
+        if parse_hex_port(remote) == Some(remote_port) {
+            return Some((inode, true));
+        }
+        fallback = fallback.or(Some((inode, false)));
+    }
+    fallback
+}
+
+/// Extracts the port from an address in the format used by `/proc/net` tables (e.g., `0100007F:0035`)
+fn parse_hex_port(address: &str) -> Option<u16> {
+    let (_, port) = address.rsplit_once(':')?;
+    u16::from_str_radix(port, 16).ok()
+}
+
+/// Extracts the socket inode from the target of a file descriptor link (e.g., `socket:[12345]`)
+fn parse_socket_link(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Scans the file descriptors of the running processes, collecting the sockets they own
+fn get_process_sockets() -> HashMap<u64, Program> {
+    let mut sockets = HashMap::new();
+    let Ok(proc_entries) = fs::read_dir("/proc") else {
+        return sockets;
+    };
+    for proc_entry in proc_entries.flatten() {
+        let Some(pid) = proc_entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let Ok(fd_entries) = fs::read_dir(proc_entry.path().join("fd")) else {
+            // process terminated, or not enough permissions
+            continue;
+        };
+        let mut program = None;
+        for fd_entry in fd_entries.flatten() {
+            let Some(inode) = fs::read_link(fd_entry.path())
+                .ok()
+                .and_then(|link| parse_socket_link(&link.to_string_lossy()))
+            else {
+                continue;
+            };
+            let program = program.get_or_insert_with(|| {
+                let name = fs::read_to_string(proc_entry.path().join("comm"))
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+                Program::Known { name, pid }
+            });
+            sockets.insert(inode, program.clone());
+        }
+    }
+    sockets
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Sub;
+
+    use chrono::Local;
+
+    use crate::networking::types::data_info::DataInfo;
+    use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+
+    use super::*;
+
+    const TCP_TABLE: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 31337 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1111 1 0000000000000000 100 0 0 10 0
+   2: 0F02000A:1F90 0202A8C0:D431 01 00000000:00000000 00:00000000 00000000  1000        0 42424 1 0000000000000000 20 4 30 10 -1
+   3: 0F02000A:C350 22D8B85D:01BB 06 00000000:00000000 03:00000DB5 00000000     0        0 0 3 0000000000000000";
+
+    #[test]
+    fn test_parse_hex_port() {
+        assert_eq!(parse_hex_port("0100007F:0035"), Some(53));
+        assert_eq!(
+            parse_hex_port("00000000000000000000000001000000:1F90"),
+            Some(8080)
+        );
+        assert_eq!(parse_hex_port("0100007F:FFFF"), Some(u16::MAX));
+        assert_eq!(parse_hex_port("0100007F"), None);
+        assert_eq!(parse_hex_port("0100007F:XYZ"), None);
+    }
+
+    #[test]
+    fn test_parse_socket_link() {
+        assert_eq!(parse_socket_link("socket:[12345]"), Some(12345));
+        assert_eq!(parse_socket_link("socket:[]"), None);
+        assert_eq!(parse_socket_link("pipe:[12345]"), None);
+        assert_eq!(parse_socket_link("/dev/null"), None);
+        assert_eq!(parse_socket_link("anon_inode:[eventpoll]"), None);
+    }
+
+    #[test]
+    fn test_parse_socket_table() {
+        // connected socket preferred over the listening one
+        assert_eq!(
+            parse_socket_table(TCP_TABLE, 8080, 54321),
+            Some((42424, true))
+        );
+        // only the listening socket matches the local port
+        assert_eq!(
+            parse_socket_table(TCP_TABLE, 8080, 443),
+            Some((31337, false))
+        );
+        assert_eq!(parse_socket_table(TCP_TABLE, 631, 0), Some((1111, true)));
+        // sockets in TIME_WAIT have no inode
+        assert_eq!(parse_socket_table(TCP_TABLE, 50000, 443), None);
+        assert_eq!(parse_socket_table(TCP_TABLE, 22, 50000), None);
+        assert_eq!(parse_socket_table("", 22, 50000), None);
+    }
+
+    fn request(protocol: Protocol, requested_at: Instant) -> ProgramRequest {
+        let port = (protocol != Protocol::ICMP).then_some(443);
+        ProgramRequest {
+            key: AddressPortPair::new(
+                "192.168.1.6".to_string(),
+                port.map(|_| 50000),
+                "140.82.121.4".to_string(),
+                port,
+                protocol,
+            ),
+            traffic_direction: TrafficDirection::Outgoing,
+            requested_at,
+        }
+    }
+
+    fn firefox() -> Program {
+        Program::Known {
+            name: "firefox".to_string(),
+            pid: 4242,
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_program_resolver_retries_at_next_refresh() {
+        let mut resolver = ProgramResolver::default();
+        resolver
+            .pending
+            .push(request(Protocol::TCP, Instant::now()));
+        let get_inode = |request: &ProgramRequest| {
+            assert_eq!(request.ports(), Some((50000, 443)));
+            Some(42424)
+        };
+
+        // socket listed, but its process not scanned yet
+        let resolved = resolver.resolve(get_inode, HashMap::new);
+        assert!(resolved.is_empty());
+        assert_eq!(resolver.pending.len(), 1);
+
+        // found at the next scan
+        let resolved = resolver.resolve(get_inode, || HashMap::from([(42424, firefox())]));
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].0.port1, Some(50000));
+        assert_eq!(resolved[0].1, firefox());
+        assert!(resolver.pending.is_empty());
+
+        // nothing to scan without pending connections
+        let resolved = resolver.resolve(get_inode, || panic!("no scan expected"));
+        assert!(resolved.is_empty());
+    }
+
+    #[test]
+    fn test_program_resolver_gives_up() {
+        let mut resolver = ProgramResolver::default();
+        let expired = Instant::now().sub(RESOLUTION_TIMEOUT);
+        resolver.pending.push(request(Protocol::TCP, expired));
+        resolver
+            .pending
+            .push(request(Protocol::UDP, Instant::now()));
+        resolver
+            .pending
+            .push(request(Protocol::ICMP, Instant::now()));
+
+        let resolved = resolver.resolve(|_| None, HashMap::new);
+        let protocols: Vec<(Protocol, Program)> = resolved
+            .into_iter()
+            .map(|(key, program)| (key.protocol, program))
+            .collect();
+        if cfg!(target_os = "linux") {
+            assert_eq!(
+                protocols,
+                vec![
+                    (Protocol::ICMP, Program::Unknown),
+                    (Protocol::TCP, Program::Unknown)
+                ]
+            );
+            assert_eq!(resolver.pending.len(), 1);
+            assert_eq!(resolver.pending[0].key.protocol, Protocol::UDP);
+        } else {
+            assert_eq!(protocols.len(), 3);
+            assert!(resolver.pending.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_set_program() {
+        let key = request(Protocol::TCP, Instant::now()).key;
+        let mut info_traffic = InfoTraffic::new();
+        info_traffic
+            .map
+            .insert(key.clone(), InfoAddressPortPair::default());
+        let mut data_info =
+            DataInfo::new_with_first_packet(100, TrafficDirection::Outgoing, Local::now());
+        data_info.add_packet(200, TrafficDirection::Incoming, Local::now());
+        info_traffic
+            .programs_waiting_resolution
+            .insert(key.clone(), data_info);
+        info_traffic.programs.insert(
+            "firefox".to_string(),
+            DataInfo::new_with_first_packet(50, TrafficDirection::Outgoing, Local::now()),
+        );
+
+        set_program(&mut info_traffic, &key, firefox());
+        assert_eq!(info_traffic.map[&key].program, firefox());
+        assert!(info_traffic.programs_waiting_resolution.is_empty());
+        assert_eq!(info_traffic.programs.len(), 1);
+        assert_eq!(info_traffic.programs["firefox"].tot_packets(), 3);
+        assert_eq!(info_traffic.programs["firefox"].tot_bytes(), 350);
+
+        // connections of unknown programs are grouped together
+        let other_key = request(Protocol::UDP, Instant::now()).key;
+        info_traffic
+            .programs_waiting_resolution
+            .insert(other_key.clone(), data_info);
+        set_program(&mut info_traffic, &other_key, Program::Unknown);
+        assert_eq!(info_traffic.programs["?"].tot_packets(), 2);
+    }
+}
@@ -0,0 +1,208 @@
+//! Module implementing the pool of threads in charge of reverse DNS lookups.
+//!
+//! Lookups are queued and served by a fixed number of threads, so that a burst of new addresses
+//! (e.g., during a port scan) doesn't result in thousands of threads waiting for the resolver.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use dns_lookup::lookup_addr;
+use once_cell::sync::Lazy;
+
+use crate::mmdb::types::mmdb_reader::MmdbReader;
+use crate::networking::manage_packets::reverse_dns_lookup;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::my_device::MyDevice;
+use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::InfoTraffic;
+
+/// Number of threads performing reverse DNS lookups
+const WORKERS: usize = 8;
+/// Maximum number of lookups waiting for a free thread
+const QUEUE_CAPACITY: usize = 1024;
+/// Lookups waiting longer than this are completed without querying the resolver
+const QUEUE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Time for which a failed lookup isn't repeated
+const NEGATIVE_CACHE_TTL: Duration = Duration::from_secs(600);
+/// Number of failed lookups above which expired ones are removed from the cache
+const NEGATIVE_CACHE_PRUNE_LEN: usize = 10_000;
+
+/// Resolver shared by all the captures
+pub static R_DNS_RESOLVER: Lazy<ReverseDnsResolver> = Lazy::new(ReverseDnsResolver::new);
+
+/// Request to build the host corresponding to the remote address of a connection
+pub struct LookupRequest {
+    pub info_traffic: Arc<Mutex<InfoTraffic>>,
+    pub key: AddressPortPair,
+    pub traffic_direction: TrafficDirection,
+    pub device: MyDevice,
+    pub country_db_reader: Arc<MmdbReader>,
+    pub asn_db_reader: Arc<MmdbReader>,
+}
+
+impl LookupRequest {
+    /// Builds the host, using the given function to perform the active rDNS lookup
+    pub fn resolve(self, lookup: impl FnOnce(&str) -> Option<String>) {
+        reverse_dns_lookup(
+            &self.info_traffic,
+            &self.key,
+            self.traffic_direction,
+            &self.device,
+            &self.country_db_reader,
+            &self.asn_db_reader,
+            lookup,
+        );
+    }
+
+    /// Builds the host without querying the resolver (i.e., from MMDB data and observed DNS responses)
+    pub fn resolve_without_lookup(self) {
+        self.resolve(|_| None);
+    }
+}
+
+/// Pool of threads performing reverse DNS lookups
+pub struct ReverseDnsResolver {
+    /// Queue of the pending lookups, with the instant they were requested
+    sender: SyncSender<(LookupRequest, Instant)>,
+}
+
+impl ReverseDnsResolver {
+    fn new() -> Self {
+        let (sender, receiver) = sync_channel(QUEUE_CAPACITY);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let negative_cache = Arc::new(Mutex::new(NegativeCache::default()));
+        for _ in 0..WORKERS {
+            let receiver = receiver.clone();
+            let negative_cache = negative_cache.clone();
+            thread::Builder::new()
+                .name("thread_reverse_dns_lookup".to_string())
+                .spawn(move || serve_lookups(&receiver, &negative_cache))
+                .unwrap();
+        }
+        Self { sender }
+    }
+
+    /// Queues the lookup; if too many lookups are pending, the host is built without querying the resolver
+    pub fn resolve(&self, request: LookupRequest) {
+        match self.sender.try_send((request, Instant::now())) {
+            Ok(()) => {}
+            Err(TrySendError::Full((request, _)) | TrySendError::Disconnected((request, _))) => {
+                request.resolve_without_lookup();
+            }
+        }
+    }
+}
+
+fn serve_lookups(
+    receiver: &Mutex<Receiver<(LookupRequest, Instant)>>,
+    negative_cache: &Mutex<NegativeCache>,
+) {
+    loop {
+        // the lock is released as soon as a request is received
+        let Ok((request, requested_at)) = receiver.lock().unwrap().recv() else {
+            return;
+        };
+        if requested_at.elapsed() > QUEUE_TIMEOUT {
+            request.resolve_without_lookup();
+            continue;
+        }
+        request.resolve(|address| {
+            if negative_cache.lock().unwrap().contains(address) {
+                return None;
+            }
+            let r_dns = address
+                .parse()
+                .ok()
+                .and_then(|ip| get_r_dns(&ip, lookup_addr(&ip).ok()));
+            if r_dns.is_none() {
+                negative_cache.lock().unwrap().insert(address);
+            }
+            r_dns
+        });
+    }
+}
+
+/// Returns the host name resulting from a lookup, or None if the address couldn't be resolved
+fn get_r_dns(address: &IpAddr, lookup_result: Option<String>) -> Option<String> {
+    // the resolver returns the address itself when no name is found
+    lookup_result.filter(|name| !name.is_empty() && name.parse::<IpAddr>().ok() != Some(*address))
+}
+
+/// Addresses whose lookup recently failed, with the instant of the failure
+#[derive(Default)]
+struct NegativeCache {
+    failures: HashMap<String, Instant>,
+}
+
+impl NegativeCache {
+    fn contains(&self, address: &str) -> bool {
+        self.failures
+            .get(address)
+            .is_some_and(|failed_at| failed_at.elapsed() < NEGATIVE_CACHE_TTL)
+    }
+
+    fn insert(&mut self, address: &str) {
+        if self.failures.len() >= NEGATIVE_CACHE_PRUNE_LEN {
+            self.failures
+                .retain(|_, failed_at| failed_at.elapsed() < NEGATIVE_CACHE_TTL);
+        }
+        self.failures.insert(address.to_string(), Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Sub;
+
+    use super::*;
+
+    #[test]
+    fn test_get_r_dns() {
+        let address: IpAddr = "140.82.121.4".parse().unwrap();
+        assert_eq!(
+            get_r_dns(&address, Some("lb-140-82-121-4-fra.github.com".to_string())),
+            Some("lb-140-82-121-4-fra.github.com".to_string())
+        );
+        assert_eq!(get_r_dns(&address, Some("140.82.121.4".to_string())), None);
+        assert_eq!(get_r_dns(&address, Some(String::new())), None);
+        assert_eq!(get_r_dns(&address, None), None);
+
+        let address: IpAddr = "2001:4860::8888".parse().unwrap();
+        assert_eq!(
+            get_r_dns(&address, Some("2001:4860:0:0:0:0:0:8888".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_negative_cache() {
+        let mut negative_cache = NegativeCache::default();
+        assert!(!negative_cache.contains("10.0.0.1"));
+        negative_cache.insert("10.0.0.1");
+        assert!(negative_cache.contains("10.0.0.1"));
+        assert!(!negative_cache.contains("10.0.0.2"));
+
+        // expired failures are retried
+        negative_cache.failures.insert(
+            "10.0.0.2".to_string(),
+            Instant::now().sub(NEGATIVE_CACHE_TTL),
+        );
+        assert!(!negative_cache.contains("10.0.0.2"));
+    }
+
+    #[test]
+    fn test_negative_cache_pruning() {
+        let mut negative_cache = NegativeCache::default();
+        let expired = Instant::now().sub(NEGATIVE_CACHE_TTL);
+        for i in 0..NEGATIVE_CACHE_PRUNE_LEN {
+            negative_cache.failures.insert(i.to_string(), expired);
+        }
+        negative_cache.insert("10.0.0.1");
+        assert_eq!(negative_cache.failures.len(), 1);
+        assert!(negative_cache.contains("10.0.0.1"));
+    }
+}
@@ -0,0 +1,268 @@
+//! Module in charge of extracting the name of the server contacted by a TCP flow,
+//! as declared by the client in the TLS handshake (SNI) or in the HTTP `Host` header.
+//!
+//! Unlike PTR records, these names identify the actual website or service,
+//! even when it's hosted by a CDN or a cloud provider.
+
+use std::net::IpAddr;
+
+use etherparse::{LaxPacketHeaders, TransportHeader};
+
+/// Content type of TLS handshake records
+const TLS_HANDSHAKE: u8 = 22;
+/// Type of the `ClientHello` handshake message
+const CLIENT_HELLO: u8 = 1;
+/// Type of the `server_name` TLS extension
+const SERVER_NAME_EXTENSION: u16 = 0;
+/// Type of the entries of the `server_name` extension containing a DNS host name
+const HOST_NAME: u8 = 0;
+/// Maximum length of a domain name
+const MAX_NAME_LEN: usize = 253;
+/// Methods starting an HTTP request
+pub const HTTP_METHODS: [&[u8]; 9] = [
+    b"GET ",
+    b"POST ",
+    b"HEAD ",
+    b"PUT ",
+    b"DELETE ",
+    b"OPTIONS ",
+    b"PATCH ",
+    b"CONNECT ",
+    b"TRACE ",
+];
+
+/// Returns the server name carried by the packet, if it's a TCP segment containing
+/// a TLS `ClientHello` with the SNI extension or the beginning of an HTTP request.
+pub fn get_server_name(headers: &LaxPacketHeaders) -> Option<String> {
+    if !matches!(headers.transport, Some(TransportHeader::Tcp(_))) {
+        return None;
+    }
+    let payload = headers.payload.slice();
+    match payload.first()? {
+        &TLS_HANDSHAKE => parse_client_hello(payload),
+        _ => parse_http_host(payload),
+    }
+    .filter(|name| is_valid_name(name))
+}
+
+/// Parses the `server_name` extension of a TLS `ClientHello`.
+///
+/// The message may be truncated, in which case the name is only returned if it's entirely available.
+fn parse_client_hello(record: &[u8]) -> Option<String> {
+    // record header: content type, version, length
+    if record.get(1) != Some(&3) {
+        return None;
+    }
+    // handshake header: type, length
+    if record.get(5) != Some(&CLIENT_HELLO) {
+        return None;
+    }
+    // skip handshake header, client version, and random
+    let mut offset = 5 + 4 + 2 + 32;
+    // skip session ID, cipher suites, and compression methods
+    offset += 1 + usize::from(*record.get(offset)?);
+    offset += 2 + usize::from(read_u16(record, offset)?);
+    offset += 1 + usize::from(*record.get(offset)?);
+
+    let extensions_end = offset + 2 + usize::from(read_u16(record, offset)?);
+    offset += 2;
+    while offset + 4 <= extensions_end {
+        let extension_type = read_u16(record, offset)?;
+        let extension_len = usize::from(read_u16(record, offset + 2)?);
+        offset += 4;
+        if extension_type == SERVER_NAME_EXTENSION {
+            let list_end = offset + 2 + usize::from(read_u16(record, offset)?);
+            offset += 2;
+            while offset + 3 <= list_end {
+                let name_type = *record.get(offset)?;
+                let name_len = usize::from(read_u16(record, offset + 1)?);
+                let name = record.get(offset + 3..offset + 3 + name_len)?;
+                if name_type == HOST_NAME {
+                    return Some(String::from_utf8_lossy(name).to_lowercase());
+                }
+                offset += 3 + name_len;
+            }
+            return None;
+        }
+        offset += extension_len;
+    }
+    None
+}
+
+/// Parses the `Host` header of an HTTP request, removing the port if present.
+fn parse_http_host(request: &[u8]) -> Option<String> {
+    if !HTTP_METHODS
+        .iter()
+        .any(|method| request.starts_with(method))
+    {
+        return None;
+    }
+    let request = String::from_utf8_lossy(request);
+    // skip the request line, and stop at the end of the headers
+    let host = request
+        .split("\r\n")
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case("host")
+                .then(|| value.trim())
+        })?;
+    let host = match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    };
+    Some(host.to_lowercase())
+}
+
+/// Checks that the name is a plausible domain name (and not an IP address)
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '_')
+        && name.parse::<IpAddr>().is_err()
+}
+
+fn read_u16(message: &[u8], offset: usize) -> Option<u16> {
+    let bytes = message.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a TLS record containing a `ClientHello` with the given extensions
+    fn client_hello(extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut body = vec![3, 3];
+        body.extend_from_slice(&[7; 32]);
+        // session ID
+        body.push(32);
+        body.extend_from_slice(&[1; 32]);
+        // cipher suites
+        body.extend_from_slice(&[0, 4, 0x13, 0x01, 0x13, 0x02]);
+        // compression methods
+        body.extend_from_slice(&[1, 0]);
+        let mut extensions_bytes = Vec::new();
+        for (extension_type, data) in extensions {
+            extensions_bytes.extend_from_slice(&extension_type.to_be_bytes());
+            extensions_bytes.extend_from_slice(&u16::try_from(data.len()).unwrap().to_be_bytes());
+            extensions_bytes.extend_from_slice(data);
+        }
+        body.extend_from_slice(&u16::try_from(extensions_bytes.len()).unwrap().to_be_bytes());
+        body.extend_from_slice(&extensions_bytes);
+
+        let mut handshake = vec![CLIENT_HELLO];
+        handshake.extend_from_slice(&u32::try_from(body.len()).unwrap().to_be_bytes()[1..]);
+        handshake.extend_from_slice(&body);
+
+        let mut record = vec![TLS_HANDSHAKE, 3, 1];
+        record.extend_from_slice(&u16::try_from(handshake.len()).unwrap().to_be_bytes());
+        record.extend_from_slice(&handshake);
+        record
+    }
+
+    fn sni_extension(name: &str) -> (u16, Vec<u8>) {
+        let mut entry = vec![HOST_NAME];
+        entry.extend_from_slice(&u16::try_from(name.len()).unwrap().to_be_bytes());
+        entry.extend_from_slice(name.as_bytes());
+        let mut data = u16::try_from(entry.len()).unwrap().to_be_bytes().to_vec();
+        data.extend_from_slice(&entry);
+        (SERVER_NAME_EXTENSION, data)
+    }
+
+    #[test]
+    fn test_parse_client_hello() {
+        let record = client_hello(&[
+            // supported groups
+            (10, vec![0, 2, 0, 29]),
+            sni_extension("WWW.Sniffnet.net"),
+            // ALPN
+            (16, vec![0, 3, 2, b'h', b'2']),
+        ]);
+        assert_eq!(
+            parse_client_hello(&record),
+            Some("www.sniffnet.net".to_string())
+        );
+
+        // without SNI
+        let record = client_hello(&[(10, vec![0, 2, 0, 29])]);
+        assert_eq!(parse_client_hello(&record), None);
+
+        // truncated before the end of the name
+        let record = client_hello(&[sni_extension("www.sniffnet.net")]);
+        assert_eq!(parse_client_hello(&record[..record.len() - 3]), None);
+
+        // other handshake messages (ServerHello)
+        let mut record = client_hello(&[sni_extension("www.sniffnet.net")]);
+        record[5] = 2;
+        assert_eq!(parse_client_hello(&record), None);
+    }
+
+    #[test]
+    fn test_parse_http_host() {
+        assert_eq!(
+            parse_http_host(
+                b"GET /index.html HTTP/1.1\r\nHost: Example.com:8080\r\nAccept: */*\r\n\r\n"
+            ),
+            Some("example.com".to_string())
+        );
+        assert_eq!(
+            parse_http_host(
+                b"POST /api HTTP/1.1\r\nContent-Length: 0\r\nhost:api.example.com\r\n\r\n"
+            ),
+            Some("api.example.com".to_string())
+        );
+        // host header in the body is ignored
+        assert_eq!(
+            parse_http_host(b"GET / HTTP/1.1\r\nAccept: */*\r\n\r\nHost: example.com\r\n"),
+            None
+        );
+        // not a request
+        assert_eq!(
+            parse_http_host(b"HTTP/1.1 200 OK\r\nHost: example.com\r\n\r\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(is_valid_name("www.sniffnet.net"));
+        assert!(is_valid_name("_dns.resolver.arpa"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("192.168.1.1"));
+        assert!(!is_valid_name("[::1]"));
+        assert!(!is_valid_name("exa mple.com"));
+        assert!(!is_valid_name("exämple.com"));
+        assert!(!is_valid_name(&"a".repeat(254)));
+    }
+
+    #[test]
+    fn test_get_server_name_from_packet() {
+        let packet = |payload: &[u8]| {
+            let builder = etherparse::PacketBuilder::ipv4([192, 168, 1, 10], [13, 32, 1, 1], 64)
+                .tcp(50000, 443, 1, 64240);
+            let mut packet = Vec::new();
+            builder.write(&mut packet, payload).unwrap();
+            packet
+        };
+
+        let tls_packet = packet(&client_hello(&[sni_extension("www.sniffnet.net")]));
+        let headers = LaxPacketHeaders::from_ip(&tls_packet).unwrap();
+        assert_eq!(
+            get_server_name(&headers),
+            Some("www.sniffnet.net".to_string())
+        );
+
+        let http_packet = packet(b"GET / HTTP/1.1\r\nHost: 13.32.1.1\r\n\r\n");
+        let headers = LaxPacketHeaders::from_ip(&http_packet).unwrap();
+        assert_eq!(get_server_name(&headers), None);
+
+        let empty_packet = packet(&[]);
+        let headers = LaxPacketHeaders::from_ip(&empty_packet).unwrap();
+        assert_eq!(get_server_name(&headers), None);
+    }
+}
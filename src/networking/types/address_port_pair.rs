@@ -1,9 +1,11 @@
 //! Module defining the `AddressPortPair` struct, which represents a network address:port pair.
 
+use serde::{Deserialize, Serialize};
+
 use crate::Protocol;
 
 /// Struct representing a network address:port pair.
-#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Serialize, Deserialize)]
 pub struct AddressPortPair {
     /// Network layer IPv4 or IPv6 source address.
     pub address1: String,
@@ -15,6 +17,10 @@ pub struct AddressPortPair {
     pub port2: Option<u16>,
     ///  Transport layer protocol carried through the associate address:port pair (TCP or UPD).
     pub protocol: Protocol,
+    /// Name of the network adapter the pair was observed on (None for capture files),
+    /// so that the same traffic seen on different adapters is kept separate.
+    #[serde(default)]
+    pub interface: Option<String>,
 }
 
 impl AddressPortPair {
@@ -38,6 +44,25 @@ pub fn new(
             address2,
             port2,
             protocol,
+            interface: None,
+        }
+    }
+
+    /// Returns the same pair, observed on the given network adapter
+    pub fn with_interface(mut self, interface: Option<String>) -> Self {
+        self.interface = interface;
+        self
+    }
+
+    /// Returns the pair of the opposite direction (source and destination swapped)
+    pub fn reversed(&self) -> Self {
+        AddressPortPair {
+            address1: self.address2.clone(),
+            port1: self.port2,
+            address2: self.address1.clone(),
+            port2: self.port1,
+            protocol: self.protocol,
+            interface: self.interface.clone(),
         }
     }
 }
@@ -1,54 +1,91 @@
-use pcap::{Active, Capture, Savefile};
+use pcap::{Activated, Active, Capture, Packet, Stat};
 
+use crate::gui::types::export_pcap::is_pcapng;
+use crate::networking::pcapng_writer::PcapngSection;
+use crate::networking::types::filters::Filters;
 use crate::networking::types::my_device::MyDevice;
 use crate::networking::types::my_link_type::MyLinkType;
+use crate::networking::types::pcap_recorder::PcapRecorder;
+use crate::networking::types::pcap_rotation::PcapRotation;
+use crate::utils::formatted_strings::APP_VERSION;
+use crate::SNIFFNET_TITLECASE;
 
 pub enum CaptureContext {
     Online(Online),
-    OnlineWithSavefile(OnlineWithSavefile),
+    OnlineWithSavefile(Box<OnlineWithSavefile>),
+    Offline(Offline),
     Error(String),
 }
 
 impl CaptureContext {
-    pub fn new(device: &MyDevice, pcap_path: &Option<String>) -> Self {
+    pub fn new(
+        device: &MyDevice,
+        pcap_path: &Option<String>,
+        pcap_rotation: PcapRotation,
+        filters: &Filters,
+        snaplen: u16,
+    ) -> Self {
         let cap_res = Capture::from_device(device.to_pcap_device())
             .expect("Capture initialization error\n\r")
             .promisc(true)
             .snaplen(if pcap_path.is_some() {
                 i32::from(u16::MAX)
             } else {
-                256 //limit stored packets slice dimension (to keep more in the buffer)
+                i32::from(snaplen)
             })
             .immediate_mode(true) //parse packets ASAP!
+            // wake up periodically even without traffic, to write the recorded packets to disk
+            .timeout(500)
             .open();
 
         if let Err(e) = &cap_res {
             return Self::Error(e.to_string());
         }
 
-        let cap = cap_res.unwrap();
+        let mut cap = cap_res.unwrap();
+
+        if let Err(e) = apply_bpf(&mut cap, &filters.bpf) {
+            return Self::Error(e.to_string());
+        }
 
         if let Some(path) = pcap_path {
-            let savefile_res = cap.savefile(path);
-            if let Err(e) = savefile_res {
-                Self::Error(e.to_string())
-            } else {
-                Self::new_online_with_savefile(cap, savefile_res.unwrap())
+            let pcapng_section = is_pcapng(path).then(|| PcapngSection {
+                application: format!("{SNIFFNET_TITLECASE} {APP_VERSION}"),
+                comment: filters.description(),
+                interface_name: device.name.clone(),
+                interface_description: device.desc.clone(),
+            });
+            match PcapRecorder::new(path, pcap_rotation, cap.get_datalink(), pcapng_section) {
+                Ok(savefile) => Self::new_online_with_savefile(cap, savefile),
+                Err(e) => Self::Error(e),
             }
         } else {
             Self::new_online(cap)
         }
     }
 
+    /// Opens a PCAP or PCAPNG file to be analyzed instead of a live network adapter
+    pub fn new_offline(file_path: &str, real_time: bool, bpf: &str) -> Self {
+        match Capture::from_file(file_path) {
+            Ok(mut cap) => {
+                if let Err(e) = apply_bpf(&mut cap, bpf) {
+                    return Self::Error(e.to_string());
+                }
+                Self::Offline(Offline { cap, real_time })
+            }
+            Err(e) => Self::Error(e.to_string()),
+        }
+    }
+
     fn new_online(cap: Capture<Active>) -> Self {
         Self::Online(Online { cap })
     }
 
-    fn new_online_with_savefile(cap: Capture<Active>, savefile: Savefile) -> Self {
-        Self::OnlineWithSavefile(OnlineWithSavefile {
+    fn new_online_with_savefile(cap: Capture<Active>, savefile: PcapRecorder) -> Self {
+        Self::OnlineWithSavefile(Box::new(OnlineWithSavefile {
             online: Online { cap },
             savefile,
-        })
+        }))
     }
 
     pub fn error(&self) -> Option<&str> {
@@ -58,10 +95,27 @@ pub fn error(&self) -> Option<&str> {
         }
     }
 
-    pub fn consume(self) -> (Capture<Active>, Option<Savefile>) {
+    /// Path of the file the packets are recorded to, if any
+    pub fn savefile_path(&self) -> Option<String> {
+        match self {
+            Self::OnlineWithSavefile(ows) => Some(ows.savefile.base_path().to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn consume(self) -> (CaptureType, Option<PcapRecorder>) {
         match self {
-            Self::Online(o) => (o.cap, None),
-            Self::OnlineWithSavefile(ows) => (ows.online.cap, Some(ows.savefile)),
+            Self::Online(o) => (CaptureType::Online(o.cap), None),
+            Self::OnlineWithSavefile(ows) => {
+                (CaptureType::Online(ows.online.cap), Some(ows.savefile))
+            }
+            Self::Offline(o) => (
+                CaptureType::Offline {
+                    cap: o.cap,
+                    real_time: o.real_time,
+                },
+                None,
+            ),
             Self::Error(_) => panic!(),
         }
     }
@@ -72,16 +126,73 @@ pub fn my_link_type(&self) -> MyLinkType {
             Self::OnlineWithSavefile(ows) => {
                 MyLinkType::from_pcap_link_type(ows.online.cap.get_datalink())
             }
+            Self::Offline(o) => MyLinkType::from_pcap_link_type(o.cap.get_datalink()),
             Self::Error(_) => MyLinkType::default(),
         }
     }
 }
 
+/// Attaches the BPF expression to the capture, so that the kernel discards unwanted packets
+fn apply_bpf<T: Activated + ?Sized>(cap: &mut Capture<T>, bpf: &str) -> Result<(), pcap::Error> {
+    if bpf.trim().is_empty() {
+        Ok(())
+    } else {
+        cap.filter(bpf, true)
+    }
+}
+
 pub struct Online {
     cap: Capture<Active>,
 }
 
 pub struct OnlineWithSavefile {
     online: Online,
-    savefile: Savefile,
+    savefile: PcapRecorder,
+}
+
+pub struct Offline {
+    cap: Capture<pcap::Offline>,
+    /// Whether packets should be replayed respecting their original timing
+    real_time: bool,
+}
+
+/// The capture handle used by the thread parsing packets
+pub enum CaptureType {
+    Online(Capture<Active>),
+    Offline {
+        cap: Capture<pcap::Offline>,
+        real_time: bool,
+    },
+}
+
+impl CaptureType {
+    pub fn next_packet(&mut self) -> Result<Packet<'_>, pcap::Error> {
+        match self {
+            Self::Online(cap) => cap.next_packet(),
+            Self::Offline { cap, .. } => cap.next_packet(),
+        }
+    }
+
+    pub fn stats(&mut self) -> Result<Stat, pcap::Error> {
+        match self {
+            Self::Online(cap) => cap.stats(),
+            Self::Offline { cap, .. } => cap.stats(),
+        }
+    }
+
+    /// Whether packets are sniffed from a live network adapter
+    pub fn is_online(&self) -> bool {
+        matches!(self, Self::Online(_))
+    }
+
+    /// Whether packets have to be replayed respecting their original timing
+    pub fn is_real_time_replay(&self) -> bool {
+        matches!(
+            self,
+            Self::Offline {
+                real_time: true,
+                ..
+            }
+        )
+    }
 }
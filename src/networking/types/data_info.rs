@@ -4,14 +4,16 @@
 use std::ops::AddAssign;
 
 use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
 
 use crate::chart::types::chart_type::ChartType;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::report::types::sort_type::SortType;
 
 /// Amount of exchanged data (packets and bytes) incoming and outgoing, with the timestamp of the latest occurrence
 // data fields are private to make them only editable via the provided methods: needed to correctly refresh timestamps
-#[derive(Clone, Default, Copy)]
+#[derive(Clone, Default, Copy, Serialize, Deserialize)]
 pub struct DataInfo {
     /// Incoming packets
     incoming_packets: u128,
@@ -50,7 +52,17 @@ pub fn tot_bytes(&self) -> u128 {
         self.incoming_bytes + self.outgoing_bytes
     }
 
-    pub fn add_packet(&mut self, bytes: u128, traffic_direction: TrafficDirection) {
+    pub fn final_timestamp(&self) -> DateTime<Local> {
+        self.final_timestamp
+    }
+
+    /// Adds a packet captured at the given time
+    pub fn add_packet(
+        &mut self,
+        bytes: u128,
+        traffic_direction: TrafficDirection,
+        timestamp: DateTime<Local>,
+    ) {
         if traffic_direction.eq(&TrafficDirection::Outgoing) {
             self.outgoing_packets += 1;
             self.outgoing_bytes += bytes;
@@ -58,17 +70,22 @@ pub fn add_packet(&mut self, bytes: u128, traffic_direction: TrafficDirection) {
             self.incoming_packets += 1;
             self.incoming_bytes += bytes;
         }
-        self.final_timestamp = Local::now();
+        // packets captured on different adapters may be processed out of order
+        self.final_timestamp = self.final_timestamp.max(timestamp);
     }
 
-    pub fn new_with_first_packet(bytes: u128, traffic_direction: TrafficDirection) -> Self {
+    pub fn new_with_first_packet(
+        bytes: u128,
+        traffic_direction: TrafficDirection,
+        timestamp: DateTime<Local>,
+    ) -> Self {
         if traffic_direction.eq(&TrafficDirection::Outgoing) {
             Self {
                 incoming_packets: 0,
                 outgoing_packets: 1,
                 incoming_bytes: 0,
                 outgoing_bytes: bytes,
-                final_timestamp: Local::now(),
+                final_timestamp: timestamp,
             }
         } else {
             Self {
@@ -76,11 +93,42 @@ pub fn new_with_first_packet(bytes: u128, traffic_direction: TrafficDirection) -
                 outgoing_packets: 0,
                 incoming_bytes: bytes,
                 outgoing_bytes: 0,
-                final_timestamp: Local::now(),
+                final_timestamp: timestamp,
             }
         }
     }
 
+    /// Adds the data exchanged by the given connection, keeping the latest time of occurrence
+    pub fn add_connection(&mut self, connection: &InfoAddressPortPair) {
+        if connection.traffic_direction.eq(&TrafficDirection::Outgoing) {
+            self.outgoing_packets += connection.transmitted_packets;
+            self.outgoing_bytes += connection.transmitted_bytes;
+        } else {
+            self.incoming_packets += connection.transmitted_packets;
+            self.incoming_bytes += connection.transmitted_bytes;
+        }
+        self.final_timestamp = self.final_timestamp.max(connection.final_timestamp);
+    }
+
+    /// Removes the data exchanged by the given connection
+    pub fn remove_connection(&mut self, connection: &InfoAddressPortPair) {
+        if connection.traffic_direction.eq(&TrafficDirection::Outgoing) {
+            self.outgoing_packets = self
+                .outgoing_packets
+                .saturating_sub(connection.transmitted_packets);
+            self.outgoing_bytes = self
+                .outgoing_bytes
+                .saturating_sub(connection.transmitted_bytes);
+        } else {
+            self.incoming_packets = self
+                .incoming_packets
+                .saturating_sub(connection.transmitted_packets);
+            self.incoming_bytes = self
+                .incoming_bytes
+                .saturating_sub(connection.transmitted_bytes);
+        }
+    }
+
     pub fn compare(&self, other: &Self, sort_type: SortType, chart_type: ChartType) -> Ordering {
         match chart_type {
             ChartType::Packets => match sort_type {
@@ -119,6 +167,6 @@ fn add_assign(&mut self, rhs: Self) {
         self.outgoing_packets += rhs.outgoing_packets;
         self.incoming_bytes += rhs.incoming_bytes;
         self.outgoing_bytes += rhs.outgoing_bytes;
-        self.final_timestamp = Local::now();
+        self.final_timestamp = self.final_timestamp.max(rhs.final_timestamp);
     }
 }
@@ -1,10 +1,13 @@
 //! Module defining the `DataInfoHost` struct related to hosts.
 
+use serde::{Deserialize, Serialize};
+
 use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::tcp_quality::TcpQuality;
 use crate::networking::types::traffic_type::TrafficType;
 
 /// Host-related information.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
 pub struct DataInfoHost {
     /// Incoming and outgoing packets and bytes
     pub data_info: DataInfo,
@@ -16,4 +19,7 @@ pub struct DataInfoHost {
     pub is_local: bool,
     /// Determine if the connection with this host is unicast, multicast, or broadcast
     pub traffic_type: TrafficType,
+    /// Quality metrics of the TCP connections with this host
+    #[serde(default)]
+    pub tcp_quality: TcpQuality,
 }
@@ -2,13 +2,16 @@
 
 use std::collections::HashSet;
 
+use pcap::{Capture, Linktype};
+use serde::{Deserialize, Serialize};
+
 use crate::networking::types::ip_collection::AddressCollection;
 use crate::networking::types::packet_filters_fields::PacketFiltersFields;
 use crate::networking::types::port_collection::PortCollection;
 use crate::{IpVersion, Protocol};
 
 /// Possible filters applicable to network traffic
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Filters {
     /// Internet Protocol versions
     pub ip_versions: HashSet<IpVersion>,
@@ -17,11 +20,18 @@ pub struct Filters {
     /// IP addresses string in Initial page text input
     pub address_str: String,
     /// IP address collection to match against traffic
+    #[serde(skip)]
     pub address_collection: AddressCollection,
     /// Ports string in Initial page text input
     pub port_str: String,
     /// Port collection to match against traffic
+    #[serde(skip)]
     pub port_collection: PortCollection,
+    /// BPF expression attached to the capture, evaluated before packets reach the app
+    pub bpf: String,
+    /// Error compiling the BPF expression, computed when the expression is changed
+    #[serde(skip)]
+    pub bpf_error: Option<String>,
 }
 
 impl Default for Filters {
@@ -33,12 +43,19 @@ fn default() -> Self {
             address_collection: AddressCollection::default(),
             port_str: String::new(),
             port_collection: PortCollection::default(),
+            bpf: String::new(),
+            bpf_error: None,
         }
     }
 }
 
 impl Filters {
+    pub const BPF_PLACEHOLDER_STR: &'static str = "not arp and tcp port 443";
+
     /// Checks whether the filters match the current packet's protocols
+    ///
+    /// A packet matches the address and port filters if at least one of its endpoints is included,
+    /// and none of them is excluded (e.g., `!192.168.1.1` hides all the traffic of that host)
     pub fn matches(&self, packet_filters_fields: &PacketFiltersFields) -> bool {
         self.ip_versions.contains(&packet_filters_fields.ip_version)
             && self.protocols.contains(&packet_filters_fields.protocol)
@@ -48,8 +65,32 @@ pub fn matches(&self, packet_filters_fields: &PacketFiltersFields) -> bool {
                 || self
                     .address_collection
                     .contains(&packet_filters_fields.dest))
+            && !self
+                .address_collection
+                .excludes(&packet_filters_fields.source)
+            && !self
+                .address_collection
+                .excludes(&packet_filters_fields.dest)
             && (self.port_collection.contains(packet_filters_fields.sport)
                 || self.port_collection.contains(packet_filters_fields.dport))
+            && !self.port_collection.excludes(packet_filters_fields.sport)
+            && !self.port_collection.excludes(packet_filters_fields.dport)
+    }
+
+    /// Checks whether the filters match the frames not carrying IP packets
+    ///
+    /// These frames have no addresses nor ports, so they're discarded
+    /// whenever the address or the port filter is active
+    pub fn matches_non_ip(&self) -> bool {
+        self.protocols.contains(&Protocol::L2) && !self.address_active() && !self.port_active()
+    }
+
+    /// Rebuilds the address and port collections from the respective strings
+    ///
+    /// Collections are not serialized, since they can be derived from the strings
+    pub fn rebuild_collections(&mut self) {
+        self.address_collection = AddressCollection::new(&self.address_str).unwrap_or_default();
+        self.port_collection = PortCollection::new(&self.port_str).unwrap_or_default();
     }
 
     pub fn are_valid(&self) -> bool {
@@ -57,6 +98,7 @@ pub fn are_valid(&self) -> bool {
             && self.protocol_valid()
             && self.address_valid()
             && self.port_valid()
+            && self.bpf_valid()
     }
 
     pub fn ip_version_valid(&self) -> bool {
@@ -75,11 +117,32 @@ pub fn port_valid(&self) -> bool {
         PortCollection::new(&self.port_str).is_some()
     }
 
+    pub fn bpf_valid(&self) -> bool {
+        self.bpf_error.is_none()
+    }
+
+    /// Sets the BPF expression, compiling it to store the libpcap error if it's not valid
+    ///
+    /// The expression is checked against an Ethernet link type, since the actual one
+    /// is only known after the capture is opened
+    pub fn set_bpf(&mut self, bpf: String) {
+        self.bpf = bpf;
+        self.bpf_error = if self.bpf_active() {
+            Capture::dead(Linktype::ETHERNET)
+                .and_then(|cap| cap.compile(&self.bpf, true))
+                .err()
+                .map(|e| e.to_string())
+        } else {
+            None
+        };
+    }
+
     pub fn none_active(&self) -> bool {
         !self.ip_version_active()
             && !self.protocol_active()
             && !self.address_active()
             && !self.port_active()
+            && !self.bpf_active()
     }
 
     pub fn ip_version_active(&self) -> bool {
@@ -98,6 +161,31 @@ pub fn port_active(&self) -> bool {
         self.port_collection != PortCollection::default()
     }
 
+    pub fn bpf_active(&self) -> bool {
+        !self.bpf.trim().is_empty()
+    }
+
+    /// Describes the active filters (e.g., `protocols: TCP; BPF: port 443`)
+    pub fn description(&self) -> String {
+        let mut active_filters = Vec::new();
+        if self.ip_version_active() {
+            active_filters.push(format!("IP versions: {}", self.pretty_print_ip()));
+        }
+        if self.protocol_active() {
+            active_filters.push(format!("protocols: {}", self.pretty_print_protocol()));
+        }
+        if self.address_active() {
+            active_filters.push(format!("addresses: {}", self.address_str.trim()));
+        }
+        if self.port_active() {
+            active_filters.push(format!("ports: {}", self.port_str.trim()));
+        }
+        if self.bpf_active() {
+            active_filters.push(format!("BPF: {}", self.bpf.trim()));
+        }
+        active_filters.join("; ")
+    }
+
     pub fn pretty_print_ip(&self) -> String {
         format!("{:?}", self.ip_versions)
             .replace('{', "")
@@ -110,3 +198,46 @@ pub fn pretty_print_protocol(&self) -> String {
             .replace('}', "")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use crate::networking::types::filters::Filters;
+    use crate::networking::types::packet_filters_fields::PacketFiltersFields;
+
+    fn fields(source: &str, dest: &str) -> PacketFiltersFields {
+        PacketFiltersFields {
+            source: source.parse::<IpAddr>().unwrap(),
+            dest: dest.parse::<IpAddr>().unwrap(),
+            sport: Some(50000),
+            dport: Some(443),
+            ..PacketFiltersFields::default()
+        }
+    }
+
+    #[test]
+    fn test_matches_with_one_endpoint_excluded() {
+        let mut filters = Filters {
+            address_str: "!192.168.1.1".to_string(),
+            ..Filters::default()
+        };
+        filters.rebuild_collections();
+        // the excluded host is hidden, whatever the other endpoint
+        assert!(!filters.matches(&fields("192.168.1.10", "192.168.1.1")));
+        assert!(!filters.matches(&fields("192.168.1.1", "8.8.8.8")));
+        assert!(filters.matches(&fields("192.168.1.10", "8.8.8.8")));
+
+        // excluding the network of the capturing host hides all its traffic
+        filters.address_str = "!10.0.0.0/8".to_string();
+        filters.rebuild_collections();
+        assert!(!filters.matches(&fields("10.0.0.5", "1.1.1.1")));
+        assert!(filters.matches(&fields("192.168.1.10", "1.1.1.1")));
+
+        // an excluded port hides the replies too
+        filters.address_str = String::new();
+        filters.port_str = "!443".to_string();
+        filters.rebuild_collections();
+        assert!(!filters.matches(&fields("192.168.1.10", "8.8.8.8")));
+    }
+}
@@ -4,15 +4,19 @@
 use std::collections::HashMap;
 
 use chrono::{DateTime, Local};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::networking::types::icmp_type::IcmpType;
+use crate::networking::types::program::Program;
+use crate::networking::types::tcp_connection::TcpConnection;
+use crate::networking::types::tcp_quality::{TcpQuality, TcpTracker};
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::Service;
 
 /// Struct useful to format the output report file and to keep track of statistics about the sniffed traffic.
 ///
 /// Each `InfoAddressPortPair` struct is associated to a single address:port pair.
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct InfoAddressPortPair {
     /// Source MAC address
     pub mac_address1: Option<String>,
@@ -28,8 +32,47 @@ pub struct InfoAddressPortPair {
     pub final_timestamp: DateTime<Local>,
     /// Upper layer service carried by the associated address:port pair.
     pub service: Service,
+    /// Whether the service was detected from the payload (otherwise it was guessed from the ports)
+    #[serde(default)]
+    pub service_from_payload: bool,
+    /// Program owning the local socket of the associated address:port pair.
+    pub program: Program,
     /// Determines if the connection is incoming or outgoing
     pub traffic_direction: TrafficDirection,
+    /// Name of the server declared by the client in the TLS handshake (SNI) or in the HTTP `Host` header
+    #[serde(default)]
+    pub server_name: Option<String>,
+    /// Index of the interface the connection was captured on (only known for Linux cooked captures)
+    #[serde(default)]
+    pub interface_index: Option<u32>,
     /// Types of the ICMP messages exchanged, with the relative count (this is empty if not ICMP)
+    #[serde(
+        serialize_with = "serialize_icmp_types",
+        deserialize_with = "deserialize_icmp_types"
+    )]
     pub icmp_types: HashMap<IcmpType, usize>,
+    /// Lifecycle of the TCP connection (None if not TCP)
+    #[serde(default)]
+    pub tcp_connection: Option<TcpConnection>,
+    /// Quality metrics of the TCP segments sent
+    #[serde(default)]
+    pub tcp_quality: TcpQuality,
+    /// Sequence numbers and windows of the TCP segments sent, to derive their quality metrics
+    #[serde(skip)]
+    pub tcp_tracker: TcpTracker,
+}
+
+/// ICMP types are serialized as a list of pairs, since they can't be used as keys of a JSON object
+fn serialize_icmp_types<S: Serializer>(
+    icmp_types: &HashMap<IcmpType, usize>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(icmp_types)
+}
+
+fn deserialize_icmp_types<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashMap<IcmpType, usize>, D::Error> {
+    let icmp_types = Vec::<(IcmpType, usize)>::deserialize(deserializer)?;
+    Ok(icmp_types.into_iter().collect())
 }
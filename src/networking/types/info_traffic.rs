@@ -1,16 +1,31 @@
 //! Module defining the `ReportInfo` struct, useful to format the output report file and
 //! to keep track of statistics about the sniffed traffic.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Local};
 
 use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_info_host::DataInfoHost;
 use crate::networking::types::host::Host;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::l2_pair::L2Pair;
+use crate::networking::types::lan_device::LanDevice;
+use crate::networking::types::name_source::NameSource;
+use crate::networking::types::packet_buffer::{CapturedPacket, PacketBuffers};
+use crate::networking::types::pcap_export::PcapExport;
+use crate::networking::types::pcap_recorder::PcapRecorder;
+use crate::networking::types::tcp_connection::TcpStats;
+use crate::networking::types::tcp_quality::TcpQuality;
 use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::Service;
 
+/// Maximum number of addresses whose domain name observed in the traffic is remembered
+const MAX_DNS_NAMES: usize = 100_000;
+
 /// Struct to be shared between the threads in charge of parsing packets and update reports.
 pub struct InfoTraffic {
     /// Total amount of filtered bytes received.
@@ -35,12 +50,48 @@ pub struct InfoTraffic {
     pub favorites_last_interval: HashSet<Host>,
     /// Map of the upper layer services with their data info
     pub services: HashMap<Service, DataInfo>,
+    /// Map of the names of the programs owning the connections with their data info
+    pub programs: HashMap<String, DataInfo>,
+    /// Map of the connections waiting for the program owning them to be identified, with their data info
+    pub programs_waiting_resolution: HashMap<AddressPortPair, DataInfo>,
     /// Map of the addresses waiting for a rDNS resolution; used to NOT send multiple rDNS for the same address
     pub addresses_waiting_resolution: HashMap<String, DataInfo>,
     /// Map of the resolved addresses with their full rDNS value and the corresponding host
     pub addresses_resolved: HashMap<String, (String, Host)>,
     /// Map of the hosts with their data info
     pub hosts: HashMap<Host, DataInfoHost>,
+    /// Map of the addresses observed in DNS responses with the corresponding queried domain name
+    pub dns_names: HashMap<String, String>,
+    /// Addresses of the observed domain names, from the oldest one
+    dns_names_order: VecDeque<String>,
+    /// Map of the network adapters being captured with their data info
+    pub adapters: HashMap<String, DataInfo>,
+    /// Filtered traffic of each second of the capture, according to the packets timestamps
+    /// (taken at every refresh to update the charts)
+    pub traffic_per_second: BTreeMap<i64, DataInfo>,
+    /// Filtered traffic of each network adapter for each second of the capture
+    pub adapters_per_second: BTreeMap<i64, HashMap<String, DataInfo>>,
+    /// Map of the filtered traffic not carrying IP packets
+    pub non_ip: HashMap<L2Pair, DataInfo>,
+    /// Map of the MAC addresses of the devices of the local network with their information
+    pub devices: HashMap<String, LanDevice>,
+    /// Map of the addresses of the local network with the name they announced and the announcing protocol
+    pub lan_names: HashMap<String, (String, NameSource)>,
+    /// Map of the resolved addresses with the source of their name
+    pub name_sources: HashMap<String, NameSource>,
+    /// Statistics about the outcome of the TCP connections
+    pub tcp_stats: TcpStats,
+    /// Map of the addresses waiting for a rDNS resolution with the quality metrics of their TCP connections
+    pub tcp_quality_waiting_resolution: HashMap<String, TcpQuality>,
+    /// Last raw packets of each entry of the filtered traffic
+    pub packets: PacketBuffers,
+    /// Exports of the packets of the selected connections and hosts to PCAP files
+    /// (also written by the threads exporting the packets already recorded)
+    pub pcap_exports: Vec<Arc<Mutex<PcapExport>>>,
+    /// Recorders of the running captures, identified by the path chosen for their file
+    pub savefiles: HashMap<String, PcapRecorder>,
+    /// Error that stopped a capture (e.g., a truncated or corrupted capture file)
+    pub capture_error: Option<String>,
 }
 
 impl InfoTraffic {
@@ -58,13 +109,35 @@ pub fn new() -> Self {
             favorite_hosts: HashSet::new(),
             favorites_last_interval: HashSet::new(),
             services: HashMap::new(),
+            programs: HashMap::new(),
+            programs_waiting_resolution: HashMap::new(),
             addresses_waiting_resolution: HashMap::new(),
             addresses_resolved: HashMap::new(),
             hosts: HashMap::new(),
+            dns_names: HashMap::new(),
+            dns_names_order: VecDeque::new(),
+            adapters: HashMap::new(),
+            traffic_per_second: BTreeMap::new(),
+            adapters_per_second: BTreeMap::new(),
+            non_ip: HashMap::new(),
+            devices: HashMap::new(),
+            lan_names: HashMap::new(),
+            name_sources: HashMap::new(),
+            tcp_stats: TcpStats::default(),
+            tcp_quality_waiting_resolution: HashMap::new(),
+            packets: PacketBuffers::default(),
+            pcap_exports: Vec::new(),
+            savefiles: HashMap::new(),
+            capture_error: None,
         }
     }
 
-    pub fn add_packet(&mut self, bytes: u128, traffic_direction: TrafficDirection) {
+    pub fn add_packet(
+        &mut self,
+        bytes: u128,
+        traffic_direction: TrafficDirection,
+        timestamp: DateTime<Local>,
+    ) {
         if traffic_direction == TrafficDirection::Outgoing {
             //increment number of sent packets and bytes
             self.tot_out_packets += 1;
@@ -74,5 +147,104 @@ pub fn add_packet(&mut self, bytes: u128, traffic_direction: TrafficDirection) {
             self.tot_in_packets += 1;
             self.tot_in_bytes += bytes;
         }
+        // traffic of the second the packet was captured in
+        self.traffic_per_second
+            .entry(timestamp.timestamp())
+            .and_modify(|data_info| data_info.add_packet(bytes, traffic_direction, timestamp))
+            .or_insert_with(|| {
+                DataInfo::new_with_first_packet(bytes, traffic_direction, timestamp)
+            });
+    }
+
+    /// Adds a packet observed on the given network adapter
+    pub fn add_adapter_packet(
+        &mut self,
+        adapter: &str,
+        bytes: u128,
+        traffic_direction: TrafficDirection,
+        timestamp: DateTime<Local>,
+    ) {
+        for adapters in [
+            &mut self.adapters,
+            self.adapters_per_second
+                .entry(timestamp.timestamp())
+                .or_default(),
+        ] {
+            adapters
+                .entry(adapter.to_string())
+                .and_modify(|data_info| data_info.add_packet(bytes, traffic_direction, timestamp))
+                .or_insert_with(|| {
+                    DataInfo::new_with_first_packet(bytes, traffic_direction, timestamp)
+                });
+        }
+    }
+
+    /// Remembers the domain name observed for an address, forgetting the oldest ones above the limit
+    pub fn add_dns_name(&mut self, address: String, name: String) {
+        match self.dns_names.entry(address) {
+            Entry::Occupied(mut entry) => {
+                entry.insert(name);
+            }
+            Entry::Vacant(entry) => {
+                self.dns_names_order.push_back(entry.key().clone());
+                entry.insert(name);
+            }
+        }
+        if self.dns_names.len() > MAX_DNS_NAMES {
+            if let Some(oldest) = self.dns_names_order.pop_front() {
+                self.dns_names.remove(&oldest);
+            }
+        }
+    }
+
+    /// Keeps the raw packet among the last ones of the given entry
+    pub fn buffer_packet(&mut self, key: &AddressPortPair, mut packet: CapturedPacket) {
+        packet.id = self.all_packets;
+        self.packets.push(key, packet);
+    }
+
+    /// Writes the packet to the PCAP files of the selected connections and hosts
+    pub fn export_packet(&self, key: &AddressPortPair, packet: &CapturedPacket) {
+        for pcap_export in &self.pcap_exports {
+            pcap_export
+                .lock()
+                .unwrap()
+                .write_captured(key, packet, &self.addresses_resolved);
+        }
+    }
+
+    /// Writes to disk the packets still buffered for the recorded and exported files
+    pub fn flush_files(&mut self) {
+        for recorder in self.savefiles.values_mut() {
+            recorder.flush();
+        }
+        for pcap_export in &self.pcap_exports {
+            pcap_export.lock().unwrap().flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dns_names_limit() {
+        let mut info_traffic = InfoTraffic::new();
+        for i in 0..MAX_DNS_NAMES {
+            info_traffic.add_dns_name(i.to_string(), format!("host-{i}.example"));
+        }
+        // updating a name doesn't make it newer
+        info_traffic.add_dns_name("0".to_string(), "updated.example".to_string());
+        assert_eq!(info_traffic.dns_names.len(), MAX_DNS_NAMES);
+        assert_eq!(info_traffic.dns_names["0"], "updated.example");
+
+        // the oldest address is forgotten
+        info_traffic.add_dns_name("140.82.121.4".to_string(), "github.com".to_string());
+        assert_eq!(info_traffic.dns_names.len(), MAX_DNS_NAMES);
+        assert_eq!(info_traffic.dns_names_order.len(), MAX_DNS_NAMES);
+        assert!(!info_traffic.dns_names.contains_key("0"));
+        assert_eq!(info_traffic.dns_names["1"], "host-1.example");
+        assert_eq!(info_traffic.dns_names["140.82.121.4"], "github.com");
     }
 }
@@ -6,11 +6,15 @@
 pub(crate) struct AddressCollection {
     pub(crate) ips: Vec<IpAddr>,
     pub(crate) ranges: Vec<RangeInclusive<IpAddr>>,
+    pub(crate) excluded_ips: Vec<IpAddr>,
+    pub(crate) excluded_ranges: Vec<RangeInclusive<IpAddr>>,
 }
 
 impl AddressCollection {
     const SEPARATOR: char = ',';
     const RANGE_SEPARATOR: char = '-';
+    const CIDR_SEPARATOR: char = '/';
+    const NEGATION: char = '!';
 
     pub const PLACEHOLDER_STR: &'static str =
         "0.0.0.0-255.255.255.255, ::-ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff";
@@ -24,10 +28,19 @@ pub(crate) fn new(str: &str) -> Option<Self> {
 
         let mut ips = Vec::new();
         let mut ranges = Vec::new();
+        let mut excluded_ips = Vec::new();
+        let mut excluded_ranges = Vec::new();
 
         let objects: Vec<&str> = str.split(Self::SEPARATOR).collect();
         for object in objects {
-            if object.contains(Self::RANGE_SEPARATOR) {
+            let (object, ips, ranges) = match object.strip_prefix(Self::NEGATION) {
+                Some(excluded_object) => (excluded_object, &mut excluded_ips, &mut excluded_ranges),
+                None => (object, &mut ips, &mut ranges),
+            };
+            if object.contains(Self::CIDR_SEPARATOR) {
+                // CIDR block
+                ranges.push(Self::cidr_to_range(object)?);
+            } else if object.contains(Self::RANGE_SEPARATOR) {
                 // IP range
                 let mut subparts = object.split(Self::RANGE_SEPARATOR);
                 let (lower_str, upper_str) =
@@ -55,16 +68,64 @@ pub(crate) fn new(str: &str) -> Option<Self> {
             }
         }
 
-        Some(Self { ips, ranges })
+        if ips.is_empty() && ranges.is_empty() {
+            // only exclusions were specified: everything else is included
+            ranges = Self::default().ranges;
+        }
+
+        Some(Self {
+            ips,
+            ranges,
+            excluded_ips,
+            excluded_ranges,
+        })
     }
 
-    pub(crate) fn contains(&self, ip: &IpAddr) -> bool {
-        for range in &self.ranges {
-            if range.contains(ip) {
-                return true;
+    /// Converts a CIDR block (e.g., 10.0.0.0/8) to the corresponding range of addresses
+    fn cidr_to_range(str: &str) -> Option<RangeInclusive<IpAddr>> {
+        let mut subparts = str.split(Self::CIDR_SEPARATOR);
+        let (ip_str, prefix_str) = (subparts.next().unwrap_or(""), subparts.next().unwrap_or(""));
+        if subparts.next().is_some() {
+            return None;
+        }
+        let ip = IpAddr::from_str(ip_str).ok()?;
+        let prefix = u32::from_str(prefix_str).ok()?;
+        match ip {
+            IpAddr::V4(ipv4) => {
+                if prefix > 32 {
+                    return None;
+                }
+                let host_mask = u32::MAX.checked_shr(prefix).unwrap_or(0);
+                let network = u32::from(ipv4) & !host_mask;
+                Some(RangeInclusive::new(
+                    IpAddr::from(network.to_be_bytes()),
+                    IpAddr::from((network | host_mask).to_be_bytes()),
+                ))
+            }
+            IpAddr::V6(ipv6) => {
+                if prefix > 128 {
+                    return None;
+                }
+                let host_mask = u128::MAX.checked_shr(prefix).unwrap_or(0);
+                let network = u128::from(ipv6) & !host_mask;
+                Some(RangeInclusive::new(
+                    IpAddr::from(network.to_be_bytes()),
+                    IpAddr::from((network | host_mask).to_be_bytes()),
+                ))
             }
         }
-        self.ips.contains(ip)
+    }
+
+    pub(crate) fn contains(&self, ip: &IpAddr) -> bool {
+        let is_included =
+            self.ips.contains(ip) || self.ranges.iter().any(|range| range.contains(ip));
+        is_included && !self.excludes(ip)
+    }
+
+    /// Checks whether the address has been explicitly excluded
+    pub(crate) fn excludes(&self, ip: &IpAddr) -> bool {
+        self.excluded_ips.contains(ip)
+            || self.excluded_ranges.iter().any(|range| range.contains(ip))
     }
 }
 
@@ -82,6 +143,8 @@ fn default() -> Self {
                     IpAddr::from_str("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff").unwrap(),
                 ),
             ],
+            excluded_ips: vec![],
+            excluded_ranges: vec![],
         }
     }
 }
@@ -119,7 +182,9 @@ fn test_new_collections_1() {
                     IpAddr::from_str("1.1.1.1").unwrap(),
                     IpAddr::from_str("2.2.2.2").unwrap()
                 ],
-                ranges: vec![]
+                ranges: vec![],
+                excluded_ips: vec![],
+                excluded_ranges: vec![],
             }
         );
 
@@ -143,7 +208,9 @@ fn test_new_collections_1() {
                         IpAddr::from_str("10.0.0.1").unwrap(),
                         IpAddr::from_str("10.0.0.255").unwrap()
                     )
-                ]
+                ],
+                excluded_ips: vec![],
+                excluded_ranges: vec![],
             }
         );
 
@@ -154,7 +221,9 @@ fn test_new_collections_1() {
                 ranges: vec![RangeInclusive::new(
                     IpAddr::from_str("bbbb::1").unwrap(),
                     IpAddr::from_str("cccc::2").unwrap()
-                )]
+                )],
+                excluded_ips: vec![],
+                excluded_ranges: vec![],
             }
         );
     }
@@ -169,7 +238,9 @@ fn test_new_collections_2() {
                     IpAddr::from_str("2.2.2.2").unwrap(),
                     IpAddr::from_str("8.8.8.8").unwrap()
                 ],
-                ranges: vec![]
+                ranges: vec![],
+                excluded_ips: vec![],
+                excluded_ranges: vec![],
             }
         );
 
@@ -180,7 +251,9 @@ fn test_new_collections_2() {
                 ranges: vec![RangeInclusive::new(
                     IpAddr::from_str("1.1.1.1").unwrap(),
                     IpAddr::from_str("1.1.1.1").unwrap()
-                ),]
+                ),],
+                excluded_ips: vec![],
+                excluded_ranges: vec![],
             }
         );
 
@@ -202,7 +275,9 @@ fn test_new_collections_2() {
                         IpAddr::from_str("10.0.0.1").unwrap(),
                         IpAddr::from_str("10.0.0.255").unwrap()
                     )
-                ]
+                ],
+                excluded_ips: vec![],
+                excluded_ranges: vec![],
             }
         );
 
@@ -216,7 +291,9 @@ fn test_new_collections_2() {
                 ranges: vec![RangeInclusive::new(
                     IpAddr::from_str("bbbb::1").unwrap(),
                     IpAddr::from_str("cccc::2").unwrap()
-                )]
+                )],
+                excluded_ips: vec![],
+                excluded_ranges: vec![],
             }
         );
     }
@@ -305,4 +382,112 @@ fn test_ip_collection_contains_ipv6() {
         assert!(!collection_3.contains(&IpAddr::from_str("192.168.1.1").unwrap()));
         assert!(!collection_3.contains(&IpAddr::from_str("0.0.0.0").unwrap()));
     }
+
+    #[test]
+    fn test_new_collections_cidr() {
+        assert_eq!(
+            AddressCollection::new("10.0.0.0/8, 192.168.1.77/24, 1.1.1.1/32").unwrap(),
+            AddressCollection {
+                ips: vec![],
+                ranges: vec![
+                    RangeInclusive::new(
+                        IpAddr::from_str("10.0.0.0").unwrap(),
+                        IpAddr::from_str("10.255.255.255").unwrap()
+                    ),
+                    RangeInclusive::new(
+                        IpAddr::from_str("192.168.1.0").unwrap(),
+                        IpAddr::from_str("192.168.1.255").unwrap()
+                    ),
+                    RangeInclusive::new(
+                        IpAddr::from_str("1.1.1.1").unwrap(),
+                        IpAddr::from_str("1.1.1.1").unwrap()
+                    )
+                ],
+                excluded_ips: vec![],
+                excluded_ranges: vec![],
+            }
+        );
+
+        assert_eq!(
+            AddressCollection::new("fe80::/10,::/0").unwrap(),
+            AddressCollection {
+                ips: vec![],
+                ranges: vec![
+                    RangeInclusive::new(
+                        IpAddr::from_str("fe80::").unwrap(),
+                        IpAddr::from_str("febf:ffff:ffff:ffff:ffff:ffff:ffff:ffff").unwrap()
+                    ),
+                    RangeInclusive::new(
+                        IpAddr::from_str("::").unwrap(),
+                        IpAddr::from_str("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff").unwrap()
+                    )
+                ],
+                excluded_ips: vec![],
+                excluded_ranges: vec![],
+            }
+        );
+
+        assert_eq!(AddressCollection::new("10.0.0.0/33"), None);
+        assert_eq!(AddressCollection::new("aa::/129"), None);
+        assert_eq!(AddressCollection::new("10.0.0.0/"), None);
+        assert_eq!(AddressCollection::new("10.0.0/8"), None);
+        assert_eq!(AddressCollection::new("10.0.0.0/8/8"), None);
+    }
+
+    #[test]
+    fn test_new_collections_negation() {
+        assert_eq!(
+            AddressCollection::new("10.0.0.0/8, !10.1.1.1, !10.2.0.0-10.2.255.255").unwrap(),
+            AddressCollection {
+                ips: vec![],
+                ranges: vec![RangeInclusive::new(
+                    IpAddr::from_str("10.0.0.0").unwrap(),
+                    IpAddr::from_str("10.255.255.255").unwrap()
+                )],
+                excluded_ips: vec![IpAddr::from_str("10.1.1.1").unwrap()],
+                excluded_ranges: vec![RangeInclusive::new(
+                    IpAddr::from_str("10.2.0.0").unwrap(),
+                    IpAddr::from_str("10.2.255.255").unwrap()
+                )],
+            }
+        );
+
+        // only exclusions: everything else is included
+        assert_eq!(
+            AddressCollection::new("!192.168.0.0/16").unwrap(),
+            AddressCollection {
+                excluded_ranges: vec![RangeInclusive::new(
+                    IpAddr::from_str("192.168.0.0").unwrap(),
+                    IpAddr::from_str("192.168.255.255").unwrap()
+                )],
+                ..AddressCollection::default()
+            }
+        );
+
+        assert_eq!(AddressCollection::new("!"), None);
+        assert_eq!(AddressCollection::new("!!1.1.1.1"), None);
+        assert_eq!(AddressCollection::new("1.1.1.1!"), None);
+    }
+
+    #[test]
+    fn test_ip_collection_contains_cidr_and_negation() {
+        let collection = AddressCollection::new("!192.168.0.0/16, !fe80::/10").unwrap();
+        assert!(collection.contains(&IpAddr::from_str("8.8.8.8").unwrap()));
+        assert!(collection.contains(&IpAddr::from_str("192.169.0.0").unwrap()));
+        assert!(collection.contains(&IpAddr::from_str("2001:db8::1").unwrap()));
+        assert!(!collection.contains(&IpAddr::from_str("192.168.0.0").unwrap()));
+        assert!(!collection.contains(&IpAddr::from_str("192.168.1.1").unwrap()));
+        assert!(!collection.contains(&IpAddr::from_str("192.168.255.255").unwrap()));
+        assert!(!collection.contains(&IpAddr::from_str("fe80::1").unwrap()));
+        assert!(collection.excludes(&IpAddr::from_str("192.168.1.1").unwrap()));
+        assert!(!collection.excludes(&IpAddr::from_str("8.8.8.8").unwrap()));
+
+        let collection_2 = AddressCollection::new("10.0.0.0/8, !10.0.0.1, 1.1.1.1").unwrap();
+        assert!(collection_2.contains(&IpAddr::from_str("10.0.0.0").unwrap()));
+        assert!(collection_2.contains(&IpAddr::from_str("10.200.3.4").unwrap()));
+        assert!(collection_2.contains(&IpAddr::from_str("1.1.1.1").unwrap()));
+        assert!(!collection_2.contains(&IpAddr::from_str("10.0.0.1").unwrap()));
+        assert!(!collection_2.contains(&IpAddr::from_str("11.0.0.0").unwrap()));
+        assert!(!collection_2.contains(&IpAddr::from_str("::a00:0").unwrap()));
+    }
 }
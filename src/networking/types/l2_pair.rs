@@ -0,0 +1,17 @@
+//! Module defining the `L2Pair` struct, which represents a pair of MAC addresses exchanging
+//! frames not carrying IP packets.
+
+use serde::{Deserialize, Serialize};
+
+use crate::networking::types::l2_protocol::L2Protocol;
+
+/// Struct representing a pair of MAC addresses exchanging frames of a given link layer protocol.
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Serialize, Deserialize)]
+pub struct L2Pair {
+    /// Source MAC address.
+    pub mac_address1: String,
+    /// Destination MAC address.
+    pub mac_address2: String,
+    /// Protocol of the exchanged frames.
+    pub protocol: L2Protocol,
+}
@@ -0,0 +1,106 @@
+//! Module defining the `L2Protocol` enum, which represents the protocols of the frames not carrying IP packets.
+
+use serde::{Deserialize, Serialize};
+
+/// `EtherType` of Address Resolution Protocol frames
+const ETHER_TYPE_ARP: u16 = 0x0806;
+/// `EtherType` of Link Layer Discovery Protocol frames
+const ETHER_TYPE_LLDP: u16 = 0x88CC;
+/// `EtherType` of EAP over LAN frames (802.1X authentication)
+const ETHER_TYPE_EAPOL: u16 = 0x888E;
+/// `EtherType`s of the IP packets, which are not handled as link layer traffic
+const ETHER_TYPES_IP: [u16; 2] = [0x0800, 0x86DD];
+/// Values lower than this are the length of 802.3 frames, not `EtherType`s
+const MIN_ETHER_TYPE: u16 = 0x0600;
+/// 802.2 LLC header of Spanning Tree Protocol frames (DSAP, SSAP, control)
+const LLC_STP: [u8; 3] = [0x42, 0x42, 0x03];
+
+/// Enum representing the protocols of the frames not carrying IP packets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum L2Protocol {
+    /// Address Resolution Protocol
+    ARP,
+    /// Link Layer Discovery Protocol
+    LLDP,
+    /// Spanning Tree Protocol
+    STP,
+    /// Extensible Authentication Protocol over LAN
+    EAPOL,
+    /// Other 802.3 frames with an 802.2 LLC header
+    LLC,
+    /// Any other `EtherType`
+    Other(u16),
+}
+
+impl L2Protocol {
+    /// Returns the protocol of a frame given its `EtherType` (or 802.3 length) and payload;
+    /// None if the frame carries an IP packet
+    pub fn from_ether_type(ether_type: u16, payload: &[u8]) -> Option<Self> {
+        Some(match ether_type {
+            _ if ETHER_TYPES_IP.contains(&ether_type) => return None,
+            ETHER_TYPE_ARP => Self::ARP,
+            ETHER_TYPE_LLDP => Self::LLDP,
+            ETHER_TYPE_EAPOL => Self::EAPOL,
+            _ if ether_type < MIN_ETHER_TYPE => {
+                if payload.starts_with(&LLC_STP) {
+                    Self::STP
+                } else {
+                    Self::LLC
+                }
+            }
+            _ => Self::Other(ether_type),
+        })
+    }
+}
+
+impl std::fmt::Display for L2Protocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Other(ether_type) => write!(f, "0x{ether_type:04X}"),
+            _ => write!(f, "{self:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l2_protocol_from_ether_type() {
+        assert_eq!(
+            L2Protocol::from_ether_type(0x0806, &[]),
+            Some(L2Protocol::ARP)
+        );
+        assert_eq!(
+            L2Protocol::from_ether_type(0x88CC, &[]),
+            Some(L2Protocol::LLDP)
+        );
+        assert_eq!(
+            L2Protocol::from_ether_type(0x888E, &[]),
+            Some(L2Protocol::EAPOL)
+        );
+        assert_eq!(
+            L2Protocol::from_ether_type(38, &[0x42, 0x42, 0x03, 0, 0]),
+            Some(L2Protocol::STP)
+        );
+        assert_eq!(
+            L2Protocol::from_ether_type(38, &[0xAA, 0xAA, 0x03]),
+            Some(L2Protocol::LLC)
+        );
+        assert_eq!(
+            L2Protocol::from_ether_type(0x8863, &[]),
+            Some(L2Protocol::Other(0x8863))
+        );
+        assert_eq!(L2Protocol::from_ether_type(0x0800, &[]), None);
+        assert_eq!(L2Protocol::from_ether_type(0x86DD, &[]), None);
+    }
+
+    #[test]
+    fn test_l2_protocol_display() {
+        assert_eq!(L2Protocol::ARP.to_string(), "ARP");
+        assert_eq!(L2Protocol::EAPOL.to_string(), "EAPOL");
+        assert_eq!(L2Protocol::Other(0x8863).to_string(), "0x8863");
+    }
+}
@@ -0,0 +1,56 @@
+//! Module defining the `LanDevice` struct, which represents a device of the local network
+//! identified by its MAC address.
+
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::traffic_direction::TrafficDirection;
+
+/// Struct representing a device of the local network.
+///
+/// Incoming and outgoing data are considered from the point of view of the device.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LanDevice {
+    /// IPv4 and IPv6 addresses used by the device
+    pub addresses: BTreeSet<IpAddr>,
+    /// Time the device was first seen (the latest time is recorded in the data info)
+    pub first_seen: DateTime<Local>,
+    /// Data sent and received by the device
+    pub data_info: DataInfo,
+}
+
+impl LanDevice {
+    pub fn new_with_first_packet(
+        address: IpAddr,
+        bytes: u128,
+        traffic_direction: TrafficDirection,
+        timestamp: DateTime<Local>,
+    ) -> Self {
+        Self {
+            addresses: BTreeSet::from([address]),
+            first_seen: timestamp,
+            data_info: DataInfo::new_with_first_packet(bytes, traffic_direction, timestamp),
+        }
+    }
+
+    pub fn add_packet(
+        &mut self,
+        address: IpAddr,
+        bytes: u128,
+        traffic_direction: TrafficDirection,
+        timestamp: DateTime<Local>,
+    ) {
+        self.addresses.insert(address);
+        self.data_info
+            .add_packet(bytes, traffic_direction, timestamp);
+    }
+
+    /// Time the device was last seen
+    pub fn last_seen(&self) -> DateTime<Local> {
+        self.data_info.final_timestamp()
+    }
+}
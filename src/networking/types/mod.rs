@@ -11,12 +11,24 @@
 pub mod info_traffic;
 pub mod ip_collection;
 pub mod ip_version;
+pub mod l2_pair;
+pub mod l2_protocol;
+pub mod lan_device;
 pub mod my_device;
 pub mod my_link_type;
+pub mod name_source;
+pub mod packet_buffer;
 pub mod packet_filters_fields;
+pub mod pcap_export;
+pub mod pcap_recorder;
+pub mod pcap_rotation;
 pub mod port_collection;
+pub mod program;
 pub mod protocol;
 pub mod service;
 pub mod service_query;
+pub mod session;
+pub mod tcp_connection;
+pub mod tcp_quality;
 pub mod traffic_direction;
 pub mod traffic_type;
@@ -16,6 +16,17 @@ pub struct MyDevice {
 }
 
 impl MyDevice {
+    /// Returns a device representing a capture file:
+    /// it has no addresses, since the local interfaces of the original capture are not known
+    pub fn from_capture_file(file_path: &str) -> Self {
+        Self {
+            name: file_path.to_string(),
+            desc: None,
+            addresses: Arc::new(Mutex::new(Vec::new())),
+            link_type: MyLinkType::default(),
+        }
+    }
+
     pub fn to_pcap_device(&self) -> Device {
         for device in Device::list().unwrap_or_default() {
             if device.name.eq(&self.name) {
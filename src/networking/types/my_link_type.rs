@@ -16,6 +16,8 @@ pub enum MyLinkType {
     Loop(Linktype),
     IPv4(Linktype),
     IPv6(Linktype),
+    LinuxSll(Linktype),
+    LinuxSll2(Linktype),
     Unsupported(Linktype),
     #[default]
     NotYetAssigned,
@@ -34,10 +36,28 @@ pub fn from_pcap_link_type(link_type: Linktype) -> Self {
             Linktype::LOOP => Self::Loop(link_type),
             Linktype::IPV4 => Self::IPv4(link_type),
             Linktype::IPV6 => Self::IPv6(link_type),
+            Linktype::LINUX_SLL => Self::LinuxSll(link_type),
+            Linktype::LINUX_SLL2 => Self::LinuxSll2(link_type),
             _ => Self::Unsupported(link_type),
         }
     }
 
+    /// Returns the link type to be declared in the PCAP files containing these packets
+    pub fn pcap_link_type(self) -> Option<Linktype> {
+        match self {
+            Self::Null(l)
+            | Self::Ethernet(l)
+            | Self::RawIp(l)
+            | Self::Loop(l)
+            | Self::IPv4(l)
+            | Self::IPv6(l)
+            | Self::LinuxSll(l)
+            | Self::LinuxSll2(l)
+            | Self::Unsupported(l) => Some(l),
+            Self::NotYetAssigned => None,
+        }
+    }
+
     pub fn full_print_on_one_line(self, language: Language) -> String {
         match self {
             Self::Null(l)
@@ -46,6 +66,8 @@ pub fn full_print_on_one_line(self, language: Language) -> String {
             | Self::Loop(l)
             | Self::IPv4(l)
             | Self::IPv6(l)
+            | Self::LinuxSll(l)
+            | Self::LinuxSll2(l)
             | Self::Unsupported(l) => {
                 format!(
                     "{}: {} ({})",
@@ -70,6 +92,8 @@ pub fn link_type_col(
             | Self::Loop(l)
             | Self::IPv4(l)
             | Self::IPv6(l)
+            | Self::LinuxSll(l)
+            | Self::LinuxSll2(l)
             | Self::Unsupported(l) => {
                 let link_info = format!(
                     "{} ({})",
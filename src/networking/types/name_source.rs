@@ -0,0 +1,41 @@
+//! Module defining the `NameSource` enum, representing the origin of the names of the hosts.
+
+use serde::{Deserialize, Serialize};
+
+/// Enum representing where the name of a host was learned from.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum NameSource {
+    /// DNS responses, TLS client hellos, or HTTP requests observed on the wire
+    Traffic,
+    /// Reverse DNS lookup
+    ReverseDns,
+    /// Host name or FQDN declared by a DHCP client (options 12 and 81)
+    Dhcp,
+    /// Multicast DNS announcement or response
+    Mdns,
+    /// NetBIOS Name Service registration or response
+    Nbns,
+    /// Link-Local Multicast Name Resolution response
+    Llmnr,
+}
+
+impl NameSource {
+    /// Whether the name was announced by the host itself on the local network
+    pub fn is_lan(self) -> bool {
+        matches!(self, Self::Dhcp | Self::Mdns | Self::Nbns | Self::Llmnr)
+    }
+}
+
+impl std::fmt::Display for NameSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let source = match self {
+            Self::Traffic => "DNS/TLS/HTTP",
+            Self::ReverseDns => "rDNS",
+            Self::Dhcp => "DHCP",
+            Self::Mdns => "mDNS",
+            Self::Nbns => "NetBIOS",
+            Self::Llmnr => "LLMNR",
+        };
+        write!(f, "{source}")
+    }
+}
@@ -0,0 +1,217 @@
+//! Module defining the `PacketBuffer` struct, which keeps the last raw packets of a connection
+//! to allow inspecting their headers and payload, and the `PacketBuffers` struct, which keeps
+//! the buffers of all the connections within a memory limit.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Local};
+use pcap::Packet;
+
+use crate::networking::manage_packets::get_packet_timestamp;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::my_link_type::MyLinkType;
+
+/// Maximum number of packets kept for each connection
+pub const MAX_PACKETS_PER_CONNECTION: usize = 50;
+
+/// Maximum number of bytes kept for all the connections
+pub const MAX_BUFFERED_BYTES: usize = 32 * 1024 * 1024;
+
+/// A raw packet, as captured from the network adapter
+#[derive(Clone)]
+pub struct CapturedPacket {
+    /// Progressive number of the packet in the capture (assigned when buffered)
+    pub id: u128,
+    /// Time the packet was captured
+    pub timestamp: DateTime<Local>,
+    /// Length of the packet on the wire (captured data may be truncated by the snapshot length)
+    pub original_len: u32,
+    /// Captured bytes, starting from the link layer header
+    pub data: Vec<u8>,
+    /// Link type of the adapter the packet was captured on
+    pub link_type: MyLinkType,
+}
+
+impl CapturedPacket {
+    pub fn new(packet: &Packet, link_type: MyLinkType) -> Self {
+        Self {
+            id: 0,
+            timestamp: get_packet_timestamp(packet),
+            original_len: packet.header.len,
+            data: packet.data.to_vec(),
+            link_type,
+        }
+    }
+}
+
+/// Ring of the last packets of a connection
+#[derive(Default)]
+pub struct PacketBuffer {
+    packets: VecDeque<CapturedPacket>,
+}
+
+impl PacketBuffer {
+    /// Adds a packet, returning the oldest one if the ring was full
+    fn push(&mut self, packet: CapturedPacket) -> Option<CapturedPacket> {
+        self.packets.push_back(packet);
+        if self.packets.len() > MAX_PACKETS_PER_CONNECTION {
+            self.packets.pop_front()
+        } else {
+            None
+        }
+    }
+
+    pub fn packets(&self) -> &VecDeque<CapturedPacket> {
+        &self.packets
+    }
+}
+
+/// Last raw packets of all the connections
+#[derive(Default)]
+pub struct PacketBuffers {
+    /// Map of the connections with their last packets
+    buffers: HashMap<AddressPortPair, PacketBuffer>,
+    /// Connections and IDs of the buffered packets, from the oldest one
+    /// (packets already dropped from the ring of their connection are skipped when evicting)
+    order: VecDeque<(AddressPortPair, u128)>,
+    /// Number of packets kept in the buffers
+    buffered_packets: usize,
+    /// Number of captured bytes kept in the buffers
+    buffered_bytes: usize,
+}
+
+impl PacketBuffers {
+    /// Adds a packet to the buffer of its connection, dropping the oldest one of the connection
+    /// when its ring is full, and the oldest ones of all the connections
+    /// when the bytes kept exceed the limit
+    pub fn push(&mut self, key: &AddressPortPair, packet: CapturedPacket) {
+        self.order.push_back((key.clone(), packet.id));
+        self.buffered_packets += 1;
+        self.buffered_bytes += packet.data.len();
+        if let Some(dropped) = self.buffers.entry(key.clone()).or_default().push(packet) {
+            self.buffered_packets -= 1;
+            self.buffered_bytes -= dropped.data.len();
+        }
+
+        while self.buffered_bytes > MAX_BUFFERED_BYTES {
+            let Some((key, id)) = self.order.pop_front() else {
+                break;
+            };
+            let Some(buffer) = self.buffers.get_mut(&key) else {
+                continue;
+            };
+            if buffer.packets.front().map(|oldest| oldest.id) != Some(id) {
+                // already dropped from the ring of its connection
+                continue;
+            }
+            if let Some(oldest) = buffer.packets.pop_front() {
+                self.buffered_packets -= 1;
+                self.buffered_bytes -= oldest.data.len();
+            }
+            if buffer.packets.is_empty() {
+                self.buffers.remove(&key);
+            }
+        }
+
+        // forget the packets already dropped from the rings, if they're the most
+        if self.order.len() > 2 * self.buffered_packets + MAX_PACKETS_PER_CONNECTION {
+            let buffers = &self.buffers;
+            self.order.retain(|(key, id)| {
+                buffers
+                    .get(key)
+                    .and_then(|buffer| buffer.packets.front())
+                    .is_some_and(|oldest| oldest.id <= *id)
+            });
+        }
+    }
+
+    /// Returns the last packets of the given connection
+    pub fn get(&self, key: &AddressPortPair) -> Option<&PacketBuffer> {
+        self.buffers.get(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&AddressPortPair, &PacketBuffer)> {
+        self.buffers.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Protocol;
+
+    use super::*;
+
+    fn packet(id: u128, len: usize) -> CapturedPacket {
+        CapturedPacket {
+            id,
+            timestamp: Local::now(),
+            original_len: u32::try_from(len).unwrap(),
+            data: vec![0; len],
+            link_type: MyLinkType::default(),
+        }
+    }
+
+    fn key(port: u16) -> AddressPortPair {
+        AddressPortPair::new(
+            "192.168.1.6".to_string(),
+            Some(port),
+            "140.82.121.4".to_string(),
+            Some(443),
+            Protocol::TCP,
+        )
+    }
+
+    #[test]
+    fn test_packet_buffer_keeps_last_packets() {
+        let mut buffers = PacketBuffers::default();
+        for id in 0..(MAX_PACKETS_PER_CONNECTION as u128 + 10) {
+            buffers.push(&key(50000), packet(id, 100));
+        }
+        let buffer = buffers.get(&key(50000)).unwrap();
+        assert_eq!(buffer.packets().len(), MAX_PACKETS_PER_CONNECTION);
+        assert_eq!(buffer.packets().front().unwrap().id, 10);
+        assert_eq!(buffers.buffered_bytes, MAX_PACKETS_PER_CONNECTION * 100);
+    }
+
+    #[test]
+    fn test_packet_buffer_memory_cap() {
+        let mut buffers = PacketBuffers::default();
+        // idle connections already use all the memory
+        let len = MAX_BUFFERED_BYTES / 4;
+        for id in 0..4 {
+            buffers.push(&key(50000 + u16::try_from(id).unwrap()), packet(id, len));
+        }
+        assert_eq!(buffers.buffered_bytes, MAX_BUFFERED_BYTES);
+        assert_eq!(buffers.iter().count(), 4);
+
+        // the oldest connection gives up its memory to the new one
+        buffers.push(&key(60000), packet(4, 100));
+        assert!(buffers.get(&key(50000)).is_none());
+        assert_eq!(buffers.iter().count(), 4);
+        assert_eq!(buffers.get(&key(60000)).unwrap().packets().len(), 1);
+        assert_eq!(buffers.buffered_bytes, 3 * len + 100);
+
+        // even if the new packet needs the memory of more than one connection
+        buffers.push(&key(60000), packet(5, 2 * len));
+        assert!(buffers.get(&key(50001)).is_none());
+        assert!(buffers.get(&key(50002)).is_none());
+        assert_eq!(buffers.iter().count(), 2);
+        let buffer = buffers.get(&key(60000)).unwrap();
+        assert_eq!(buffer.packets().len(), 2);
+        assert_eq!(buffer.packets().back().unwrap().id, 5);
+        assert_eq!(buffers.buffered_bytes, 3 * len + 100);
+    }
+
+    #[test]
+    fn test_packet_buffer_order_compaction() {
+        let mut buffers = PacketBuffers::default();
+        // an idle connection followed by a busy one filling its ring many times
+        buffers.push(&key(50000), packet(0, 100));
+        for id in 1..(MAX_PACKETS_PER_CONNECTION as u128 * 20) {
+            buffers.push(&key(60000), packet(id, 100));
+        }
+        assert!(buffers.order.len() <= 3 * MAX_PACKETS_PER_CONNECTION + 2);
+        assert_eq!(buffers.order.front().unwrap().1, 0);
+        assert_eq!(buffers.buffered_packets, MAX_PACKETS_PER_CONNECTION + 1);
+    }
+}
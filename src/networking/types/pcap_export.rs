@@ -0,0 +1,367 @@
+//! Module defining the `PcapExport` struct, which writes the packets of the selected connections
+//! or hosts to their own PCAP files.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+
+use pcap::Linktype;
+
+use crate::gui::types::export_pcap::path_with_suffix;
+use crate::networking::pcap_writer::PcapWriter;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::host::Host;
+use crate::networking::types::my_link_type::MyLinkType;
+use crate::networking::types::packet_buffer::{CapturedPacket, PacketBuffers};
+
+/// Traffic to be exported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PcapExportTarget {
+    /// Connections with their remote address (both the directions of each connection are included)
+    Connections(HashMap<AddressPortPair, String>),
+    /// All the connections of a host
+    Host(Host),
+}
+
+/// Export of the packets of the selected connections or hosts, still receiving the new ones
+pub struct PcapExport {
+    target: PcapExportTarget,
+    /// Path chosen by the user (when splitting by host, each file name is derived from it)
+    path: String,
+    /// Whether each remote host is written to its own file
+    split_by_host: bool,
+    /// Files being written, identified by the name of their host (empty when not splitting)
+    writers: HashMap<String, PcapWriter<BufWriter<File>>>,
+    /// Packets captured while the ones already recorded are being exported,
+    /// written when that is done (`None` if there are no recorded packets to export)
+    queued: Option<Vec<(AddressPortPair, CapturedPacket)>>,
+    /// Error that interrupted the export
+    pub error: Option<String>,
+}
+
+impl PcapExport {
+    pub fn new(
+        target: PcapExportTarget,
+        path: String,
+        split_by_host: bool,
+        has_recorded_packets: bool,
+    ) -> Self {
+        Self {
+            target,
+            path,
+            split_by_host,
+            writers: HashMap::new(),
+            queued: has_recorded_packets.then(Vec::new),
+            error: None,
+        }
+    }
+
+    /// Creates the file right away, so that it exists even before any packet is exported
+    /// (not possible when splitting by host, since hosts are only known when their packets are seen)
+    pub fn create_file(&mut self, link_type: MyLinkType) {
+        if let Some(link_type) = link_type.pcap_link_type() {
+            if !self.split_by_host {
+                self.get_writer(String::new(), link_type);
+            }
+        }
+    }
+
+    /// Writes a packet just captured, or queues it if the recorded ones are still being exported
+    pub fn write_captured(
+        &mut self,
+        key: &AddressPortPair,
+        packet: &CapturedPacket,
+        addresses_resolved: &HashMap<String, (String, Host)>,
+    ) {
+        if self.queued.is_none() {
+            self.write(key, packet, addresses_resolved);
+        } else if self.get_label(key, addresses_resolved).is_some() {
+            self.queued
+                .get_or_insert_with(Vec::new)
+                .push((key.clone(), packet.clone()));
+        }
+    }
+
+    /// Writes the packets captured while the recorded ones were being exported,
+    /// so that the following ones are written as soon as they're captured
+    pub fn write_queued(&mut self, addresses_resolved: &HashMap<String, (String, Host)>) {
+        for (key, packet) in self.queued.take().unwrap_or_default() {
+            self.write(&key, &packet, addresses_resolved);
+        }
+        self.flush();
+    }
+
+    /// Writes the packet to the corresponding file, if it belongs to the exported traffic
+    pub fn write(
+        &mut self,
+        key: &AddressPortPair,
+        packet: &CapturedPacket,
+        addresses_resolved: &HashMap<String, (String, Host)>,
+    ) {
+        if self.error.is_some() {
+            return;
+        }
+        let Some(label) = self.get_label(key, addresses_resolved) else {
+            return;
+        };
+        let Some(link_type) = packet.link_type.pcap_link_type() else {
+            return;
+        };
+        let Some(writer) = self.get_writer(label, link_type) else {
+            return;
+        };
+        // a PCAP file can only contain packets of a single link type
+        if writer.link_type() != link_type {
+            return;
+        }
+        if let Err(e) = writer.write_packet(packet) {
+            self.error = Some(e.to_string());
+        }
+    }
+
+    /// Writes the buffered packets belonging to the exported traffic, in the order they were captured
+    pub fn write_buffered(
+        &mut self,
+        buffers: &PacketBuffers,
+        addresses_resolved: &HashMap<String, (String, Host)>,
+    ) {
+        let mut packets: Vec<(&AddressPortPair, &CapturedPacket)> = buffers
+            .iter()
+            .filter(|(key, _)| self.get_label(key, addresses_resolved).is_some())
+            .flat_map(|(key, buffer)| buffer.packets().iter().map(move |packet| (key, packet)))
+            .collect();
+        packets.sort_by_key(|(_, packet)| packet.id);
+        for (key, packet) in packets {
+            self.write(key, packet, addresses_resolved);
+        }
+        self.flush();
+    }
+
+    /// Writes to disk the packets still buffered
+    pub fn flush(&mut self) {
+        if self.error.is_some() {
+            return;
+        }
+        for writer in self.writers.values_mut() {
+            if let Err(e) = writer.flush() {
+                self.error = Some(e.to_string());
+                return;
+            }
+        }
+    }
+
+    /// Returns the writer of the file with the given name, creating it if needed
+    fn get_writer(
+        &mut self,
+        label: String,
+        link_type: Linktype,
+    ) -> Option<&mut PcapWriter<BufWriter<File>>> {
+        match self.writers.entry(label) {
+            Entry::Occupied(entry) => Some(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let path = if entry.key().is_empty() {
+                    self.path.clone()
+                } else {
+                    path_with_suffix(&self.path, entry.key())
+                };
+                match File::create(&path)
+                    .and_then(|file| PcapWriter::new(BufWriter::new(file), link_type))
+                {
+                    Ok(writer) => Some(entry.insert(writer)),
+                    Err(e) => {
+                        self.error = Some(format!("{path}: {e}"));
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the name of the file the packet has to be written to,
+    /// or `None` if it doesn't belong to the exported traffic
+    fn get_label(
+        &self,
+        key: &AddressPortPair,
+        addresses_resolved: &HashMap<String, (String, Host)>,
+    ) -> Option<String> {
+        let remote_address = match &self.target {
+            PcapExportTarget::Connections(connections) => connections.get(key)?,
+            PcapExportTarget::Host(host) => {
+                [&key.address1, &key.address2].into_iter().find(|address| {
+                    addresses_resolved
+                        .get(*address)
+                        .is_some_and(|(_, resolved_host)| resolved_host.eq(host))
+                })?
+            }
+        };
+        if !self.split_by_host {
+            return Some(String::new());
+        }
+        Some(match addresses_resolved.get(remote_address) {
+            Some((_, host)) if !host.domain.is_empty() => host.domain.clone(),
+            _ => remote_address.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+
+    use crate::Protocol;
+
+    use super::*;
+
+    fn key(address2: &str, port1: u16) -> AddressPortPair {
+        AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(port1),
+            address2.to_string(),
+            Some(443),
+            Protocol::TCP,
+        )
+    }
+
+    fn packet(id: u128, link_type: MyLinkType) -> CapturedPacket {
+        CapturedPacket {
+            id,
+            timestamp: Local::now(),
+            original_len: 10,
+            data: vec![0; 10],
+            link_type,
+        }
+    }
+
+    fn host(domain: &str) -> Host {
+        Host {
+            domain: domain.to_string(),
+            ..Host::default()
+        }
+    }
+
+    fn count_packets(path: &str) -> usize {
+        get_packets_len(path).len()
+    }
+
+    /// Returns the captured length of each packet of the file
+    fn get_packets_len(path: &str) -> Vec<u32> {
+        let bytes = std::fs::read(path).unwrap();
+        // skip the global header, then each record header and its captured bytes
+        let mut offset = 24;
+        let mut lens = Vec::new();
+        while offset + 16 <= bytes.len() {
+            let caplen = u32::from_le_bytes(bytes[offset + 8..offset + 12].try_into().unwrap());
+            offset += 16 + caplen as usize;
+            lens.push(caplen);
+        }
+        lens
+    }
+
+    #[test]
+    fn test_pcap_export_connections() {
+        let path = "test_pcap_export_connections.pcap";
+        let ethernet = MyLinkType::Ethernet(Linktype::ETHERNET);
+        let connection = key("1.1.1.1", 5000);
+        let target = PcapExportTarget::Connections(HashMap::from([
+            (connection.clone(), "1.1.1.1".to_string()),
+            (connection.reversed(), "1.1.1.1".to_string()),
+        ]));
+        let mut export = PcapExport::new(target, path.to_string(), false, false);
+        export.create_file(ethernet);
+        assert!(std::path::Path::new(path).exists());
+
+        let resolved = HashMap::new();
+        export.write(&connection, &packet(1, ethernet), &resolved);
+        export.write(&connection.reversed(), &packet(2, ethernet), &resolved);
+        // other connections are not exported
+        export.write(&key("1.1.1.1", 5001), &packet(3, ethernet), &resolved);
+        // packets with a different link type are skipped
+        export.write(
+            &connection,
+            &packet(4, MyLinkType::LinuxSll(Linktype::LINUX_SLL)),
+            &resolved,
+        );
+        assert_eq!(export.error, None);
+        drop(export);
+
+        assert_eq!(count_packets(path), 2);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_pcap_export_host_split() {
+        let path = "test_pcap_export_host_split.pcap";
+        let ethernet = MyLinkType::Ethernet(Linktype::ETHERNET);
+        let resolved = HashMap::from([
+            (
+                "1.1.1.1".to_string(),
+                ("one.one.one.one".to_string(), host("one.one.one.one")),
+            ),
+            (
+                "2.2.2.2".to_string(),
+                ("other.example".to_string(), host("example")),
+            ),
+        ]);
+        let mut buffers = PacketBuffers::default();
+        for (id, key) in [
+            (1, key("1.1.1.1", 5000)),
+            (2, key("2.2.2.2", 5000)),
+            (3, key("1.1.1.1", 5001).reversed()),
+        ] {
+            buffers.push(&key, packet(id, ethernet));
+        }
+
+        let mut export = PcapExport::new(
+            PcapExportTarget::Host(host("one.one.one.one")),
+            path.to_string(),
+            true,
+            false,
+        );
+        export.write_buffered(&buffers, &resolved);
+        assert_eq!(export.error, None);
+        drop(export);
+
+        let host_path = "test_pcap_export_host_split_one_one_one_one.pcap";
+        assert_eq!(count_packets(host_path), 2);
+        assert!(!std::path::Path::new(path).exists());
+        std::fs::remove_file(host_path).unwrap();
+    }
+
+    #[test]
+    fn test_pcap_export_queues_captured_packets() {
+        let path = "test_pcap_export_queues_captured_packets.pcap";
+        let ethernet = MyLinkType::Ethernet(Linktype::ETHERNET);
+        let connection = key("1.1.1.1", 5000);
+        let target = PcapExportTarget::Connections(HashMap::from([(
+            connection.clone(),
+            "1.1.1.1".to_string(),
+        )]));
+        let mut export = PcapExport::new(target, path.to_string(), false, true);
+        export.create_file(ethernet);
+
+        // packets of different lengths, to check the order they're written in
+        let packet = |len: usize| CapturedPacket {
+            data: vec![0; len],
+            ..packet(0, ethernet)
+        };
+
+        // packets captured while the recorded ones are being exported wait for them
+        let resolved = HashMap::new();
+        export.write_captured(&connection, &packet(3), &resolved);
+        export.write_captured(&key("1.1.1.1", 5001), &packet(4), &resolved);
+        assert_eq!(export.queued.as_ref().map(Vec::len), Some(1));
+        export.write(&connection, &packet(1), &resolved);
+        export.write(&connection, &packet(2), &resolved);
+        export.write_queued(&resolved);
+        assert!(export.queued.is_none());
+
+        // then they're written right away
+        export.write_captured(&connection, &packet(5), &resolved);
+        assert_eq!(export.error, None);
+        drop(export);
+
+        assert_eq!(get_packets_len(path), vec![1, 2, 3, 5]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,411 @@
+//! Module defining the `PcapRecorder` struct, which records the filtered packets to a PCAP
+//! or PCAPNG file, or to a ring of files when rotation is enabled.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::BufWriter;
+
+use chrono::{DateTime, Local};
+use pcap::{Linktype, Packet};
+
+use crate::gui::types::export_pcap::path_with_suffix;
+use crate::networking::pcap_writer::{PcapWriter, PCAP_HEADER_LEN, PCAP_RECORD_HEADER_LEN};
+use crate::networking::pcapng_writer::{PacketRecord, PcapngSection, PcapngWriter};
+use crate::networking::types::pcap_rotation::PcapRotation;
+use crate::networking::types::traffic_direction::TrafficDirection;
+
+/// Writes the packets of a capture to the file chosen by the user, switching to a new file
+/// when the current one gets too big or too old
+pub struct PcapRecorder {
+    /// Path chosen by the user (rotated files are named after it)
+    base_path: String,
+    rotation: PcapRotation,
+    link_type: Linktype,
+    /// Information written at the beginning of each file, when recording in the PCAPNG format
+    pcapng_section: Option<PcapngSection>,
+    writer: RecordWriter,
+    /// Bytes written to the current file
+    file_size: u64,
+    /// Time the current file was started
+    file_start: DateTime<Local>,
+    /// Paths of the files kept, from the oldest to the current one
+    files: VecDeque<String>,
+    /// Packets written to the current file
+    file_packets: usize,
+    /// Network adapter the packets are captured from (None for capture files)
+    pub interface: Option<String>,
+}
+
+/// Files recorded up to a given moment, to read the packets they contained at that time
+/// while the capture goes on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedFiles {
+    /// Paths of the files, from the oldest to the current one
+    pub files: Vec<String>,
+    /// Packets written to the current file
+    pub last_file_packets: usize,
+    /// Network adapter the packets were captured from (None for capture files)
+    pub interface: Option<String>,
+}
+
+impl PcapRecorder {
+    pub fn new(
+        base_path: &str,
+        rotation: PcapRotation,
+        link_type: Linktype,
+        pcapng_section: Option<PcapngSection>,
+    ) -> Result<Self, String> {
+        let now = Local::now();
+        let path = if rotation.is_enabled() {
+            PcapRotation::file_path(base_path, now)
+        } else {
+            base_path.to_string()
+        };
+        let writer = RecordWriter::new(&path, link_type, pcapng_section.as_ref())?;
+        Ok(Self {
+            base_path: base_path.to_string(),
+            rotation,
+            link_type,
+            pcapng_section,
+            file_size: writer.header_len(),
+            writer,
+            file_start: now,
+            files: VecDeque::from([path]),
+            file_packets: 0,
+            interface: None,
+        })
+    }
+
+    /// Path chosen by the user, identifying the recorded capture
+    pub fn base_path(&self) -> &str {
+        &self.base_path
+    }
+
+    /// Whether the packets are recorded in the PCAPNG format, the only one supporting comments
+    pub fn is_pcapng(&self) -> bool {
+        self.pcapng_section.is_some()
+    }
+
+    /// Files recorded so far, with the packets written to the current one
+    /// (which are flushed, to be readable from the file)
+    pub fn recorded_files(&mut self) -> RecordedFiles {
+        self.flush();
+        RecordedFiles {
+            files: self.files.iter().cloned().collect(),
+            last_file_packets: self.file_packets,
+            interface: self.interface.clone(),
+        }
+    }
+
+    /// Writes the packet, starting a new file first if needed
+    ///
+    /// The direction and the comment are only recorded in the PCAPNG format
+    pub fn write(&mut self, packet: &Packet, direction: TrafficDirection, comment: Option<&str>) {
+        let ts = packet.header.ts;
+        let record = PacketRecord {
+            ts_sec: u32::try_from(ts.tv_sec).unwrap_or_default(),
+            ts_usec: u32::try_from(ts.tv_usec).unwrap_or_default(),
+            original_len: packet.header.len,
+            data: packet.data,
+            direction,
+            comment,
+        };
+        self.write_record(&record, Local::now());
+    }
+
+    /// Writes to disk the packets still buffered
+    pub fn flush(&mut self) {
+        // like for libpcap savefiles, write errors are not reported
+        let _ = self.writer.flush();
+    }
+
+    fn write_record(&mut self, record: &PacketRecord, now: DateTime<Local>) {
+        let record_len = self.writer.record_len(record);
+        if self.needs_rotation(record_len, now) {
+            // like for libpcap savefiles, write errors are not reported:
+            // if the new file can't be created, the current one is still used
+            let _ = self.rotate(now);
+        }
+        if self.writer.write_record(record).is_ok() {
+            self.file_size += record_len;
+            self.file_packets += 1;
+        }
+    }
+
+    fn needs_rotation(&self, record_len: u64, now: DateTime<Local>) -> bool {
+        // a file always contains at least a packet, even if bigger than the maximum size
+        let is_full = self.rotation.max_size_bytes().is_some_and(|max_size| {
+            self.file_size > self.writer.header_len() && self.file_size + record_len > max_size
+        });
+        let is_old = self.rotation.max_minutes.is_some_and(|max_minutes| {
+            u64::try_from((now - self.file_start).num_minutes()).unwrap_or_default() >= max_minutes
+        });
+        is_full || is_old
+    }
+
+    fn rotate(&mut self, now: DateTime<Local>) -> Result<(), String> {
+        let mut path = PcapRotation::file_path(&self.base_path, now);
+        // several files may be started in the same second
+        let mut counter = 0;
+        while self.files.contains(&path) {
+            counter += 1;
+            path = path_with_suffix(
+                &PcapRotation::file_path(&self.base_path, now),
+                &counter.to_string(),
+            );
+        }
+        let writer = RecordWriter::new(&path, self.link_type, self.pcapng_section.as_ref())?;
+        // complete the previous file before moving to the new one
+        self.flush();
+        self.writer = writer;
+        self.file_size = self.writer.header_len();
+        self.file_start = now;
+        self.file_packets = 0;
+        self.files.push_back(path);
+        // delete the oldest files
+        while self
+            .rotation
+            .max_files
+            .is_some_and(|max_files| self.files.len() > max_files)
+        {
+            if let Some(oldest) = self.files.pop_front() {
+                let _ = std::fs::remove_file(oldest);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writer of the current file, in the format chosen by the user
+/// (packets are buffered, to not issue a system call for each of them)
+enum RecordWriter {
+    Pcap(PcapWriter<BufWriter<File>>),
+    Pcapng(PcapngWriter<BufWriter<File>>),
+}
+
+impl RecordWriter {
+    fn new(
+        path: &str,
+        link_type: Linktype,
+        pcapng_section: Option<&PcapngSection>,
+    ) -> Result<Self, String> {
+        File::create(path)
+            .map(BufWriter::new)
+            .and_then(|file| match pcapng_section {
+                Some(section) => PcapngWriter::new(file, link_type, section).map(Self::Pcapng),
+                None => PcapWriter::new(file, link_type).map(Self::Pcap),
+            })
+            .map_err(|e| format!("{path}: {e}"))
+    }
+
+    /// Bytes written at the beginning of each file
+    fn header_len(&self) -> u64 {
+        match self {
+            Self::Pcap(_) => PCAP_HEADER_LEN as u64,
+            Self::Pcapng(writer) => writer.header_len() as u64,
+        }
+    }
+
+    /// Bytes needed to write the packet
+    fn record_len(&self, record: &PacketRecord) -> u64 {
+        match self {
+            Self::Pcap(_) => (PCAP_RECORD_HEADER_LEN + record.data.len()) as u64,
+            Self::Pcapng(_) => {
+                PcapngWriter::<File>::record_len(record.data.len(), record.comment) as u64
+            }
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Pcap(writer) => writer.flush(),
+            Self::Pcapng(writer) => writer.flush(),
+        }
+    }
+
+    fn write_record(&mut self, record: &PacketRecord) -> std::io::Result<()> {
+        match self {
+            Self::Pcap(writer) => writer.write_record(
+                record.ts_sec,
+                record.ts_usec,
+                record.original_len,
+                record.data,
+            ),
+            Self::Pcapng(writer) => writer.write_record(record),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use chrono::{TimeDelta, TimeZone};
+
+    use super::*;
+
+    fn record(data: &[u8]) -> PacketRecord<'_> {
+        PacketRecord {
+            ts_sec: 1,
+            ts_usec: 2,
+            original_len: u32::try_from(data.len()).unwrap(),
+            data,
+            direction: TrafficDirection::Outgoing,
+            comment: None,
+        }
+    }
+
+    fn remove_files(recorder: &PcapRecorder) {
+        for path in &recorder.files {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_pcap_recorder_without_rotation() {
+        let path = "test_pcap_recorder_without_rotation.pcap";
+        let mut recorder =
+            PcapRecorder::new(path, PcapRotation::default(), Linktype::ETHERNET, None).unwrap();
+        let now = Local::now();
+        for _ in 0..10 {
+            recorder.write_record(&record(&[0; 100]), now);
+        }
+        assert_eq!(recorder.base_path(), path);
+        assert_eq!(recorder.files, VecDeque::from([path.to_string()]));
+        // packets are buffered until flushed
+        assert_eq!(std::fs::read(path).unwrap().len(), 24);
+        recorder.flush();
+        assert_eq!(std::fs::read(path).unwrap().len(), 24 + 10 * (16 + 100));
+        remove_files(&recorder);
+
+        // the file can't be created
+        assert!(PcapRecorder::new(
+            "not_existing_dir/test.pcap",
+            PcapRotation::default(),
+            Linktype::ETHERNET,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_pcap_recorder_rotation_by_size() {
+        let base_path = "test_pcap_recorder_rotation_by_size.pcap";
+        let rotation = PcapRotation {
+            max_size_mb: Some(1),
+            max_minutes: None,
+            max_files: Some(2),
+        };
+        let mut recorder =
+            PcapRecorder::new(base_path, rotation, Linktype::ETHERNET, None).unwrap();
+        let first_file = recorder.files[0].clone();
+        assert!(first_file.starts_with("test_pcap_recorder_rotation_by_size_20"));
+
+        // 4 packets fit in each file
+        let now = Local.with_ymd_and_hms(2026, 10, 18, 10, 15, 0).unwrap();
+        let data = vec![0; 240_000];
+        for _ in 0..10 {
+            recorder.write_record(&record(&data), now);
+        }
+        recorder.flush();
+        // only the last 2 files are kept, and files started in the same second are numbered
+        let files = recorder.files.clone();
+        assert_eq!(files.len(), 2);
+        assert!(!Path::new(&first_file).exists());
+        assert_eq!(
+            files[0],
+            "test_pcap_recorder_rotation_by_size_20261018_101500.pcap"
+        );
+        assert_eq!(
+            files[1],
+            "test_pcap_recorder_rotation_by_size_20261018_101500_1.pcap"
+        );
+        assert_eq!(
+            std::fs::read(&files[0]).unwrap().len(),
+            24 + 4 * (16 + 240_000)
+        );
+        assert_eq!(
+            std::fs::read(&files[1]).unwrap().len(),
+            24 + 2 * (16 + 240_000)
+        );
+        assert!(!Path::new(base_path).exists());
+        remove_files(&recorder);
+    }
+
+    #[test]
+    fn test_pcap_recorder_rotation_by_duration() {
+        let base_path = "test_pcap_recorder_rotation_by_duration.pcap";
+        let rotation = PcapRotation {
+            max_size_mb: None,
+            max_minutes: Some(5),
+            max_files: None,
+        };
+        let mut recorder =
+            PcapRecorder::new(base_path, rotation, Linktype::ETHERNET, None).unwrap();
+        let start = Local::now();
+        recorder.write_record(&record(&[0; 10]), start);
+        recorder.write_record(&record(&[0; 10]), start + TimeDelta::minutes(4));
+        assert_eq!(recorder.files.len(), 1);
+        recorder.write_record(&record(&[0; 10]), start + TimeDelta::minutes(5));
+        recorder.write_record(&record(&[0; 10]), start + TimeDelta::minutes(11));
+        let files = recorder.files.clone();
+        assert_eq!(files.len(), 3);
+        assert_eq!(
+            files[2],
+            PcapRotation::file_path(base_path, start + TimeDelta::minutes(11))
+        );
+        assert_eq!(std::fs::read(&files[0]).unwrap().len(), 24 + 2 * (16 + 10));
+        assert_eq!(std::fs::read(&files[1]).unwrap().len(), 24 + 16 + 10);
+        assert_eq!(
+            recorder.recorded_files(),
+            RecordedFiles {
+                files: files.into_iter().collect(),
+                last_file_packets: 1,
+                interface: None,
+            }
+        );
+        remove_files(&recorder);
+    }
+
+    #[test]
+    fn test_pcap_recorder_pcapng() {
+        let base_path = "test_pcap_recorder_pcapng.pcapng";
+        let rotation = PcapRotation {
+            max_size_mb: Some(1),
+            max_minutes: None,
+            max_files: None,
+        };
+        let section = PcapngSection {
+            application: "Sniffnet".to_string(),
+            comment: String::new(),
+            interface_name: "eth0".to_string(),
+            interface_description: None,
+        };
+        let mut recorder =
+            PcapRecorder::new(base_path, rotation, Linktype::ETHERNET, Some(section)).unwrap();
+        let now = Local.with_ymd_and_hms(2026, 10, 18, 10, 15, 0).unwrap();
+        let data = vec![0; 400_000];
+        let comment = Some("Favorite: example.com");
+        for _ in 0..3 {
+            recorder.write_record(
+                &PacketRecord {
+                    comment,
+                    ..record(&data)
+                },
+                now,
+            );
+        }
+        recorder.flush();
+        // every file starts with its own section and interface description
+        let files = recorder.files.clone();
+        assert_eq!(files.len(), 2);
+        let header_len = recorder.writer.header_len();
+        let record_len = PcapngWriter::<File>::record_len(400_000, comment) as u64;
+        for (path, packets) in files.iter().zip([2, 1]) {
+            let bytes = std::fs::read(path).unwrap();
+            assert_eq!(bytes[0..4], [0x0a, 0x0d, 0x0d, 0x0a]);
+            assert_eq!(bytes.len() as u64, header_len + packets * record_len);
+        }
+        remove_files(&recorder);
+    }
+}
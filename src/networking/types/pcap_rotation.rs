@@ -0,0 +1,88 @@
+//! Module defining the `PcapRotation` struct, which bounds the size of the exported capture
+//! by splitting it into several files, like a ring buffer.
+
+use chrono::{DateTime, Local};
+
+use crate::gui::types::export_pcap::path_with_suffix;
+
+/// Criteria to switch the exported capture to a new file, and number of files to keep
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PcapRotation {
+    /// Megabytes after which a new file is started
+    pub max_size_mb: Option<u64>,
+    /// Minutes after which a new file is started
+    pub max_minutes: Option<u64>,
+    /// Maximum number of files kept (the oldest one is deleted when a new file is started)
+    pub max_files: Option<usize>,
+}
+
+impl PcapRotation {
+    /// Whether the capture is split into several files
+    pub fn is_enabled(&self) -> bool {
+        self.max_size_mb.is_some() || self.max_minutes.is_some()
+    }
+
+    /// Maximum size of each file, in bytes
+    pub fn max_size_bytes(&self) -> Option<u64> {
+        self.max_size_mb.map(|mb| mb.saturating_mul(1_000_000))
+    }
+
+    /// Path of the file started at the given time (e.g., `sniffnet_20261018_101500.pcap`)
+    pub fn file_path(base_path: &str, timestamp: DateTime<Local>) -> String {
+        path_with_suffix(base_path, &timestamp.format("%Y%m%d_%H%M%S").to_string())
+    }
+}
+
+/// Parses a limit typed by the user: an empty value means no limit,
+/// while `None` is returned if the value is not a positive number
+pub fn parse_limit<T: std::str::FromStr + Default + PartialEq>(value: &str) -> Option<Option<T>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Some(None);
+    }
+    match value.parse::<T>() {
+        Ok(limit) if limit != T::default() => Some(Some(limit)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_pcap_rotation() {
+        let mut rotation = PcapRotation::default();
+        assert!(!rotation.is_enabled());
+        rotation.max_files = Some(5);
+        assert!(!rotation.is_enabled());
+        rotation.max_minutes = Some(10);
+        assert!(rotation.is_enabled());
+        assert_eq!(rotation.max_size_bytes(), None);
+        rotation.max_size_mb = Some(100);
+        assert_eq!(rotation.max_size_bytes(), Some(100_000_000));
+
+        let timestamp = Local.with_ymd_and_hms(2026, 10, 18, 10, 15, 0).unwrap();
+        let mut base_path = PathBuf::from("/tmp");
+        base_path.push("sniffnet.pcap");
+        let mut expected = PathBuf::from("/tmp");
+        expected.push("sniffnet_20261018_101500.pcap");
+        assert_eq!(
+            PcapRotation::file_path(&base_path.to_string_lossy(), timestamp),
+            expected.to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn test_parse_limit() {
+        assert_eq!(parse_limit::<u64>(""), Some(None));
+        assert_eq!(parse_limit::<u64>(" 20 "), Some(Some(20)));
+        assert_eq!(parse_limit::<usize>("0"), None);
+        assert_eq!(parse_limit::<usize>("-1"), None);
+        assert_eq!(parse_limit::<usize>("ten"), None);
+    }
+}
@@ -5,11 +5,14 @@
 pub(crate) struct PortCollection {
     pub(crate) ports: Vec<u16>,
     pub(crate) ranges: Vec<RangeInclusive<u16>>,
+    pub(crate) excluded_ports: Vec<u16>,
+    pub(crate) excluded_ranges: Vec<RangeInclusive<u16>>,
 }
 
 impl PortCollection {
     const SEPARATOR: char = ',';
     const RANGE_SEPARATOR: char = '-';
+    const NEGATION: char = '!';
 
     pub const PLACEHOLDER_STR: &'static str = "0-65535";
 
@@ -22,9 +25,17 @@ pub(crate) fn new(str: &str) -> Option<Self> {
 
         let mut ports = Vec::new();
         let mut ranges = Vec::new();
+        let mut excluded_ports = Vec::new();
+        let mut excluded_ranges = Vec::new();
 
         let objects: Vec<&str> = str.split(Self::SEPARATOR).collect();
         for object in objects {
+            let (object, ports, ranges) = match object.strip_prefix(Self::NEGATION) {
+                Some(excluded_object) => {
+                    (excluded_object, &mut excluded_ports, &mut excluded_ranges)
+                }
+                None => (object, &mut ports, &mut ranges),
+            };
             if object.contains(Self::RANGE_SEPARATOR) {
                 // port range
                 let mut subparts = object.split(Self::RANGE_SEPARATOR);
@@ -53,21 +64,41 @@ pub(crate) fn new(str: &str) -> Option<Self> {
             }
         }
 
-        Some(Self { ports, ranges })
+        if ports.is_empty() && ranges.is_empty() {
+            // only exclusions were specified: everything else is included
+            ranges = Self::default().ranges;
+        }
+
+        Some(Self {
+            ports,
+            ranges,
+            excluded_ports,
+            excluded_ranges,
+        })
     }
 
     pub(crate) fn contains(&self, port: Option<u16>) -> bool {
         // ignore port filter in case of ICMP
-        if port.is_none() {
+        let Some(port) = port else {
             return true;
-        }
+        };
 
-        for range in &self.ranges {
-            if range.contains(&port.unwrap()) {
-                return true;
-            }
-        }
-        self.ports.contains(&port.unwrap())
+        let is_included =
+            self.ports.contains(&port) || self.ranges.iter().any(|range| range.contains(&port));
+        is_included && !self.excludes(Some(port))
+    }
+
+    /// Checks whether the port has been explicitly excluded
+    pub(crate) fn excludes(&self, port: Option<u16>) -> bool {
+        let Some(port) = port else {
+            return false;
+        };
+
+        self.excluded_ports.contains(&port)
+            || self
+                .excluded_ranges
+                .iter()
+                .any(|range| range.contains(&port))
     }
 }
 
@@ -76,6 +107,8 @@ fn default() -> Self {
         PortCollection {
             ports: vec![],
             ranges: vec![RangeInclusive::new(u16::MIN, u16::MAX)],
+            excluded_ports: vec![],
+            excluded_ranges: vec![],
         }
     }
 }
@@ -102,7 +135,9 @@ fn test_new_port_collections() {
             PortCollection::new("0").unwrap(),
             PortCollection {
                 ports: vec![0],
-                ranges: vec![]
+                ranges: vec![],
+                excluded_ports: vec![],
+                excluded_ranges: vec![],
             }
         );
 
@@ -110,7 +145,9 @@ fn test_new_port_collections() {
             PortCollection::new(" 0 ").unwrap(),
             PortCollection {
                 ports: vec![0],
-                ranges: vec![]
+                ranges: vec![],
+                excluded_ports: vec![],
+                excluded_ranges: vec![],
             }
         );
 
@@ -118,7 +155,9 @@ fn test_new_port_collections() {
             PortCollection::new("1,2,3,4,999").unwrap(),
             PortCollection {
                 ports: vec![1, 2, 3, 4, 999],
-                ranges: vec![]
+                ranges: vec![],
+                excluded_ports: vec![],
+                excluded_ranges: vec![],
             }
         );
 
@@ -126,7 +165,9 @@ fn test_new_port_collections() {
             PortCollection::new("1, 2, 3, 4, 900-999").unwrap(),
             PortCollection {
                 ports: vec![1, 2, 3, 4],
-                ranges: vec![900..=999]
+                ranges: vec![900..=999],
+                excluded_ports: vec![],
+                excluded_ranges: vec![],
             }
         );
 
@@ -134,7 +175,9 @@ fn test_new_port_collections() {
             PortCollection::new("1 - 999").unwrap(),
             PortCollection {
                 ports: vec![],
-                ranges: vec![1..=999]
+                ranges: vec![1..=999],
+                excluded_ports: vec![],
+                excluded_ranges: vec![],
             }
         );
 
@@ -142,7 +185,9 @@ fn test_new_port_collections() {
             PortCollection::new("   1,2,10-20,3,4,  999-1200    ").unwrap(),
             PortCollection {
                 ports: vec![1, 2, 3, 4],
-                ranges: vec![10..=20, 999..=1200]
+                ranges: vec![10..=20, 999..=1200],
+                excluded_ports: vec![],
+                excluded_ranges: vec![],
             }
         );
     }
@@ -179,4 +224,54 @@ fn test_port_collection_contains() {
         assert!(!collection.contains(Some(118)));
         assert!(!collection.contains(Some(8080)));
     }
+
+    #[test]
+    fn test_new_port_collections_negation() {
+        assert_eq!(
+            PortCollection::new("1-1000, !53, !100-200").unwrap(),
+            PortCollection {
+                ports: vec![],
+                ranges: vec![1..=1000],
+                excluded_ports: vec![53],
+                excluded_ranges: vec![100..=200],
+            }
+        );
+
+        // only exclusions: everything else is included
+        assert_eq!(
+            PortCollection::new("!53,!5353").unwrap(),
+            PortCollection {
+                excluded_ports: vec![53, 5353],
+                ..PortCollection::default()
+            }
+        );
+
+        assert_eq!(PortCollection::new("!"), None);
+        assert_eq!(PortCollection::new("!!53"), None);
+        assert_eq!(PortCollection::new("53!"), None);
+        assert_eq!(PortCollection::new("!200-100"), None);
+    }
+
+    #[test]
+    fn test_port_collection_contains_negation() {
+        let collection = PortCollection::new("!53,!5353").unwrap();
+        assert!(collection.contains(Some(0)));
+        assert!(collection.contains(Some(80)));
+        assert!(collection.contains(Some(65535)));
+        assert!(!collection.contains(Some(53)));
+        assert!(!collection.contains(Some(5353)));
+        assert!(collection.excludes(Some(53)));
+        assert!(!collection.excludes(Some(80)));
+        // ICMP packets are never excluded
+        assert!(collection.contains(None));
+        assert!(!collection.excludes(None));
+
+        let collection_2 = PortCollection::new("8000-8999, !8080-8089, 443").unwrap();
+        assert!(collection_2.contains(Some(443)));
+        assert!(collection_2.contains(Some(8000)));
+        assert!(collection_2.contains(Some(8090)));
+        assert!(!collection_2.contains(Some(8080)));
+        assert!(!collection_2.contains(Some(8085)));
+        assert!(!collection_2.contains(Some(80)));
+    }
 }
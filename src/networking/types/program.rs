@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Program owning the local socket of a connection.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Program {
+    /// A running program, with its name and process ID.
+    Known { name: String, pid: u32 },
+    /// Not identified
+    #[default]
+    Unknown,
+}
+
+impl Program {
+    /// Returns the program name followed by its process ID
+    pub fn to_string_with_pid(&self) -> String {
+        match self {
+            Program::Known { name, pid } => format!("{name} (PID {pid})"),
+            Program::Unknown => self.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Program {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Program::Known { name, .. } => write!(f, "{name}"),
+            Program::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_program_display_unknown() {
+        assert_eq!(Program::Unknown.to_string(), "?");
+        assert_eq!(Program::Unknown.to_string_with_pid(), "?");
+    }
+
+    #[test]
+    fn test_program_display_known() {
+        let program = Program::Known {
+            name: "firefox".to_string(),
+            pid: 4242,
+        };
+        assert_eq!(program.to_string(), "firefox");
+        assert_eq!(program.to_string_with_pid(), "firefox (PID 4242)");
+    }
+}
@@ -1,3 +1,7 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::networking::manage_packets::get_service_by_name;
+
 /// Upper layer services.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Service {
@@ -29,8 +33,29 @@ fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
     }
 }
 
+/// Services are serialized with their displayed name
+impl Serialize for Service {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Names that don't correspond to any of the known services are deserialized as unknown
+impl<'de> Deserialize<'de> for Service {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(match name.as_str() {
+            "?" => Service::Unknown,
+            "-" => Service::NotApplicable,
+            name => get_service_by_name(name).unwrap_or_default(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use serde_test::{assert_de_tokens, assert_tokens, Token};
+
     use super::*;
 
     #[test]
@@ -57,4 +82,24 @@ fn test_service_to_string_with_equal_prefix() {
         // unknown should not have the prefix
         assert_eq!(Service::Unknown.to_string_with_equal_prefix(), "?");
     }
+
+    // test if deserializing and serializing a Service works
+    #[test]
+    fn test_service_round_trip() {
+        assert_tokens(&Service::Name("https"), &[Token::Str("https")]);
+        assert_tokens(&Service::Name("mdns"), &[Token::Str("mdns")]);
+        assert_tokens(&Service::Unknown, &[Token::Str("?")]);
+        assert_tokens(&Service::NotApplicable, &[Token::Str("-")]);
+    }
+
+    #[test]
+    fn test_service_round_trip_payload_only() {
+        assert_tokens(&Service::Name("quic"), &[Token::Str("quic")]);
+        assert_tokens(&Service::Name("bittorrent"), &[Token::Str("bittorrent")]);
+    }
+
+    #[test]
+    fn test_service_deserialize_not_known() {
+        assert_de_tokens(&Service::Unknown, &[Token::Str("not-a-service")]);
+    }
 }
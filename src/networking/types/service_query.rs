@@ -3,7 +3,7 @@
 use std::hash::Hash;
 
 /// Used to query the phf services map (key of the map).
-#[derive(Hash, Eq, PartialEq)]
+#[derive(Hash, Eq, PartialEq, Debug)]
 pub struct ServiceQuery(pub u16, pub crate::Protocol);
 
 impl phf_shared::PhfHash for ServiceQuery {
@@ -0,0 +1,583 @@
+//! Module defining the `Session` struct, used to save the results of a capture to disk
+//! and to reload them later for a read-only analysis.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Local};
+use pcap::Address;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::data_info::DataInfo;
+use crate::networking::types::data_info_host::DataInfoHost;
+use crate::networking::types::filters::Filters;
+use crate::networking::types::host::Host;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::l2_pair::L2Pair;
+use crate::networking::types::lan_device::LanDevice;
+use crate::networking::types::my_device::MyDevice;
+use crate::networking::types::name_source::NameSource;
+use crate::networking::types::tcp_connection::TcpStats;
+use crate::{InfoTraffic, Protocol, Service};
+
+/// Version of the session format, to be incremented at every incompatible change
+pub const SESSION_VERSION: u32 = 1;
+
+/// A capture session, including everything needed to analyze it again after it's been stopped
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    /// Version of the session format
+    pub version: u32,
+    /// Information about the analyzed source
+    pub info: SessionInfo,
+    /// Total amount of filtered bytes received.
+    pub tot_in_bytes: u128,
+    /// Total amount of filtered bytes sent.
+    pub tot_out_bytes: u128,
+    /// Total amount of filtered packets received.
+    pub tot_in_packets: u128,
+    /// Total amount of filtered packets sent.
+    pub tot_out_packets: u128,
+    /// Total packets including those not filtered
+    pub all_packets: u128,
+    /// Total bytes including those not filtered
+    pub all_bytes: u128,
+    /// Number of dropped packets
+    pub dropped_packets: u32,
+    /// Filtered traffic of each address:port pair
+    pub connections: Vec<(AddressPortPair, InfoAddressPortPair)>,
+    /// Hosts with their data info
+    pub hosts: Vec<(Host, DataInfoHost)>,
+    /// Resolved addresses with their full rDNS value and the corresponding host
+    pub addresses_resolved: Vec<(String, String, Host)>,
+    /// Upper layer services with their data info
+    pub services: Vec<(Service, DataInfo)>,
+    /// Names of the programs owning the connections with their data info
+    pub programs: Vec<(String, DataInfo)>,
+    /// Network adapters captured with their data info
+    #[serde(default)]
+    pub adapters: Vec<(String, DataInfo)>,
+    /// Filtered traffic not carrying IP packets
+    #[serde(default)]
+    pub non_ip: Vec<(L2Pair, DataInfo)>,
+    /// Devices of the local network, identified by their MAC address
+    #[serde(default)]
+    pub devices: Vec<(String, LanDevice)>,
+    /// Resolved addresses with the source of their name
+    #[serde(default)]
+    pub name_sources: Vec<(String, NameSource)>,
+    /// Statistics about the outcome of the TCP connections
+    #[serde(default)]
+    pub tcp_stats: TcpStats,
+}
+
+/// Information about the source analyzed in a capture session
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SessionInfo {
+    /// Name of the network adapter, or path of the imported capture file
+    pub device_name: String,
+    /// Description of the network adapter
+    pub device_desc: Option<String>,
+    /// Addresses of the network adapter
+    pub device_addresses: Vec<SessionAddress>,
+    /// Filters applied to the observed traffic
+    pub filters: Filters,
+    /// Time the capture was started
+    pub start_timestamp: DateTime<Local>,
+    /// Time the session was saved
+    pub stop_timestamp: DateTime<Local>,
+}
+
+/// Serializable version of a network adapter address
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SessionAddress {
+    pub addr: IpAddr,
+    pub netmask: Option<IpAddr>,
+    pub broadcast_addr: Option<IpAddr>,
+    pub dst_addr: Option<IpAddr>,
+}
+
+/// Used to check the session version before deserializing the whole content
+#[derive(Deserialize)]
+struct SessionVersion {
+    version: u32,
+}
+
+impl Session {
+    /// Collects the data of the current capture into a new session
+    pub fn new(
+        device: &MyDevice,
+        filters: &Filters,
+        start_timestamp: DateTime<Local>,
+        info_traffic: &InfoTraffic,
+    ) -> Self {
+        let device_addresses = device
+            .addresses
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|address| SessionAddress {
+                addr: address.addr,
+                netmask: address.netmask,
+                broadcast_addr: address.broadcast_addr,
+                dst_addr: address.dst_addr,
+            })
+            .collect();
+        Self {
+            version: SESSION_VERSION,
+            info: SessionInfo {
+                device_name: device.name.clone(),
+                device_desc: device.desc.clone(),
+                device_addresses,
+                filters: filters.clone(),
+                start_timestamp,
+                stop_timestamp: Local::now(),
+            },
+            tot_in_bytes: info_traffic.tot_in_bytes,
+            tot_out_bytes: info_traffic.tot_out_bytes,
+            tot_in_packets: info_traffic.tot_in_packets,
+            tot_out_packets: info_traffic.tot_out_packets,
+            all_packets: info_traffic.all_packets,
+            all_bytes: info_traffic.all_bytes,
+            dropped_packets: info_traffic.dropped_packets,
+            connections: info_traffic
+                .map
+                .iter()
+                .map(|(key, val)| (key.clone(), val.clone()))
+                .collect(),
+            hosts: info_traffic
+                .hosts
+                .iter()
+                .map(|(host, data_info_host)| (host.clone(), *data_info_host))
+                .collect(),
+            addresses_resolved: info_traffic
+                .addresses_resolved
+                .iter()
+                .map(|(address, (r_dns, host))| (address.clone(), r_dns.clone(), host.clone()))
+                .collect(),
+            services: info_traffic
+                .services
+                .iter()
+                .map(|(service, data_info)| (*service, *data_info))
+                .collect(),
+            programs: info_traffic
+                .programs
+                .iter()
+                .map(|(program, data_info)| (program.clone(), *data_info))
+                .collect(),
+            adapters: info_traffic
+                .adapters
+                .iter()
+                .map(|(adapter, data_info)| (adapter.clone(), *data_info))
+                .collect(),
+            non_ip: info_traffic
+                .non_ip
+                .iter()
+                .map(|(key, data_info)| (key.clone(), *data_info))
+                .collect(),
+            devices: info_traffic
+                .devices
+                .iter()
+                .map(|(mac_address, device)| (mac_address.clone(), device.clone()))
+                .collect(),
+            name_sources: info_traffic
+                .name_sources
+                .iter()
+                .map(|(address, source)| (address.clone(), *source))
+                .collect(),
+            tcp_stats: info_traffic.tcp_stats,
+        }
+    }
+
+    /// Writes the session to the given file
+    pub fn store(&self, path: &str) -> Result<(), String> {
+        let content = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| e.to_string())
+    }
+
+    /// Reads a session from the given file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Self::from_json(&content)
+    }
+
+    fn from_json(content: &str) -> Result<Self, String> {
+        let SessionVersion { version } =
+            serde_json::from_str(content).map_err(|e| e.to_string())?;
+        if version != SESSION_VERSION {
+            return Err(format!("unsupported session version: {version}"));
+        }
+        let mut session: Self = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        session.info.filters.rebuild_collections();
+        Ok(session)
+    }
+
+    /// Splits the session into its information and the traffic data to be displayed
+    pub fn into_info_traffic(self) -> (SessionInfo, InfoTraffic) {
+        let mut info_traffic = InfoTraffic::new();
+        info_traffic.tot_in_bytes = self.tot_in_bytes;
+        info_traffic.tot_out_bytes = self.tot_out_bytes;
+        info_traffic.tot_in_packets = self.tot_in_packets;
+        info_traffic.tot_out_packets = self.tot_out_packets;
+        info_traffic.all_packets = self.all_packets;
+        info_traffic.all_bytes = self.all_bytes;
+        info_traffic.dropped_packets = self.dropped_packets;
+        info_traffic.map = self.connections.into_iter().collect();
+        info_traffic.favorite_hosts = self
+            .hosts
+            .iter()
+            .filter(|(_, data_info_host)| data_info_host.is_favorite)
+            .map(|(host, _)| host.clone())
+            .collect::<HashSet<Host>>();
+        info_traffic.hosts = self.hosts.into_iter().collect();
+        info_traffic.addresses_resolved = self
+            .addresses_resolved
+            .into_iter()
+            .map(|(address, r_dns, host)| (address, (r_dns, host)))
+            .collect::<HashMap<String, (String, Host)>>();
+        info_traffic.services = self.services.into_iter().collect();
+        info_traffic.programs = self.programs.into_iter().collect();
+        info_traffic.adapters = self.adapters.into_iter().collect();
+        info_traffic.non_ip = self.non_ip.into_iter().collect();
+        info_traffic.devices = self.devices.into_iter().collect();
+        info_traffic.name_sources = self.name_sources.into_iter().collect();
+        info_traffic.tcp_stats = self.tcp_stats;
+        (self.info, info_traffic)
+    }
+}
+
+impl SessionInfo {
+    /// Returns the addresses of the network adapter, in the format used by pcap
+    pub fn device_addresses(&self) -> Vec<Address> {
+        self.device_addresses
+            .iter()
+            .map(|address| Address {
+                addr: address.addr,
+                netmask: address.netmask,
+                broadcast_addr: address.broadcast_addr,
+                dst_addr: address.dst_addr,
+            })
+            .collect()
+    }
+}
+
+/// Returns the name proposed when saving a session started at the given time
+pub fn session_file_name(start_timestamp: &DateTime<Local>) -> String {
+    format!(
+        "sniffnet_session_{}.json",
+        start_timestamp.format("%Y-%m-%d_%H-%M-%S")
+    )
+}
+
+// implemented here since protocol.rs is also imported in build.rs, where serde is not available
+impl Serialize for Protocol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Protocol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Protocol::ALL
+            .into_iter()
+            .find(|protocol| protocol.to_string().eq(&name))
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown protocol: {name}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::sync::{Arc, Mutex};
+
+    use std::time::Duration;
+
+    use chrono::TimeZone;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    use crate::countries::types::country::Country;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::icmp_type::{IcmpType, IcmpTypeV4};
+    use crate::networking::types::l2_protocol::L2Protocol;
+    use crate::networking::types::my_link_type::MyLinkType;
+    use crate::networking::types::program::Program;
+    use crate::networking::types::tcp_connection::{TcpConnection, TcpState};
+    use crate::networking::types::tcp_quality::TcpQuality;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::networking::types::traffic_type::TrafficType;
+
+    use super::*;
+
+    fn device_for_tests() -> MyDevice {
+        MyDevice {
+            name: "en0".to_string(),
+            desc: Some("Wi-Fi".to_string()),
+            addresses: Arc::new(Mutex::new(vec![Address {
+                addr: "192.168.1.10".parse().unwrap(),
+                netmask: Some("255.255.255.0".parse().unwrap()),
+                broadcast_addr: Some("192.168.1.255".parse().unwrap()),
+                dst_addr: None,
+            }])),
+            link_type: MyLinkType::default(),
+        }
+    }
+
+    fn info_traffic_for_tests() -> InfoTraffic {
+        let mut info_traffic = InfoTraffic::new();
+        info_traffic.tot_in_bytes = 1200;
+        info_traffic.tot_out_bytes = 600;
+        info_traffic.tot_in_packets = 2;
+        info_traffic.tot_out_packets = 1;
+        info_traffic.all_packets = 5;
+        info_traffic.all_bytes = 2500;
+        info_traffic.dropped_packets = 3;
+
+        let now = Local::now();
+        let mut data_info = DataInfo::new_with_first_packet(1200, TrafficDirection::Incoming, now);
+        data_info.add_packet(600, TrafficDirection::Outgoing, now);
+
+        let tcp_key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(54321),
+            "8.8.8.8".to_string(),
+            Some(443),
+            Protocol::TCP,
+        )
+        .with_interface(Some("en0".to_string()));
+        let tcp_info = InfoAddressPortPair {
+            mac_address1: Some("aa:bb:cc:dd:ee:ff".to_string()),
+            transmitted_bytes: 1800,
+            transmitted_packets: 2,
+            service: Service::Name("https"),
+            program: Program::Known {
+                name: "firefox".to_string(),
+                pid: 4242,
+            },
+            traffic_direction: TrafficDirection::Outgoing,
+            tcp_connection: Some(TcpConnection {
+                state: TcpState::Reset,
+                syn_at: Some(Local::now()),
+                opened_at: Some(Local::now()),
+                closed_at: Some(Local::now()),
+                closed_by: Some("8.8.8.8:443".to_string()),
+            }),
+            ..InfoAddressPortPair::default()
+        };
+        let icmp_key = AddressPortPair::new(
+            "8.8.8.8".to_string(),
+            None,
+            "192.168.1.10".to_string(),
+            None,
+            Protocol::ICMP,
+        );
+        let icmp_info = InfoAddressPortPair {
+            transmitted_bytes: 64,
+            transmitted_packets: 1,
+            service: Service::NotApplicable,
+            icmp_types: HashMap::from([(IcmpType::V4(IcmpTypeV4::EchoReply), 1)]),
+            ..InfoAddressPortPair::default()
+        };
+        info_traffic.map = HashMap::from([(tcp_key, tcp_info), (icmp_key, icmp_info)]);
+
+        let host = Host {
+            domain: "dns.google".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE".to_string(),
+            },
+            country: Country::US,
+        };
+        info_traffic.hosts = HashMap::from([(
+            host.clone(),
+            DataInfoHost {
+                data_info,
+                is_favorite: true,
+                is_loopback: false,
+                is_local: false,
+                traffic_type: TrafficType::Unicast,
+                tcp_quality: TcpQuality {
+                    segments: 10,
+                    retransmissions: 1,
+                    max_handshake_rtt: Some(Duration::from_millis(25)),
+                    ..TcpQuality::default()
+                },
+            },
+        )]);
+        info_traffic.addresses_resolved = HashMap::from([(
+            "8.8.8.8".to_string(),
+            ("dns.google".to_string(), host.clone()),
+        )]);
+        info_traffic.favorite_hosts = HashSet::from([host]);
+        info_traffic.tcp_stats = TcpStats {
+            failed_handshakes: 1,
+            resets: 2,
+            half_open: 3,
+        };
+        info_traffic.name_sources = HashMap::from([("8.8.8.8".to_string(), NameSource::Traffic)]);
+        info_traffic.services = HashMap::from([(Service::Name("https"), data_info)]);
+        info_traffic.programs = HashMap::from([("firefox".to_string(), data_info)]);
+        info_traffic.adapters = HashMap::from([("en0".to_string(), data_info)]);
+        info_traffic.non_ip = HashMap::from([(
+            L2Pair {
+                mac_address1: "aa:bb:cc:dd:ee:ff".to_string(),
+                mac_address2: "ff:ff:ff:ff:ff:ff".to_string(),
+                protocol: L2Protocol::ARP,
+            },
+            data_info,
+        )]);
+        info_traffic.devices = HashMap::from([(
+            "aa:bb:cc:dd:ee:ff".to_string(),
+            LanDevice::new_with_first_packet(
+                "192.168.1.10".parse().unwrap(),
+                600,
+                TrafficDirection::Outgoing,
+                now,
+            ),
+        )]);
+        info_traffic
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let mut filters = Filters {
+            address_str: "8.8.8.0/24".to_string(),
+            port_str: "443, !22".to_string(),
+            ..Filters::default()
+        };
+        filters.rebuild_collections();
+        let info_traffic = info_traffic_for_tests();
+
+        let session = Session::new(&device_for_tests(), &filters, Local::now(), &info_traffic);
+        let json = serde_json::to_string(&session).unwrap();
+        let (info, loaded) = Session::from_json(&json).unwrap().into_info_traffic();
+
+        assert_eq!(info.device_name, "en0");
+        assert_eq!(info.device_desc, Some("Wi-Fi".to_string()));
+        let addresses = info.device_addresses();
+        assert_eq!(addresses.len(), 1);
+        assert_eq!(addresses[0].addr.to_string(), "192.168.1.10");
+        assert_eq!(addresses[0].netmask.unwrap().to_string(), "255.255.255.0");
+        assert_eq!(
+            addresses[0].broadcast_addr.unwrap().to_string(),
+            "192.168.1.255"
+        );
+        assert_eq!(addresses[0].dst_addr, None);
+        assert_eq!(info.filters, filters);
+        assert!(info.start_timestamp <= info.stop_timestamp);
+
+        assert_eq!(loaded.tot_in_bytes, 1200);
+        assert_eq!(loaded.tot_out_bytes, 600);
+        assert_eq!(loaded.tot_in_packets, 2);
+        assert_eq!(loaded.tot_out_packets, 1);
+        assert_eq!(loaded.all_packets, 5);
+        assert_eq!(loaded.all_bytes, 2500);
+        assert_eq!(loaded.dropped_packets, 3);
+        assert_eq!(loaded.map.len(), 2);
+        for (key, val) in &info_traffic.map {
+            let loaded_val = loaded.map.get(key).unwrap();
+            assert_eq!(loaded_val.mac_address1, val.mac_address1);
+            assert_eq!(loaded_val.transmitted_bytes, val.transmitted_bytes);
+            assert_eq!(loaded_val.transmitted_packets, val.transmitted_packets);
+            assert_eq!(loaded_val.initial_timestamp, val.initial_timestamp);
+            assert_eq!(loaded_val.final_timestamp, val.final_timestamp);
+            assert_eq!(loaded_val.service, val.service);
+            assert_eq!(loaded_val.program, val.program);
+            assert_eq!(loaded_val.traffic_direction, val.traffic_direction);
+            assert!(loaded_val.icmp_types == val.icmp_types);
+            assert_eq!(loaded_val.tcp_connection, val.tcp_connection);
+        }
+        assert_eq!(loaded.hosts.len(), 1);
+        for (host, data_info_host) in &info_traffic.hosts {
+            let loaded_data_info_host = loaded.hosts.get(host).unwrap();
+            assert_eq!(
+                loaded_data_info_host.data_info.incoming_bytes(),
+                data_info_host.data_info.incoming_bytes()
+            );
+            assert_eq!(
+                loaded_data_info_host.data_info.outgoing_packets(),
+                data_info_host.data_info.outgoing_packets()
+            );
+            assert!(loaded_data_info_host.is_favorite);
+        }
+        assert_eq!(loaded.addresses_resolved, info_traffic.addresses_resolved);
+        assert_eq!(loaded.name_sources, info_traffic.name_sources);
+        assert_eq!(loaded.tcp_stats, info_traffic.tcp_stats);
+        assert_eq!(loaded.favorite_hosts, info_traffic.favorite_hosts);
+        assert_eq!(
+            loaded.services.keys().collect::<Vec<_>>(),
+            vec![&Service::Name("https")]
+        );
+        assert_eq!(
+            loaded.programs.keys().collect::<Vec<_>>(),
+            vec![&"firefox".to_string()]
+        );
+        assert_eq!(
+            loaded.adapters.keys().collect::<Vec<_>>(),
+            vec![&"en0".to_string()]
+        );
+        assert_eq!(
+            loaded.non_ip.keys().collect::<Vec<_>>(),
+            info_traffic.non_ip.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(loaded.devices.len(), 1);
+        let loaded_device = loaded.devices.get("aa:bb:cc:dd:ee:ff").unwrap();
+        assert_eq!(
+            loaded_device.addresses,
+            BTreeSet::from(["192.168.1.10".parse().unwrap()])
+        );
+        assert_eq!(loaded_device.data_info.outgoing_bytes(), 600);
+        assert_eq!(
+            loaded_device.first_seen,
+            info_traffic.devices["aa:bb:cc:dd:ee:ff"].first_seen
+        );
+    }
+
+    #[test]
+    fn test_session_unsupported_version() {
+        let session = Session::new(
+            &device_for_tests(),
+            &Filters::default(),
+            Local::now(),
+            &InfoTraffic::new(),
+        );
+        let json = serde_json::to_string(&session)
+            .unwrap()
+            .replace("\"version\":1", "\"version\":999");
+        assert_eq!(
+            Session::from_json(&json).err(),
+            Some("unsupported session version: 999".to_string())
+        );
+    }
+
+    #[test]
+    fn test_session_not_valid() {
+        assert!(Session::from_json("").is_err());
+        assert!(Session::from_json("{\"version\":1}").is_err());
+        assert!(Session::from_json("[1, 2, 3]").is_err());
+    }
+
+    #[test]
+    fn test_session_file_name() {
+        let start_timestamp = Local.with_ymd_and_hms(2024, 4, 20, 10, 5, 30).unwrap();
+        assert_eq!(
+            session_file_name(&start_timestamp),
+            "sniffnet_session_2024-04-20_10-05-30.json"
+        );
+    }
+
+    // test if deserializing and serializing a Protocol works
+    #[test]
+    fn test_protocol_round_trip() {
+        assert_tokens(&Protocol::TCP, &[Token::Str("TCP")]);
+        assert_tokens(&Protocol::UDP, &[Token::Str("UDP")]);
+        assert_tokens(&Protocol::ICMP, &[Token::Str("ICMP")]);
+    }
+
+    #[test]
+    fn test_protocol_deserialize_not_valid() {
+        assert_de_tokens_error::<Protocol>(&[Token::Str("SCTP")], "unknown protocol: SCTP");
+    }
+}
@@ -0,0 +1,310 @@
+//! Module defining the `TcpConnection` struct, which tracks the lifecycle of a TCP connection
+//! from the flags of the segments exchanged.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use etherparse::TcpHeader;
+use serde::{Deserialize, Serialize};
+
+/// Flags of a TCP segment relevant to track the state of the connection
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct TcpFlags {
+    pub syn: bool,
+    pub ack: bool,
+    pub fin: bool,
+    pub rst: bool,
+}
+
+/// Fields of a TCP segment relevant to track the connection and its quality
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct TcpSegment {
+    pub flags: TcpFlags,
+    pub sequence_number: u32,
+    pub acknowledgment_number: u32,
+    pub window_size: u16,
+    /// Length of the payload carried by the segment
+    pub payload_len: u32,
+}
+
+impl TcpSegment {
+    pub fn from_etherparse(tcp_header: &TcpHeader, payload_len: usize) -> Self {
+        Self {
+            flags: TcpFlags {
+                syn: tcp_header.syn,
+                ack: tcp_header.ack,
+                fin: tcp_header.fin,
+                rst: tcp_header.rst,
+            },
+            sequence_number: tcp_header.sequence_number,
+            acknowledgment_number: tcp_header.acknowledgment_number,
+            window_size: tcp_header.window_size,
+            payload_len: u32::try_from(payload_len).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+/// State of a TCP connection, as observed on the wire
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum TcpState {
+    /// Connection requested (SYN)
+    SynSent,
+    /// Connection request accepted (SYN-ACK)
+    SynReceived,
+    /// Handshake completed, or connection already open when first observed
+    Established,
+    /// One of the endpoints closed its side of the connection (FIN)
+    Closing,
+    /// Both endpoints closed their side of the connection (FIN)
+    Closed,
+    /// Connection aborted by one of the endpoints (RST)
+    Reset,
+    /// Connection request rejected (RST during the handshake)
+    Refused,
+}
+
+impl TcpState {
+    /// Whether the handshake was started but not completed yet
+    pub fn is_half_open(self) -> bool {
+        matches!(self, Self::SynSent | Self::SynReceived)
+    }
+
+    /// Whether the connection is over
+    pub fn is_terminated(self) -> bool {
+        matches!(self, Self::Closed | Self::Reset | Self::Refused)
+    }
+}
+
+impl std::fmt::Display for TcpState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let state = match self {
+            Self::SynSent => "SYN",
+            Self::SynReceived => "SYN-ACK",
+            Self::Established => "ESTAB",
+            Self::Closing => "FIN",
+            Self::Closed => "CLOSED",
+            Self::Reset => "RST",
+            Self::Refused => "REFUSED",
+        };
+        write!(f, "{state}")
+    }
+}
+
+/// Lifecycle of a TCP connection, shared by the two directions of the flow
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct TcpConnection {
+    /// Current state of the connection
+    pub state: TcpState,
+    /// Time of the (last) connection request (None if it wasn't observed)
+    #[serde(default)]
+    pub syn_at: Option<DateTime<Local>>,
+    /// Time the handshake completed (None if it wasn't observed)
+    pub opened_at: Option<DateTime<Local>>,
+    /// Time the connection was closed, reset, or refused
+    pub closed_at: Option<DateTime<Local>>,
+    /// Socket address of the endpoint that first closed (FIN) or reset (RST) the connection
+    pub closed_by: Option<String>,
+}
+
+impl TcpConnection {
+    /// Returns the connection as observed from its first segment
+    pub fn new(flags: TcpFlags, sender: &str, timestamp: DateTime<Local>) -> Self {
+        let state = match (flags.syn, flags.ack) {
+            (true, false) => TcpState::SynSent,
+            (true, true) => TcpState::SynReceived,
+            // the capture started when the connection was already open
+            _ => TcpState::Established,
+        };
+        let mut connection = Self {
+            state,
+            syn_at: (state == TcpState::SynSent).then_some(timestamp),
+            opened_at: None,
+            closed_at: None,
+            closed_by: None,
+        };
+        if !flags.syn {
+            connection.update(flags, sender, timestamp);
+        }
+        connection
+    }
+
+    /// Updates the state of the connection with a new segment sent by the given endpoint
+    pub fn update(&mut self, flags: TcpFlags, sender: &str, timestamp: DateTime<Local>) {
+        // a new connection reusing the same ports
+        if flags.syn && !flags.ack && self.state.is_terminated() {
+            *self = Self::new(flags, sender, timestamp);
+            return;
+        }
+
+        if flags.rst {
+            let new_state = match self.state {
+                TcpState::SynSent | TcpState::SynReceived => TcpState::Refused,
+                TcpState::Established | TcpState::Closing => TcpState::Reset,
+                state => state,
+            };
+            if new_state != self.state {
+                self.state = new_state;
+                self.closed_at = Some(timestamp);
+                self.closed_by = Some(sender.to_string());
+            }
+            return;
+        }
+
+        match self.state {
+            // the round-trip time is measured from the request that was answered
+            TcpState::SynSent if flags.syn && !flags.ack => self.syn_at = Some(timestamp),
+            TcpState::SynSent if flags.syn && flags.ack => self.state = TcpState::SynReceived,
+            TcpState::SynSent | TcpState::SynReceived if !flags.syn && flags.ack => {
+                self.state = TcpState::Established;
+                self.opened_at = Some(timestamp);
+            }
+            _ => {}
+        }
+
+        if flags.fin {
+            match self.state {
+                TcpState::Established => {
+                    self.state = TcpState::Closing;
+                    self.closed_by = Some(sender.to_string());
+                }
+                TcpState::Closing if self.closed_by.as_deref() != Some(sender) => {
+                    self.state = TcpState::Closed;
+                    self.closed_at = Some(timestamp);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Time elapsed from the connection request to the completion of the handshake
+    /// (SYN, SYN-ACK, ACK), if both were observed
+    pub fn handshake_rtt(&self) -> Option<Duration> {
+        (self.opened_at? - self.syn_at?).to_std().ok()
+    }
+}
+
+/// Statistics about the outcome of the TCP connections
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TcpStats {
+    /// Number of connection requests rejected or aborted during the handshake
+    pub failed_handshakes: usize,
+    /// Number of established connections reset
+    pub resets: usize,
+    /// Number of connections whose handshake is not completed
+    pub half_open: usize,
+}
+
+impl TcpStats {
+    /// Updates the statistics after a state transition (`previous` is None for new connections)
+    pub fn update(&mut self, previous: Option<TcpState>, current: TcpState) {
+        if previous == Some(current) {
+            return;
+        }
+        if previous.is_some_and(TcpState::is_half_open) {
+            self.half_open = self.half_open.saturating_sub(1);
+        }
+        match current {
+            state if state.is_half_open() => self.half_open += 1,
+            TcpState::Refused => self.failed_handshakes += 1,
+            TcpState::Reset => self.resets += 1,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT: &str = "192.168.1.10:50000";
+    const SERVER: &str = "140.82.121.4:443";
+
+    fn flags(flags: &str) -> TcpFlags {
+        TcpFlags {
+            syn: flags.contains('S'),
+            ack: flags.contains('A'),
+            fin: flags.contains('F'),
+            rst: flags.contains('R'),
+        }
+    }
+
+    #[test]
+    fn test_tcp_connection_lifecycle() {
+        let now = Local::now();
+        let mut connection = TcpConnection::new(flags("S"), CLIENT, now);
+        assert_eq!(connection.state, TcpState::SynSent);
+        connection.update(flags("S"), CLIENT, now);
+        assert_eq!(connection.state, TcpState::SynSent);
+        connection.update(flags("SA"), SERVER, now);
+        assert_eq!(connection.state, TcpState::SynReceived);
+        assert_eq!(connection.opened_at, None);
+        connection.update(flags("A"), CLIENT, now);
+        assert_eq!(connection.state, TcpState::Established);
+        assert_eq!(connection.opened_at, Some(now));
+
+        // the server closes first
+        connection.update(flags("FA"), SERVER, now);
+        assert_eq!(connection.state, TcpState::Closing);
+        connection.update(flags("A"), CLIENT, now);
+        connection.update(flags("FA"), SERVER, now);
+        assert_eq!(connection.state, TcpState::Closing);
+        connection.update(flags("FA"), CLIENT, now);
+        assert_eq!(connection.state, TcpState::Closed);
+        assert_eq!(connection.closed_at, Some(now));
+        assert_eq!(connection.closed_by.as_deref(), Some(SERVER));
+
+        // late segments don't change the outcome
+        connection.update(flags("R"), SERVER, now);
+        assert_eq!(connection.state, TcpState::Closed);
+
+        // the same ports are reused for a new connection
+        connection.update(flags("S"), CLIENT, now);
+        assert_eq!(connection.state, TcpState::SynSent);
+        assert_eq!(connection.closed_by, None);
+    }
+
+    #[test]
+    fn test_tcp_connection_reset_and_refused() {
+        let now = Local::now();
+        let mut connection = TcpConnection::new(flags("S"), CLIENT, now);
+        connection.update(flags("RA"), SERVER, now);
+        assert_eq!(connection.state, TcpState::Refused);
+        assert_eq!(connection.closed_by.as_deref(), Some(SERVER));
+
+        // first observed when already open
+        let mut connection = TcpConnection::new(flags("A"), SERVER, now);
+        assert_eq!(connection.state, TcpState::Established);
+        assert_eq!(connection.opened_at, None);
+        connection.update(flags("FA"), CLIENT, now);
+        connection.update(flags("R"), SERVER, now);
+        assert_eq!(connection.state, TcpState::Reset);
+        assert_eq!(connection.closed_by.as_deref(), Some(SERVER));
+
+        // first observed when closing
+        let connection = TcpConnection::new(flags("FA"), CLIENT, now);
+        assert_eq!(connection.state, TcpState::Closing);
+        assert_eq!(connection.closed_by.as_deref(), Some(CLIENT));
+    }
+
+    #[test]
+    fn test_tcp_stats() {
+        let mut stats = TcpStats::default();
+        stats.update(None, TcpState::SynSent);
+        stats.update(None, TcpState::SynSent);
+        stats.update(Some(TcpState::SynSent), TcpState::SynReceived);
+        assert_eq!(stats.half_open, 2);
+        stats.update(Some(TcpState::SynReceived), TcpState::Established);
+        stats.update(Some(TcpState::SynSent), TcpState::Refused);
+        stats.update(None, TcpState::Established);
+        stats.update(Some(TcpState::Established), TcpState::Reset);
+        stats.update(Some(TcpState::Reset), TcpState::Reset);
+        assert_eq!(
+            stats,
+            TcpStats {
+                failed_handshakes: 1,
+                resets: 1,
+                half_open: 0,
+            }
+        );
+    }
+}
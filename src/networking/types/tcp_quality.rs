@@ -0,0 +1,246 @@
+//! Module defining the `TcpQuality` struct, which collects metrics about the quality of TCP traffic,
+//! and the `TcpTracker` struct, which derives them from the segments sent in a direction of a connection.
+
+use std::ops::AddAssign;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::networking::types::tcp_connection::TcpSegment;
+
+/// Segments older than the expected one are considered out-of-order (rather than retransmitted)
+/// if they arrive within this time from the newest one, when the round-trip time is unknown
+const OUT_OF_ORDER_THRESHOLD: Duration = Duration::from_millis(3);
+
+/// Quality metrics of TCP traffic, for a direction of a connection or for a host
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TcpQuality {
+    /// Number of segments observed
+    pub segments: u128,
+    /// Number of segments carrying data already sent
+    pub retransmissions: u128,
+    /// Number of segments arrived after a newer one
+    pub out_of_order: u128,
+    /// Number of acknowledgments repeating the previous one
+    pub duplicate_acks: u128,
+    /// Number of times the receive window was closed
+    pub zero_windows: u128,
+    /// Highest round-trip time of the handshakes
+    pub max_handshake_rtt: Option<Duration>,
+}
+
+impl TcpQuality {
+    /// Percentage of retransmitted segments
+    pub fn retransmission_rate(&self) -> f64 {
+        if self.segments == 0 {
+            return 0.0;
+        }
+        self.retransmissions as f64 / self.segments as f64 * 100.0
+    }
+}
+
+impl AddAssign for TcpQuality {
+    fn add_assign(&mut self, rhs: Self) {
+        self.segments += rhs.segments;
+        self.retransmissions += rhs.retransmissions;
+        self.out_of_order += rhs.out_of_order;
+        self.duplicate_acks += rhs.duplicate_acks;
+        self.zero_windows += rhs.zero_windows;
+        self.max_handshake_rtt = self.max_handshake_rtt.max(rhs.max_handshake_rtt);
+    }
+}
+
+/// Sequence numbers, acknowledgments, and windows observed in a direction of a TCP connection
+#[derive(Clone, Copy, Default, Debug)]
+pub struct TcpTracker {
+    /// Sequence number expected for the next new data
+    next_sequence_number: Option<u32>,
+    /// Time the expected sequence number last advanced
+    last_advance: Option<DateTime<Local>>,
+    /// Last acknowledgment number with the advertised window
+    last_ack: Option<(u32, u16)>,
+    /// Whether the last advertised window was zero
+    zero_window: bool,
+}
+
+impl TcpTracker {
+    /// Updates the tracker with a new segment, returning the metrics it contributes to
+    pub fn update(
+        &mut self,
+        segment: &TcpSegment,
+        timestamp: DateTime<Local>,
+        rtt: Option<Duration>,
+    ) -> TcpQuality {
+        let mut quality = TcpQuality {
+            segments: 1,
+            ..TcpQuality::default()
+        };
+        let flags = segment.flags;
+        if flags.rst {
+            return quality;
+        }
+
+        // SYN and FIN consume a sequence number
+        let segment_len = segment
+            .payload_len
+            .saturating_add(u32::from(flags.syn) + u32::from(flags.fin));
+        if segment_len > 0 {
+            let sequence_number = segment.sequence_number;
+            let end = sequence_number.wrapping_add(segment_len);
+            match self.next_sequence_number {
+                Some(expected) if is_before(sequence_number, expected) => {
+                    let threshold = rtt.unwrap_or(OUT_OF_ORDER_THRESHOLD);
+                    let is_recent = self.last_advance.is_some_and(|last_advance| {
+                        (timestamp - last_advance)
+                            .to_std()
+                            .is_ok_and(|elapsed| elapsed < threshold)
+                    });
+                    if is_recent {
+                        quality.out_of_order = 1;
+                    } else {
+                        quality.retransmissions = 1;
+                    }
+                    if is_before(expected, end) {
+                        self.next_sequence_number = Some(end);
+                        self.last_advance = Some(timestamp);
+                    }
+                }
+                _ => {
+                    self.next_sequence_number = Some(end);
+                    self.last_advance = Some(timestamp);
+                }
+            }
+        }
+
+        if flags.ack {
+            let ack = (segment.acknowledgment_number, segment.window_size);
+            // duplicate acknowledgments carry no data and don't update the window
+            if segment_len == 0 && self.last_ack == Some(ack) {
+                quality.duplicate_acks = 1;
+            }
+            self.last_ack = Some(ack);
+        }
+
+        let zero_window = segment.window_size == 0 && !flags.syn;
+        if zero_window && !self.zero_window {
+            quality.zero_windows = 1;
+        }
+        self.zero_window = zero_window;
+
+        quality
+    }
+}
+
+/// Compares sequence numbers, taking into account that they wrap around
+fn is_before(sequence_number: u32, other: u32) -> bool {
+    sequence_number != other && other.wrapping_sub(sequence_number) < 1 << 31
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use crate::networking::types::tcp_connection::TcpFlags;
+
+    use super::*;
+
+    fn segment(sequence_number: u32, payload_len: u32, ack: u32, window_size: u16) -> TcpSegment {
+        TcpSegment {
+            flags: TcpFlags {
+                ack: true,
+                ..TcpFlags::default()
+            },
+            sequence_number,
+            acknowledgment_number: ack,
+            window_size,
+            payload_len,
+        }
+    }
+
+    #[test]
+    fn test_is_before() {
+        assert!(is_before(1, 2));
+        assert!(!is_before(2, 1));
+        assert!(!is_before(2, 2));
+        assert!(is_before(u32::MAX - 10, 5));
+        assert!(!is_before(5, u32::MAX - 10));
+    }
+
+    #[test]
+    fn test_retransmissions_and_out_of_order() {
+        let start = Local::now();
+        let at = |millis| start + TimeDelta::milliseconds(millis);
+        let rtt = Some(Duration::from_millis(20));
+        let mut tracker = TcpTracker::default();
+        let mut quality = TcpQuality::default();
+
+        quality += tracker.update(&segment(1000, 100, 1, 500), at(0), rtt);
+        quality += tracker.update(&segment(1100, 100, 1, 500), at(1), rtt);
+        // the segment at 1200 is late: 1300 arrives first
+        quality += tracker.update(&segment(1300, 100, 1, 500), at(2), rtt);
+        quality += tracker.update(&segment(1200, 100, 1, 500), at(3), rtt);
+        assert_eq!(quality.out_of_order, 1);
+        assert_eq!(quality.retransmissions, 0);
+        // the segment at 1100 is sent again after a timeout
+        quality += tracker.update(&segment(1100, 100, 1, 500), at(300), rtt);
+        assert_eq!(quality.retransmissions, 1);
+        // sequence numbers wrap around
+        let mut tracker = TcpTracker::default();
+        tracker.update(&segment(u32::MAX - 49, 100, 1, 500), at(0), rtt);
+        let wrapped = tracker.update(&segment(50, 100, 1, 500), at(1), rtt);
+        assert_eq!(wrapped.retransmissions + wrapped.out_of_order, 0);
+
+        assert_eq!(quality.segments, 5);
+        assert_eq!(quality.duplicate_acks, 0);
+        assert_eq!(quality.zero_windows, 0);
+    }
+
+    #[test]
+    fn test_duplicate_acks_and_zero_windows() {
+        let now = Local::now();
+        let mut tracker = TcpTracker::default();
+        let mut quality = TcpQuality::default();
+
+        quality += tracker.update(&segment(1, 0, 5000, 500), now, None);
+        quality += tracker.update(&segment(1, 0, 5000, 500), now, None);
+        quality += tracker.update(&segment(1, 0, 5000, 500), now, None);
+        // window update
+        quality += tracker.update(&segment(1, 0, 5000, 600), now, None);
+        // data segments acknowledging the same bytes
+        quality += tracker.update(&segment(1, 10, 5000, 600), now, None);
+        assert_eq!(quality.duplicate_acks, 2);
+
+        // the window is closed twice
+        quality += tracker.update(&segment(11, 0, 6000, 0), now, None);
+        quality += tracker.update(&segment(11, 0, 6000, 0), now, None);
+        quality += tracker.update(&segment(11, 0, 6000, 100), now, None);
+        quality += tracker.update(&segment(11, 0, 7000, 0), now, None);
+        assert_eq!(quality.zero_windows, 2);
+        assert_eq!(quality.duplicate_acks, 3);
+        assert_eq!(quality.segments, 9);
+    }
+
+    #[test]
+    fn test_tcp_quality_add_assign() {
+        let mut quality = TcpQuality {
+            segments: 10,
+            retransmissions: 1,
+            max_handshake_rtt: Some(Duration::from_millis(30)),
+            ..TcpQuality::default()
+        };
+        quality += TcpQuality {
+            segments: 10,
+            retransmissions: 2,
+            zero_windows: 1,
+            max_handshake_rtt: Some(Duration::from_millis(10)),
+            ..TcpQuality::default()
+        };
+        assert_eq!(quality.segments, 20);
+        assert_eq!(quality.retransmissions, 3);
+        assert_eq!(quality.zero_windows, 1);
+        assert_eq!(quality.max_handshake_rtt, Some(Duration::from_millis(30)));
+        assert!((quality.retransmission_rate() - 15.0).abs() < f64::EPSILON);
+        assert!(TcpQuality::default().retransmission_rate().abs() < f64::EPSILON);
+    }
+}
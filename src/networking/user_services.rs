@@ -0,0 +1,161 @@
+//! Module in charge of the services defined by the user in a custom file,
+//! which override and extend the ones listed in `services.txt`.
+//!
+//! The file has the same format of `services.txt`: one `name<TAB>port/protocol` entry per line.
+//! Empty lines and comments (starting with `#`) are ignored.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::sync::{Mutex, RwLock};
+
+use once_cell::sync::Lazy;
+
+use crate::networking::types::service_query::ServiceQuery;
+use crate::{Protocol, Service};
+
+/// Services defined by the user, consulted before the built-in ones
+pub static USER_SERVICES: Lazy<RwLock<HashMap<ServiceQuery, Service>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Names of the services defined by the user (kept to not allocate them again when reloading the file)
+static USER_SERVICE_NAMES: Lazy<Mutex<HashSet<&'static str>>> =
+    Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Loads the services defined in the given file, replacing the ones previously loaded
+/// (an empty path removes all the user services).
+///
+/// Returns the errors found in the file; invalid lines are skipped.
+pub fn load_user_services(path: &str) -> Vec<String> {
+    let (services, errors) = if path.is_empty() {
+        (HashMap::new(), Vec::new())
+    } else {
+        match fs::read_to_string(path) {
+            Ok(content) => parse_user_services(&content),
+            Err(e) => (HashMap::new(), vec![e.to_string()]),
+        }
+    };
+    *USER_SERVICES.write().unwrap() = services;
+    errors
+}
+
+/// Returns the user service with the given name, if any
+pub fn get_user_service_by_name(name: &str) -> Option<Service> {
+    USER_SERVICE_NAMES
+        .lock()
+        .unwrap()
+        .get(name)
+        .map(|name| Service::Name(name))
+}
+
+fn parse_user_services(content: &str) -> (HashMap<ServiceQuery, Service>, Vec<String>) {
+    let mut services = HashMap::new();
+    let mut errors = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        match parse_line(line) {
+            Ok(Some((query, name))) => {
+                services.insert(query, Service::Name(intern(name)));
+            }
+            Ok(None) => {}
+            Err(e) => errors.push(format!("Line {}: {e}", i + 1)),
+        }
+    }
+    (services, errors)
+}
+
+/// Parses a line of the file, returning None if it's empty or a comment
+fn parse_line(line: &str) -> Result<Option<(ServiceQuery, &str)>, String> {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let (Some(name), Some(port_protocol), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err("expected 'name<TAB>port/protocol'".to_string());
+    };
+    if ["unknown", "-", "?"].contains(&name) || !name.is_ascii() || name.contains('?') {
+        return Err(format!("invalid service name '{name}'"));
+    }
+    let Some((port, protocol)) = port_protocol.split_once('/') else {
+        return Err(format!("expected 'port/protocol', found '{port_protocol}'"));
+    };
+    let Ok(port) = port.parse::<u16>() else {
+        return Err(format!("invalid port '{port}'"));
+    };
+    let protocol = match protocol.to_lowercase().as_str() {
+        "tcp" => Protocol::TCP,
+        "udp" => Protocol::UDP,
+        _ => return Err(format!("invalid protocol '{protocol}'")),
+    };
+    Ok(Some((ServiceQuery(port, protocol), name)))
+}
+
+/// Returns a static reference to the given name, as required by `Service`
+fn intern(name: &str) -> &'static str {
+    let mut names = USER_SERVICE_NAMES.lock().unwrap();
+    if let Some(interned) = names.get(name) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+    names.insert(interned);
+    interned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_user_services() {
+        let content = "# internal services\n\
+            vault\t7443/tcp\n\
+            \n\
+            kafka-internal 9092/tcp # brokers\n\
+            https\t8443/udp\n\
+            syslog-tls\t6514/TCP\n\
+            broken\n\
+            ?\t80/tcp\n\
+            bad-port\t70000/tcp\n\
+            bad-protocol\t5000/sctp\n\
+            no-protocol\t5000\n\
+            too many\t5000/tcp\n";
+        let (services, errors) = parse_user_services(content);
+        assert_eq!(
+            services,
+            HashMap::from([
+                (ServiceQuery(7443, Protocol::TCP), Service::Name("vault")),
+                (
+                    ServiceQuery(9092, Protocol::TCP),
+                    Service::Name("kafka-internal")
+                ),
+                (ServiceQuery(8443, Protocol::UDP), Service::Name("https")),
+                (
+                    ServiceQuery(6514, Protocol::TCP),
+                    Service::Name("syslog-tls")
+                ),
+            ])
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "Line 7: expected 'name<TAB>port/protocol'",
+                "Line 8: invalid service name '?'",
+                "Line 9: invalid port '70000'",
+                "Line 10: invalid protocol 'sctp'",
+                "Line 11: expected 'port/protocol', found '5000'",
+                "Line 12: expected 'name<TAB>port/protocol'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_intern() {
+        let name = intern("vault");
+        assert_eq!(name, "vault");
+        assert!(std::ptr::eq(name, intern(&String::from("vault"))));
+        assert_eq!(
+            get_user_service_by_name("vault"),
+            Some(Service::Name("vault"))
+        );
+        assert_eq!(get_user_service_by_name("not-defined"), None);
+    }
+}
@@ -0,0 +1,402 @@
+//! Module in charge of exporting the connections of the inspect page to file.
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+use crate::networking::manage_packets::get_address_to_lookup;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::host::Host;
+use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::program::Program;
+use crate::networking::types::traffic_direction::TrafficDirection;
+use crate::report::get_report_entries::get_sorted_searched_entries;
+use crate::report::types::export_format::ExportFormat;
+use crate::report::types::search_parameters::SearchParameters;
+use crate::{InfoTraffic, ReportSortType};
+
+/// Header of the exported CSV files
+const CSV_HEADER: &str = "src_ip,src_port,dst_ip,dst_port,protocol,service,program,program_pid,\
+    bytes,packets,src_mac,dst_mac,initial_timestamp,final_timestamp,direction,icmp_types,\
+    server_name,domain,asn_number,asn_name,country,interface";
+
+/// A connection of the inspect page, with all the information available about it
+#[derive(Serialize)]
+struct ExportedEntry {
+    src_ip: String,
+    src_port: Option<u16>,
+    dst_ip: String,
+    dst_port: Option<u16>,
+    protocol: String,
+    service: String,
+    program: Option<String>,
+    program_pid: Option<u32>,
+    bytes: u128,
+    packets: u128,
+    src_mac: Option<String>,
+    dst_mac: Option<String>,
+    initial_timestamp: String,
+    final_timestamp: String,
+    direction: String,
+    icmp_types: BTreeMap<String, usize>,
+    server_name: Option<String>,
+    domain: Option<String>,
+    asn_number: Option<u32>,
+    asn_name: Option<String>,
+    country: Option<String>,
+    interface: Option<String>,
+}
+
+impl ExportedEntry {
+    fn new(key: &AddressPortPair, val: &InfoAddressPortPair, host: Option<&Host>) -> Self {
+        let (program, program_pid) = match &val.program {
+            Program::Known { name, pid } => (Some(name.clone()), Some(*pid)),
+            Program::Unknown => (None, None),
+        };
+        Self {
+            src_ip: key.address1.clone(),
+            src_port: key.port1,
+            dst_ip: key.address2.clone(),
+            dst_port: key.port2,
+            protocol: key.protocol.to_string(),
+            service: val.service.to_string(),
+            program,
+            program_pid,
+            bytes: val.transmitted_bytes,
+            packets: val.transmitted_packets,
+            src_mac: val.mac_address1.clone(),
+            dst_mac: val.mac_address2.clone(),
+            initial_timestamp: val.initial_timestamp.to_rfc3339(),
+            final_timestamp: val.final_timestamp.to_rfc3339(),
+            direction: match val.traffic_direction {
+                TrafficDirection::Incoming => "incoming",
+                TrafficDirection::Outgoing => "outgoing",
+            }
+            .to_string(),
+            icmp_types: val
+                .icmp_types
+                .iter()
+                .map(|(icmp_type, n)| (icmp_type.to_string(), *n))
+                .collect(),
+            server_name: val.server_name.clone(),
+            domain: host.map(|h| h.domain.clone()),
+            asn_number: host.map(|h| h.asn.number),
+            asn_name: host.map(|h| h.asn.name.clone()),
+            country: host.map(|h| h.country.to_string()),
+            interface: key.interface.clone(),
+        }
+    }
+
+    fn to_csv_row(&self) -> String {
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        let icmp_types = self
+            .icmp_types
+            .iter()
+            .map(|(icmp_type, n)| format!("{icmp_type} ({n})"))
+            .collect::<Vec<String>>()
+            .join("; ");
+        [
+            self.src_ip.clone(),
+            optional(self.src_port.map(|p| p.to_string())),
+            self.dst_ip.clone(),
+            optional(self.dst_port.map(|p| p.to_string())),
+            self.protocol.clone(),
+            self.service.clone(),
+            optional(self.program.clone()),
+            optional(self.program_pid.map(|p| p.to_string())),
+            self.bytes.to_string(),
+            self.packets.to_string(),
+            optional(self.src_mac.clone()),
+            optional(self.dst_mac.clone()),
+            self.initial_timestamp.clone(),
+            self.final_timestamp.clone(),
+            self.direction.clone(),
+            icmp_types,
+            optional(self.server_name.clone()),
+            optional(self.domain.clone()),
+            optional(self.asn_number.map(|n| n.to_string())),
+            optional(self.asn_name.clone()),
+            optional(self.country.clone()),
+            optional(self.interface.clone()),
+        ]
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<String>>()
+        .join(",")
+    }
+}
+
+/// Writes to file all the connections satisfying the search constraints, in the given sort order.
+///
+/// The format is chosen based on the file extension (see [`ExportFormat::from_path`]).
+pub fn export_entries(
+    info_traffic: &InfoTraffic,
+    search: &SearchParameters,
+    report_sort_type: ReportSortType,
+    path: &str,
+) -> Result<(), String> {
+    let entries = get_exported_entries(info_traffic, search, report_sort_type);
+    let content = match ExportFormat::from_path(path) {
+        ExportFormat::Csv => entries_to_csv(&entries),
+        ExportFormat::Json => serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?,
+    };
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+/// Returns the name proposed when exporting the connections at the given time
+pub fn report_file_name(timestamp: &DateTime<Local>) -> String {
+    format!(
+        "sniffnet_connections_{}.csv",
+        timestamp.format("%Y-%m-%d_%H-%M-%S")
+    )
+}
+
+fn get_exported_entries(
+    info_traffic: &InfoTraffic,
+    search: &SearchParameters,
+    report_sort_type: ReportSortType,
+) -> Vec<ExportedEntry> {
+    get_sorted_searched_entries(info_traffic, search, report_sort_type)
+        .into_iter()
+        .map(|(key, val)| {
+            let address_to_lookup = get_address_to_lookup(key, val.traffic_direction);
+            let host = info_traffic
+                .addresses_resolved
+                .get(&address_to_lookup)
+                .map(|(_, host)| host);
+            ExportedEntry::new(key, val, host)
+        })
+        .collect()
+}
+
+fn entries_to_csv(entries: &[ExportedEntry]) -> String {
+    let mut csv = format!("{CSV_HEADER}\n");
+    for entry in entries {
+        csv.push_str(&entry.to_csv_row());
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quotes a CSV field if it contains separators, quotes, or line breaks
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{Local, TimeZone};
+
+    use crate::countries::types::country::Country;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::data_info_host::DataInfoHost;
+    use crate::networking::types::icmp_type::{IcmpType, IcmpTypeV4};
+    use crate::report::types::sort_type::SortType;
+    use crate::{Protocol, Service};
+
+    use super::*;
+
+    fn info_traffic_for_tests() -> InfoTraffic {
+        let timestamp = Local.with_ymd_and_hms(2024, 4, 20, 10, 0, 0).unwrap();
+        let mut info_traffic = InfoTraffic::new();
+        let tcp_key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(54321),
+            "8.8.8.8".to_string(),
+            Some(443),
+            Protocol::TCP,
+        )
+        .with_interface(Some("en0".to_string()));
+        let tcp_info = InfoAddressPortPair {
+            mac_address1: Some("aa:bb:cc:dd:ee:ff".to_string()),
+            mac_address2: Some("11:22:33:44:55:66".to_string()),
+            transmitted_bytes: 1800,
+            transmitted_packets: 2,
+            initial_timestamp: timestamp,
+            final_timestamp: timestamp,
+            service: Service::Name("https"),
+            program: Program::Known {
+                name: "firefox".to_string(),
+                pid: 4242,
+            },
+            traffic_direction: TrafficDirection::Outgoing,
+            server_name: Some("dns.google".to_string()),
+            ..InfoAddressPortPair::default()
+        };
+        let icmp_key = AddressPortPair::new(
+            "8.8.4.4".to_string(),
+            None,
+            "192.168.1.10".to_string(),
+            None,
+            Protocol::ICMP,
+        );
+        let icmp_info = InfoAddressPortPair {
+            transmitted_bytes: 64,
+            transmitted_packets: 3,
+            initial_timestamp: timestamp,
+            final_timestamp: timestamp,
+            service: Service::NotApplicable,
+            traffic_direction: TrafficDirection::Incoming,
+            icmp_types: HashMap::from([
+                (IcmpType::V4(IcmpTypeV4::EchoReply), 2),
+                (IcmpType::V4(IcmpTypeV4::DestinationUnreachable), 1),
+            ]),
+            ..InfoAddressPortPair::default()
+        };
+        info_traffic.map = HashMap::from([(tcp_key, tcp_info), (icmp_key, icmp_info)]);
+        let host = Host {
+            domain: "dns.google".to_string(),
+            asn: Asn {
+                number: 15169,
+                name: "GOOGLE, LLC".to_string(),
+            },
+            country: Country::US,
+        };
+        info_traffic.addresses_resolved = HashMap::from([(
+            "8.8.8.8".to_string(),
+            ("dns.google".to_string(), host.clone()),
+        )]);
+        info_traffic.hosts = HashMap::from([(host, DataInfoHost::default())]);
+        info_traffic
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("dns.google"), "dns.google");
+        assert_eq!(csv_field(""), "");
+        assert_eq!(csv_field("GOOGLE, LLC"), "\"GOOGLE, LLC\"");
+        assert_eq!(csv_field("a \"quoted\" name"), "\"a \"\"quoted\"\" name\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_exported_entries_sorted_and_searched() {
+        let info_traffic = info_traffic_for_tests();
+        let bytes_descending = ReportSortType {
+            byte_sort: SortType::Descending,
+            packet_sort: SortType::Neutral,
+        };
+        let packets_descending = ReportSortType {
+            byte_sort: SortType::Neutral,
+            packet_sort: SortType::Descending,
+        };
+
+        let entries = get_exported_entries(
+            &info_traffic,
+            &SearchParameters::default(),
+            bytes_descending,
+        );
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].protocol, "TCP");
+        assert_eq!(entries[1].protocol, "ICMP");
+
+        let entries = get_exported_entries(
+            &info_traffic,
+            &SearchParameters::default(),
+            packets_descending,
+        );
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].protocol, "ICMP");
+        assert_eq!(entries[1].protocol, "TCP");
+
+        let search = SearchParameters {
+            domain: "google".to_string(),
+            ..SearchParameters::default()
+        };
+        let entries = get_exported_entries(&info_traffic, &search, packets_descending);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].domain, Some("dns.google".to_string()));
+    }
+
+    #[test]
+    fn test_entries_to_csv() {
+        let info_traffic = info_traffic_for_tests();
+        let entries = get_exported_entries(
+            &info_traffic,
+            &SearchParameters::default(),
+            ReportSortType {
+                byte_sort: SortType::Descending,
+                packet_sort: SortType::Neutral,
+            },
+        );
+        let csv = entries_to_csv(&entries);
+        let timestamp = Local
+            .with_ymd_and_hms(2024, 4, 20, 10, 0, 0)
+            .unwrap()
+            .to_rfc3339();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            format!(
+                "192.168.1.10,54321,8.8.8.8,443,TCP,https,firefox,4242,1800,2,\
+                aa:bb:cc:dd:ee:ff,11:22:33:44:55:66,{timestamp},{timestamp},outgoing,,\
+                dns.google,dns.google,15169,\"GOOGLE, LLC\",US,en0"
+            )
+        );
+        assert_eq!(
+            lines[2],
+            format!(
+                "8.8.4.4,,192.168.1.10,,ICMP,-,,,64,3,,,{timestamp},{timestamp},incoming,\
+                Destination Unreachable (1); Echo Reply (2),,,,,,"
+            )
+        );
+    }
+
+    #[test]
+    fn test_entries_to_json() {
+        let info_traffic = info_traffic_for_tests();
+        let entries = get_exported_entries(
+            &info_traffic,
+            &SearchParameters {
+                proto: "=icmp".to_string(),
+                ..SearchParameters::default()
+            },
+            ReportSortType::default(),
+        );
+        let json = serde_json::to_value(&entries).unwrap();
+        let timestamp = Local
+            .with_ymd_and_hms(2024, 4, 20, 10, 0, 0)
+            .unwrap()
+            .to_rfc3339();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "src_ip": "8.8.4.4",
+                "src_port": null,
+                "dst_ip": "192.168.1.10",
+                "dst_port": null,
+                "protocol": "ICMP",
+                "service": "-",
+                "program": null,
+                "program_pid": null,
+                "bytes": 64,
+                "packets": 3,
+                "src_mac": null,
+                "dst_mac": null,
+                "initial_timestamp": timestamp,
+                "final_timestamp": timestamp,
+                "direction": "incoming",
+                "icmp_types": {
+                    "Destination Unreachable": 1,
+                    "Echo Reply": 2,
+                },
+                "server_name": null,
+                "domain": null,
+                "asn_number": null,
+                "asn_name": null,
+                "country": null,
+                "interface": null,
+            }])
+        );
+    }
+}
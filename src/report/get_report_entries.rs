@@ -1,12 +1,19 @@
-use std::cmp::min;
+use std::cmp::{min, Ordering};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+use crate::networking::lan_devices::get_vendor;
 use crate::networking::manage_packets::get_address_to_lookup;
 use crate::networking::types::address_port_pair::AddressPortPair;
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::data_info_host::DataInfoHost;
 use crate::networking::types::host::Host;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+use crate::networking::types::l2_pair::L2Pair;
+use crate::networking::types::l2_protocol::L2Protocol;
+use crate::networking::types::lan_device::LanDevice;
+use crate::report::types::host_sort_metric::HostSortMetric;
+use crate::report::types::search_parameters::SearchParameters;
 use crate::report::types::sort_type::SortType;
 use crate::{ChartType, InfoTraffic, ReportSortType, Service, Sniffer};
 
@@ -16,23 +23,104 @@ pub fn get_searched_entries(
     sniffer: &Sniffer,
 ) -> (Vec<(AddressPortPair, InfoAddressPortPair)>, usize) {
     let info_traffic_lock = sniffer.info_traffic.lock().unwrap();
-    let mut all_results: Vec<(&AddressPortPair, &InfoAddressPortPair)> = info_traffic_lock
+    let all_results = get_sorted_searched_entries(
+        &info_traffic_lock,
+        &sniffer.search,
+        sniffer.report_sort_type,
+    );
+
+    (
+        get_page(&all_results, sniffer.page_number)
+            .iter()
+            .map(|&(key, val)| (key.to_owned(), val.to_owned()))
+            .collect(),
+        all_results.len(),
+    )
+}
+
+/// Returns the hosts which satisfy the search constraints and belong to the given page of
+/// the hosts page, and the total number of hosts which satisfy the search constraints
+pub fn get_searched_hosts(sniffer: &Sniffer) -> (Vec<(Host, DataInfoHost)>, usize) {
+    let info_traffic_lock = sniffer.info_traffic.lock().unwrap();
+    let all_results = get_sorted_searched_hosts(
+        &info_traffic_lock,
+        &sniffer.hosts_search,
+        sniffer.hosts_sort_type,
+    );
+
+    (
+        get_page(&all_results, sniffer.hosts_page_number)
+            .iter()
+            .map(|&(host, data_info_host)| (host.to_owned(), data_info_host.to_owned()))
+            .collect(),
+        all_results.len(),
+    )
+}
+
+/// Returns the services which satisfy the search constraints and belong to the given page of
+/// the services page, and the total number of services which satisfy the search constraints
+pub fn get_searched_services(sniffer: &Sniffer) -> (Vec<(Service, DataInfo)>, usize) {
+    let info_traffic_lock = sniffer.info_traffic.lock().unwrap();
+    let all_results = get_sorted_searched_services(
+        &info_traffic_lock,
+        &sniffer.hosts_search,
+        sniffer.services_sort_type,
+    );
+
+    (
+        get_page(&all_results, sniffer.services_page_number).to_vec(),
+        all_results.len(),
+    )
+}
+
+/// Returns the devices which satisfy the search string and belong to the given page of
+/// the devices page, and the total number of devices which satisfy the search string
+pub fn get_searched_devices(sniffer: &Sniffer) -> (Vec<(String, LanDevice)>, usize) {
+    let info_traffic_lock = sniffer.info_traffic.lock().unwrap();
+    let all_results = get_sorted_searched_devices(
+        &info_traffic_lock,
+        &sniffer.devices_search,
+        sniffer.devices_sort_type,
+    );
+
+    (
+        get_page(&all_results, sniffer.devices_page_number)
+            .iter()
+            .map(|&(mac_address, device)| (mac_address.to_owned(), device.to_owned()))
+            .collect(),
+        all_results.len(),
+    )
+}
+
+/// Returns the entries belonging to the given page (pages are made of 20 entries, starting from 1)
+fn get_page<T>(all_results: &[T], page_number: usize) -> &[T] {
+    let upper_bound = min(page_number * 20, all_results.len());
+    all_results
+        .get((page_number - 1) * 20..upper_bound)
+        .unwrap_or(&[])
+}
+
+/// Returns all the elements which satisfy the search constraints, in the given sort order
+pub fn get_sorted_searched_entries<'a>(
+    info_traffic: &'a InfoTraffic,
+    search: &SearchParameters,
+    report_sort_type: ReportSortType,
+) -> Vec<(&'a AddressPortPair, &'a InfoAddressPortPair)> {
+    let mut all_results: Vec<(&AddressPortPair, &InfoAddressPortPair)> = info_traffic
         .map
         .iter()
         .filter(|(key, value)| {
             let address_to_lookup = &get_address_to_lookup(key, value.traffic_direction);
-            let r_dns_host = info_traffic_lock.addresses_resolved.get(address_to_lookup);
+            let r_dns_host = info_traffic.addresses_resolved.get(address_to_lookup);
             let is_favorite = if let Some(e) = r_dns_host {
-                info_traffic_lock.hosts.get(&e.1).unwrap().is_favorite
+                info_traffic.hosts.get(&e.1).unwrap().is_favorite
             } else {
                 false
             };
-            sniffer
-                .search
-                .match_entry(key, value, r_dns_host, is_favorite)
+            search.match_entry(key, value, r_dns_host, is_favorite)
         })
         .collect();
-    all_results.sort_by(|&(_, a), &(_, b)| match sniffer.report_sort_type {
+    all_results.sort_by(|&(_, a), &(_, b)| match report_sort_type {
         ReportSortType {
             byte_sort,
             packet_sort: SortType::Neutral,
@@ -51,29 +139,124 @@ pub fn get_searched_entries(
         },
         _ => b.final_timestamp.cmp(&a.final_timestamp),
     });
+    all_results
+}
 
-    let upper_bound = min(sniffer.page_number * 20, all_results.len());
+/// Returns all the hosts which satisfy the host-related search constraints, in the given sort order
+fn get_sorted_searched_hosts<'a>(
+    info_traffic: &'a InfoTraffic,
+    search: &SearchParameters,
+    sort_type: ReportSortType,
+) -> Vec<(&'a Host, &'a DataInfoHost)> {
+    let mut all_results: Vec<(&Host, &DataInfoHost)> = info_traffic
+        .hosts
+        .iter()
+        .filter(|(host, data_info_host)| search.match_host(host, data_info_host.is_favorite))
+        .collect();
+    all_results
+        .sort_by(|&(_, a), &(_, b)| compare_data_info(&a.data_info, &b.data_info, sort_type));
+    all_results
+}
 
-    (
-        all_results
-            .get((sniffer.page_number - 1) * 20..upper_bound)
-            .unwrap_or(&Vec::new())
+/// Returns all the services which satisfy the search constraints, in the given sort order.
+///
+/// If some host-related filter is active, only the traffic exchanged with the matching hosts is considered.
+fn get_sorted_searched_services(
+    info_traffic: &InfoTraffic,
+    search: &SearchParameters,
+    sort_type: ReportSortType,
+) -> Vec<(Service, DataInfo)> {
+    let mut all_results: Vec<(Service, DataInfo)> = if search.is_some_host_filter_active() {
+        let mut services: HashMap<Service, DataInfo> = HashMap::new();
+        for (key, value) in &info_traffic.map {
+            let address_to_lookup = get_address_to_lookup(key, value.traffic_direction);
+            let Some((_, host)) = info_traffic.addresses_resolved.get(&address_to_lookup) else {
+                continue;
+            };
+            let is_favorite = info_traffic
+                .hosts
+                .get(host)
+                .is_some_and(|data_info_host| data_info_host.is_favorite);
+            if search.match_host(host, is_favorite) {
+                services
+                    .entry(value.service)
+                    .or_default()
+                    .add_connection(value);
+            }
+        }
+        services.into_iter().collect()
+    } else {
+        info_traffic
+            .services
             .iter()
-            .map(|&(key, val)| (key.to_owned(), val.to_owned()))
-            .collect(),
-        all_results.len(),
-    )
+            .map(|(service, data_info)| (*service, *data_info))
+            .collect()
+    };
+    all_results.retain(|(service, _)| service != &Service::NotApplicable);
+    all_results.sort_by(|(_, a), (_, b)| compare_data_info(a, b, sort_type));
+    all_results
+}
+
+/// Returns all the devices which satisfy the search string, in the given sort order.
+///
+/// The search is matched against the MAC address, the vendor, and the IP addresses of each device.
+fn get_sorted_searched_devices<'a>(
+    info_traffic: &'a InfoTraffic,
+    search: &str,
+    sort_type: ReportSortType,
+) -> Vec<(&'a String, &'a LanDevice)> {
+    let search = search.trim().to_lowercase();
+    let mut all_results: Vec<(&String, &LanDevice)> = info_traffic
+        .devices
+        .iter()
+        .filter(|(mac_address, device)| {
+            matches_search(&search, mac_address)
+                || get_vendor(mac_address).is_some_and(|vendor| matches_search(&search, vendor))
+                || device
+                    .addresses
+                    .iter()
+                    .any(|address| matches_search(&search, &address.to_string()))
+        })
+        .collect();
+    all_results
+        .sort_by(|&(_, a), &(_, b)| compare_data_info(&a.data_info, &b.data_info, sort_type));
+    all_results
+}
+
+/// Checks if the value satisfies the lowercase search string (exact match if it starts with `=`)
+fn matches_search(search: &str, value: &str) -> bool {
+    let value = value.to_lowercase();
+    match search.strip_prefix('=') {
+        Some(stripped_search) => value.eq(stripped_search),
+        None => value.contains(search),
+    }
+}
+
+/// Compares data by bytes or packets depending on the sort type (by latest occurrence if neutral)
+fn compare_data_info(a: &DataInfo, b: &DataInfo, sort_type: ReportSortType) -> Ordering {
+    match sort_type {
+        ReportSortType {
+            byte_sort,
+            packet_sort: SortType::Neutral,
+        } => a.compare(b, byte_sort, ChartType::Bytes),
+        ReportSortType {
+            byte_sort: SortType::Neutral,
+            packet_sort,
+        } => a.compare(b, packet_sort, ChartType::Packets),
+        _ => a.compare(b, SortType::Neutral, ChartType::Bytes),
+    }
 }
 
 pub fn get_host_entries(
     info_traffic: &Arc<Mutex<InfoTraffic>>,
     chart_type: ChartType,
     sort_type: SortType,
+    sort_metric: HostSortMetric,
 ) -> Vec<(Host, DataInfoHost)> {
     let info_traffic_lock = info_traffic.lock().unwrap();
     let mut sorted_vec: Vec<(&Host, &DataInfoHost)> = info_traffic_lock.hosts.iter().collect();
 
-    sorted_vec.sort_by(|&(_, a), &(_, b)| a.data_info.compare(&b.data_info, sort_type, chart_type));
+    sorted_vec.sort_by(|&(_, a), &(_, b)| sort_metric.compare(a, b, sort_type, chart_type));
 
     let n_entry = min(sorted_vec.len(), 30);
     sorted_vec[0..n_entry]
@@ -102,3 +285,397 @@ pub fn get_service_entries(
         .map(|&(service, data_info)| (*service, *data_info))
         .collect()
 }
+
+pub fn get_program_entries(
+    info_traffic: &Arc<Mutex<InfoTraffic>>,
+    chart_type: ChartType,
+    sort_type: SortType,
+) -> Vec<(String, DataInfo)> {
+    let info_traffic_lock = info_traffic.lock().unwrap();
+    let mut sorted_vec: Vec<(&String, &DataInfo)> = info_traffic_lock.programs.iter().collect();
+
+    sorted_vec.sort_by(|&(_, a), &(_, b)| a.compare(b, sort_type, chart_type));
+
+    let n_entry = min(sorted_vec.len(), 30);
+    sorted_vec[0..n_entry]
+        .iter()
+        .map(|&(program, data_info)| (program.to_owned(), *data_info))
+        .collect()
+}
+
+/// Returns the non-IP traffic aggregated by protocol
+pub fn get_non_ip_entries(
+    info_traffic: &Arc<Mutex<InfoTraffic>>,
+    chart_type: ChartType,
+    sort_type: SortType,
+) -> Vec<(L2Protocol, DataInfo)> {
+    let info_traffic_lock = info_traffic.lock().unwrap();
+    let mut protocols: HashMap<L2Protocol, DataInfo> = HashMap::new();
+    for (key, data_info) in &info_traffic_lock.non_ip {
+        *protocols.entry(key.protocol).or_default() += *data_info;
+    }
+    drop(info_traffic_lock);
+
+    let mut sorted_vec: Vec<(L2Protocol, DataInfo)> = protocols.into_iter().collect();
+    sorted_vec.sort_by(|(_, a), (_, b)| a.compare(b, sort_type, chart_type));
+    sorted_vec.truncate(30);
+    sorted_vec
+}
+
+/// Returns the non-IP traffic matching the search string, from the largest in terms of bytes.
+///
+/// The search is matched against the protocol and the MAC addresses (exact match if it starts with `=`).
+pub fn get_searched_non_ip_entries(
+    info_traffic: &InfoTraffic,
+    search: &str,
+) -> Vec<(L2Pair, DataInfo)> {
+    let search = search.trim().to_lowercase();
+    let matches = |value: &str| matches_search(&search, value);
+    let mut all_results: Vec<(L2Pair, DataInfo)> = info_traffic
+        .non_ip
+        .iter()
+        .filter(|(key, _)| {
+            matches(&key.protocol.to_string())
+                || matches(&key.mac_address1)
+                || matches(&key.mac_address2)
+        })
+        .map(|(key, data_info)| (key.clone(), *data_info))
+        .collect();
+    all_results.sort_by(|(_, a), (_, b)| a.compare(b, SortType::Descending, ChartType::Bytes));
+    all_results
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+
+    use crate::countries::types::country::Country;
+    use crate::networking::types::address_port_pair::AddressPortPair;
+    use crate::networking::types::asn::Asn;
+    use crate::networking::types::data_info::DataInfo;
+    use crate::networking::types::host::Host;
+    use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
+    use crate::networking::types::l2_pair::L2Pair;
+    use crate::networking::types::l2_protocol::L2Protocol;
+    use crate::networking::types::lan_device::LanDevice;
+    use crate::networking::types::traffic_direction::TrafficDirection;
+    use crate::report::get_report_entries::{
+        get_page, get_searched_non_ip_entries, get_sorted_searched_devices,
+        get_sorted_searched_hosts, get_sorted_searched_services,
+    };
+    use crate::report::types::search_parameters::SearchParameters;
+    use crate::report::types::sort_type::SortType;
+    use crate::{InfoTraffic, Protocol, ReportSortType, Service};
+
+    fn host(domain: &str, as_name: &str, country: Country) -> Host {
+        Host {
+            domain: domain.to_string(),
+            asn: Asn {
+                number: 1,
+                name: as_name.to_string(),
+            },
+            country,
+        }
+    }
+
+    fn add_connection(
+        info_traffic: &mut InfoTraffic,
+        remote_address: &str,
+        host: &Host,
+        service: Service,
+        packets: u128,
+        bytes: u128,
+    ) {
+        let key = AddressPortPair::new(
+            "192.168.1.10".to_string(),
+            Some(54321),
+            remote_address.to_string(),
+            Some(443),
+            Protocol::TCP,
+        );
+        let value = InfoAddressPortPair {
+            transmitted_packets: packets,
+            transmitted_bytes: bytes,
+            service,
+            traffic_direction: TrafficDirection::Outgoing,
+            ..InfoAddressPortPair::default()
+        };
+        info_traffic
+            .services
+            .entry(service)
+            .or_default()
+            .add_connection(&value);
+        let data_info_host = info_traffic.hosts.entry(host.clone()).or_default();
+        data_info_host.data_info.add_connection(&value);
+        info_traffic.addresses_resolved.insert(
+            remote_address.to_string(),
+            (host.domain.clone(), host.clone()),
+        );
+        info_traffic.map.insert(key, value);
+    }
+
+    fn info_traffic_for_tests() -> InfoTraffic {
+        let mut info_traffic = InfoTraffic::new();
+        let google = host("google.com", "GOOGLE", Country::US);
+        let github = host("github.com", "GITHUB", Country::US);
+        let polimi = host("polimi.it", "GARR", Country::IT);
+        add_connection(
+            &mut info_traffic,
+            "8.8.8.8",
+            &google,
+            Service::Name("https"),
+            10,
+            5000,
+        );
+        add_connection(
+            &mut info_traffic,
+            "8.8.4.4",
+            &google,
+            Service::Name("dns"),
+            4,
+            200,
+        );
+        add_connection(
+            &mut info_traffic,
+            "140.82.121.4",
+            &github,
+            Service::Name("ssh"),
+            30,
+            3000,
+        );
+        add_connection(
+            &mut info_traffic,
+            "131.175.7.1",
+            &polimi,
+            Service::NotApplicable,
+            1,
+            100,
+        );
+        info_traffic.hosts.get_mut(&github).unwrap().is_favorite = true;
+        info_traffic
+    }
+
+    #[test]
+    fn test_get_page() {
+        let entries: Vec<usize> = (0..45).collect();
+        assert_eq!(get_page(&entries, 1), &entries[0..20]);
+        assert_eq!(get_page(&entries, 2), &entries[20..40]);
+        assert_eq!(get_page(&entries, 3), &entries[40..45]);
+        assert!(get_page(&entries, 4).is_empty());
+    }
+
+    #[test]
+    fn test_sorted_searched_hosts() {
+        let info_traffic = info_traffic_for_tests();
+        let by_bytes = ReportSortType {
+            byte_sort: SortType::Descending,
+            packet_sort: SortType::Neutral,
+        };
+        let by_packets = ReportSortType {
+            byte_sort: SortType::Neutral,
+            packet_sort: SortType::Ascending,
+        };
+        let domains = |search: &SearchParameters, sort_type: ReportSortType| {
+            get_sorted_searched_hosts(&info_traffic, search, sort_type)
+                .iter()
+                .map(|(host, _)| host.domain.clone())
+                .collect::<Vec<String>>()
+        };
+
+        let search = SearchParameters::default();
+        assert_eq!(
+            domains(&search, by_bytes),
+            vec!["google.com", "github.com", "polimi.it"]
+        );
+        assert_eq!(
+            domains(&search, by_packets),
+            vec!["polimi.it", "google.com", "github.com"]
+        );
+
+        let search = SearchParameters {
+            country: "us".to_string(),
+            ..SearchParameters::default()
+        };
+        assert_eq!(domains(&search, by_bytes), vec!["google.com", "github.com"]);
+
+        let search = SearchParameters {
+            only_favorites: true,
+            ..SearchParameters::default()
+        };
+        assert_eq!(domains(&search, by_bytes), vec!["github.com"]);
+
+        let search = SearchParameters {
+            domain: "=google".to_string(),
+            ..SearchParameters::default()
+        };
+        assert!(domains(&search, by_bytes).is_empty());
+
+        let search = SearchParameters::new_host_search(&host("polimi.it", "GARR", Country::IT));
+        assert_eq!(domains(&search, by_bytes), vec!["polimi.it"]);
+    }
+
+    #[test]
+    fn test_sorted_searched_services() {
+        let info_traffic = info_traffic_for_tests();
+        let by_bytes = ReportSortType {
+            byte_sort: SortType::Descending,
+            packet_sort: SortType::Neutral,
+        };
+
+        // services not applicable are never reported
+        let services =
+            get_sorted_searched_services(&info_traffic, &SearchParameters::default(), by_bytes);
+        assert_eq!(
+            services.iter().map(|(s, _)| *s).collect::<Vec<Service>>(),
+            vec![
+                Service::Name("https"),
+                Service::Name("ssh"),
+                Service::Name("dns")
+            ]
+        );
+
+        // only the traffic exchanged with the searched hosts is considered
+        let search = SearchParameters {
+            as_name: "google".to_string(),
+            ..SearchParameters::default()
+        };
+        let services = get_sorted_searched_services(&info_traffic, &search, by_bytes);
+        assert_eq!(services.len(), 2);
+        assert_eq!(services[0].0, Service::Name("https"));
+        assert_eq!(services[0].1.tot_bytes(), 5000);
+        assert_eq!(services[0].1.tot_packets(), 10);
+        assert_eq!(services[1].0, Service::Name("dns"));
+        assert_eq!(services[1].1.tot_bytes(), 200);
+
+        let search = SearchParameters {
+            only_favorites: true,
+            ..SearchParameters::default()
+        };
+        let services = get_sorted_searched_services(&info_traffic, &search, by_bytes);
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].0, Service::Name("ssh"));
+        assert_eq!(services[0].1.outgoing_bytes(), 3000);
+        assert_eq!(services[0].1.incoming_bytes(), 0);
+    }
+
+    #[test]
+    fn test_searched_non_ip_entries() {
+        let mut info_traffic = InfoTraffic::new();
+        let mut add_frames = |mac_address2: &str, protocol: L2Protocol, frames: u128| {
+            let key = L2Pair {
+                mac_address1: "aa:bb:cc:dd:ee:ff".to_string(),
+                mac_address2: mac_address2.to_string(),
+                protocol,
+            };
+            let mut data_info = DataInfo::default();
+            for _ in 0..frames {
+                data_info.add_packet(60, TrafficDirection::Incoming, Local::now());
+            }
+            info_traffic.non_ip.insert(key, data_info);
+        };
+        add_frames("ff:ff:ff:ff:ff:ff", L2Protocol::ARP, 3);
+        add_frames("01:80:c2:00:00:0e", L2Protocol::LLDP, 5);
+        add_frames("01:80:c2:00:00:00", L2Protocol::STP, 1);
+        let protocols = |search: &str| {
+            get_searched_non_ip_entries(&info_traffic, search)
+                .iter()
+                .map(|(key, _)| key.protocol)
+                .collect::<Vec<L2Protocol>>()
+        };
+
+        assert_eq!(
+            protocols(""),
+            vec![L2Protocol::LLDP, L2Protocol::ARP, L2Protocol::STP]
+        );
+        assert_eq!(protocols("arp"), vec![L2Protocol::ARP]);
+        assert_eq!(
+            protocols("01:80:C2"),
+            vec![L2Protocol::LLDP, L2Protocol::STP]
+        );
+        assert_eq!(protocols("=01:80:c2:00:00:00"), vec![L2Protocol::STP]);
+        assert_eq!(
+            protocols("aa:bb"),
+            vec![L2Protocol::LLDP, L2Protocol::ARP, L2Protocol::STP]
+        );
+        assert!(protocols("=lld").is_empty());
+    }
+
+    #[test]
+    fn test_sorted_searched_devices() {
+        let mut info_traffic = InfoTraffic::new();
+        let mut add_device = |mac_address: &str, addresses: &[&str], packets: u128| {
+            let mut device = LanDevice::new_with_first_packet(
+                addresses[0].parse().unwrap(),
+                100,
+                TrafficDirection::Outgoing,
+                Local::now(),
+            );
+            for address in addresses {
+                for _ in 0..packets {
+                    device.add_packet(
+                        address.parse().unwrap(),
+                        100,
+                        TrafficDirection::Incoming,
+                        Local::now(),
+                    );
+                }
+            }
+            info_traffic.devices.insert(mac_address.to_string(), device);
+        };
+        add_device("b8:27:eb:12:34:56", &["192.168.1.20", "fe80::1"], 1);
+        add_device("00:0c:29:ab:cd:ef", &["192.168.1.30"], 5);
+        add_device("aa:bb:cc:dd:ee:ff", &["192.168.1.10"], 3);
+        let by_bytes = ReportSortType {
+            byte_sort: SortType::Descending,
+            packet_sort: SortType::Neutral,
+        };
+        let macs = |search: &str, sort_type: ReportSortType| {
+            get_sorted_searched_devices(&info_traffic, search, sort_type)
+                .iter()
+                .map(|&(mac_address, _)| mac_address.as_str())
+                .collect::<Vec<&str>>()
+        };
+
+        assert_eq!(
+            macs("", by_bytes),
+            vec![
+                "00:0c:29:ab:cd:ef",
+                "aa:bb:cc:dd:ee:ff",
+                "b8:27:eb:12:34:56"
+            ]
+        );
+        assert_eq!(
+            macs(
+                "",
+                ReportSortType {
+                    byte_sort: SortType::Ascending,
+                    packet_sort: SortType::Neutral,
+                }
+            ),
+            vec![
+                "b8:27:eb:12:34:56",
+                "aa:bb:cc:dd:ee:ff",
+                "00:0c:29:ab:cd:ef"
+            ]
+        );
+        // vendor
+        assert_eq!(macs("vmware", by_bytes), vec!["00:0c:29:ab:cd:ef"]);
+        assert_eq!(macs("raspberry", by_bytes), vec!["b8:27:eb:12:34:56"]);
+        // IP addresses
+        assert_eq!(macs("fe80::", by_bytes), vec!["b8:27:eb:12:34:56"]);
+        assert!(macs("=192.168.1.1", by_bytes).is_empty());
+        assert_eq!(
+            macs("192.168.1.", by_bytes),
+            vec![
+                "00:0c:29:ab:cd:ef",
+                "aa:bb:cc:dd:ee:ff",
+                "b8:27:eb:12:34:56"
+            ]
+        );
+        // MAC address
+        assert_eq!(
+            macs("=AA:BB:CC:DD:EE:FF", by_bytes),
+            vec!["aa:bb:cc:dd:ee:ff"]
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::path::Path;
+
+/// Formats available to export the connections of the inspect page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma-separated values, one row per connection
+    Csv,
+    /// JSON array, one object per connection
+    Json,
+}
+
+impl ExportFormat {
+    /// Returns the format corresponding to the extension of the given file (CSV if not JSON)
+    pub fn from_path(path: &str) -> Self {
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Csv,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_format_from_path() {
+        assert_eq!(ExportFormat::from_path("report.json"), ExportFormat::Json);
+        assert_eq!(
+            ExportFormat::from_path("/tmp/REPORT.JSON"),
+            ExportFormat::Json
+        );
+        assert_eq!(ExportFormat::from_path("report.csv"), ExportFormat::Csv);
+        assert_eq!(ExportFormat::from_path("report"), ExportFormat::Csv);
+        assert_eq!(ExportFormat::from_path("json"), ExportFormat::Csv);
+        assert_eq!(
+            ExportFormat::from_path("report.json.txt"),
+            ExportFormat::Csv
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+
+use crate::networking::types::data_info_host::DataInfoHost;
+use crate::report::types::sort_type::SortType;
+use crate::translations::translations_3::{
+    latency_translation, retransmissions_translation, traffic_translation,
+};
+use crate::translations::types::language::Language;
+use crate::ChartType;
+
+/// Metric used to sort the hosts displayed in the overview page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HostSortMetric {
+    /// Exchanged bytes or packets, depending on the chart type
+    #[default]
+    Traffic,
+    /// Highest round-trip time of the TCP handshakes
+    Latency,
+    /// Percentage of retransmitted TCP segments
+    Retransmissions,
+}
+
+impl HostSortMetric {
+    pub fn next(self) -> Self {
+        match self {
+            HostSortMetric::Traffic => HostSortMetric::Latency,
+            HostSortMetric::Latency => HostSortMetric::Retransmissions,
+            HostSortMetric::Retransmissions => HostSortMetric::Traffic,
+        }
+    }
+
+    pub fn get_label(self, language: Language) -> &'static str {
+        match self {
+            HostSortMetric::Traffic => traffic_translation(language),
+            HostSortMetric::Latency => latency_translation(language),
+            HostSortMetric::Retransmissions => retransmissions_translation(language),
+        }
+    }
+
+    /// Formatted value of the metric for the given host (None for traffic or if unknown)
+    pub fn get_value(self, data_info_host: &DataInfoHost) -> Option<String> {
+        let tcp_quality = &data_info_host.tcp_quality;
+        match self {
+            HostSortMetric::Traffic => None,
+            HostSortMetric::Latency => tcp_quality
+                .max_handshake_rtt
+                .map(|rtt| format!("{:.1} ms", rtt.as_secs_f64() * 1000.0)),
+            HostSortMetric::Retransmissions => (tcp_quality.segments > 0)
+                .then(|| format!("{:.1}%", tcp_quality.retransmission_rate())),
+        }
+    }
+
+    /// Compares two hosts: the worst ones come first, unless the sort is ascending;
+    /// hosts without the metric always come last
+    pub fn compare(
+        self,
+        a: &DataInfoHost,
+        b: &DataInfoHost,
+        sort_type: SortType,
+        chart_type: ChartType,
+    ) -> Ordering {
+        let (value_a, value_b) = match self {
+            HostSortMetric::Traffic => {
+                return a.data_info.compare(&b.data_info, sort_type, chart_type);
+            }
+            HostSortMetric::Latency => (
+                a.tcp_quality.max_handshake_rtt.map(|rtt| rtt.as_secs_f64()),
+                b.tcp_quality.max_handshake_rtt.map(|rtt| rtt.as_secs_f64()),
+            ),
+            HostSortMetric::Retransmissions => (
+                (a.tcp_quality.segments > 0).then(|| a.tcp_quality.retransmission_rate()),
+                (b.tcp_quality.segments > 0).then(|| b.tcp_quality.retransmission_rate()),
+            ),
+        };
+        match (value_a, value_b) {
+            (Some(value_a), Some(value_b)) => {
+                if sort_type == SortType::Ascending {
+                    value_a.total_cmp(&value_b)
+                } else {
+                    value_b.total_cmp(&value_a)
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::networking::types::tcp_quality::TcpQuality;
+
+    use super::*;
+
+    fn host_with_quality(rtt_millis: Option<u64>, retransmissions: u128) -> DataInfoHost {
+        DataInfoHost {
+            tcp_quality: TcpQuality {
+                segments: 100,
+                retransmissions,
+                max_handshake_rtt: rtt_millis.map(Duration::from_millis),
+                ..TcpQuality::default()
+            },
+            ..DataInfoHost::default()
+        }
+    }
+
+    #[test]
+    fn test_host_sort_metric_compare() {
+        let fast = host_with_quality(Some(10), 5);
+        let slow = host_with_quality(Some(200), 1);
+        let unknown = host_with_quality(None, 0);
+        let chart_type = ChartType::Bytes;
+
+        let rtt_millis = |hosts: &[&DataInfoHost]| {
+            hosts
+                .iter()
+                .map(|host| {
+                    host.tcp_quality
+                        .max_handshake_rtt
+                        .map(|rtt| rtt.as_millis())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut hosts = vec![&unknown, &fast, &slow];
+        hosts.sort_by(|a, b| HostSortMetric::Latency.compare(a, b, SortType::Neutral, chart_type));
+        assert_eq!(rtt_millis(&hosts), vec![Some(200), Some(10), None]);
+        hosts
+            .sort_by(|a, b| HostSortMetric::Latency.compare(a, b, SortType::Ascending, chart_type));
+        assert_eq!(rtt_millis(&hosts), vec![Some(10), Some(200), None]);
+        // the faster host retransmits more
+        hosts.sort_by(|a, b| {
+            HostSortMetric::Retransmissions.compare(a, b, SortType::Descending, chart_type)
+        });
+        assert_eq!(rtt_millis(&hosts), vec![Some(10), Some(200), None]);
+
+        assert_eq!(
+            HostSortMetric::Latency.get_value(&slow),
+            Some("200.0 ms".to_string())
+        );
+        assert_eq!(
+            HostSortMetric::Retransmissions.get_value(&fast),
+            Some("5.0%".to_string())
+        );
+        assert_eq!(HostSortMetric::Latency.get_value(&unknown), None);
+        assert_eq!(HostSortMetric::Traffic.get_value(&fast), None);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod export_format;
+pub mod host_sort_metric;
 pub mod report_col;
 pub mod report_sort_type;
 pub mod search_parameters;
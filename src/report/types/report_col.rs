@@ -5,17 +5,20 @@
     address_translation, bytes_translation, packets_translation, protocol_translation,
 };
 use crate::translations::translations_2::{destination_translation, source_translation};
-use crate::translations::translations_3::{port_translation, service_translation};
+use crate::translations::translations_3::{
+    port_translation, program_translation, server_name_translation, service_translation,
+    tcp_state_translation,
+};
 use crate::translations::types::language::Language;
 use crate::ByteMultiple;
 
 // total width: 1012.0
 
-const LARGE_COL_WIDTH: f32 = 221.0;
-const SMALL_COL_WIDTH: f32 = 95.0;
+const LARGE_COL_WIDTH: f32 = 156.0;
+const SMALL_COL_WIDTH: f32 = 68.0;
 
-const LARGE_COL_MAX_CHARS: usize = 25;
-const SMALL_COL_MAX_CHARS: usize = 10;
+const LARGE_COL_MAX_CHARS: usize = 18;
+const SMALL_COL_MAX_CHARS: usize = 8;
 
 #[derive(Eq, PartialEq)]
 pub enum ReportCol {
@@ -23,20 +26,26 @@ pub enum ReportCol {
     SrcPort,
     DstIp,
     DstPort,
+    ServerName,
     Proto,
+    TcpState,
     Service,
+    Program,
     Bytes,
     Packets,
 }
 
 impl ReportCol {
-    pub(crate) const ALL: [ReportCol; 8] = [
+    pub(crate) const ALL: [ReportCol; 11] = [
         ReportCol::SrcIp,
         ReportCol::SrcPort,
         ReportCol::DstIp,
         ReportCol::DstPort,
+        ReportCol::ServerName,
         ReportCol::Proto,
+        ReportCol::TcpState,
         ReportCol::Service,
+        ReportCol::Program,
         ReportCol::Bytes,
         ReportCol::Packets,
     ];
@@ -45,8 +54,11 @@ pub(crate) fn get_title(&self, language: Language) -> String {
         match self {
             ReportCol::SrcIp | ReportCol::DstIp => address_translation(language).to_string(),
             ReportCol::SrcPort | ReportCol::DstPort => port_translation(language).to_string(),
+            ReportCol::ServerName => server_name_translation(language).to_string(),
             ReportCol::Proto => protocol_translation(language).to_string(),
+            ReportCol::TcpState => tcp_state_translation(language).to_string(),
             ReportCol::Service => service_translation(language).to_string(),
+            ReportCol::Program => program_translation(language).to_string(),
             ReportCol::Bytes => {
                 let mut str = bytes_translation(language).to_string();
                 str.remove(0).to_uppercase().to_string() + &str
@@ -88,8 +100,14 @@ pub(crate) fn get_value(&self, key: &AddressPortPair, val: &InfoAddressPortPair)
                     "-".to_string()
                 }
             }
+            ReportCol::ServerName => val.server_name.clone().unwrap_or_else(|| "-".to_string()),
             ReportCol::Proto => key.protocol.to_string(),
+            ReportCol::TcpState => val.tcp_connection.as_ref().map_or_else(
+                || "-".to_string(),
+                |connection| connection.state.to_string(),
+            ),
             ReportCol::Service => val.service.to_string(),
+            ReportCol::Program => val.program.to_string(),
             ReportCol::Bytes => ByteMultiple::formatted_string(val.transmitted_bytes),
             ReportCol::Packets => val.transmitted_packets.to_string(),
         }
@@ -97,7 +115,7 @@ pub(crate) fn get_value(&self, key: &AddressPortPair, val: &InfoAddressPortPair)
 
     pub(crate) fn get_width(&self) -> f32 {
         match self {
-            ReportCol::SrcIp | ReportCol::DstIp => LARGE_COL_WIDTH,
+            ReportCol::SrcIp | ReportCol::DstIp | ReportCol::ServerName => LARGE_COL_WIDTH,
             _ => SMALL_COL_WIDTH,
         }
     }
@@ -111,7 +129,9 @@ pub(crate) fn get_max_chars(&self, language_opt: Option<Language>) -> usize {
             1
         };
         match self {
-            ReportCol::SrcIp | ReportCol::DstIp => LARGE_COL_MAX_CHARS / reduction_factor,
+            ReportCol::SrcIp | ReportCol::DstIp | ReportCol::ServerName => {
+                LARGE_COL_MAX_CHARS / reduction_factor
+            }
             _ => SMALL_COL_MAX_CHARS / reduction_factor,
         }
     }
@@ -122,8 +142,11 @@ pub(crate) fn get_filter_input_type(&self) -> FilterInputType {
             ReportCol::DstIp => FilterInputType::AddressDst,
             ReportCol::SrcPort => FilterInputType::PortSrc,
             ReportCol::DstPort => FilterInputType::PortDst,
+            ReportCol::ServerName => FilterInputType::Domain,
             ReportCol::Proto => FilterInputType::Proto,
+            ReportCol::TcpState => FilterInputType::TcpState,
             ReportCol::Service => FilterInputType::Service,
+            ReportCol::Program => FilterInputType::Program,
             ReportCol::Bytes | ReportCol::Packets => FilterInputType::Country, // just to not panic...
         }
     }
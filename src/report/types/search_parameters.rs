@@ -19,12 +19,20 @@ pub struct SearchParameters {
     pub proto: String,
     /// Service
     pub service: String,
+    /// Program
+    pub program: String,
     /// Country
     pub country: String,
     /// Domain
     pub domain: String,
     /// Autonomous System name
     pub as_name: String,
+    /// Network adapter
+    pub interface: String,
+    /// MAC address (source or destination)
+    pub mac: String,
+    /// State of the TCP connection
+    pub tcp_state: String,
     /// Whether to display only favorites
     pub only_favorites: bool,
 }
@@ -57,6 +65,23 @@ pub fn match_entry(
         true
     }
 
+    /// Checks if the given host satisfies the host-related search constraints
+    pub fn match_host(&self, host: &Host, is_favorite: bool) -> bool {
+        if self.only_favorites && !is_favorite {
+            return false;
+        }
+
+        [
+            FilterInputType::Country,
+            FilterInputType::Domain,
+            FilterInputType::AsName,
+        ]
+        .iter()
+        .all(|filter_input_type| {
+            filter_input_type.matches_value(self, &filter_input_type.host_value(host))
+        })
+    }
+
     pub fn is_some_host_filter_active(&self) -> bool {
         self.only_favorites
             || !self.country.is_empty()
@@ -93,6 +118,20 @@ pub fn new_service_search(service: &Service) -> Self {
             ..SearchParameters::default()
         }
     }
+
+    pub fn new_program_search(program: &str) -> Self {
+        Self {
+            program: ["=", program].concat(),
+            ..SearchParameters::default()
+        }
+    }
+
+    pub fn new_device_search(mac_address: &str) -> Self {
+        Self {
+            mac: ["=", mac_address].concat(),
+            ..SearchParameters::default()
+        }
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -103,22 +142,30 @@ pub enum FilterInputType {
     PortDst,
     Proto,
     Service,
+    Program,
     Country,
     Domain,
     AsName,
+    Interface,
+    Mac,
+    TcpState,
 }
 
 impl FilterInputType {
-    pub const ALL: [FilterInputType; 9] = [
+    pub const ALL: [FilterInputType; 13] = [
         Self::AddressSrc,
         Self::PortSrc,
         Self::AddressDst,
         Self::PortDst,
         Self::Proto,
         Self::Service,
+        Self::Program,
         Self::Country,
         Self::Domain,
         Self::AsName,
+        Self::Interface,
+        Self::Mac,
+        Self::TcpState,
     ];
 
     pub fn matches_entry(
@@ -128,13 +175,42 @@ pub fn matches_entry(
         value: &InfoAddressPortPair,
         r_dns_host: Option<&(String, Host)>,
     ) -> bool {
+        if self.current_value(search_params).is_empty() {
+            return true;
+        }
+
+        if self.matches_value(search_params, &self.entry_value(key, value, r_dns_host)) {
+            return true;
+        }
+
+        match self {
+            // the domain filter is also satisfied by the server name declared by the client
+            FilterInputType::Domain => value
+                .server_name
+                .as_ref()
+                .is_some_and(|server_name| self.matches_value(search_params, server_name)),
+            // the MAC address filter is satisfied by either the source or the destination
+            FilterInputType::Mac => value
+                .mac_address2
+                .as_ref()
+                .is_some_and(|mac_address| self.matches_value(search_params, mac_address)),
+            // the TCP state filter is also satisfied by "half-open" for incomplete handshakes
+            FilterInputType::TcpState => value.tcp_connection.as_ref().is_some_and(|connection| {
+                connection.state.is_half_open() && self.matches_value(search_params, "half-open")
+            }),
+            _ => false,
+        }
+    }
+
+    /// Checks if the given value satisfies the filter (exact match if the filter starts with `=`)
+    fn matches_value(self, search_params: &SearchParameters, entry_value: &str) -> bool {
         let filter_value = self.current_value(search_params).to_lowercase();
 
         if filter_value.is_empty() {
             return true;
         }
 
-        let entry_value = self.entry_value(key, value, r_dns_host).to_lowercase();
+        let entry_value = entry_value.to_lowercase();
 
         if let Some(stripped_filter) = filter_value.strip_prefix('=') {
             return entry_value.eq(stripped_filter);
@@ -151,9 +227,13 @@ pub fn current_value(self, search_params: &SearchParameters) -> &str {
             FilterInputType::PortDst => &search_params.port_dst,
             FilterInputType::Proto => &search_params.proto,
             FilterInputType::Service => &search_params.service,
+            FilterInputType::Program => &search_params.program,
             FilterInputType::Country => &search_params.country,
             FilterInputType::Domain => &search_params.domain,
             FilterInputType::AsName => &search_params.as_name,
+            FilterInputType::Interface => &search_params.interface,
+            FilterInputType::Mac => &search_params.mac,
+            FilterInputType::TcpState => &search_params.tcp_state,
         }
     }
 
@@ -182,9 +262,29 @@ pub fn entry_value(
             }
             FilterInputType::Proto => key.protocol.to_string(),
             FilterInputType::Service => value.service.to_string(),
+            FilterInputType::Program => value.program.to_string(),
             FilterInputType::Country => r_dns_host.unwrap().1.country.to_string(),
             FilterInputType::Domain => r_dns_host.unwrap().0.to_string(),
             FilterInputType::AsName => r_dns_host.unwrap().1.asn.name.to_string(),
+            FilterInputType::Interface => key.interface.clone().unwrap_or_else(|| "-".to_string()),
+            FilterInputType::Mac => value
+                .mac_address1
+                .clone()
+                .unwrap_or_else(|| "-".to_string()),
+            FilterInputType::TcpState => value.tcp_connection.as_ref().map_or_else(
+                || "-".to_string(),
+                |connection| connection.state.to_string(),
+            ),
+        }
+    }
+
+    /// Returns the value of the given host compared with host-related filters
+    fn host_value(self, host: &Host) -> String {
+        match self {
+            FilterInputType::Country => host.country.to_string(),
+            FilterInputType::Domain => host.domain.clone(),
+            FilterInputType::AsName => host.asn.name.clone(),
+            _ => String::new(),
         }
     }
 
@@ -214,6 +314,10 @@ pub fn clear_search(self, search_params: &SearchParameters) -> SearchParameters
                 service: String::new(),
                 ..search_params.clone()
             },
+            FilterInputType::Program => SearchParameters {
+                program: String::new(),
+                ..search_params.clone()
+            },
             FilterInputType::Domain => SearchParameters {
                 domain: String::new(),
                 ..search_params.clone()
@@ -226,6 +330,18 @@ pub fn clear_search(self, search_params: &SearchParameters) -> SearchParameters
                 as_name: String::new(),
                 ..search_params.clone()
             },
+            FilterInputType::Interface => SearchParameters {
+                interface: String::new(),
+                ..search_params.clone()
+            },
+            FilterInputType::Mac => SearchParameters {
+                mac: String::new(),
+                ..search_params.clone()
+            },
+            FilterInputType::TcpState => SearchParameters {
+                tcp_state: String::new(),
+                ..search_params.clone()
+            },
         }
     }
 
@@ -259,6 +375,10 @@ pub fn new_search(
                 service: new_value.trim().to_string(),
                 ..search_params.clone()
             },
+            FilterInputType::Program => SearchParameters {
+                program: new_value.trim().to_string(),
+                ..search_params.clone()
+            },
             FilterInputType::Domain => SearchParameters {
                 domain: new_value.trim().to_string(),
                 ..search_params.clone()
@@ -271,6 +391,18 @@ pub fn new_search(
                 as_name: new_value,
                 ..search_params.clone()
             },
+            FilterInputType::Interface => SearchParameters {
+                interface: new_value.trim().to_string(),
+                ..search_params.clone()
+            },
+            FilterInputType::Mac => SearchParameters {
+                mac: new_value.trim().to_string(),
+                ..search_params.clone()
+            },
+            FilterInputType::TcpState => SearchParameters {
+                tcp_state: new_value.trim().to_string(),
+                ..search_params.clone()
+            },
         }
     }
 }
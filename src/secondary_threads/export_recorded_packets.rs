@@ -0,0 +1,93 @@
+//! Module containing functions executed by the thread in charge of exporting the packets
+//! already recorded when the user selects some connections or hosts to be exported.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use pcap::Capture;
+
+use crate::networking::manage_packets::analyze_headers;
+use crate::networking::types::host::Host;
+use crate::networking::types::icmp_type::IcmpType;
+use crate::networking::types::my_link_type::MyLinkType;
+use crate::networking::types::packet_buffer::CapturedPacket;
+use crate::networking::types::packet_filters_fields::PacketFiltersFields;
+use crate::networking::types::pcap_export::PcapExport;
+use crate::networking::types::pcap_recorder::RecordedFiles;
+use crate::secondary_threads::parse_packets::get_sniffable_headers;
+use crate::InfoTraffic;
+
+/// The calling thread reads the files recorded until the export was created,
+/// writing their packets to the PCAP files of the selected connections or hosts;
+/// the packets captured in the meantime are written afterwards, to keep them in order.
+pub fn export_recorded_packets(
+    current_capture_id: &Arc<Mutex<usize>>,
+    info_traffic_mutex: &Arc<Mutex<InfoTraffic>>,
+    pcap_export: &Arc<Mutex<PcapExport>>,
+    recordings: Vec<RecordedFiles>,
+    addresses_resolved: &HashMap<String, (String, Host)>,
+) {
+    let capture_id = *current_capture_id.lock().unwrap();
+
+    for recording in recordings {
+        let files_number = recording.files.len();
+        // all the files still kept for a rotated capture, from the oldest one
+        for (i, path) in recording.files.into_iter().enumerate() {
+            // the last file is still being written: only read the packets it had at the time
+            let max_packets = if i + 1 == files_number {
+                recording.last_file_packets
+            } else {
+                usize::MAX
+            };
+            match Capture::from_file(&path) {
+                Ok(mut cap) => {
+                    let my_link_type = MyLinkType::from_pcap_link_type(cap.get_datalink());
+                    let mut read_packets = 0;
+                    while read_packets < max_packets {
+                        let Ok(packet) = cap.next_packet() else {
+                            break;
+                        };
+                        read_packets += 1;
+                        if *current_capture_id.lock().unwrap() != capture_id {
+                            // capture was reset: the export has been closed
+                            return;
+                        }
+                        let Ok((headers, _)) = get_sniffable_headers(packet.data, my_link_type)
+                        else {
+                            continue;
+                        };
+                        let Some(key) = analyze_headers(
+                            headers,
+                            recording.interface.as_ref(),
+                            &mut (None, None),
+                            &mut 0,
+                            &mut IcmpType::default(),
+                            &mut None,
+                            &mut PacketFiltersFields::default(),
+                        ) else {
+                            continue;
+                        };
+                        let captured_packet = CapturedPacket::new(&packet, my_link_type);
+                        pcap_export.lock().unwrap().write(
+                            &key,
+                            &captured_packet,
+                            addresses_resolved,
+                        );
+                    }
+                }
+                Err(e) => {
+                    pcap_export.lock().unwrap().error = Some(format!("{path}: {e}"));
+                }
+            }
+        }
+    }
+
+    // the hosts of the packets captured in the meantime may have been resolved later
+    let info_traffic = info_traffic_mutex
+        .lock()
+        .expect("Error acquiring mutex\n\r");
+    pcap_export
+        .lock()
+        .unwrap()
+        .write_queued(&info_traffic.addresses_resolved);
+}
@@ -1,2 +1,3 @@
 pub mod check_updates;
+pub mod export_recorded_packets;
 pub mod parse_packets;
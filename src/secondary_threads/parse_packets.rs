@@ -1,30 +1,53 @@
 //! Module containing functions executed by the thread in charge of parsing sniffed packets and
 //! inserting them in the shared map.
 
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Local};
 use etherparse::err::ip::{HeaderError, LaxHeaderSliceError};
 use etherparse::err::{Layer, LenError};
 use etherparse::{LaxPacketHeaders, LenSource};
 use pcap::Packet;
 
 use crate::mmdb::types::mmdb_reader::MmdbReader;
+use crate::networking::lan_devices::{
+    get_arp_lan_endpoint, get_lan_endpoints, is_lan_address, update_lan_devices,
+};
+use crate::networking::lan_names::{get_lan_names, update_lan_names};
+use crate::networking::linux_cooked::{from_sll, from_sll2, LinuxCookedHeader};
 use crate::networking::manage_packets::{
-    analyze_headers, get_address_to_lookup, modify_or_insert_in_map, reverse_dns_lookup,
+    analyze_headers, get_address_to_lookup, get_packet_timestamp, modify_or_insert_in_map,
+    set_service_from_payload,
 };
-use crate::networking::types::capture_context::CaptureContext;
+use crate::networking::non_ip::{get_non_ip_frame, get_non_ip_traffic_direction};
+use crate::networking::passive_dns::get_dns_answers;
+use crate::networking::payload_classifier::classify_payload;
+use crate::networking::program_lookup::ProgramLookup;
+use crate::networking::reverse_dns_resolver::{LookupRequest, R_DNS_RESOLVER};
+use crate::networking::server_name::get_server_name;
+use crate::networking::types::address_port_pair::AddressPortPair;
+use crate::networking::types::capture_context::{CaptureContext, CaptureType};
 use crate::networking::types::data_info::DataInfo;
 use crate::networking::types::filters::Filters;
 use crate::networking::types::icmp_type::IcmpType;
 use crate::networking::types::info_address_port_pair::InfoAddressPortPair;
 use crate::networking::types::my_device::MyDevice;
 use crate::networking::types::my_link_type::MyLinkType;
+use crate::networking::types::packet_buffer::CapturedPacket;
 use crate::networking::types::packet_filters_fields::PacketFiltersFields;
+use crate::networking::types::traffic_direction::TrafficDirection;
 use crate::InfoTraffic;
 
+/// Maximum time the recorded and exported packets are kept in memory before being written to disk
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
 /// The calling thread enters a loop in which it waits for network packets, parses them according
 /// to the user specified filters, and inserts them into the shared map variable.
+#[allow(clippy::too_many_arguments)]
 pub fn parse_packets(
     current_capture_id: &Arc<Mutex<usize>>,
     device: &MyDevice,
@@ -33,50 +56,198 @@ pub fn parse_packets(
     country_mmdb_reader: &Arc<MmdbReader>,
     asn_mmdb_reader: &Arc<MmdbReader>,
     capture_context: CaptureContext,
+    reverse_dns: bool,
 ) {
     let my_link_type = capture_context.my_link_type();
-    let (mut cap, mut savefile) = capture_context.consume();
+    let savefile_path = capture_context.savefile_path();
+    let (mut cap, savefile) = capture_context.consume();
+    // adapter name recorded for each connection (not meaningful when reading a capture file)
+    let interface = cap.is_online().then(|| device.name.clone());
+    // the recorder is shared, to allow exporting the packets already recorded at any time
+    if let (Some(path), Some(mut savefile)) = (&savefile_path, savefile) {
+        savefile.interface.clone_from(&interface);
+        info_traffic_mutex
+            .lock()
+            .expect("Error acquiring mutex\n\r")
+            .savefiles
+            .insert(path.clone(), savefile);
+    }
+    let real_time_replay = cap.is_real_time_replay();
+    // programs owning the connections can only be identified when sniffing live traffic
+    let program_lookup = cap
+        .is_online()
+        .then(|| ProgramLookup::new(info_traffic_mutex.clone()));
+    // dropped packets already accounted for, since several captures may run at the same time
+    let mut dropped_packets = 0;
+    // MAC address of the adapter, learned from the outgoing IP traffic
+    let mut local_mac_address: Option<String> = None;
+    // wall-clock instant and packet timestamp of the first replayed packet
+    let mut replay_start: Option<(Instant, DateTime<Local>)> = None;
+
+    // last time the recorded and exported packets were written to disk
+    let mut last_flush = Instant::now();
 
     let capture_id = *current_capture_id.lock().unwrap();
 
     loop {
+        if last_flush.elapsed() >= FLUSH_INTERVAL {
+            info_traffic_mutex
+                .lock()
+                .expect("Error acquiring mutex\n\r")
+                .flush_files();
+            last_flush = Instant::now();
+        }
         match cap.next_packet() {
-            Err(_) => {
+            Err(e) => {
                 if *current_capture_id.lock().unwrap() != capture_id {
+                    // capture was reset
                     return;
                 }
-                continue;
+                if cap.is_online() && e == pcap::Error::TimeoutExpired {
+                    // no packets received in the meantime
+                    continue;
+                }
+                let mut info_traffic = info_traffic_mutex
+                    .lock()
+                    .expect("Error acquiring mutex\n\r");
+                // the capture is over: complete its files
+                info_traffic.flush_files();
+                if e != pcap::Error::NoMorePackets {
+                    // any other error would be returned again (e.g., a corrupted capture file)
+                    info_traffic.capture_error = Some(e.to_string());
+                }
+                return;
             }
             Ok(packet) => {
+                // time the packet was captured, not the one it's processed at
+                let timestamp = get_packet_timestamp(&packet);
+                if real_time_replay
+                    && !wait_original_timing(
+                        timestamp,
+                        &mut replay_start,
+                        current_capture_id,
+                        capture_id,
+                    )
+                {
+                    return;
+                }
                 if *current_capture_id.lock().unwrap() != capture_id {
                     return;
                 }
-                if let Ok(headers) = get_sniffable_headers(&packet, my_link_type) {
+                if let Ok((headers, cooked_header)) =
+                    get_sniffable_headers(packet.data, my_link_type)
+                {
+                    let dns_answers = get_dns_answers(&headers);
+                    let non_ip_frame = get_non_ip_frame(&headers);
+                    let server_name = get_server_name(&headers);
+                    let payload_service = classify_payload(&headers);
+                    let mut lan_names = get_lan_names(&headers);
                     let mut exchanged_bytes = 0;
                     let mut mac_addresses = (None, None);
                     let mut icmp_type = IcmpType::default();
+                    let mut tcp_segment = None;
                     let mut packet_filters_fields = PacketFiltersFields::default();
 
                     let key_option = analyze_headers(
                         headers,
+                        interface.as_ref(),
                         &mut mac_addresses,
                         &mut exchanged_bytes,
                         &mut icmp_type,
+                        &mut tcp_segment,
                         &mut packet_filters_fields,
                     );
-                    if key_option.is_none() {
-                        continue;
-                    }
+                    let Some(key) = key_option else {
+                        if let Some(non_ip_frame) = non_ip_frame {
+                            let passed_filters = filters.matches_non_ip();
+                            let my_interface_addresses = device.addresses.lock().unwrap();
+                            let traffic_direction = get_non_ip_traffic_direction(
+                                &non_ip_frame,
+                                &my_interface_addresses,
+                                local_mac_address.as_deref(),
+                            );
+                            // ARP messages also reveal the devices of the local network
+                            let arp_endpoint = non_ip_frame.arp_sender.and_then(|sender| {
+                                get_arp_lan_endpoint(
+                                    &non_ip_frame.key.mac_address1,
+                                    sender,
+                                    &my_interface_addresses,
+                                )
+                            });
+                            drop(my_interface_addresses);
+                            let exchanged_bytes = non_ip_frame.exchanged_bytes;
 
-                    let key = key_option.unwrap();
+                            let mut info_traffic = info_traffic_mutex
+                                .lock()
+                                .expect("Error acquiring mutex\n\r");
+                            info_traffic.all_packets += 1;
+                            info_traffic.all_bytes += exchanged_bytes;
+                            if passed_filters {
+                                // save this packet to PCAP file
+                                record_packet(
+                                    &mut info_traffic,
+                                    savefile_path.as_deref(),
+                                    &packet,
+                                    traffic_direction,
+                                    |_| None,
+                                );
+                            }
+                            update_dropped_packets(
+                                &mut cap,
+                                &mut info_traffic,
+                                &mut dropped_packets,
+                            );
+                            if passed_filters {
+                                info_traffic.add_packet(
+                                    exchanged_bytes,
+                                    traffic_direction,
+                                    timestamp,
+                                );
+                                if let Some(interface) = &interface {
+                                    info_traffic.add_adapter_packet(
+                                        interface,
+                                        exchanged_bytes,
+                                        traffic_direction,
+                                        timestamp,
+                                    );
+                                }
+                                add_packet_to_entry(
+                                    &mut info_traffic.non_ip,
+                                    non_ip_frame.key,
+                                    exchanged_bytes,
+                                    traffic_direction,
+                                    timestamp,
+                                );
+                                update_lan_devices(
+                                    &mut info_traffic.devices,
+                                    arp_endpoint,
+                                    exchanged_bytes,
+                                    timestamp,
+                                );
+                            }
+                        }
+                        continue;
+                    };
                     let mut new_info = InfoAddressPortPair::default();
 
                     let passed_filters = filters.matches(&packet_filters_fields);
+                    let my_interface_addresses = device.addresses.lock().unwrap();
+                    // devices of the local network exchanging this packet
+                    let lan_endpoints = if passed_filters {
+                        get_lan_endpoints(&key, &mac_addresses, &my_interface_addresses)
+                    } else {
+                        Vec::new()
+                    };
+                    // names are only trusted for the hosts of the local network
+                    lan_names.retain(|lan_name| {
+                        is_lan_address(
+                            &lan_name.address.to_string(),
+                            lan_name.address,
+                            &my_interface_addresses,
+                        )
+                    });
+                    drop(my_interface_addresses);
                     if passed_filters {
-                        // save this packet to PCAP file
-                        if let Some(file) = savefile.as_mut() {
-                            file.write(&packet);
-                        }
                         // update the shared map
                         new_info = modify_or_insert_in_map(
                             info_traffic_mutex,
@@ -84,23 +255,102 @@ pub fn parse_packets(
                             device,
                             mac_addresses,
                             icmp_type,
+                            tcp_segment,
                             exchanged_bytes,
+                            program_lookup.as_ref(),
+                            cooked_header,
+                            timestamp,
                         );
                     }
 
+                    let captured_packet =
+                        passed_filters.then(|| CapturedPacket::new(&packet, my_link_type));
+                    let mut lookup_request = None;
                     let mut info_traffic = info_traffic_mutex
                         .lock()
                         .expect("Error acquiring mutex\n\r");
                     //increment number of sniffed packets and bytes
                     info_traffic.all_packets += 1;
                     info_traffic.all_bytes += exchanged_bytes;
-                    // update dropped packets number
-                    if let Ok(stats) = cap.stats() {
-                        info_traffic.dropped_packets = stats.dropped;
+                    if passed_filters {
+                        // save this packet to PCAP file
+                        record_packet(
+                            &mut info_traffic,
+                            savefile_path.as_deref(),
+                            &packet,
+                            new_info.traffic_direction,
+                            |info_traffic| {
+                                get_favorite_comment(info_traffic, &key, new_info.traffic_direction)
+                            },
+                        );
                     }
+                    // remember the domain names observed in DNS responses
+                    for (address, name) in dns_answers {
+                        info_traffic.add_dns_name(address, name);
+                    }
+                    // remember the names announced by the hosts of the local network
+                    update_lan_names(&mut info_traffic, lan_names);
+                    update_dropped_packets(&mut cap, &mut info_traffic, &mut dropped_packets);
 
                     if passed_filters {
-                        info_traffic.add_packet(exchanged_bytes, new_info.traffic_direction);
+                        info_traffic.add_packet(
+                            exchanged_bytes,
+                            new_info.traffic_direction,
+                            timestamp,
+                        );
+
+                        // keep the raw packet to allow inspecting it, and export it if selected
+                        if let Some(captured_packet) = captured_packet {
+                            info_traffic.export_packet(&key, &captured_packet);
+                            info_traffic.buffer_packet(&key, captured_packet);
+                        }
+
+                        // keep track of the traffic of each adapter
+                        if let Some(interface) = &interface {
+                            info_traffic.add_adapter_packet(
+                                interface,
+                                exchanged_bytes,
+                                new_info.traffic_direction,
+                                timestamp,
+                            );
+                        }
+
+                        update_lan_devices(
+                            &mut info_traffic.devices,
+                            lan_endpoints,
+                            exchanged_bytes,
+                            timestamp,
+                        );
+
+                        // learn the MAC address of the adapter, to tell the direction of non-IP frames
+                        if local_mac_address.is_none()
+                            && new_info.traffic_direction == TrafficDirection::Outgoing
+                        {
+                            local_mac_address.clone_from(&new_info.mac_address1);
+                        }
+
+                        // prefer the service detected from the payload to the one guessed from the ports
+                        if let Some(service) = payload_service {
+                            if let Some(service) = set_service_from_payload(
+                                &mut info_traffic,
+                                &key,
+                                service,
+                                exchanged_bytes,
+                            ) {
+                                new_info.service = service;
+                            }
+                        }
+
+                        // remember the server name declared by the client of this flow,
+                        // also using it to name the server if no DNS response was observed
+                        if let Some(server_name) = server_name {
+                            if let Some(info) = info_traffic.map.get_mut(&key) {
+                                info.server_name.get_or_insert(server_name.clone());
+                            }
+                            if !info_traffic.dns_names.contains_key(&key.address2) {
+                                info_traffic.add_dns_name(key.address2.clone(), server_name);
+                            }
+                        }
 
                         // check the rDNS status of this address and act accordingly
                         let address_to_lookup =
@@ -126,28 +376,19 @@ pub fn parse_packets(
                                     DataInfo::new_with_first_packet(
                                         exchanged_bytes,
                                         new_info.traffic_direction,
+                                        timestamp,
                                     ),
                                 );
 
-                                // launch new thread to resolve host name
-                                let key2 = key.clone();
-                                let info_traffic2 = info_traffic_mutex.clone();
-                                let device2 = device.clone();
-                                let country_db_reader_2 = country_mmdb_reader.clone();
-                                let asn_db_reader_2 = asn_mmdb_reader.clone();
-                                thread::Builder::new()
-                                    .name("thread_reverse_dns_lookup".to_string())
-                                    .spawn(move || {
-                                        reverse_dns_lookup(
-                                            &info_traffic2,
-                                            &key2,
-                                            new_info.traffic_direction,
-                                            &device2,
-                                            &country_db_reader_2,
-                                            &asn_db_reader_2,
-                                        );
-                                    })
-                                    .unwrap();
+                                // build the host once the lock is released
+                                lookup_request = Some(LookupRequest {
+                                    info_traffic: info_traffic_mutex.clone(),
+                                    key: key.clone(),
+                                    traffic_direction: new_info.traffic_direction,
+                                    device: device.clone(),
+                                    country_db_reader: country_mmdb_reader.clone(),
+                                    asn_db_reader: asn_mmdb_reader.clone(),
+                                });
                             }
                             (true, false) => {
                                 // waiting for a previously requested rDNS resolution
@@ -159,6 +400,7 @@ pub fn parse_packets(
                                         data_info.add_packet(
                                             exchanged_bytes,
                                             new_info.traffic_direction,
+                                            timestamp,
                                         );
                                     });
                             }
@@ -172,9 +414,11 @@ pub fn parse_packets(
                                     .1
                                     .clone();
                                 info_traffic.hosts.entry(host).and_modify(|data_info_host| {
-                                    data_info_host
-                                        .data_info
-                                        .add_packet(exchanged_bytes, new_info.traffic_direction);
+                                    data_info_host.data_info.add_packet(
+                                        exchanged_bytes,
+                                        new_info.traffic_direction,
+                                        timestamp,
+                                    );
                                 });
                             }
                         }
@@ -184,14 +428,39 @@ pub fn parse_packets(
                             .services
                             .entry(new_info.service)
                             .and_modify(|data_info| {
-                                data_info.add_packet(exchanged_bytes, new_info.traffic_direction);
+                                data_info.add_packet(
+                                    exchanged_bytes,
+                                    new_info.traffic_direction,
+                                    timestamp,
+                                );
                             })
                             .or_insert_with(|| {
                                 DataInfo::new_with_first_packet(
                                     exchanged_bytes,
                                     new_info.traffic_direction,
+                                    timestamp,
                                 )
                             });
+
+                        //increment the packet count for the program owning the connection
+                        if program_lookup.is_some() {
+                            add_program_packet(
+                                &mut info_traffic,
+                                &key,
+                                exchanged_bytes,
+                                new_info.traffic_direction,
+                                timestamp,
+                            );
+                        }
+                    }
+                    drop(info_traffic);
+
+                    if let Some(lookup_request) = lookup_request {
+                        if reverse_dns {
+                            R_DNS_RESOLVER.resolve(lookup_request);
+                        } else {
+                            lookup_request.resolve_without_lookup();
+                        }
                     }
                 }
             }
@@ -199,18 +468,157 @@ pub fn parse_packets(
     }
 }
 
-fn get_sniffable_headers<'a>(
-    packet: &'a Packet,
+/// Writes the packet to the file the capture is recorded to, if any
+fn record_packet(
+    info_traffic: &mut InfoTraffic,
+    savefile_path: Option<&str>,
+    packet: &Packet,
+    traffic_direction: TrafficDirection,
+    get_comment: impl FnOnce(&InfoTraffic) -> Option<String>,
+) {
+    let Some(path) = savefile_path else {
+        return;
+    };
+    // comments are only computed when they can be recorded
+    let comment = match info_traffic.savefiles.get(path) {
+        Some(savefile) if savefile.is_pcapng() => get_comment(info_traffic),
+        Some(_) => None,
+        None => return,
+    };
+    if let Some(savefile) = info_traffic.savefiles.get_mut(path) {
+        savefile.write(packet, traffic_direction, comment.as_deref());
+    }
+}
+
+/// Adds a packet to the data info of the program owning the connection,
+/// or to the one of the connection if its program is still being identified
+fn add_program_packet(
+    info_traffic: &mut InfoTraffic,
+    key: &AddressPortPair,
+    exchanged_bytes: u128,
+    traffic_direction: TrafficDirection,
+    timestamp: DateTime<Local>,
+) {
+    if let Some(data_info) = info_traffic.programs_waiting_resolution.get_mut(key) {
+        data_info.add_packet(exchanged_bytes, traffic_direction, timestamp);
+        return;
+    }
+    // the program may have been identified since the connection was updated
+    let program = info_traffic
+        .map
+        .get(key)
+        .map(|info| info.program.clone())
+        .unwrap_or_default()
+        .to_string();
+    info_traffic
+        .programs
+        .entry(program)
+        .and_modify(|data_info| {
+            data_info.add_packet(exchanged_bytes, traffic_direction, timestamp);
+        })
+        .or_insert_with(|| {
+            DataInfo::new_with_first_packet(exchanged_bytes, traffic_direction, timestamp)
+        });
+}
+
+/// Returns the comment attached to the recorded packets exchanged with a favorite host
+fn get_favorite_comment(
+    info_traffic: &InfoTraffic,
+    key: &AddressPortPair,
+    traffic_direction: TrafficDirection,
+) -> Option<String> {
+    let address = get_address_to_lookup(key, traffic_direction);
+    let (_, host) = info_traffic.addresses_resolved.get(&address)?;
+    if !info_traffic.favorite_hosts.contains(host) {
+        return None;
+    }
+    let name = if host.domain.is_empty() {
+        &address
+    } else {
+        &host.domain
+    };
+    Some(format!("Favorite host: {name}"))
+}
+
+/// Updates the number of dropped packets with the ones dropped since the last update
+fn update_dropped_packets(
+    cap: &mut CaptureType,
+    info_traffic: &mut InfoTraffic,
+    dropped_packets: &mut u32,
+) {
+    if let Ok(stats) = cap.stats() {
+        info_traffic.dropped_packets += stats.dropped.saturating_sub(*dropped_packets);
+        *dropped_packets = stats.dropped;
+    }
+}
+
+/// Adds a packet to the data info of the given entry, inserting it if not present yet
+fn add_packet_to_entry<K: Eq + Hash>(
+    map: &mut HashMap<K, DataInfo>,
+    key: K,
+    exchanged_bytes: u128,
+    traffic_direction: TrafficDirection,
+    timestamp: DateTime<Local>,
+) {
+    map.entry(key)
+        .and_modify(|data_info| data_info.add_packet(exchanged_bytes, traffic_direction, timestamp))
+        .or_insert_with(|| {
+            DataInfo::new_with_first_packet(exchanged_bytes, traffic_direction, timestamp)
+        });
+}
+
+/// Waits until the time elapsed from the first replayed packet matches
+/// the interval between their original timestamps.
+///
+/// Returns false if the capture was reset in the meantime.
+fn wait_original_timing(
+    timestamp: DateTime<Local>,
+    replay_start: &mut Option<(Instant, DateTime<Local>)>,
+    current_capture_id: &Arc<Mutex<usize>>,
+    capture_id: usize,
+) -> bool {
+    // sleep in short steps to promptly react to a capture reset
+    const MAX_SLEEP_STEP: Duration = Duration::from_millis(100);
+
+    let (start_instant, start_time) = *replay_start.get_or_insert((Instant::now(), timestamp));
+    // packets earlier than the first one are replayed right away
+    let target = start_instant + (timestamp - start_time).to_std().unwrap_or_default();
+
+    loop {
+        let now = Instant::now();
+        if now >= target {
+            return true;
+        }
+        if *current_capture_id.lock().unwrap() != capture_id {
+            return false;
+        }
+        thread::sleep(MAX_SLEEP_STEP.min(target - now));
+    }
+}
+
+/// Returns the headers of the packet, together with the Linux cooked capture header if present
+pub fn get_sniffable_headers(
+    packet: &[u8],
     my_link_type: MyLinkType,
-) -> Result<LaxPacketHeaders<'a>, LaxHeaderSliceError> {
+) -> Result<(LaxPacketHeaders, Option<LinuxCookedHeader>), LaxHeaderSliceError> {
     match my_link_type {
         MyLinkType::Ethernet(_) | MyLinkType::Unsupported(_) | MyLinkType::NotYetAssigned => {
-            LaxPacketHeaders::from_ethernet(packet).map_err(LaxHeaderSliceError::Len)
+            LaxPacketHeaders::from_ethernet(packet)
+                .map(|headers| (headers, None))
+                .map_err(LaxHeaderSliceError::Len)
         }
         MyLinkType::RawIp(_) | MyLinkType::IPv4(_) | MyLinkType::IPv6(_) => {
-            LaxPacketHeaders::from_ip(packet)
+            LaxPacketHeaders::from_ip(packet).map(|headers| (headers, None))
+        }
+        MyLinkType::Null(_) | MyLinkType::Loop(_) => {
+            from_null(packet).map(|headers| (headers, None))
+        }
+        MyLinkType::LinuxSll(_) => {
+            from_sll(packet).map(|(headers, cooked_header)| (headers, Some(cooked_header)))
+        }
+        MyLinkType::LinuxSll2(_) => {
+            from_sll2(packet).map(|(headers, cooked_header)| (headers, Some(cooked_header)))
         }
-        MyLinkType::Null(_) | MyLinkType::Loop(_) => from_null(packet),
     }
 }
 
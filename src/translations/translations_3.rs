@@ -238,3 +238,502 @@ pub fn learn_more_translation(language: Language) -> &'static str {
         _ => "Do you want to learn more?",
     }
 }
+
+pub fn capture_from_file_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Select capture file",
+        Language::IT => "Seleziona file di cattura",
+        Language::FR => "Sélectionner un fichier de capture",
+        Language::DE => "Aufzeichnungsdatei wählen",
+        _ => "Select capture file",
+    }
+}
+
+pub fn import_capture_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Import capture file",
+        Language::IT => "Importa file di cattura",
+        Language::FR => "Importer un fichier de capture",
+        Language::DE => "Aufzeichnungsdatei importieren",
+        _ => "Import capture file",
+    }
+}
+
+pub fn capture_file_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Capture file",
+        Language::IT => "File di cattura",
+        Language::FR => "Fichier de capture",
+        Language::DE => "Aufzeichnungsdatei",
+        _ => "Capture file",
+    }
+}
+
+pub fn replay_real_time_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Replay with original timing",
+        Language::IT => "Riproduci con le tempistiche originali",
+        Language::FR => "Rejouer avec le timing d'origine",
+        Language::DE => "Mit ursprünglichem Timing abspielen",
+        _ => "Replay with original timing",
+    }
+}
+
+pub fn bpf_filter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "BPF filter",
+        Language::IT => "Filtro BPF",
+        Language::FR => "Filtre BPF",
+        Language::DE => "BPF-Filter",
+        _ => "BPF filter",
+    }
+}
+
+pub fn program_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Program",
+        Language::IT => "Programma",
+        Language::FR => "Programme",
+        Language::DE => "Programm",
+        _ => "Program",
+    }
+}
+
+pub fn save_session_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Save session",
+        Language::IT => "Salva sessione",
+        Language::FR => "Enregistrer la session",
+        Language::DE => "Sitzung speichern",
+        _ => "Save session",
+    }
+}
+
+pub fn open_session_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Open saved session",
+        Language::IT => "Apri sessione salvata",
+        Language::FR => "Ouvrir une session enregistrée",
+        Language::DE => "Gespeicherte Sitzung öffnen",
+        _ => "Open saved session",
+    }
+}
+
+pub fn session_from_file_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Select session file",
+        Language::IT => "Seleziona file di sessione",
+        Language::FR => "Sélectionner un fichier de session",
+        Language::DE => "Sitzungsdatei wählen",
+        _ => "Select session file",
+    }
+}
+
+pub fn saved_session_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Saved session",
+        Language::IT => "Sessione salvata",
+        Language::FR => "Session enregistrée",
+        Language::DE => "Gespeicherte Sitzung",
+        _ => "Saved session",
+    }
+}
+
+pub fn export_results_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Export results",
+        Language::IT => "Esporta risultati",
+        Language::FR => "Exporter les résultats",
+        Language::DE => "Ergebnisse exportieren",
+        _ => "Export results",
+    }
+}
+
+pub fn hosts_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN | Language::DE => "Hosts",
+        Language::IT => "Host",
+        Language::FR => "Hôtes",
+        _ => "Hosts",
+    }
+}
+
+pub fn services_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN | Language::FR => "Services",
+        Language::IT => "Servizi",
+        Language::DE => "Dienste",
+        _ => "Services",
+    }
+}
+
+pub fn reverse_dns_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Reverse DNS",
+        Language::IT => "DNS inverso",
+        Language::FR => "DNS inverse",
+        Language::DE => "Reverse-DNS",
+        _ => "Reverse DNS",
+    }
+}
+
+pub fn look_up_host_names_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Look up host names",
+        Language::IT => "Cerca i nomi degli host",
+        Language::FR => "Rechercher les noms d'hôte",
+        Language::DE => "Hostnamen nachschlagen",
+        _ => "Look up host names",
+    }
+}
+
+pub fn server_name_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Server name",
+        Language::IT => "Nome del server",
+        Language::FR => "Nom du serveur",
+        Language::DE => "Servername",
+        _ => "Server name",
+    }
+}
+
+pub fn detected_from_payload_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "detected from payload",
+        Language::IT => "rilevato dal payload",
+        Language::FR => "détecté à partir du contenu",
+        Language::DE => "aus den Nutzdaten erkannt",
+        _ => "detected from payload",
+    }
+}
+
+pub fn guessed_from_port_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "guessed from port",
+        Language::IT => "dedotto dalla porta",
+        Language::FR => "déduit du port",
+        Language::DE => "anhand des Ports vermutet",
+        _ => "guessed from port",
+    }
+}
+
+pub fn services_from_file_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Select services file",
+        Language::IT => "Seleziona file dei servizi",
+        Language::FR => "Sélection d'un fichier de services",
+        Language::DE => "Dienste Datei auswählen",
+        _ => "Select services file",
+    }
+}
+
+pub fn custom_services_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Custom services",
+        Language::IT => "Servizi personalizzati",
+        Language::FR => "Services personnalisés",
+        Language::DE => "Benutzerdefinierte Dienste",
+        _ => "Custom services",
+    }
+}
+
+pub fn split_by_adapter_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Split by adapter",
+        Language::IT => "Dividi per adattatore",
+        Language::FR => "Séparer par carte réseau",
+        Language::DE => "Nach Adapter aufteilen",
+        _ => "Split by adapter",
+    }
+}
+
+pub fn non_ip_traffic_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Non-IP traffic",
+        Language::IT => "Traffico non IP",
+        Language::FR => "Trafic non IP",
+        Language::DE => "Nicht-IP-Verkehr",
+        _ => "Non-IP traffic",
+    }
+}
+
+pub fn devices_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Devices",
+        Language::IT => "Dispositivi",
+        Language::FR => "Appareils",
+        Language::DE => "Geräte",
+        _ => "Devices",
+    }
+}
+
+pub fn tcp_state_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "State",
+        Language::IT => "Stato",
+        Language::FR => "État",
+        Language::DE => "Status",
+        _ => "State",
+    }
+}
+
+pub fn tcp_connections_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "TCP connections",
+        Language::IT => "Connessioni TCP",
+        Language::FR => "Connexions TCP",
+        Language::DE => "TCP-Verbindungen",
+        _ => "TCP connections",
+    }
+}
+
+pub fn failed_handshakes_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Failed handshakes",
+        Language::IT => "Handshake falliti",
+        Language::FR => "Handshakes échoués",
+        Language::DE => "Fehlgeschlagene Handshakes",
+        _ => "Failed handshakes",
+    }
+}
+
+pub fn resets_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Resets",
+        Language::IT => "Reset",
+        Language::FR => "Réinitialisations",
+        Language::DE => "Zurückgesetzt",
+        _ => "Resets",
+    }
+}
+
+pub fn half_open_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Half-open",
+        Language::IT => "Semiaperte",
+        Language::FR => "Semi-ouvertes",
+        Language::DE => "Halboffen",
+        _ => "Half-open",
+    }
+}
+
+pub fn opened_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Opened",
+        Language::IT => "Aperta",
+        Language::FR => "Ouverte",
+        Language::DE => "Geöffnet",
+        _ => "Opened",
+    }
+}
+
+pub fn closed_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Closed",
+        Language::IT => "Chiusa",
+        Language::FR => "Fermée",
+        Language::DE => "Geschlossen",
+        _ => "Closed",
+    }
+}
+
+pub fn closed_by_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Closed by",
+        Language::IT => "Chiusa da",
+        Language::FR => "Fermée par",
+        Language::DE => "Geschlossen von",
+        _ => "Closed by",
+    }
+}
+
+pub fn traffic_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Traffic",
+        Language::IT => "Traffico",
+        Language::FR => "Trafic",
+        Language::DE => "Verkehr",
+        _ => "Traffic",
+    }
+}
+
+pub fn latency_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Latency",
+        Language::IT => "Latenza",
+        Language::FR => "Latence",
+        Language::DE => "Latenz",
+        _ => "Latency",
+    }
+}
+
+pub fn retransmissions_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Retransmissions",
+        Language::IT => "Ritrasmissioni",
+        Language::FR => "Retransmissions",
+        Language::DE => "Neuübertragungen",
+        _ => "Retransmissions",
+    }
+}
+
+pub fn handshake_rtt_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Handshake RTT",
+        Language::IT => "RTT dell'handshake",
+        Language::FR => "RTT de la poignée de main",
+        Language::DE => "Handshake-RTT",
+        _ => "Handshake RTT",
+    }
+}
+
+pub fn segments_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Segments",
+        Language::IT => "Segmenti",
+        Language::FR => "Segments",
+        Language::DE => "Segmente",
+        _ => "Segments",
+    }
+}
+
+pub fn out_of_order_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Out-of-order",
+        Language::IT => "Fuori ordine",
+        Language::FR => "Hors séquence",
+        Language::DE => "Außer der Reihe",
+        _ => "Out-of-order",
+    }
+}
+
+pub fn duplicate_acks_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Duplicate ACKs",
+        Language::IT => "ACK duplicati",
+        Language::FR => "ACK dupliqués",
+        Language::DE => "Doppelte ACKs",
+        _ => "Duplicate ACKs",
+    }
+}
+
+pub fn zero_windows_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Zero windows",
+        Language::IT => "Finestre nulle",
+        Language::FR => "Fenêtres nulles",
+        Language::DE => "Nullfenster",
+        _ => "Zero windows",
+    }
+}
+
+pub fn snapshot_length_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Bytes captured per packet",
+        Language::IT => "Byte catturati per pacchetto",
+        Language::FR => "Octets capturés par paquet",
+        Language::DE => "Erfasste Bytes pro Paket",
+        _ => "Bytes captured per packet",
+    }
+}
+
+pub fn packet_list_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Packets",
+        Language::IT => "Pacchetti",
+        Language::FR => "Paquets",
+        Language::DE => "Pakete",
+        _ => "Packets",
+    }
+}
+
+pub fn details_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Details",
+        Language::IT => "Dettagli",
+        Language::FR => "Détails",
+        Language::DE => "Details",
+        _ => "Details",
+    }
+}
+
+pub fn payload_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Payload",
+        Language::IT => "Payload",
+        Language::FR => "Charge utile",
+        Language::DE => "Nutzdaten",
+        _ => "Payload",
+    }
+}
+
+pub fn select_packet_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Select a packet to inspect its headers and payload",
+        Language::IT => "Seleziona un pacchetto per ispezionarne intestazioni e payload",
+        Language::FR => "Sélectionnez un paquet pour inspecter ses en-têtes et sa charge utile",
+        Language::DE => "Wähle ein Paket aus, um seine Header und Nutzdaten zu untersuchen",
+        _ => "Select a packet to inspect its headers and payload",
+    }
+}
+
+pub fn no_packets_buffered_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "No packets kept for this connection",
+        Language::IT => "Nessun pacchetto conservato per questa connessione",
+        Language::FR => "Aucun paquet conservé pour cette connexion",
+        Language::DE => "Keine Pakete für diese Verbindung gespeichert",
+        _ => "No packets kept for this connection",
+    }
+}
+
+pub fn export_packets_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Export packets to PCAP",
+        Language::IT => "Esporta pacchetti in PCAP",
+        Language::FR => "Exporter les paquets en PCAP",
+        Language::DE => "Pakete als PCAP exportieren",
+        _ => "Export packets to PCAP",
+    }
+}
+
+pub fn split_by_host_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "One file per host",
+        Language::IT => "Un file per host",
+        Language::FR => "Un fichier par hôte",
+        Language::DE => "Eine Datei pro Host",
+        _ => "One file per host",
+    }
+}
+
+// (followed by a size and a duration, e.g., "New file every: 100 MB 60 min")
+pub fn new_file_every_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "New file every",
+        Language::IT => "Nuovo file ogni",
+        Language::FR => "Nouveau fichier tous les",
+        Language::DE => "Neue Datei alle",
+        _ => "New file every",
+    }
+}
+
+pub fn max_files_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "Maximum number of files",
+        Language::IT => "Numero massimo di file",
+        Language::FR => "Nombre maximal de fichiers",
+        Language::DE => "Maximale Anzahl an Dateien",
+        _ => "Maximum number of files",
+    }
+}
+
+pub fn pcapng_format_translation(language: Language) -> &'static str {
+    match language {
+        Language::EN => "PCAPNG format (with adapter, direction and comments)",
+        Language::IT => "Formato PCAPNG (con adattatore, direzione e commenti)",
+        Language::FR => "Format PCAPNG (avec carte réseau, direction et commentaires)",
+        Language::DE => "PCAPNG-Format (mit Netzwerkadapter, Richtung und Kommentaren)",
+        _ => "PCAPNG format (with adapter, direction and comments)",
+    }
+}
@@ -5,7 +5,9 @@
 use crate::translations::translations::{
     address_translation, ip_version_translation, protocol_translation,
 };
-use crate::translations::translations_3::{invalid_filters_translation, port_translation};
+use crate::translations::translations_3::{
+    bpf_filter_translation, invalid_filters_translation, port_translation,
+};
 use crate::Language;
 
 /// Application version number (to be displayed in gui footer)
@@ -38,6 +40,12 @@ pub fn get_invalid_filters_string(filters: &Filters, language: Language) -> Stri
     if !filters.port_valid() {
         ret_val.push_str(&format!("\n • {}", port_translation(language)));
     }
+    if let Some(error) = &filters.bpf_error {
+        ret_val.push_str(&format!(
+            "\n • {}: {error}",
+            bpf_filter_translation(language)
+        ));
+    }
     ret_val
 }
 
@@ -72,6 +80,13 @@ pub fn get_active_filters_string(filters: &Filters, language: Language) -> Strin
             filters.port_str
         ));
     }
+    if filters.bpf_active() {
+        filters_string.push_str(&format!(
+            "• {}: {}\n",
+            bpf_filter_translation(language),
+            filters.bpf
+        ));
+    }
     filters_string
 }
 
@@ -1,5 +1,7 @@
 use crate::translations::translations_3::{
-    database_from_file_translation, select_directory_translation, style_from_file_translation,
+    capture_from_file_translation, database_from_file_translation, export_results_translation,
+    select_directory_translation, services_from_file_translation, session_from_file_translation,
+    style_from_file_translation,
 };
 use crate::translations::types::language::Language;
 
@@ -8,6 +10,10 @@ pub enum FileInfo {
     Style,
     Database,
     Directory,
+    Capture,
+    Session,
+    Report,
+    Services,
 }
 
 impl FileInfo {
@@ -16,6 +22,24 @@ pub fn get_extension(&self) -> &'static str {
             FileInfo::Style => "toml",
             FileInfo::Database => "mmdb",
             FileInfo::Directory => "",
+            FileInfo::Capture => "pcap",
+            FileInfo::Session => "json",
+            FileInfo::Report => "csv",
+            FileInfo::Services => "",
+        }
+    }
+
+    /// Extensions accepted by the file dialog
+    pub fn get_extensions(&self) -> &'static [&'static str] {
+        match self {
+            FileInfo::Style => &["toml"],
+            FileInfo::Database => &["mmdb"],
+            FileInfo::Directory => &[],
+            FileInfo::Capture => &["pcap", "pcapng", "cap"],
+            FileInfo::Session => &["json"],
+            FileInfo::Report => &["csv", "json"],
+            // services files usually have no extension
+            FileInfo::Services => &[],
         }
     }
 
@@ -24,6 +48,10 @@ pub fn action_info(&self, language: Language) -> &'static str {
             FileInfo::Style => style_from_file_translation(language),
             FileInfo::Database => database_from_file_translation(language),
             FileInfo::Directory => select_directory_translation(language),
+            FileInfo::Capture => capture_from_file_translation(language),
+            FileInfo::Session => session_from_file_translation(language),
+            FileInfo::Report => export_results_translation(language),
+            FileInfo::Services => services_from_file_translation(language),
         }
     }
 }